@@ -0,0 +1,204 @@
+//! Clocks and tick scaling for callers who do not want to compute ticks by hand.
+//!
+//! The cores take an explicit `tick` on every call. This module supplies the
+//! pieces to derive that tick from a time source:
+//!
+//! - **[`Clock`]** - Reports the time elapsed since the clock's own epoch
+//! - **[`MonotonicClock`]** - `Clock` backed by [`std::time::Instant`]
+//! - **[`ManualClock`]** - `Clock` advanced explicitly, for tests and simulations
+//! - **[`TickScale`]** - Converts an elapsed [`Duration`] into ticks
+//! - **[`BoundLimiter`]** - Owns a limiter, a clock and a scale, and acquires "now"
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// A source of elapsed time.
+///
+/// `now` must never decrease; the cores reject ticks that go backwards.
+pub trait Clock {
+    /// Returns the time elapsed since this clock's epoch.
+    fn now(&self) -> Duration;
+}
+
+/// A [`Clock`] measuring time since its creation with [`Instant`].
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    /// Instant the clock was created
+    epoch: Instant,
+}
+
+impl MonotonicClock {
+    /// Creates a clock whose epoch is the current instant.
+    pub fn new() -> Self {
+        MonotonicClock { epoch: Instant::now() }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    #[inline(always)]
+    fn now(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+}
+
+/// A [`Clock`] that only moves when told to.
+///
+/// Starts at zero. Useful for deterministic tests of code built on [`BoundLimiter`].
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    /// Current elapsed time
+    now: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// Creates a clock reading zero.
+    pub fn new() -> Self {
+        ManualClock { now: Mutex::new(Duration::ZERO) }
+    }
+
+    /// Moves the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now = now.saturating_add(by);
+    }
+
+    /// Sets the clock to `to`. Setting it backwards makes limiters reject the
+    /// resulting ticks as expired.
+    pub fn set(&self, to: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = to;
+    }
+}
+
+impl Clock for ManualClock {
+    #[inline(always)]
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Converts elapsed time into ticks of a fixed duration.
+///
+/// Partial ticks are truncated, and results beyond `Uint::MAX` saturate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TickScale {
+    /// Duration of one tick
+    tick: Duration,
+}
+
+impl TickScale {
+    /// Creates a scale where one tick lasts `tick`.
+    ///
+    /// # Panics
+    /// Panics if `tick` is zero.
+    pub fn new(tick: Duration) -> Self {
+        assert!(!tick.is_zero(), "tick duration must be greater than 0");
+        TickScale { tick }
+    }
+
+    /// Creates a scale of one tick per millisecond.
+    pub fn millis() -> Self {
+        Self::new(Duration::from_millis(1))
+    }
+
+    /// Creates a scale of one tick per second.
+    pub fn secs() -> Self {
+        Self::new(Duration::from_secs(1))
+    }
+
+    /// Returns the duration of one tick.
+    pub fn tick_duration(&self) -> Duration {
+        self.tick
+    }
+
+    /// Returns the number of whole ticks in `elapsed`.
+    pub fn to_ticks(&self, elapsed: Duration) -> Uint {
+        let ticks = elapsed.as_nanos() / self.tick.as_nanos();
+        Uint::try_from(ticks).unwrap_or(Uint::MAX)
+    }
+}
+
+/// A limiter bound to a clock and a tick scale.
+///
+/// Every call reads `clock.now()`, converts it with `scale`, and passes the
+/// resulting tick to the inner limiter, so callers never handle ticks directly.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rate_guard_core::clock::{BoundLimiter, ManualClock, TickScale};
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+///
+/// // 10 tokens per window of 1000 ticks, one tick per millisecond
+/// let limiter = BoundLimiter::new(FixedWindowCounterCore::new(10, 1000), ManualClock::new(), TickScale::millis());
+/// assert_eq!(limiter.acquire_now(10), Ok(()));
+/// assert!(limiter.acquire_now(1).is_err());
+///
+/// limiter.clock().advance(Duration::from_secs(1));
+/// assert_eq!(limiter.acquire_now(1), Ok(()));
+/// ```
+pub struct BoundLimiter<L: RateLimitCore, C: Clock> {
+    /// Limiter making the admission decision
+    inner: L,
+    /// Time source for every call
+    clock: C,
+    /// Conversion from elapsed time to ticks
+    scale: TickScale,
+}
+
+impl<L: RateLimitCore, C: Clock> BoundLimiter<L, C> {
+    /// Creates a new bound limiter.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `clock` - Time source
+    /// * `scale` - Conversion from `clock.now()` to ticks
+    pub fn new(inner: L, clock: C, scale: TickScale) -> Self {
+        BoundLimiter { inner, clock, scale }
+    }
+
+    /// Returns the current tick according to the clock and scale.
+    #[inline(always)]
+    pub fn now_tick(&self) -> Uint {
+        self.scale.to_ticks(self.clock.now())
+    }
+
+    /// Attempts to acquire tokens at the current tick.
+    pub fn acquire_now(&self, tokens: Uint) -> SimpleRateLimitResult {
+        self.inner.try_acquire_at(self.now_tick(), tokens)
+    }
+
+    /// Attempts to acquire tokens at the current tick, returning detailed
+    /// diagnostics on failure. `retry_after_ticks` is in ticks of `scale`.
+    pub fn acquire_now_verbose(&self, tokens: Uint) -> VerboseRateLimitResult {
+        self.inner.try_acquire_verbose_at(self.now_tick(), tokens)
+    }
+
+    /// Returns the remaining capacity at the current tick.
+    pub fn capacity_remaining_now(&self) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(self.now_tick())
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// Returns a reference to the clock.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the tick scale.
+    pub fn scale(&self) -> TickScale {
+        self.scale
+    }
+}
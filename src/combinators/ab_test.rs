@@ -0,0 +1,165 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Enforces one limiter while shadow-evaluating a candidate replacement.
+///
+/// Every acquire is charged to both the enforced and the shadow limiter, and
+/// the enforced limiter's decision is returned. The two decisions are then
+/// compared and tallied as an agreement, a denial by the enforced limiter only,
+/// or a denial by the shadow limiter only, so the denial rates of two
+/// configurations can be compared on live traffic before switching.
+///
+/// Only policy decisions are compared: a call where either limiter returns
+/// `ExpiredTick` or `ContentionFailure` is not tallied.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::AbTest;
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+///
+/// let limiter = AbTest::new(
+///     FixedWindowCounterCore::new(10, 60),
+///     FixedWindowCounterCore::new(5, 60),
+/// );
+///
+/// assert_eq!(limiter.try_acquire_at(0, 8), Ok(()));
+/// assert_eq!(limiter.shadow_only_denied(), 1);
+/// ```
+pub struct AbTest<E: RateLimitCore, S: RateLimitCore = E> {
+    /// Limiter whose decisions are returned
+    enforced: E,
+    /// Candidate limiter evaluated alongside
+    shadow: S,
+    /// Calls where both limiters made the same decision
+    agreements: AtomicU64,
+    /// Calls denied by the enforced limiter but admitted by the shadow limiter
+    enforced_only_denied: AtomicU64,
+    /// Calls admitted by the enforced limiter but denied by the shadow limiter
+    shadow_only_denied: AtomicU64,
+}
+
+/// Classifies a result as admitted (`Some(true)`), denied for capacity
+/// (`Some(false)`), or not a policy decision (`None`).
+#[inline(always)]
+fn admitted(result: &SimpleRateLimitResult) -> Option<bool> {
+    match result {
+        Ok(()) => Some(true),
+        Err(SimpleRateLimitError::InsufficientCapacity) | Err(SimpleRateLimitError::BeyondCapacity) => Some(false),
+        Err(_) => None,
+    }
+}
+
+/// Verbose counterpart of [`admitted`].
+#[inline(always)]
+fn admitted_verbose(result: &VerboseRateLimitResult) -> Option<bool> {
+    match result {
+        Ok(()) => Some(true),
+        Err(VerboseRateLimitError::InsufficientCapacity { .. })
+        | Err(VerboseRateLimitError::BeyondCapacity { .. }) => Some(false),
+        Err(_) => None,
+    }
+}
+
+impl<E: RateLimitCore, S: RateLimitCore> AbTest<E, S> {
+    /// Creates a new A/B test with zero tallies.
+    ///
+    /// # Parameters
+    /// * `enforced` - Limiter whose decisions are returned
+    /// * `shadow` - Candidate limiter evaluated alongside
+    pub fn new(enforced: E, shadow: S) -> Self {
+        AbTest {
+            enforced,
+            shadow,
+            agreements: AtomicU64::new(0),
+            enforced_only_denied: AtomicU64::new(0),
+            shadow_only_denied: AtomicU64::new(0),
+        }
+    }
+
+    /// Tallies one pair of decisions.
+    fn record(&self, enforced: Option<bool>, shadow: Option<bool>) {
+        let counter = match (enforced, shadow) {
+            (Some(e), Some(s)) if e == s => &self.agreements,
+            (Some(false), Some(true)) => &self.enforced_only_denied,
+            (Some(true), Some(false)) => &self.shadow_only_denied,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Attempts to acquire tokens from both limiters, returning the enforced decision.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let enforced = self.enforced.try_acquire_at(tick, tokens);
+        let shadow = self.shadow.try_acquire_at(tick, tokens);
+        self.record(admitted(&enforced), admitted(&shadow));
+        enforced
+    }
+
+    /// Attempts to acquire tokens from both limiters, returning the enforced
+    /// decision with detailed diagnostics.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let enforced = self.enforced.try_acquire_verbose_at(tick, tokens);
+        let shadow = self.shadow.try_acquire_verbose_at(tick, tokens);
+        self.record(admitted_verbose(&enforced), admitted_verbose(&shadow));
+        enforced
+    }
+
+    /// Returns the remaining capacity of the enforced limiter.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.enforced.capacity_remaining(tick)
+    }
+
+    /// Returns the steady-state allowance of the enforced limiter.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.enforced.steady_state_allowance()
+    }
+
+    /// Returns the number of calls where both limiters made the same decision.
+    pub fn agreements(&self) -> u64 {
+        self.agreements.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of calls denied by the enforced limiter but admitted by the shadow limiter.
+    pub fn enforced_only_denied(&self) -> u64 {
+        self.enforced_only_denied.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of calls admitted by the enforced limiter but denied by the shadow limiter.
+    pub fn shadow_only_denied(&self) -> u64 {
+        self.shadow_only_denied.load(Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the enforced limiter.
+    pub fn enforced(&self) -> &E {
+        &self.enforced
+    }
+
+    /// Returns a reference to the shadow limiter.
+    pub fn shadow(&self) -> &S {
+        &self.shadow
+    }
+}
+
+impl<E: RateLimitCore, S: RateLimitCore> RateLimitCore for AbTest<E, S> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
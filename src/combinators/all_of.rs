@@ -0,0 +1,192 @@
+use crate::rate_limit::{compare_allowance, roll_back_verbose, RateLimitCore, RefundableCore};
+use crate::{
+    LabeledRateLimitError, LabeledRateLimitResult, SimpleRateLimitError, SimpleRateLimitResult,
+    Uint, VerboseRateLimitResult,
+};
+
+/// A limiter paired with a static label identifying it in error reports.
+///
+/// The inner limiter must be [`RefundableCore`] so that [`AllOf`] can give its
+/// tokens back when a sibling limiter denies the same request.
+pub struct Labeled {
+    /// Name reported when this limiter denies a request
+    label: &'static str,
+    /// The labeled limiter
+    core: Box<dyn RefundableCore>,
+}
+
+impl Labeled {
+    /// Creates a new labeled limiter.
+    ///
+    /// # Parameters
+    /// * `label` - Name reported when this limiter denies a request
+    /// * `core` - The limiter to label
+    pub fn new<L: RefundableCore + 'static>(label: &'static str, core: L) -> Self {
+        Labeled { label, core: Box::new(core) }
+    }
+
+    /// Returns the label of this limiter.
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Returns a reference to the labeled limiter.
+    pub fn core(&self) -> &dyn RefundableCore {
+        self.core.as_ref()
+    }
+}
+
+/// Admits a request only if every child limiter admits it.
+///
+/// Children are consulted in order. When a child denies, the tokens already
+/// taken from the children before it are refunded, so a denied request leaves
+/// every child as it was. A refund is retried while its child is contended; if
+/// another caller has already moved a child past the tick, the refund cannot be
+/// made and `ExpiredTick` is returned instead of the denial. `capacity_remaining`
+/// reports the smallest remaining capacity among the children.
+///
+/// [`AllOf::try_acquire_labeled_at`] additionally reports which child denied
+/// the request, for example to tell a client whether it hit a burst limit or a
+/// daily quota.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::{AllOf, Labeled};
+/// use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+///
+/// let limiter = AllOf::new(vec![
+///     Labeled::new("burst", TokenBucketCore::new(5, 10, 1)),
+///     Labeled::new("quota", FixedWindowCounterCore::new(100, 1000)),
+/// ]);
+///
+/// assert_eq!(limiter.try_acquire_labeled_at(0, 5), Ok(()));
+/// let err = limiter.try_acquire_labeled_at(0, 1).unwrap_err();
+/// assert_eq!(err.label, "burst");
+/// assert_eq!(err.retry_after_ticks(), Some(10));
+/// ```
+pub struct AllOf {
+    /// Child limiters, consulted in order
+    children: Vec<Labeled>,
+}
+
+impl AllOf {
+    /// Creates a new combinator from its children.
+    ///
+    /// # Panics
+    /// Panics if `children` is empty.
+    pub fn new(children: Vec<Labeled>) -> Self {
+        assert!(!children.is_empty(), "children must not be empty");
+        AllOf { children }
+    }
+
+    /// Gives `tokens` back to the first `count` children.
+    ///
+    /// Each refund is retried while its child is contended. Every child is
+    /// refunded even if one fails; the first failure is returned together
+    /// with the label of its child.
+    fn roll_back(&self, count: usize, tick: Uint, tokens: Uint) -> Result<(), LabeledRateLimitError> {
+        let mut first_failure = None;
+        for child in &self.children[..count] {
+            if let Err(error) = roll_back_verbose(child.core.as_ref(), tick, tokens) {
+                first_failure.get_or_insert(LabeledRateLimitError { label: child.label, error });
+            }
+        }
+        first_failure.map_or(Ok(()), Err)
+    }
+
+    /// Attempts to acquire tokens from every child.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If every child admitted the request
+    /// * `Err(_)` - The error of the first child that denied it, or `ExpiredTick`
+    ///   if the children before it could not be rolled back
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        for (i, child) in self.children.iter().enumerate() {
+            if let Err(e) = child.core.try_acquire_at(tick, tokens) {
+                self.roll_back(i, tick, tokens).map_err(|_| SimpleRateLimitError::ExpiredTick)?;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Attempts to acquire tokens from every child, returning the denying
+    /// child's verbose error.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_labeled_at(tick, tokens).map_err(|e| e.error)
+    }
+
+    /// Attempts to acquire tokens from every child, returning the denying
+    /// child's label together with its verbose error.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If every child admitted the request
+    /// * `Err(LabeledRateLimitError { label, error })` - The first child that denied it,
+    ///   or the first child that could not be rolled back, with `ExpiredTick`
+    pub fn try_acquire_labeled_at(&self, tick: Uint, tokens: Uint) -> LabeledRateLimitResult {
+        for (i, child) in self.children.iter().enumerate() {
+            if let Err(error) = child.core.try_acquire_verbose_at(tick, tokens) {
+                self.roll_back(i, tick, tokens)?;
+                return Err(LabeledRateLimitError { label: child.label, error });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the smallest remaining capacity among the children.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut remaining = Uint::MAX;
+        for child in &self.children {
+            remaining = remaining.min(child.core.capacity_remaining(tick)?);
+        }
+        Ok(remaining)
+    }
+
+    /// Returns the smallest steady-state allowance among the children, since
+    /// every request is charged to all of them.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.children
+            .iter()
+            .map(|child| child.core.steady_state_allowance())
+            .min_by(|a, b| compare_allowance(*a, *b))
+            .unwrap_or((0, 1))
+    }
+
+    /// Returns the children of this combinator.
+    pub fn children(&self) -> &[Labeled] {
+        &self.children
+    }
+}
+
+impl RateLimitCore for AllOf {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
+
+impl RefundableCore for AllOf {
+    /// Refunds `tokens` to every child.
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        for child in &self.children {
+            child.core.refund_at(tick, tokens)?;
+        }
+        Ok(())
+    }
+}
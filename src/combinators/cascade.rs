@@ -0,0 +1,76 @@
+use crate::combinators::{AllOf, Labeled};
+use crate::cores::FixedWindowCounterCore;
+use crate::Uint;
+
+/// Builder for the common "N per second, M per minute, K per hour" cascade.
+///
+/// Each configured tier becomes a [`FixedWindowCounterCore`] with a window of one
+/// second, minute or hour, and the tiers are combined with [`AllOf`], so the
+/// built limiter admits a request only if every tier does. Tiers are labeled
+/// `"second"`, `"minute"` and `"hour"`, and
+/// [`AllOf::try_acquire_labeled_at`] reports the first tier that denied.
+/// Tiers that are not configured are left out.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::Cascade;
+///
+/// // Ticks are milliseconds
+/// let limiter = Cascade::per_second(100).per_minute(1000).per_hour(10_000).build(1000);
+///
+/// assert_eq!(limiter.try_acquire_labeled_at(0, 100), Ok(()));
+/// let err = limiter.try_acquire_labeled_at(500, 1).unwrap_err();
+/// assert_eq!(err.label, "second");
+/// assert_eq!(err.retry_after_ticks(), Some(500));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Cascade {
+    /// Limit per second, if any
+    per_second: Option<Uint>,
+    /// Limit per minute, if any
+    per_minute: Option<Uint>,
+    /// Limit per hour, if any
+    per_hour: Option<Uint>,
+}
+
+impl Cascade {
+    /// Starts a cascade with a per-second limit.
+    pub fn per_second(limit: Uint) -> Self {
+        Cascade { per_second: Some(limit), ..Cascade::default() }
+    }
+
+    /// Adds a per-minute limit.
+    pub fn per_minute(mut self, limit: Uint) -> Self {
+        self.per_minute = Some(limit);
+        self
+    }
+
+    /// Adds a per-hour limit.
+    pub fn per_hour(mut self, limit: Uint) -> Self {
+        self.per_hour = Some(limit);
+        self
+    }
+
+    /// Builds the cascade for a clock running at `ticks_per_second`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ticks_per_second` or any configured limit is zero.
+    pub fn build(self, ticks_per_second: Uint) -> AllOf {
+        assert!(ticks_per_second > 0, "ticks_per_second must be greater than 0");
+        let tiers = [
+            ("second", self.per_second, ticks_per_second),
+            ("minute", self.per_minute, ticks_per_second.saturating_mul(60)),
+            ("hour", self.per_hour, ticks_per_second.saturating_mul(3600)),
+        ];
+
+        let children = tiers
+            .iter()
+            .filter_map(|&(label, limit, window_ticks)| {
+                limit.map(|limit| Labeled::new(label, FixedWindowCounterCore::new(limit, window_ticks)))
+            })
+            .collect();
+        AllOf::new(children)
+    }
+}
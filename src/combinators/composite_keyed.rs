@@ -0,0 +1,172 @@
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use crate::combinators::key_table::KeyTable;
+use crate::rate_limit::{roll_back, roll_back_verbose, RateLimitCore, RefundableCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Factory building the limiter for a new key.
+type GlobalFactory<K, G> = Box<dyn Fn(&K) -> G + Send + Sync>;
+/// Factory building the limiter for a new `(key, method)` pair.
+type MethodFactory<K, M, L> = Box<dyn Fn(&K, &M) -> L + Send + Sync>;
+
+/// Limits each `(key, method)` pair separately while every method of a key
+/// also draws on that key's shared global budget.
+///
+/// A request is admitted only if both its method limiter and its key's global
+/// limiter admit it. The method limiter is consulted first; if the global
+/// limiter then denies, the tokens just taken from the method limiter are
+/// refunded, so a denied request leaves both untouched. The refund is retried
+/// while the method limiter is contended; if another caller has already moved
+/// it past the tick, `ExpiredTick` is returned instead of the denial.
+///
+/// Limiters are created on first use by the factories given to [`new`](Self::new).
+/// At most `max_keys` keys and `max_keys` `(key, method)` pairs are kept; the
+/// least recently used is evicted to make room for a new one and starts over
+/// with a fresh limiter on its next request.
+/// Because an acquire is addressed by key and method, this type exposes its own
+/// methods instead of implementing [`RateLimitCore`].
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::CompositeKeyed;
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::SimpleRateLimitError;
+///
+/// // 10 requests per key and 6 per method of a key, per window of 100 ticks
+/// let limiter = CompositeKeyed::new(
+///     |_key: &&str| FixedWindowCounterCore::new(10, 100),
+///     |_key: &&str, _method: &&str| FixedWindowCounterCore::new(6, 100),
+///     1024,
+/// );
+///
+/// assert_eq!(limiter.try_acquire_at(&"alice", &"POST", 0, 6), Ok(()));
+/// assert_eq!(limiter.try_acquire_at(&"alice", &"GET", 0, 5), Err(SimpleRateLimitError::InsufficientCapacity));
+/// assert_eq!(limiter.capacity_remaining(&"alice", &"GET", 0), Ok((6, 4)));
+/// ```
+pub struct CompositeKeyed<K, M, G, L> {
+    /// Builds the global limiter of a new key
+    new_global: GlobalFactory<K, G>,
+    /// Builds the limiter of a new `(key, method)` pair
+    new_method: MethodFactory<K, M, L>,
+    /// Global limiter of the most recently used keys
+    globals: Mutex<KeyTable<K, G>>,
+    /// Limiter of the most recently used `(key, method)` pairs
+    methods: Mutex<KeyTable<(K, M), L>>,
+}
+
+impl<K, M, G, L> CompositeKeyed<K, M, G, L>
+where
+    K: Eq + Hash + Clone,
+    M: Eq + Hash + Clone,
+    G: RateLimitCore,
+    L: RefundableCore,
+{
+    /// Creates a composite limiter with no keys yet.
+    ///
+    /// # Parameters
+    /// * `new_global` - Builds the global limiter of a key on its first request
+    /// * `new_method` - Builds the limiter of a `(key, method)` pair on its first request
+    /// * `max_keys` - Maximum number of keys, and of `(key, method)` pairs, kept at once
+    ///
+    /// # Panics
+    /// Panics if `max_keys` is zero.
+    pub fn new(
+        new_global: impl Fn(&K) -> G + Send + Sync + 'static,
+        new_method: impl Fn(&K, &M) -> L + Send + Sync + 'static,
+        max_keys: usize,
+    ) -> Self {
+        CompositeKeyed {
+            new_global: Box::new(new_global),
+            new_method: Box::new(new_method),
+            globals: Mutex::new(KeyTable::new(max_keys)),
+            methods: Mutex::new(KeyTable::new(max_keys)),
+        }
+    }
+
+    /// Returns the limiters for `key` and `method` as `(method, global)`,
+    /// creating any that do not exist yet.
+    fn limiters(&self, key: &K, method: &M) -> Result<(Arc<L>, Arc<G>), SimpleRateLimitError> {
+        let method_limiter = {
+            let mut methods = match self.methods.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+            };
+            methods.get_or_insert_with(&(key.clone(), method.clone()), || (self.new_method)(key, method))
+        };
+        let global_limiter = {
+            let mut globals = match self.globals.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+            };
+            globals.get_or_insert_with(key, || (self.new_global)(key))
+        };
+        Ok((method_limiter, global_limiter))
+    }
+
+    /// Attempts to acquire `tokens` for `method` of `key` at the given tick.
+    ///
+    /// Both the method limiter and the key's global limiter must admit the
+    /// request. If the global limiter denies, the tokens already acquired from
+    /// the method limiter are refunded and the global limiter's error is returned.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If both limiters admitted the request
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire a key table lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the refund could not be made
+    /// * `Err(_)` - Otherwise, the error of the first limiter that denied it
+    pub fn try_acquire_at(&self, key: &K, method: &M, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let (method_limiter, global_limiter) = self.limiters(key, method)?;
+        method_limiter.try_acquire_at(tick, tokens)?;
+
+        match global_limiter.try_acquire_at(tick, tokens) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                roll_back(method_limiter.as_ref(), tick, tokens)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Attempts to acquire `tokens` for `method` of `key` at the given tick,
+    /// with diagnostic information on failure.
+    ///
+    /// Like [`try_acquire_at`](Self::try_acquire_at), the method limiter is
+    /// consulted first and refunded if the global limiter denies; a refund that
+    /// fails is reported as `ExpiredTick`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If both limiters admitted the request
+    /// * `Err(VerboseRateLimitError::ContentionFailure)` - If unable to acquire a key table lock
+    /// * `Err(VerboseRateLimitError::ExpiredTick { .. })` - If the refund could not be made
+    /// * `Err(_)` - Otherwise, the error of the first limiter that denied it
+    pub fn try_acquire_verbose_at(&self, key: &K, method: &M, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let (method_limiter, global_limiter) = match self.limiters(key, method) {
+            Ok(limiters) => limiters,
+            Err(_) => return Err(VerboseRateLimitError::ContentionFailure),
+        };
+        method_limiter.try_acquire_verbose_at(tick, tokens)?;
+
+        match global_limiter.try_acquire_verbose_at(tick, tokens) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                roll_back_verbose(method_limiter.as_ref(), tick, tokens)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the remaining capacity for `method` of `key` as `(method, global)`.
+    ///
+    /// A request can be admitted up to the smaller of the two.
+    ///
+    /// # Returns
+    /// * `Ok((method, global))` - Remaining capacity of each limiter
+    /// * `Err(_)` - The error of the first limiter that could not report its capacity
+    pub fn capacity_remaining(&self, key: &K, method: &M, tick: Uint) -> Result<(Uint, Uint), SimpleRateLimitError> {
+        let (method_limiter, global_limiter) = self.limiters(key, method)?;
+        let method_remaining = method_limiter.capacity_remaining(tick)?;
+        let global_remaining = global_limiter.capacity_remaining(tick)?;
+        Ok((method_remaining, global_remaining))
+    }
+}
@@ -0,0 +1,97 @@
+//! Bounded table of per-key limiters.
+//!
+//! [`CompositeKeyed`](crate::combinators::CompositeKeyed) and
+//! [`ProvidedKeyed`](crate::combinators::ProvidedKeyed) build a limiter the
+//! first time they see a key. [`KeyTable`] keeps at most `max_keys` of them,
+//! evicting the least recently used key to make room for a new one. An
+//! evicted key starts over with a fresh limiter on its next request.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Limiters by key, at most `max_keys` of them, in least recently used order.
+pub(crate) struct KeyTable<K, V> {
+    /// Maximum number of keys kept at once
+    max_keys: usize,
+    /// Limiter of every kept key, with the tag of its latest use in `order`
+    entries: HashMap<K, (Arc<V>, u64)>,
+    /// Keys in order of use, least recent first, tagged with the use they
+    /// record; a tag older than the key's latest use is stale and skipped
+    order: VecDeque<(K, u64)>,
+    /// Counter tagging each use of a key
+    next_use: u64,
+}
+
+impl<K: Eq + Hash + Clone, V> KeyTable<K, V> {
+    /// Creates an empty table.
+    ///
+    /// # Panics
+    /// Panics if `max_keys` is zero.
+    pub(crate) fn new(max_keys: usize) -> Self {
+        assert!(max_keys > 0, "max_keys must be greater than 0");
+        KeyTable {
+            max_keys,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            next_use: 0,
+        }
+    }
+
+    /// Records a use of `key`, making it the most recently used.
+    fn touch(&mut self, key: &K) -> u64 {
+        let tag = self.next_use;
+        self.next_use += 1;
+        self.order.push_back((key.clone(), tag));
+        tag
+    }
+
+    /// Forgets the least recently used key.
+    fn evict_oldest(&mut self) {
+        while let Some((key, tag)) = self.order.pop_front() {
+            if self.entries.get(&key).map_or(false, |(_, last_use)| *last_use == tag) {
+                self.entries.remove(&key);
+                return;
+            }
+        }
+    }
+
+    /// Drops stale tags once they outnumber the live ones, keeping `order`
+    /// within twice the number of kept keys.
+    fn compact(&mut self) {
+        if self.order.len() > 2 * self.entries.len() {
+            let entries = &self.entries;
+            self.order.retain(|(key, tag)| entries.get(key).map_or(false, |(_, last_use)| last_use == tag));
+        }
+    }
+
+    /// Returns the limiter of `key` if it is kept, marking it most recently used.
+    pub(crate) fn get(&mut self, key: &K) -> Option<Arc<V>> {
+        if !self.entries.contains_key(key) {
+            return None;
+        }
+        let tag = self.touch(key);
+        let entry = self.entries.get_mut(key)?;
+        entry.1 = tag;
+        let value = entry.0.clone();
+        self.compact();
+        Some(value)
+    }
+
+    /// Returns the limiter of `key`, building it with `make` if it is not kept.
+    ///
+    /// Making room for a new key evicts the least recently used one.
+    pub(crate) fn get_or_insert_with(&mut self, key: &K, make: impl FnOnce() -> V) -> Arc<V> {
+        if let Some(value) = self.get(key) {
+            return value;
+        }
+        if self.entries.len() == self.max_keys {
+            self.evict_oldest();
+        }
+        let tag = self.touch(key);
+        let value = Arc::new(make());
+        self.entries.insert(key.clone(), (value.clone(), tag));
+        self.compact();
+        value
+    }
+}
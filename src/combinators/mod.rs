@@ -0,0 +1,56 @@
+//! Rate limiters composed from several cores.
+//!
+//! Combinators make a single admission decision out of multiple underlying
+//! limiters. When one limiter denies a request after another has already
+//! admitted it, the admitted tokens are rolled back through
+//! [`RefundableCore`](crate::rate_limit::RefundableCore), so a denied request
+//! never consumes capacity.
+//!
+//! # Available Combinators
+//!
+//! - **[`AbTest`]** - Enforces one limiter while tallying disagreements with a shadow candidate
+//! - **[`TwoDimensional`]** - Limits request count and byte volume at the same time
+//! - **[`AllOf`]** - Admits only if every [`Labeled`] child admits, reporting which one denied
+//! - **[`Cascade`]** - Builds per-second, per-minute and per-hour tiers combined with [`AllOf`]
+//! - **[`CompositeKeyed`]** - Per-`(key, method)` limits that also share a per-key global budget
+//! - **[`MoreGenerous`]** - Charges whichever child has the most remaining capacity
+//! - **[`ProvidedKeyed`]** - Per-key token buckets built from quotas looked up through a [`QuotaProvider`]
+//! - **[`Scheduled`]** - Routes to a peak or off-peak limiter according to a tick schedule
+//! - **[`ShardedTokenBucket`]** - Token bucket split into shards so concurrent acquires rarely contend
+//! - **[`SplitLimiter`]** - Separate budgets for success and error responses
+//! - **[`WithReserve`]** - Borrows from a reserve limiter once the primary runs out
+
+pub mod ab_test;
+pub use ab_test::AbTest;
+
+pub mod two_dimensional;
+pub use two_dimensional::TwoDimensional;
+
+pub mod all_of;
+pub use all_of::{AllOf, Labeled};
+
+pub mod cascade;
+pub use cascade::Cascade;
+
+pub mod composite_keyed;
+pub use composite_keyed::CompositeKeyed;
+
+pub(crate) mod key_table;
+
+pub mod more_generous;
+pub use more_generous::MoreGenerous;
+
+pub mod provided_keyed;
+pub use provided_keyed::{ProvidedKeyed, QuotaProvider};
+
+pub mod scheduled;
+pub use scheduled::Scheduled;
+
+pub mod sharded;
+pub use sharded::ShardedTokenBucket;
+
+pub mod split;
+pub use split::SplitLimiter;
+
+pub mod with_reserve;
+pub use with_reserve::{Pool, WithReserve};
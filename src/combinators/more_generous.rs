@@ -0,0 +1,134 @@
+use crate::rate_limit::{summed_allowance, RateLimitCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Charges whichever child limiter has the most remaining capacity.
+///
+/// Each call asks every child for its `capacity_remaining` and forwards the
+/// request to the child with the most headroom (the first one on a tie), so
+/// "100 per minute or 1000 per hour, whichever gives the client more right now"
+/// admits as long as either quota has room. `capacity_remaining` reports the
+/// largest remaining capacity among the children.
+///
+/// Only the most generous child is charged per call. The other children do not
+/// see the request, so they under-enforce their own limits: a client can spend
+/// the whole hourly quota without the minute window ever filling up. This is
+/// intended for forgiving tiers; use [`AllOf`](crate::combinators::AllOf) when
+/// every limit must hold.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::MoreGenerous;
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+///
+/// let limiter = MoreGenerous::new(vec![
+///     Box::new(FixedWindowCounterCore::new(100, 60)),
+///     Box::new(FixedWindowCounterCore::new(1000, 3600)),
+/// ]);
+///
+/// assert_eq!(limiter.capacity_remaining(0), Ok(1000));
+/// assert_eq!(limiter.try_acquire_at(0, 500), Ok(()));
+/// ```
+pub struct MoreGenerous {
+    /// Child limiters; the first wins a tie on remaining capacity
+    children: Vec<Box<dyn RateLimitCore>>,
+}
+
+impl MoreGenerous {
+    /// Creates a new combinator from its children.
+    ///
+    /// # Panics
+    /// Panics if `children` is empty.
+    pub fn new(children: Vec<Box<dyn RateLimitCore>>) -> Self {
+        assert!(!children.is_empty(), "children must not be empty");
+        MoreGenerous { children }
+    }
+
+    /// Returns the index and remaining capacity of the child with the most
+    /// remaining capacity at `tick`.
+    ///
+    /// # Returns
+    /// * `Ok((index, remaining))` - The most generous child
+    /// * `Err(_)` - The error of the first child whose capacity could not be read
+    pub fn most_generous_at(&self, tick: Uint) -> Result<(usize, Uint), SimpleRateLimitError> {
+        self.select(tick).map_err(|(_, e)| e)
+    }
+
+    /// Finds the most generous child, or the index and error of the first
+    /// child whose capacity could not be read.
+    fn select(&self, tick: Uint) -> Result<(usize, Uint), (usize, SimpleRateLimitError)> {
+        let mut best: Option<(usize, Uint)> = None;
+        for (i, child) in self.children.iter().enumerate() {
+            let remaining = child.capacity_remaining(tick).map_err(|e| (i, e))?;
+            if best.map_or(true, |(_, most)| remaining > most) {
+                best = Some((i, remaining));
+            }
+        }
+        Ok(best.expect("children is not empty"))
+    }
+
+    /// Attempts to acquire tokens from the child with the most remaining capacity.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If that child admitted the request
+    /// * `Err(_)` - Its error, or the error reading a child's remaining capacity
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let (index, _) = self.most_generous_at(tick)?;
+        self.children[index].try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens from the child with the most remaining
+    /// capacity, returning that child's verbose error.
+    ///
+    /// If a child's remaining capacity cannot be read, the request is forwarded
+    /// to that child so that its verbose error describes the failure.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let index = match self.select(tick) {
+            Ok((index, _)) => index,
+            Err((index, _)) => index,
+        };
+        self.children[index].try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the largest remaining capacity among the children.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.most_generous_at(tick).map(|(_, remaining)| remaining)
+    }
+
+    /// Returns the sum of the children's steady-state allowances.
+    ///
+    /// Each request is charged to a single child, so over time the children
+    /// renew their allowances side by side.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.children
+            .iter()
+            .fold((0, 1), |sum, child| summed_allowance(sum, child.steady_state_allowance()))
+    }
+
+    /// Returns the children of this combinator.
+    pub fn children(&self) -> &[Box<dyn RateLimitCore>] {
+        &self.children
+    }
+}
+
+impl RateLimitCore for MoreGenerous {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
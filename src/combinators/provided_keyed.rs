@@ -0,0 +1,138 @@
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use crate::combinators::key_table::KeyTable;
+use crate::cores::{TokenBucketCore, TokenBucketCoreConfig};
+use crate::{KeyedRateLimitError, KeyedRateLimitResult, SimpleRateLimitError, Uint};
+
+/// Source of per-key token bucket quotas, such as a tenant database.
+///
+/// Implemented for any `Fn(&K) -> Option<TokenBucketCoreConfig>`.
+pub trait QuotaProvider<K> {
+    /// Returns the quota for `key`, or `None` if the key is unknown.
+    fn quota_for(&self, key: &K) -> Option<TokenBucketCoreConfig>;
+}
+
+impl<K, F> QuotaProvider<K> for F
+where
+    F: Fn(&K) -> Option<TokenBucketCoreConfig>,
+{
+    fn quota_for(&self, key: &K) -> Option<TokenBucketCoreConfig> {
+        self(key)
+    }
+}
+
+/// Keeps one token bucket per key, built from quotas looked up on demand.
+///
+/// The first acquire for a key asks the [`QuotaProvider`] for its quota and
+/// caches the bucket built from it; later acquires use the cached bucket
+/// without consulting the provider. A key the provider does not know is
+/// denied with [`KeyedRateLimitError::UnknownTenant`], and a quota no bucket
+/// can be built from, such as one with a zero capacity, with
+/// [`KeyedRateLimitError::InvalidQuota`]. Neither is cached, so the key is
+/// looked up again on its next acquire.
+///
+/// At most `max_keys` buckets are cached; the least recently used key is
+/// evicted to make room for a new one and starts over with a full bucket on
+/// its next acquire.
+///
+/// The provider is called without holding the key table lock. If two first
+/// acquires for the same key race, both may call the provider and the first
+/// bucket stored is kept.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::ProvidedKeyed;
+/// use rate_guard_core::cores::TokenBucketCoreConfig;
+/// use rate_guard_core::KeyedRateLimitError;
+///
+/// let limiter = ProvidedKeyed::new(
+///     |tenant: &&str| match *tenant {
+///         "acme" => Some(TokenBucketCoreConfig::new(10, 1, 1)),
+///         _ => None,
+///     },
+///     1024,
+/// );
+///
+/// assert_eq!(limiter.try_acquire_at(&"acme", 0, 10), Ok(()));
+/// assert_eq!(limiter.try_acquire_at(&"globex", 0, 1), Err(KeyedRateLimitError::UnknownTenant));
+/// ```
+pub struct ProvidedKeyed<K, P> {
+    /// Source of quotas for keys seen for the first time
+    provider: P,
+    /// Bucket of the most recently used known keys
+    buckets: Mutex<KeyTable<K, TokenBucketCore>>,
+}
+
+impl<K, P> ProvidedKeyed<K, P>
+where
+    K: Eq + Hash + Clone,
+    P: QuotaProvider<K>,
+{
+    /// Creates a keyed limiter with no cached keys.
+    ///
+    /// # Parameters
+    /// * `provider` - Source of quotas for keys seen for the first time
+    /// * `max_keys` - Maximum number of buckets cached at once
+    ///
+    /// # Panics
+    /// Panics if `max_keys` is zero.
+    pub fn new(provider: P, max_keys: usize) -> Self {
+        ProvidedKeyed {
+            provider,
+            buckets: Mutex::new(KeyTable::new(max_keys)),
+        }
+    }
+
+    /// Returns the bucket of `key`, building it from the provider's quota on first use.
+    fn bucket(&self, key: &K) -> Result<Arc<TokenBucketCore>, KeyedRateLimitError> {
+        {
+            let mut buckets = match self.buckets.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => return Err(SimpleRateLimitError::ContentionFailure.into()),
+            };
+            if let Some(bucket) = buckets.get(key) {
+                return Ok(bucket);
+            }
+        }
+
+        let config = self.provider.quota_for(key).ok_or(KeyedRateLimitError::UnknownTenant)?;
+        let bucket = TokenBucketCore::try_from_config(config).map_err(KeyedRateLimitError::InvalidQuota)?;
+        let mut buckets = match self.buckets.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure.into()),
+        };
+        Ok(buckets.get_or_insert_with(key, || bucket))
+    }
+
+    /// Attempts to acquire `tokens` for `key` at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the key's bucket admitted the request
+    /// * `Err(KeyedRateLimitError::UnknownTenant)` - If the provider has no quota for `key`
+    /// * `Err(KeyedRateLimitError::InvalidQuota(_))` - If the provider's quota for `key` is invalid
+    /// * `Err(KeyedRateLimitError::Limit(_))` - If the bucket denied the request, or the key
+    ///   table lock was unavailable (`ContentionFailure`)
+    pub fn try_acquire_at(&self, key: &K, tick: Uint, tokens: Uint) -> KeyedRateLimitResult {
+        self.bucket(key)?.try_acquire_at(tick, tokens)?;
+        Ok(())
+    }
+
+    /// Returns the remaining capacity of `key`'s bucket at the given tick.
+    ///
+    /// Like an acquire, this looks up and caches the quota of a key seen for the first time.
+    ///
+    /// # Returns
+    /// * `Ok(remaining)` - Tokens available in the key's bucket
+    /// * `Err(KeyedRateLimitError::UnknownTenant)` - If the provider has no quota for `key`
+    /// * `Err(KeyedRateLimitError::InvalidQuota(_))` - If the provider's quota for `key` is invalid
+    /// * `Err(KeyedRateLimitError::Limit(_))` - If the bucket could not report its capacity
+    pub fn capacity_remaining(&self, key: &K, tick: Uint) -> Result<Uint, KeyedRateLimitError> {
+        Ok(self.bucket(key)?.capacity_remaining(tick)?)
+    }
+
+    /// Returns a reference to the quota provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+}
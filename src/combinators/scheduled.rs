@@ -0,0 +1,124 @@
+use std::cmp;
+use crate::rate_limit::{compare_allowance, RateLimitCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Switches between a peak and an off-peak limiter on a tick-based schedule.
+///
+/// Every call asks the schedule predicate whether `tick` is in a peak period and
+/// routes to the peak limiter if so, or to the off-peak limiter otherwise, for
+/// example to enforce stricter limits during business hours.
+///
+/// The two limiters keep their own state. Switching periods does not move
+/// consumption between them: tokens acquired from the off-peak limiter just
+/// before the peak period starts are not charged to the peak limiter, and are
+/// still counted by the off-peak limiter when the schedule switches back.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::Scheduled;
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+///
+/// // Ticks are hours; peak is 9:00-17:00
+/// let limiter = Scheduled::new(
+///     FixedWindowCounterCore::new(10, 1),
+///     FixedWindowCounterCore::new(100, 1),
+///     |tick| (9..17).contains(&(tick % 24)),
+/// );
+///
+/// assert!(limiter.try_acquire_at(10, 11).is_err());
+/// assert_eq!(limiter.try_acquire_at(20, 11), Ok(()));
+/// ```
+pub struct Scheduled<L: RateLimitCore, R: RateLimitCore> {
+    /// Limiter used while the schedule reports a peak period
+    peak: L,
+    /// Limiter used outside peak periods
+    off_peak: R,
+    /// Returns whether a tick falls in a peak period
+    is_peak: Box<dyn Fn(Uint) -> bool + Send + Sync>,
+}
+
+impl<L: RateLimitCore, R: RateLimitCore> Scheduled<L, R> {
+    /// Creates a new scheduled limiter.
+    ///
+    /// # Parameters
+    /// * `peak` - Limiter used while `is_peak(tick)` is true
+    /// * `off_peak` - Limiter used while `is_peak(tick)` is false
+    /// * `is_peak` - Schedule predicate over the tick
+    pub fn new<F>(peak: L, off_peak: R, is_peak: F) -> Self
+    where
+        F: Fn(Uint) -> bool + Send + Sync + 'static,
+    {
+        Scheduled { peak, off_peak, is_peak: Box::new(is_peak) }
+    }
+
+    /// Returns the limiter in force at `tick`.
+    #[inline(always)]
+    fn select(&self, tick: Uint) -> &dyn RateLimitCore {
+        if (self.is_peak)(tick) {
+            &self.peak
+        } else {
+            &self.off_peak
+        }
+    }
+
+    /// Attempts to acquire tokens from the limiter in force at `tick`.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.select(tick).try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens from the limiter in force at `tick`,
+    /// returning detailed diagnostics on failure.
+    ///
+    /// `retry_after_ticks` comes from that limiter alone and does not account
+    /// for the schedule switching in the meantime.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.select(tick).try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the remaining capacity of the limiter in force at `tick`.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.select(tick).capacity_remaining(tick)
+    }
+
+    /// Returns the lower of the peak and off-peak steady-state allowances.
+    ///
+    /// The share of ticks the schedule spends in each period is unknown, so
+    /// this is the rate that holds at any time of day.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        let (peak, off_peak) = (self.peak.steady_state_allowance(), self.off_peak.steady_state_allowance());
+        cmp::min_by(peak, off_peak, |a, b| compare_allowance(*a, *b))
+    }
+
+    /// Returns a reference to the peak limiter.
+    pub fn peak(&self) -> &L {
+        &self.peak
+    }
+
+    /// Returns a reference to the off-peak limiter.
+    pub fn off_peak(&self) -> &R {
+        &self.off_peak
+    }
+}
+
+impl<L: RateLimitCore, R: RateLimitCore> RateLimitCore for Scheduled<L, R> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
@@ -0,0 +1,208 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::cores::TokenBucketCore;
+use crate::rate_limit::{reduced_allowance, RateLimitCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Token bucket split into independent shards, so concurrent acquires rarely
+/// contend for the same lock.
+///
+/// The capacity and refill amount are divided among `shard_count`
+/// [`TokenBucketCore`] shards. Every shard gets the same capacity, with the
+/// capacity rounded down to a multiple of `shard_count`, so a request is either
+/// within every shard's capacity or beyond all of them, whichever shard it is
+/// routed to. The refill remainder goes to the first shards, so the shards
+/// together refill at the configured rate. Each acquire is charged to one shard only: the next one in
+/// round-robin order for [`try_acquire_at`](Self::try_acquire_at), or the one
+/// selected by a caller-supplied hash for [`try_acquire_hashed_at`](Self::try_acquire_hashed_at).
+/// Acquires routed to different shards take different locks, so they no longer
+/// fail with `ContentionFailure` because of each other.
+///
+/// # Accuracy
+///
+/// The shards do not share tokens. A request routed to an empty shard is
+/// denied even if another shard has room, so the limiter may admit fewer
+/// tokens than a single bucket while traffic is uneven across shards. It never
+/// admits more. A single request can take at most one shard's capacity and is
+/// denied with `BeyondCapacity` above it. [`capacity_remaining`](Self::capacity_remaining)
+/// reports the sum across shards, which may be more than any one request can get;
+/// [`max_acquirable_at`](Self::max_acquirable_at) reports the fullest shard.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::ShardedTokenBucket;
+///
+/// // 100 tokens refilled at 8 per tick, across 4 shards of 25
+/// let limiter = ShardedTokenBucket::new(4, 100, 1, 8);
+///
+/// assert_eq!(limiter.try_acquire_hashed_at(7, 0, 25), Ok(()));
+/// // Shard 3 is empty, the others still have room
+/// assert!(limiter.try_acquire_hashed_at(7, 0, 1).is_err());
+/// assert_eq!(limiter.try_acquire_hashed_at(8, 0, 1), Ok(()));
+/// assert_eq!(limiter.capacity_remaining(0), Ok(74));
+/// ```
+pub struct ShardedTokenBucket {
+    /// Independent buckets sharing the configured capacity and refill
+    shards: Vec<TokenBucketCore>,
+    /// Round-robin position of the next unhashed acquire
+    next_shard: AtomicUsize,
+    /// Number of ticks between each refill event
+    refill_interval: Uint,
+    /// Number of tokens added in each refill event, across all shards
+    refill_amount: Uint,
+}
+
+impl ShardedTokenBucket {
+    /// Creates a sharded token bucket, with every shard starting full.
+    ///
+    /// # Parameters
+    /// * `shard_count` - Number of independent shards
+    /// * `capacity` - Total capacity, rounded down to a multiple of `shard_count` and divided among the shards
+    /// * `refill_interval` - Number of ticks between each refill event
+    /// * `refill_amount` - Total tokens added per refill event, divided among the shards
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parameter is zero, or if `capacity` or `refill_amount` is
+    /// less than `shard_count`, which would leave a shard with nothing.
+    pub fn new(shard_count: usize, capacity: Uint, refill_interval: Uint, refill_amount: Uint) -> Self {
+        assert!(shard_count > 0, "shard_count must be greater than 0");
+        assert!(refill_interval > 0, "refill_interval must be greater than 0");
+        let count = Uint::try_from(shard_count).expect("shard_count must fit in Uint");
+        assert!(capacity >= count, "capacity must be at least shard_count");
+        assert!(refill_amount >= count, "refill_amount must be at least shard_count");
+
+        // Refill share of shard `index`, with the remainder spread over the first shards
+        let refill_share = |index: Uint| refill_amount / count + Uint::from(index < refill_amount % count);
+        let shards = (0..count)
+            .map(|index| TokenBucketCore::new(capacity / count, refill_interval, refill_share(index)))
+            .collect();
+
+        ShardedTokenBucket {
+            shards,
+            next_shard: AtomicUsize::new(0),
+            refill_interval,
+            refill_amount,
+        }
+    }
+
+    /// Returns the number of shards.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns a reference to the shard at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`shard_count`](Self::shard_count).
+    pub fn shard(&self, index: usize) -> &TokenBucketCore {
+        &self.shards[index]
+    }
+
+    /// Returns the next shard in round-robin order.
+    #[inline(always)]
+    fn next(&self) -> &TokenBucketCore {
+        let index = self.next_shard.fetch_add(1, Ordering::Relaxed) % self.shards.len();
+        &self.shards[index]
+    }
+
+    /// Returns the shard selected by `hash`.
+    #[inline(always)]
+    fn hashed(&self, hash: u64) -> &TokenBucketCore {
+        // The remainder is less than the shard count, so it fits in usize
+        &self.shards[(hash % self.shards.len() as u64) as usize]
+    }
+
+    /// Attempts to acquire tokens from the next shard in round-robin order.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.next().try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens from the next shard in round-robin order,
+    /// returning detailed diagnostics on failure.
+    ///
+    /// The diagnostics describe that shard alone.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.next().try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens from the shard selected by `hash`, such as a
+    /// hash of the caller's thread or connection.
+    ///
+    /// The shard is `hash % shard_count`, so callers with stable hashes keep
+    /// using the same shard.
+    pub fn try_acquire_hashed_at(&self, hash: u64, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.hashed(hash).try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens from the shard selected by `hash`, returning
+    /// detailed diagnostics on failure.
+    ///
+    /// The diagnostics describe that shard alone.
+    pub fn try_acquire_hashed_verbose_at(&self, hash: u64, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.hashed(hash).try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the sum of the remaining capacity of all shards.
+    ///
+    /// # Returns
+    /// * `Ok(remaining)` - Tokens available across all shards
+    /// * `Err(_)` - The error of the first shard that could not report its capacity
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut remaining: Uint = 0;
+        for shard in &self.shards {
+            remaining = remaining.saturating_add(shard.capacity_remaining(tick)?);
+        }
+        Ok(remaining)
+    }
+
+    /// Returns the largest remaining capacity of any one shard.
+    ///
+    /// A request is charged to a single shard, so this is the most any request
+    /// can get at `tick`. It is only admitted if it is routed to that shard.
+    ///
+    /// # Returns
+    /// * `Ok(max)` - Tokens available in the fullest shard
+    /// * `Err(_)` - The error of the first shard that could not report its capacity
+    pub fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut max: Uint = 0;
+        for shard in &self.shards {
+            max = max.max(shard.max_acquirable_at(tick)?);
+        }
+        Ok(max)
+    }
+
+    /// Returns the long-run sustainable rate of all shards together as a reduced
+    /// `(tokens, ticks)` fraction, `refill_amount / refill_interval`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.refill_amount, self.refill_interval)
+    }
+}
+
+impl RateLimitCore for ShardedTokenBucket {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.max_acquirable_at(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
@@ -0,0 +1,83 @@
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Keeps separate budgets for success and error responses.
+///
+/// Every acquire is routed by its `is_error` flag: successes are charged to the
+/// success limiter and errors to the error limiter. The two budgets never
+/// affect each other, so a burst of upstream failures can be throttled to avoid
+/// amplification without slowing down healthy traffic.
+///
+/// Because every call carries the routing flag, this type exposes its own
+/// methods instead of implementing [`RateLimitCore`].
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::SplitLimiter;
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+///
+/// let limiter = SplitLimiter::new(
+///     FixedWindowCounterCore::new(100, 60),
+///     FixedWindowCounterCore::new(5, 60),
+/// );
+///
+/// assert_eq!(limiter.try_acquire_at(0, 5, true), Ok(()));
+/// assert!(limiter.try_acquire_at(0, 1, true).is_err());
+/// assert_eq!(limiter.try_acquire_at(0, 1, false), Ok(()));
+/// ```
+pub struct SplitLimiter<S: RateLimitCore, E: RateLimitCore> {
+    /// Limiter charged for success responses
+    success: S,
+    /// Limiter charged for error responses
+    error: E,
+}
+
+impl<S: RateLimitCore, E: RateLimitCore> SplitLimiter<S, E> {
+    /// Creates a new split limiter.
+    ///
+    /// # Parameters
+    /// * `success` - Limiter charged for success responses
+    /// * `error` - Limiter charged for error responses
+    pub fn new(success: S, error: E) -> Self {
+        SplitLimiter { success, error }
+    }
+
+    /// Attempts to acquire tokens from the budget selected by `is_error`.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint, is_error: bool) -> SimpleRateLimitResult {
+        if is_error {
+            self.error.try_acquire_at(tick, tokens)
+        } else {
+            self.success.try_acquire_at(tick, tokens)
+        }
+    }
+
+    /// Attempts to acquire tokens from the budget selected by `is_error`,
+    /// returning detailed diagnostics on failure.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint, is_error: bool) -> VerboseRateLimitResult {
+        if is_error {
+            self.error.try_acquire_verbose_at(tick, tokens)
+        } else {
+            self.success.try_acquire_verbose_at(tick, tokens)
+        }
+    }
+
+    /// Returns the remaining capacity of the budget selected by `is_error`.
+    pub fn capacity_remaining(&self, tick: Uint, is_error: bool) -> Result<Uint, SimpleRateLimitError> {
+        if is_error {
+            self.error.capacity_remaining(tick)
+        } else {
+            self.success.capacity_remaining(tick)
+        }
+    }
+
+    /// Returns a reference to the success limiter.
+    pub fn success(&self) -> &S {
+        &self.success
+    }
+
+    /// Returns a reference to the error limiter.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+}
@@ -0,0 +1,158 @@
+use crate::rate_limit::{roll_back, roll_back_verbose, RateLimitCore, RefundableCore};
+use crate::{
+    Dimension, DimensionalRateLimitError, DimensionalRateLimitResult,
+    SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError,
+};
+
+/// Limits requests by count and by byte volume simultaneously.
+///
+/// A request is admitted only if both the request limiter and the byte limiter
+/// admit it. The request limiter is consulted first; if the byte limiter then
+/// denies, the requests just taken are refunded so the denied call leaves the
+/// request dimension untouched. The refund is retried while the request limiter
+/// is contended, so it is never lost to contention.
+///
+/// Because an acquire carries two amounts, this type exposes its own
+/// `try_acquire_at(tick, requests, bytes)` instead of implementing the
+/// single-amount [`RateLimitCore`] trait.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::TwoDimensional;
+/// use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+///
+/// // 10 requests per window of 100 ticks, 1000 bytes of burst refilled at 100 per 10 ticks
+/// let limiter = TwoDimensional::new(
+///     FixedWindowCounterCore::new(10, 100),
+///     TokenBucketCore::new(1000, 10, 100),
+/// );
+///
+/// assert_eq!(limiter.try_acquire_at(0, 1, 600), Ok(()));
+/// assert!(limiter.try_acquire_at(0, 1, 600).is_err());
+/// assert_eq!(limiter.capacity_remaining(0), Ok((9, 400)));
+/// ```
+pub struct TwoDimensional<R: RefundableCore, B: RateLimitCore> {
+    /// Limiter counting requests
+    requests: R,
+    /// Limiter counting bytes
+    bytes: B,
+}
+
+impl<R: RefundableCore, B: RateLimitCore> TwoDimensional<R, B> {
+    /// Creates a new two-dimensional limiter from a request limiter and a byte limiter.
+    ///
+    /// # Parameters
+    /// * `requests` - Limiter charged with the number of requests
+    /// * `bytes` - Limiter charged with the number of bytes
+    pub fn new(requests: R, bytes: B) -> Self {
+        TwoDimensional { requests, bytes }
+    }
+
+    /// Attempts to acquire `requests` and `bytes` at the given tick.
+    ///
+    /// Both limiters must admit the request. If the byte limiter denies, the
+    /// requests already acquired are refunded and the byte limiter's error is
+    /// returned. If the refund fails because another caller has already moved
+    /// the request limiter past `tick`, its `ExpiredTick` is returned instead,
+    /// since the requests were not given back.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick for the operation
+    /// * `requests` - Number of requests to charge
+    /// * `bytes` - Number of bytes to charge
+    ///
+    /// # Returns
+    /// * `Ok(())` - If both dimensions admitted the request
+    /// * `Err(_)` - The error of the first limiter that denied it
+    pub fn try_acquire_at(&self, tick: Uint, requests: Uint, bytes: Uint) -> SimpleRateLimitResult {
+        self.requests.try_acquire_at(tick, requests)?;
+
+        match self.bytes.try_acquire_at(tick, bytes) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                roll_back(&self.requests, tick, requests)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Attempts to acquire `requests` and `bytes` at the given tick, reporting
+    /// which dimension denied the request.
+    ///
+    /// Both limiters are consulted even when the request limiter denies, so the
+    /// caller learns the real wait: if both dimensions are short of capacity, the
+    /// one with the later `retry_after_ticks` is reported (requests on a tie),
+    /// and any other byte error takes precedence since waiting will not fix it.
+    /// After a request denial the byte limiter is only read, through
+    /// `max_acquirable_at`, and asked for its diagnostics only if it is short of
+    /// `bytes`, so the denied call takes nothing from it. If it cannot be read,
+    /// the request denial is returned.
+    ///
+    /// If the request limiter fails for a reason other than insufficient capacity,
+    /// that error is returned without consulting the byte limiter. If the byte
+    /// limiter denies after the requests were taken, they are refunded as in
+    /// [`try_acquire_at`](Self::try_acquire_at); a refund that fails is reported
+    /// as `ExpiredTick` on the request dimension.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If both dimensions admitted the request
+    /// * `Err(DimensionalRateLimitError { dimension, error })` - The binding dimension and its error
+    pub fn try_acquire_verbose_at(&self, tick: Uint, requests: Uint, bytes: Uint) -> DimensionalRateLimitResult
+    where
+        B: RefundableCore,
+    {
+        let tag = |dimension, error| DimensionalRateLimitError { dimension, error };
+
+        let requests_error = match self.requests.try_acquire_verbose_at(tick, requests) {
+            Ok(()) => {
+                return match self.bytes.try_acquire_verbose_at(tick, bytes) {
+                    Ok(()) => Ok(()),
+                    Err(e) => {
+                        roll_back_verbose(&self.requests, tick, requests).map_err(|e| tag(Dimension::Requests, e))?;
+                        Err(tag(Dimension::Bytes, e))
+                    }
+                };
+            }
+            Err(e) => e,
+        };
+        let requests_retry = match requests_error {
+            VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. } => retry_after_ticks,
+            _ => return Err(tag(Dimension::Requests, requests_error)),
+        };
+
+        match self.bytes.max_acquirable_at(tick) {
+            Ok(max) if bytes > max => {}
+            _ => return Err(tag(Dimension::Requests, requests_error)),
+        }
+        match self.bytes.try_acquire_verbose_at(tick, bytes) {
+            // Another caller freed bytes since the read; give them back
+            Ok(()) => roll_back_verbose(&self.bytes, tick, bytes).map_err(|e| tag(Dimension::Bytes, e))?,
+            Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. })
+                if retry_after_ticks <= requests_retry => {}
+            Err(e) => return Err(tag(Dimension::Bytes, e)),
+        }
+        Err(tag(Dimension::Requests, requests_error))
+    }
+
+    /// Returns the remaining capacity of both dimensions as `(requests, bytes)`.
+    ///
+    /// # Returns
+    /// * `Ok((requests, bytes))` - Remaining capacity of each limiter
+    /// * `Err(_)` - The error of the first limiter that could not report its capacity
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<(Uint, Uint), SimpleRateLimitError> {
+        let requests = self.requests.capacity_remaining(tick)?;
+        let bytes = self.bytes.capacity_remaining(tick)?;
+        Ok((requests, bytes))
+    }
+
+    /// Returns a reference to the request limiter.
+    pub fn requests(&self) -> &R {
+        &self.requests
+    }
+
+    /// Returns a reference to the byte limiter.
+    pub fn bytes(&self) -> &B {
+        &self.bytes
+    }
+}
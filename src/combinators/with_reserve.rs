@@ -0,0 +1,171 @@
+use crate::rate_limit::{summed_allowance, RateLimitCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// The pool an admission by [`WithReserve`] was charged to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pool {
+    /// The primary limiter admitted the request
+    Primary,
+    /// The primary limiter was out of capacity and the request was borrowed from the reserve
+    Reserve,
+}
+
+/// Borrows from a reserve limiter when the primary limiter runs out.
+///
+/// Every request is offered to the primary limiter first. Only if it is denied
+/// with `InsufficientCapacity` is it offered to the reserve, so other denials
+/// (a request above the primary capacity, an expired tick, lock contention) are
+/// returned unchanged. A typical reserve is a smaller bucket that refills more
+/// slowly, absorbing occasional overflow without raising the sustained rate.
+///
+/// [`WithReserve::try_acquire_pool_at`] reports which pool each admission came
+/// from. `capacity_remaining` reports the sum of both pools.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::combinators::{Pool, WithReserve};
+/// use rate_guard_core::cores::TokenBucketCore;
+///
+/// let limiter = WithReserve::new(
+///     TokenBucketCore::new(10, 1, 1),
+///     TokenBucketCore::new(5, 10, 1),
+/// );
+///
+/// assert_eq!(limiter.try_acquire_pool_at(0, 10), Ok(Pool::Primary));
+/// assert_eq!(limiter.try_acquire_pool_at(0, 3), Ok(Pool::Reserve));
+/// assert_eq!(limiter.capacity_remaining(0), Ok(2));
+/// ```
+pub struct WithReserve<P: RateLimitCore, R: RateLimitCore> {
+    /// Limiter charged first
+    primary: P,
+    /// Limiter borrowed from when the primary is out of capacity
+    reserve: R,
+}
+
+impl<P: RateLimitCore, R: RateLimitCore> WithReserve<P, R> {
+    /// Creates a new limiter with a reserve.
+    ///
+    /// # Parameters
+    /// * `primary` - Limiter charged first
+    /// * `reserve` - Limiter borrowed from when `primary` is out of capacity
+    pub fn new(primary: P, reserve: R) -> Self {
+        WithReserve { primary, reserve }
+    }
+
+    /// Attempts to acquire tokens, reporting which pool admitted them.
+    ///
+    /// # Returns
+    /// * `Ok(Pool::Primary)` - If the primary limiter admitted the request
+    /// * `Ok(Pool::Reserve)` - If the primary was out of capacity and the reserve admitted it
+    /// * `Err(_)` - The reserve's error if both pools were out of capacity, otherwise the primary's error
+    pub fn try_acquire_pool_at(&self, tick: Uint, tokens: Uint) -> Result<Pool, SimpleRateLimitError> {
+        match self.primary.try_acquire_at(tick, tokens) {
+            Ok(()) => Ok(Pool::Primary),
+            Err(SimpleRateLimitError::InsufficientCapacity) => {
+                self.reserve.try_acquire_at(tick, tokens).map(|()| Pool::Reserve)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Attempts to acquire tokens from the primary limiter, then from the reserve.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_pool_at(tick, tokens).map(|_| ())
+    }
+
+    /// Attempts to acquire tokens from the primary limiter, then from the
+    /// reserve, returning detailed diagnostics on failure.
+    ///
+    /// When both pools are out of capacity, the error reports the larger of the
+    /// two pools' available tokens, now and at the next refill, since a request
+    /// is never split across them, and the earlier of the two retry times.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let primary_err = match self.primary.try_acquire_verbose_at(tick, tokens) {
+            Ok(()) => return Ok(()),
+            Err(e @ VerboseRateLimitError::InsufficientCapacity { .. }) => e,
+            Err(e) => return Err(e),
+        };
+        match (primary_err, self.reserve.try_acquire_verbose_at(tick, tokens)) {
+            (_, Ok(())) => Ok(()),
+            (
+                VerboseRateLimitError::InsufficientCapacity {
+                    available: primary_available,
+                    retry_after_ticks: primary_retry,
+                    available_next_refill: primary_next,
+                    ..
+                },
+                Err(VerboseRateLimitError::InsufficientCapacity { available, retry_after_ticks, available_next_refill, .. }),
+            ) => Err(VerboseRateLimitError::InsufficientCapacity {
+                acquiring: tokens,
+                available: primary_available.max(available),
+                retry_after_ticks: primary_retry.min(retry_after_ticks),
+                available_next_refill: primary_next.max(available_next_refill),
+            }),
+            (_, Err(e)) => Err(e),
+        }
+    }
+
+    /// Returns the sum of the remaining capacity of both pools.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let primary = self.primary.capacity_remaining(tick)?;
+        let reserve = self.reserve.capacity_remaining(tick)?;
+        Ok(primary.saturating_add(reserve))
+    }
+
+    /// Returns the largest single acquire that would succeed at `tick`.
+    ///
+    /// A request is charged to one pool, never split across both, so this is
+    /// the larger of the two pools' `max_acquirable_at`, not the sum that
+    /// `capacity_remaining` reports.
+    pub fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let primary = self.primary.max_acquirable_at(tick)?;
+        let reserve = self.reserve.max_acquirable_at(tick)?;
+        Ok(primary.max(reserve))
+    }
+
+    /// Returns the sum of both pools' steady-state allowances.
+    ///
+    /// Once the primary pool is exhausted the reserve keeps admitting at its
+    /// own rate, so the two renew side by side.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        summed_allowance(self.primary.steady_state_allowance(), self.reserve.steady_state_allowance())
+    }
+
+    /// Returns a reference to the primary limiter.
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns a reference to the reserve limiter.
+    pub fn reserve(&self) -> &R {
+        &self.reserve
+    }
+}
+
+impl<P: RateLimitCore, R: RateLimitCore> RateLimitCore for WithReserve<P, R> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.max_acquirable_at(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
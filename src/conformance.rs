@@ -0,0 +1,164 @@
+//! Behavioral contract tests for [`RateLimitCore`](crate::rate_limit::RateLimitCore) implementations.
+//!
+//! This module is only compiled with the `test-util` feature. It provides the
+//! [`conformance!`](crate::conformance) macro, which expands to a module of
+//! `#[test]` functions checking the invariants every core in this crate upholds.
+//!
+//! # Usage from another crate
+//!
+//! Enable the feature for tests only:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! rate-guard-core = { version = "0.7.2", features = ["test-util"] }
+//! ```
+//!
+//! Then invoke the macro in an integration test with a module name, the
+//! limiter's capacity, and an expression that builds a fresh limiter:
+//!
+//! ```rust,ignore
+//! use my_crate::MyLimiter;
+//!
+//! rate_guard_core::conformance!(my_limiter, 10, MyLimiter::new(10));
+//! ```
+//!
+//! The expression is evaluated once per test, so every test starts from a
+//! fresh limiter. It must be valid inside the generated module; `use super::*;`
+//! is inserted for you, so items imported by the enclosing file are in scope.
+//!
+//! # Checked invariants
+//!
+//! - Acquiring zero tokens always succeeds, on both the simple and verbose paths
+//! - A fresh limiter reports `capacity` tokens remaining
+//! - At a single tick, a fresh limiter admits exactly `capacity` tokens and no more
+//! - Requests larger than `capacity` fail with `BeyondCapacity` on both paths
+//! - After an operation at tick 1 000 000, an operation at tick 0 fails with `ExpiredTick`
+//! - The simple and verbose paths make the same decision for the same sequence
+//! - `capacity_remaining` decreases by exactly the number of tokens acquired at one tick
+//!
+//! The backwards-time check assumes every period of the limiter (refill interval,
+//! window or bucket size) is at most 1 000 000 ticks.
+
+/// Generates a module of `#[test]` functions checking the `RateLimitCore` contract.
+///
+/// See the [module documentation](crate::conformance) for usage and the list of
+/// checked invariants.
+///
+/// # Parameters
+/// * `$name` - Name of the generated test module
+/// * `$capacity` - Configured capacity of the limiter
+/// * `$make` - Expression building a fresh limiter implementing `RateLimitCore`
+#[macro_export]
+macro_rules! conformance {
+    ($name:ident, $capacity:expr, $make:expr) => {
+        #[allow(unused_imports)]
+        mod $name {
+            use super::*;
+            use $crate::rate_limit::RateLimitCore;
+            use $crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+            fn make() -> impl RateLimitCore {
+                $make
+            }
+
+            fn capacity() -> Uint {
+                $capacity
+            }
+
+            fn to_simple(result: VerboseRateLimitResult) -> SimpleRateLimitResult {
+                match result {
+                    Ok(()) => Ok(()),
+                    Err(VerboseRateLimitError::InsufficientCapacity { .. }) => Err(SimpleRateLimitError::InsufficientCapacity),
+                    Err(VerboseRateLimitError::BeyondCapacity { .. }) => Err(SimpleRateLimitError::BeyondCapacity),
+                    Err(VerboseRateLimitError::ExpiredTick { .. }) => Err(SimpleRateLimitError::ExpiredTick),
+                    Err(VerboseRateLimitError::ContentionFailure) => Err(SimpleRateLimitError::ContentionFailure),
+                }
+            }
+
+            #[test]
+            fn zero_tokens_always_succeed() {
+                let limiter = make();
+                assert_eq!(limiter.try_acquire_at(0, capacity()), Ok(()));
+                for tick in 0..10 {
+                    assert_eq!(limiter.try_acquire_at(tick, 0), Ok(()));
+                    assert_eq!(limiter.try_acquire_verbose_at(tick, 0), Ok(()));
+                }
+            }
+
+            #[test]
+            fn fresh_limiter_reports_full_capacity() {
+                let limiter = make();
+                assert_eq!(limiter.capacity_remaining(0), Ok(capacity()));
+            }
+
+            #[test]
+            fn capacity_is_never_exceeded_at_one_tick() {
+                let limiter = make();
+                let mut admitted: Uint = 0;
+                while limiter.try_acquire_at(0, 1).is_ok() {
+                    admitted += 1;
+                    assert!(admitted <= capacity(), "admitted more than capacity");
+                }
+                assert_eq!(admitted, capacity());
+            }
+
+            #[test]
+            fn beyond_capacity_is_rejected() {
+                let limiter = make();
+                assert_eq!(limiter.try_acquire_at(0, capacity() + 1), Err(SimpleRateLimitError::BeyondCapacity));
+                assert!(matches!(
+                    limiter.try_acquire_verbose_at(0, capacity() + 1),
+                    Err(VerboseRateLimitError::BeyondCapacity { .. })
+                ));
+                assert_eq!(limiter.capacity_remaining(0), Ok(capacity()));
+            }
+
+            #[test]
+            fn backwards_time_is_expired() {
+                let limiter = make();
+                assert_eq!(limiter.try_acquire_at(1_000_000, 1), Ok(()));
+                assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::ExpiredTick));
+                assert!(matches!(
+                    limiter.try_acquire_verbose_at(0, 1),
+                    Err(VerboseRateLimitError::ExpiredTick { .. })
+                ));
+                assert_eq!(limiter.capacity_remaining(0), Err(SimpleRateLimitError::ExpiredTick));
+            }
+
+            #[test]
+            fn simple_and_verbose_paths_agree() {
+                let simple = make();
+                let verbose = make();
+                let cap = capacity();
+                let mut tick: Uint = 0;
+                for step in 0..200 {
+                    let step = step as Uint;
+                    tick += step % 3;
+                    let tokens = (step * 7) % (cap + 2);
+                    assert_eq!(
+                        simple.try_acquire_at(tick, tokens),
+                        to_simple(verbose.try_acquire_verbose_at(tick, tokens)),
+                        "diverged at tick {} acquiring {}",
+                        tick,
+                        tokens
+                    );
+                }
+            }
+
+            #[test]
+            fn capacity_remaining_tracks_acquires() {
+                let limiter = make();
+                let cap = capacity();
+                let mut expected = cap;
+                for tokens in 1..=cap {
+                    if tokens > expected {
+                        break;
+                    }
+                    assert_eq!(limiter.try_acquire_at(0, tokens), Ok(()));
+                    expected -= tokens;
+                    assert_eq!(limiter.capacity_remaining(0), Ok(expected));
+                }
+            }
+        }
+    };
+}
@@ -4,7 +4,8 @@
 //! a two-window approach to efficiently approximate a true sliding window.
 
 use std::sync::Mutex;
-use crate::{rate_limit::RateLimitCore, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+use crate::{rate_limit::{RateLimitCore, RefundableCore, UnsyncRateLimit, reduced_allowance}, cores::UnsyncCore, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError, AcquireCode, TransitionInfo, InvalidConfig, StateDecodeError};
+use crate::cores::state_bytes::{StateReader, StateWriter, KIND_APPROXIMATE_SLIDING_WINDOW};
 
 /// Toggles between window indices 0 and 1.
 ///
@@ -63,6 +64,15 @@ macro_rules! other_window {
 /// - Current window: `tokens * window_ticks` (full weight)
 /// - Previous window: `tokens * overlap_length` (partial weight based on overlap)
 ///
+/// # Admission Boundary
+///
+/// A request for `k` tokens is admitted iff
+/// `current * window_ticks + previous * overlap <= (capacity - k) * window_ticks`,
+/// with equality admitted. For a tick at offset `o` into the current window,
+/// `overlap = window_ticks - 1 - o`, so a request for exactly `capacity` is only
+/// admitted when the current window is empty and either the previous window is
+/// empty or `o = window_ticks - 1` (its last tick, where the overlap reaches 0).
+///
 /// # Example
 ///
 /// ```rust
@@ -130,24 +140,71 @@ impl RateLimitCore for ApproximateSlidingWindowCore {
         self.capacity_remaining(tick)
     }
 
-    /// Gets the current remaining capacity.
-    ///
-    /// # Arguments
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
     ///
-    /// * `tick` - Current time tick
+    /// This method is a wrapper that calls the main `max_acquirable_at` logic.
+    #[inline(always)]
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.max_acquirable_at(tick)
+    }
+
+    /// Attempts to acquire tokens and reports the state transitions the call performed.
     ///
-    /// # Returns
+    /// This method is a wrapper that calls the main `try_acquire_detailed_at` logic.
+    #[inline(always)]
+    fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        self.try_acquire_detailed_at(tick, tokens)
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction.
     ///
-    /// Number of tokens currently available for acquisition
-    /// This is a convenience method that returns 0 if the capacity is not available.
+    /// This method is a wrapper that calls the main `steady_state_allowance` logic.
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
+
+impl RefundableCore for ApproximateSlidingWindowCore {
     #[inline(always)]
-    fn capacity_remaining_or_0(&self, tick: Uint) -> Uint {
-        self.capacity_remaining_or_0(tick)
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.refund_at(tick, tokens)
     }
 }
 
+/// Split of the weighted usage of an [`ApproximateSlidingWindowCore`] between its two windows.
+///
+/// Admission compares `current_window_tokens * window_ticks +
+/// previous_window_tokens * previous_window_overlap_ticks` against
+/// `capacity * window_ticks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowBreakdown {
+    /// Tokens acquired in the current window
+    pub current_window_tokens: Uint,
+    /// Tokens acquired in the previous window
+    pub previous_window_tokens: Uint,
+    /// Ticks of the previous window that fall in the sliding window
+    pub previous_window_overlap_ticks: Uint,
+    /// Tokens the previous window still contributes, `previous_window_tokens *
+    /// previous_window_overlap_ticks / window_ticks` rounded down
+    pub previous_window_overlap_contribution: Uint,
+    /// Sliding window `[head, tick]`, both ends inclusive
+    pub sliding_window_range: (Uint, Uint),
+}
+
+/// Verbose error of [`ApproximateSlidingWindowCore::try_acquire_explained_at`],
+/// with the window split the decision saw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainedRateLimitError {
+    /// Error of the verbose acquire.
+    pub error: VerboseRateLimitError,
+    /// Split of the weighted usage behind an `InsufficientCapacity` denial;
+    /// `None` for any other error.
+    pub breakdown: Option<WindowBreakdown>,
+}
+
 /// Internal state of the approximate sliding window counter
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct ApproximateSlidingWindowCoreState {
     /// Token counts for the two alternating windows
     windows: [Uint; 2],
@@ -155,6 +212,8 @@ struct ApproximateSlidingWindowCoreState {
     window_starts: [Uint; 2],
     /// Index (0 or 1) of the currently active window
     current_index: usize,
+    /// How far behind the minimum acceptable tick the last `ExpiredTick` rejection was
+    last_expired_delta: Option<Uint>,
 }
 
 impl ApproximateSlidingWindowCoreState {
@@ -164,6 +223,7 @@ impl ApproximateSlidingWindowCoreState {
             windows: [0, 0],
             window_starts: [0, 0],
             current_index: 0,
+            last_expired_delta: None,
         }
     }
 }
@@ -198,6 +258,23 @@ impl ApproximateSlidingWindowCore {
         }
     }
 
+    /// Creates a new approximate sliding window counter, returning an error instead of panicking on invalid parameters.
+    ///
+    /// Accepts exactly the parameters [`new`](Self::new) accepts.
+    ///
+    /// # Returns
+    /// * `Ok(ApproximateSlidingWindowCore)` - If every parameter is non-zero
+    /// * `Err(InvalidConfig)` - The first zero parameter, in argument order
+    pub fn try_new(capacity: Uint, window_ticks: Uint) -> Result<Self, InvalidConfig> {
+        if capacity == 0 {
+            return Err(InvalidConfig::ZeroCapacity);
+        }
+        if window_ticks == 0 {
+            return Err(InvalidConfig::ZeroWindowTicks);
+        }
+        Ok(Self::new(capacity, window_ticks))
+    }
+
     /// Performs state transition based on the given tick.
     ///
     /// This function updates the window state to ensure the current window
@@ -217,6 +294,9 @@ impl ApproximateSlidingWindowCore {
         tick: Uint,
         window_ticks: Uint,
     ) {
+        // Every caller passes the value validated by `new`; keep it that way if the
+        // window size ever becomes mutable, or the divisions below will panic.
+        debug_assert!(window_ticks > 0, "window_ticks must be greater than 0");
         let expected_index = ((tick / window_ticks) % 2) as usize;
         let expected_start = (tick / window_ticks) * window_ticks;
 
@@ -261,7 +341,6 @@ impl ApproximateSlidingWindowCore {
     /// # Returns
     ///
     /// Total weighted contribution from all active windows
-    
     #[inline(always)]
     fn calculate_weighted_contribution_by_state(
         state: &ApproximateSlidingWindowCoreState,
@@ -273,31 +352,67 @@ impl ApproximateSlidingWindowCore {
         let other_idx = other_window!(current_idx);
 
         // Current window always contributes with full weight
-        let current_contribution = state.windows[current_idx] * window_ticks;
+        let current_contribution = state.windows[current_idx].saturating_mul(window_ticks);
 
-        // Check if the other window overlaps with the sliding window
+        // Other window contributes based on overlap length
+        let overlap = Self::other_window_overlap_by_state(state, sw_head, sw_end, window_ticks);
+        let other_contribution = state.windows[other_idx].saturating_mul(overlap);
+        current_contribution.saturating_add(other_contribution)
+    }
+
+    /// Returns how many ticks of the non-current window fall in the sliding window
+    /// `[sw_head, sw_end]`, or 0 if it has completely expired.
+    #[inline(always)]
+    fn other_window_overlap_by_state(
+        state: &ApproximateSlidingWindowCoreState,
+        sw_head: Uint,
+        sw_end: Uint,
+        window_ticks: Uint,
+    ) -> Uint {
+        let other_idx = other_window!(state.current_index);
         let other_window_start = state.window_starts[other_idx];
-        let other_window_end = other_window_start + window_ticks - 1;
+        let other_window_end = other_window_start.saturating_add(window_ticks.saturating_sub(1));
 
-        if sw_head > other_window_end {
-            // Other window completely expired - no contribution
-            current_contribution
+        // Calculate overlap length between other window and sliding window
+        let overlap_start = sw_head.max(other_window_start);
+        let overlap_end = sw_end.min(other_window_end);
+        if overlap_start <= overlap_end {
+            overlap_end - overlap_start + 1
         } else {
-            // Calculate overlap length between other window and sliding window
-            let overlap_start = sw_head.max(other_window_start);
-            let overlap_end = sw_end.min(other_window_end);
-            let overlap = if overlap_start <= overlap_end {
-                overlap_end - overlap_start + 1
-            } else {
-                0
-            };
-
-            // Other window contributes based on overlap length
-            let other_contribution = state.windows[other_idx] * overlap;
-            current_contribution + other_contribution
+            0
         }
     }
 
+    /// Converts a weighted contribution into the number of tokens still acquirable.
+    ///
+    /// `try_acquire_at(tick, k)` admits iff `total + k * window_ticks <= capacity * window_ticks`,
+    /// which for integers holds exactly when `k <= floor((capacity * window_ticks - total) / window_ticks)`.
+    /// Flooring here therefore yields the largest `k` the acquire path would accept.
+    #[inline(always)]
+    fn remaining_tokens(&self, total_contribution: Uint) -> Uint {
+        let capacity_contribution = self.capacity.saturating_mul(self.window_ticks);
+        let remaining_contribution = capacity_contribution.saturating_sub(total_contribution);
+        remaining_contribution / self.window_ticks
+    }
+
+    /// Returns whether `tokens` would be admitted at `tick`, projecting on a copy of `state`
+    /// as if nothing else were acquired in the meantime.
+    #[inline(always)]
+    fn admits_at(&self, state: &ApproximateSlidingWindowCoreState, tick: Uint, tokens: Uint) -> bool {
+        tokens <= self.remaining_at(state, tick)
+    }
+
+    /// Returns the tokens acquirable at `tick`, projecting on a copy of `state`
+    /// as if nothing else were acquired in the meantime.
+    #[inline(always)]
+    fn remaining_at(&self, state: &ApproximateSlidingWindowCoreState, tick: Uint) -> Uint {
+        let mut projected = state.clone();
+        self.update_windows(&mut projected, tick);
+        let sw_head = tick.saturating_sub(self.window_ticks.saturating_sub(1));
+        let total = self.calculate_weighted_contribution(&projected, sw_head, tick);
+        self.remaining_tokens(total)
+    }
+
     /// Updates window state to cover the given tick.
     ///
     /// This method calls the pure state transition function.
@@ -332,6 +447,51 @@ impl ApproximateSlidingWindowCore {
         Self::calculate_weighted_contribution_by_state(state, sw_head, sw_end, self.window_ticks)
     }
 
+    /// Rejects a `tick` older than the latest window start.
+    #[inline(always)]
+    fn check_tick_locked(&self, state: &mut ApproximateSlidingWindowCoreState, tick: Uint) -> SimpleRateLimitResult {
+        let max_window_start = state.window_starts[0].max(state.window_starts[1]);
+        if tick < max_window_start {
+            state.last_expired_delta = Some(max_window_start - tick);
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+        Ok(())
+    }
+
+    /// Brings the window state up to `tick`. Every path that updates the
+    /// counter goes through here.
+    ///
+    /// # Returns
+    /// * `Ok(rotated)` - Whether `tick` fell outside the current window, switching windows
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the latest window start
+    #[inline(always)]
+    fn update_locked(&self, state: &mut ApproximateSlidingWindowCoreState, tick: Uint) -> Result<bool, SimpleRateLimitError> {
+        self.check_tick_locked(state, tick)?;
+
+        let before = (state.current_index, state.window_starts[state.current_index]);
+        self.update_windows(state, tick);
+        Ok(before != (state.current_index, state.window_starts[state.current_index]))
+    }
+
+    /// Counts `tokens` into the current window if the weighted contribution at
+    /// `tick` leaves room for them. Shared by every acquire path.
+    ///
+    /// # Returns
+    /// Whether the tokens were admitted, and the tokens acquirable before the request.
+    #[inline(always)]
+    fn admit_locked(&self, state: &mut ApproximateSlidingWindowCoreState, tick: Uint, tokens: Uint) -> (bool, Uint) {
+        let sw_head = tick.saturating_sub(self.window_ticks.saturating_sub(1));
+        let total_contribution = self.calculate_weighted_contribution(state, sw_head, tick);
+        let required_contribution = self.window_ticks.saturating_mul(tokens);
+        let capacity_contribution = self.capacity.saturating_mul(self.window_ticks);
+        let admitted = total_contribution <= capacity_contribution.saturating_sub(required_contribution);
+        if admitted {
+            let current_index = state.current_index;
+            state.windows[current_index] += tokens;
+        }
+        (admitted, self.remaining_tokens(total_contribution))
+    }
+
     /// Attempts to acquire the specified number of tokens at the given tick.
     ///
     /// This method updates the window state, calculates the weighted contribution
@@ -366,27 +526,16 @@ impl ApproximateSlidingWindowCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        // Prevent time from going backwards - check against the latest window start
-        let max_window_start = state.window_starts[0].max(state.window_starts[1]);
-        if tick < max_window_start {
-            return Err(SimpleRateLimitError::ExpiredTick);
-        }
-
-        // Update window state based on current tick
-        self.update_windows(&mut state, tick);
-
-        // Calculate sliding window range [sw_head, tick]
-        let sw_head = tick.saturating_sub(self.window_ticks - 1);
+        self.try_acquire_at_locked(&mut state, tick, tokens)
+    }
 
-        // Calculate weighted contributions and check capacity
-        let total_contribution = self.calculate_weighted_contribution(&state, sw_head, tick);
-        let required_contribution = self.window_ticks.saturating_mul(tokens);
-        let capacity_contribution = self.capacity * self.window_ticks;
-        let current_index = state.current_index;
+    /// Body of [`try_acquire_at`](Self::try_acquire_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_at_locked(&self, state: &mut ApproximateSlidingWindowCoreState, tick: Uint,tokens: Uint) -> SimpleRateLimitResult {
+        self.update_locked(state, tick)?;
 
-        // Check if request can be accommodated
-        if total_contribution <= capacity_contribution.saturating_sub(required_contribution) {
-            state.windows[current_index] += tokens;
+        if self.admit_locked(state, tick, tokens).0 {
             Ok(())
         } else {
             Err(SimpleRateLimitError::InsufficientCapacity)
@@ -402,67 +551,169 @@ impl ApproximateSlidingWindowCore {
         let mut state = self.state.try_lock()
             .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
 
-        let max_window_start = state.window_starts[0].max(state.window_starts[1]);
-        if tick < max_window_start {
-            return Err(VerboseRateLimitError::ExpiredTick {
-                min_acceptable_tick: max_window_start,
-            });
-        }
+        self.try_acquire_verbose_at_locked(&mut state, tick, tokens)
+    }
 
-        let capacity = self.capacity;
-        let window_ticks = self.window_ticks;
+    /// Body of [`try_acquire_verbose_at`](Self::try_acquire_verbose_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_verbose_at_locked(&self, state: &mut ApproximateSlidingWindowCoreState, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.check_tick_locked(state, tick).map_err(|_| VerboseRateLimitError::ExpiredTick {
+            min_acceptable_tick: state.window_starts[0].max(state.window_starts[1]),
+        })?;
 
-        if tokens > capacity {
+        if tokens > self.capacity {
             return Err(VerboseRateLimitError::BeyondCapacity {
                 acquiring: tokens,
-                capacity,
+                capacity: self.capacity,
             });
         }
 
-        self.update_windows(&mut state, tick);
+        self.update_windows(state, tick);
 
-        let sw_head = tick.saturating_sub(window_ticks - 1);
-        let sw_end = tick;
-
-        let current_idx = state.current_index;
-        //let other_idx = other_window!(current_idx);
+        let (admitted, available) = self.admit_locked(state, tick, tokens);
+        if admitted {
+            return Ok(());
+        }
 
-        let active_tokens = state.windows[current_idx];
-        //let active_start = state.window_starts[current_idx];
+        // Without further acquires the contribution only decays, so binary search the
+        // earliest admitting tick. Both windows have left the sliding window by
+        // `tick + 2 * window_ticks`, which bounds the search from above.
+        let mut lo = tick;
+        let mut hi = tick.saturating_add(self.window_ticks.saturating_mul(2));
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.admits_at(state, mid, tokens) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        let retry_after_ticks = hi - tick;
 
-        // Calculate total contribution using existing core logic
-        let total_contrib = self.calculate_weighted_contribution(&state, sw_head, sw_end);
-        let capacity_contrib = capacity * window_ticks;
-        let required_contrib = tokens * window_ticks;
+        // The contribution decays every tick, so the next refill is the next tick
+        Err(VerboseRateLimitError::InsufficientCapacity {
+            acquiring: tokens,
+            available,
+            retry_after_ticks,
+            available_next_refill: self.remaining_at(state, tick.saturating_add(1)),
+        })
+    }
 
-        if total_contrib <= capacity_contrib.saturating_sub(required_contrib) {
-            state.windows[current_idx] += tokens;
-            return Ok(());
+    /// Attempts to acquire tokens like `try_acquire_at`, and reports whether this
+    /// call switched the current window.
+    ///
+    /// `window_rotated` is set when `tick` falls outside the current window, so
+    /// the other window is reset and becomes current. `refilled` and `leaked`
+    /// are always 0.
+    ///
+    /// # Returns
+    /// * `Ok(TransitionInfo)` - With `allowed` set to whether the tokens were acquired;
+    ///   a request denied for insufficient capacity is reported here, not as an error
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    pub fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        if tokens == 0 {
+            return Ok(TransitionInfo { allowed: true, ..TransitionInfo::default() });
         }
 
-        let available_contrib = capacity_contrib.saturating_sub(total_contrib);
-        let active_contrib = active_tokens * window_ticks;
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
 
-        let retry_after_ticks = if required_contrib > capacity_contrib.saturating_sub(active_contrib) {
-            // Must rely on active window to decay after it becomes inactive
-            required_contrib.saturating_sub(capacity_contrib.saturating_sub(active_contrib))
-        } else {
-            // Can rely on decay from the current inactive window
-            required_contrib.saturating_sub(available_contrib)
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        Err(VerboseRateLimitError::InsufficientCapacity {
-            acquiring: tokens,
-            available: available_contrib / window_ticks,
-            retry_after_ticks,
+        let window_rotated = self.update_locked(&mut state, tick)?;
+        Ok(TransitionInfo {
+            allowed: self.admit_locked(&mut state, tick, tokens).0,
+            window_rotated,
+            ..TransitionInfo::default()
         })
     }
 
+    /// Attempts to acquire tokens at the given tick, returning a plain [`AcquireCode`].
+    ///
+    /// This is the fast-path `try_acquire_at` with its result mapped to a `Copy`
+    /// integer code, intended for FFI layers that cannot carry a Rust `Result`.
+    ///
+    /// # Arguments
+    /// * `tick` - Current time tick
+    /// * `tokens` - Number of tokens to acquire
+    #[inline(always)]
+    pub fn try_acquire_code_at(&self, tick: Uint, tokens: Uint) -> AcquireCode {
+        AcquireCode::from(self.try_acquire_at(tick, tokens))
+    }
+
+    /// Returns previously acquired tokens to the current window.
+    ///
+    /// The refund is credited to the window containing `tick`, after the usual
+    /// window transition. Refunds larger than the window's count are clamped to zero.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick for the operation
+    /// * `tokens` - Number of tokens to give back
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were returned (zero tokens is a no-op)
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the latest window start
+    pub fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        self.update_locked(&mut state, tick)?;
+        let current_index = state.current_index;
+        state.windows[current_index] = state.windows[current_index].saturating_sub(tokens);
+        Ok(())
+    }
+
+    /// Returns the largest number of tokens by which the weighted estimate can
+    /// differ from the exact count of a true sliding window, in either direction.
+    ///
+    /// The estimate counts the previous window's tokens as if they were spread
+    /// evenly over it. At offset `o` into the current window, `overlap =
+    /// window_ticks - 1 - o` of its ticks are still inside the exact window:
+    ///
+    /// - All previous tokens on its first tick, already outside the exact
+    ///   window, are over-counted by `previous * overlap / window_ticks`, at
+    ///   most `previous * (window_ticks - 1) / window_ticks` at `o = 0`.
+    /// - All previous tokens on its last tick, still inside, are under-counted
+    ///   by `previous * (window_ticks - overlap) / window_ticks`, at most the
+    ///   same amount at `o = window_ticks - 2`.
+    ///
+    /// Admission keeps every window's count at or below `capacity`, so the
+    /// error never exceeds `capacity * (window_ticks - 1) / window_ticks`,
+    /// rounded up here to whole tokens. Both extremes are reachable, so the
+    /// bound is tight; a single-tick window is exact.
+    pub fn max_error_bound(&self) -> Uint {
+        self.capacity - self.capacity / self.window_ticks
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `capacity / window_ticks`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.capacity, self.window_ticks)
+    }
+
     /// Gets the current remaining token capacity using approximate sliding window calculation.
     ///
     /// This method updates the window state and calculates remaining capacity based on
     /// the current usage across all relevant windows.
     ///
+    /// The result is the largest `k` for which `try_acquire_at(tick, k)` would succeed:
+    /// acquiring exactly the reported amount at the same tick is always admitted, and
+    /// acquiring one more is always denied.
+    ///
     /// # Arguments
     ///
     /// * `tick` - Current time tick
@@ -479,20 +730,209 @@ impl ApproximateSlidingWindowCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        let max_window_start = state.window_starts[0].max(state.window_starts[1]);
-        if tick < max_window_start {
-            return Err(SimpleRateLimitError::ExpiredTick);
-        }
+        self.capacity_remaining_locked(&mut state, tick)
+    }
 
+    /// Renders the capacity and the remaining capacity at `tick` as Prometheus
+    /// gauges labeled with `name`. See [`metrics`](crate::metrics) for the format.
+    ///
+    /// # Returns
+    /// * `Ok(text)` - The rendered lines, ending with a newline
+    /// * `Err(_)` - The error of `capacity_remaining` at `tick`
+    #[cfg(feature = "metrics")]
+    pub fn render_prometheus(&self, name: &str, tick: Uint) -> Result<String, SimpleRateLimitError> {
+        let remaining = self.capacity_remaining(tick)?;
+        Ok(crate::metrics::render_gauges(name, self.capacity, remaining))
+    }
+
+    /// Body of [`capacity_remaining`](Self::capacity_remaining) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn capacity_remaining_locked(&self, state: &mut ApproximateSlidingWindowCoreState, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
         // Update actual state
-        Self::state_transition_by_tick(&mut state, tick, self.window_ticks);
+        self.update_locked(state, tick)?;
 
-        let sw_head = tick.saturating_sub(self.window_ticks - 1);
-        let total_contribution = self.calculate_weighted_contribution(&state, sw_head, tick);
-        let capacity_contribution = self.capacity * self.window_ticks;
-        let remaining_contribution = capacity_contribution.saturating_sub(total_contribution);
+        let sw_head = tick.saturating_sub(self.window_ticks.saturating_sub(1));
+        let total_contribution = self.calculate_weighted_contribution(state, sw_head, tick);
+        Ok(self.remaining_tokens(total_contribution))
+    }
 
-        Ok(remaining_contribution / self.window_ticks)
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
+    ///
+    /// This is the floor of the weighted remaining capacity, which is exactly the
+    /// bound the acquire inequality admits, i.e. the same value as `capacity_remaining`.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick
+    ///
+    /// # Returns
+    /// * `Ok(max_tokens)` - `try_acquire_at(tick, max_tokens)` succeeds and `max_tokens + 1` does not
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    #[inline(always)]
+    pub fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    /// Returns the estimated tokens admitted in the sliding window ending at `tick`.
+    ///
+    /// The weighted contribution of both windows is divided by `window_ticks`
+    /// and rounded up, which is exactly the complement of the floored
+    /// [`capacity_remaining`](Self::capacity_remaining): the two sum to the
+    /// capacity. The windows are projected on a copy, so nothing is committed.
+    ///
+    /// # Returns
+    /// * `Ok(used)` - Weighted token count within the sliding window, rounded up
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn used_in_window(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        self.check_tick_locked(&mut state, tick)?;
+
+        let mut projected = state.clone();
+        self.update_windows(&mut projected, tick);
+        let sw_head = tick.saturating_sub(self.window_ticks.saturating_sub(1));
+        let total = self.calculate_weighted_contribution(&projected, sw_head, tick);
+        Ok(total / self.window_ticks + Uint::from(total % self.window_ticks != 0))
+    }
+
+    /// Returns the counter to its freshly constructed state.
+    ///
+    /// Both windows are cleared and restart at tick 0.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the state was reset
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn reset(&self) -> Result<(), SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        *state = ApproximateSlidingWindowCoreState::new();
+        Ok(())
+    }
+
+    /// Serializes the window state into a compact binary buffer.
+    ///
+    /// The layout is the header described in [`cores`](crate::cores#binary-persistence)
+    /// followed by the current window index, the two window counts and the two
+    /// window start ticks.
+    ///
+    /// # Returns
+    /// * `Ok(bytes)` - The serialized state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let mut writer = StateWriter::new(KIND_APPROXIMATE_SLIDING_WINDOW, 5);
+        writer.uint(state.current_index as Uint);
+        writer.uint(state.windows[0]);
+        writer.uint(state.windows[1]);
+        writer.uint(state.window_starts[0]);
+        writer.uint(state.window_starts[1]);
+        Ok(writer.finish())
+    }
+
+    /// Restores a counter built from `config` with the state in `bytes`,
+    /// as produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Returns
+    /// * `Ok(counter)` - The restored counter
+    /// * `Err(StateDecodeError)` - If the header does not match this build and core,
+    ///   the buffer has the wrong length, or the window index is not 0 or 1
+    ///
+    /// # Panics
+    /// Panics if any field of `config` is zero, like the `From` conversion.
+    pub fn from_bytes(config: ApproximateSlidingWindowCoreConfig, bytes: &[u8]) -> Result<Self, StateDecodeError> {
+        let mut reader = StateReader::new(bytes, KIND_APPROXIMATE_SLIDING_WINDOW)?;
+        let current_index = reader.uint()?;
+        let windows = [reader.uint()?, reader.uint()?];
+        let window_starts = [reader.uint()?, reader.uint()?];
+        reader.finish()?;
+        if current_index > 1 {
+            return Err(StateDecodeError::InvalidState);
+        }
+
+        let mut counter: Self = config.into();
+        let state = counter.state.get_mut().unwrap();
+        state.current_index = current_index as usize;
+        state.windows = windows;
+        state.window_starts = window_starts;
+        Ok(counter)
+    }
+
+    /// Returns how the weighted usage at `tick` splits between the two windows.
+    ///
+    /// This explains why the approximate limiter can feel "sticky" after a burst:
+    /// tokens from the previous window keep counting, weighted by how many of
+    /// its ticks still fall in the sliding window.
+    ///
+    /// # Returns
+    /// * `Ok(WindowBreakdown)` - The split at `tick`
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn window_breakdown_at(&self, tick: Uint) -> Result<WindowBreakdown, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        self.check_tick_locked(&mut state, tick)?;
+
+        let mut projected = state.clone();
+        self.update_windows(&mut projected, tick);
+        Ok(self.breakdown_of(&projected, tick))
+    }
+
+    /// Returns the window split at `tick` of a state already brought up to `tick`.
+    fn breakdown_of(&self, state: &ApproximateSlidingWindowCoreState, tick: Uint) -> WindowBreakdown {
+        let sw_head = tick.saturating_sub(self.window_ticks.saturating_sub(1));
+        let overlap = Self::other_window_overlap_by_state(state, sw_head, tick, self.window_ticks);
+        let previous_window_tokens = state.windows[other_window!(state.current_index)];
+        WindowBreakdown {
+            current_window_tokens: state.windows[state.current_index],
+            previous_window_tokens,
+            previous_window_overlap_ticks: overlap,
+            previous_window_overlap_contribution: previous_window_tokens.saturating_mul(overlap) / self.window_ticks,
+            sliding_window_range: (sw_head, tick),
+        }
+    }
+
+    /// Attempts to acquire tokens like `try_acquire_verbose_at`, and on an
+    /// `InsufficientCapacity` denial also returns the [`WindowBreakdown`] that
+    /// led to it.
+    ///
+    /// The breakdown is taken in the same locked section as the decision, so it
+    /// is exactly the split the denial was based on.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were acquired
+    /// * `Err(explained)` - The verbose error, with `breakdown` set if it is `InsufficientCapacity`
+    pub fn try_acquire_explained_at(&self, tick: Uint, tokens: Uint) -> Result<(), Box<ExplainedRateLimitError>> {
+        let explained = |error, breakdown| Box::new(ExplainedRateLimitError { error, breakdown });
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(explained(VerboseRateLimitError::ContentionFailure, None)),
+        };
+
+        match self.try_acquire_verbose_at_locked(&mut state, tick, tokens) {
+            Ok(()) => Ok(()),
+            // The denial brought the windows up to `tick` without acquiring
+            Err(error @ VerboseRateLimitError::InsufficientCapacity { .. }) => {
+                Err(explained(error, Some(self.breakdown_of(&state, tick))))
+            }
+            Err(error) => Err(explained(error, None)),
+        }
     }
 
     /// Gets the current remaining capacity.
@@ -536,18 +976,37 @@ impl ApproximateSlidingWindowCore {
             windows: state.windows,
             window_starts: state.window_starts,
             current_index: state.current_index,
+            last_expired_delta: state.last_expired_delta,
         };
 
         // Do fake update on cloned state
         Self::state_transition_by_tick(&mut fake_state, tick, self.window_ticks);
 
         // Now use the existing calculation with the updated fake state
-        let sw_head = tick.saturating_sub(self.window_ticks - 1);
+        let sw_head = tick.saturating_sub(self.window_ticks.saturating_sub(1));
         let total_contribution = Self::calculate_weighted_contribution_by_state(&fake_state, sw_head, tick, self.window_ticks);
-        let capacity_contribution = self.capacity * self.window_ticks;
-        let remaining_contribution = capacity_contribution.saturating_sub(total_contribution);
+        Ok(self.remaining_tokens(total_contribution))
+    }
+
+    /// Returns how far backwards the most recently rejected tick was.
+    ///
+    /// `SimpleRateLimitError::ExpiredTick` carries no payload, to keep the fast path
+    /// small and existing comparisons against it working. When a tick is rejected as
+    /// expired, the core records `min_acceptable_tick - tick`, where the minimum
+    /// acceptable tick is the latest window start, and this accessor reports it.
+    /// The value is kept until the next expired rejection overwrites it.
+    ///
+    /// # Returns
+    /// * `Ok(Some(delta))` - Distance of the last `ExpiredTick` rejection behind the minimum acceptable tick
+    /// * `Ok(None)` - If no tick has been rejected as expired
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    pub fn last_rejected_expired_delta(&self) -> Result<Option<Uint>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
 
-        Ok(remaining_contribution / self.window_ticks)
+        Ok(state.last_expired_delta)
     }
 
     /// Gets the current capacity based on the existing window state.
@@ -569,15 +1028,12 @@ impl ApproximateSlidingWindowCore {
 
         // Use the current window's end as the reference tick for sliding window calculation
         let current_window_start = state.window_starts[state.current_index];
-        let reference_tick = current_window_start + self.window_ticks - 1;
+        let reference_tick = current_window_start.saturating_add(self.window_ticks.saturating_sub(1));
 
         // Calculate capacity based on current state without any updates
-        let sw_head = reference_tick.saturating_sub(self.window_ticks - 1);
+        let sw_head = reference_tick.saturating_sub(self.window_ticks.saturating_sub(1));
         let total_contribution = Self::calculate_weighted_contribution_by_state(&state, sw_head, reference_tick, self.window_ticks);
-        let capacity_contribution = self.capacity * self.window_ticks;
-        let remaining_contribution = capacity_contribution.saturating_sub(total_contribution);
-
-        Ok(remaining_contribution / self.window_ticks)
+        Ok(self.remaining_tokens(total_contribution))
     }
 
 
@@ -590,8 +1046,51 @@ impl ApproximateSlidingWindowCore {
     }
 }
 
+impl ApproximateSlidingWindowCore {
+    /// Converts this core into an [`UnsyncCore`] for single-threaded use.
+    ///
+    /// The current state is kept. The returned limiter makes the same decisions
+    /// as this core without taking the lock.
+    pub fn into_unsync(self) -> UnsyncCore<Self> {
+        UnsyncCore { core: self }
+    }
+
+    /// Runs `f` on the state without locking, for [`UnsyncCore`].
+    fn with_state_mut<R>(&mut self, f: impl FnOnce(&Self, &mut ApproximateSlidingWindowCoreState) -> R) -> R {
+        let mut state = std::mem::take(self.state.get_mut().unwrap_or_else(|e| e.into_inner()));
+        let result = f(self, &mut state);
+        *self.state.get_mut().unwrap_or_else(|e| e.into_inner()) = state;
+        result
+    }
+}
+
+/// Single-threaded implementation, sharing the locked path's logic.
+impl UnsyncRateLimit for UnsyncCore<ApproximateSlidingWindowCore> {
+    fn try_acquire_at(&mut self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        if tokens > self.core.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_at_locked(state, tick, tokens))
+    }
+
+    fn try_acquire_verbose_at(&mut self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_verbose_at_locked(state, tick, tokens))
+    }
+
+    fn capacity_remaining(&mut self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.core.with_state_mut(|core, state| core.capacity_remaining_locked(state, tick))
+    }
+}
+
 /// Configuration structure for creating an `ApproximateSlidingWindowCore` limiter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ApproximateSlidingWindowCoreConfig {
     /// Maximum number of actions allowed in the sliding window.
     pub capacity: Uint,
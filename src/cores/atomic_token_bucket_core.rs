@@ -0,0 +1,278 @@
+//! Token bucket whose state is one atomic word, counted from an atomic epoch.
+//!
+//! Only available when `Uint` is `u64` (i.e. without the `tick-u128` feature),
+//! because the state is stored in [`AtomicU64`] words; there is no stable 128-bit
+//! atomic to hold `u128` state.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use crate::{SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+use crate::rate_limit::{reduced_allowance, RateLimitCore};
+
+/// Token bucket updated by compare-and-swap instead of a mutex, for high contention.
+///
+/// The algorithm is the same as [`TokenBucketCore`](crate::cores::TokenBucketCore),
+/// but an acquire never fails with `ContentionFailure`. The bucket's state is
+/// packed into a single [`AtomicU64`], and each acquire reads it, computes the
+/// refilled and debited state, and publishes it with one compare-and-swap. If
+/// another acquire changed the word in between, the swap fails and the acquire
+/// starts over from the new state. A failed swap always means another acquire
+/// succeeded, so the loop is lock-free outside of a rebase (see below).
+///
+/// Because every decision is made on one consistent state, a denial with
+/// `InsufficientCapacity` means the bucket really held too few tokens.
+///
+/// # Packing
+///
+/// The last refill tick is always a multiple of `refill_interval`, so the word
+/// stores it as a number of refill periods, counted from the start of the
+/// current epoch: `(offset * (capacity + 1) + available) * 2`. The low bit marks
+/// a rebase in progress. The epoch lives in a second atomic, and every epoch
+/// spans the same number of periods, about half of what the word can count.
+///
+/// When the offset no longer fits, the acquire that publishes the new state
+/// rebases it onto the epoch holding its refill period. It swaps in the rebased
+/// word with the low bit set, stores the new epoch, then clears the bit. Other
+/// calls that see the bit set yield until it is cleared, so they never read a
+/// word against the wrong epoch. Rebases are at least one epoch apart, which
+/// with a capacity of one million is over 4.6e12 refill intervals.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::AtomicTokenBucketCore;
+///
+/// let bucket = AtomicTokenBucketCore::new(10, 5, 2);
+/// assert_eq!(bucket.try_acquire_at(0, 8), Ok(()));
+/// assert!(bucket.try_acquire_at(0, 3).is_err());
+/// assert_eq!(bucket.try_acquire_at(5, 4), Ok(()));
+/// ```
+pub struct AtomicTokenBucketCore {
+    /// Maximum number of tokens the bucket can hold
+    capacity: Uint,
+    /// Number of ticks between each refill event
+    refill_interval: Uint,
+    /// Number of tokens added in each refill event
+    refill_amount: Uint,
+    /// Largest period offset the state word can represent
+    max_offset: Uint,
+    /// Number of refill periods in each epoch
+    epoch_periods: Uint,
+    /// Index of the epoch the state word counts periods from
+    epoch: AtomicU64,
+    /// `(offset * (capacity + 1) + available) * 2 + rebasing`, where `offset` is
+    /// the last refill tick divided by `refill_interval`, minus the epoch's start
+    state: AtomicU64,
+}
+
+/// Low bit of a state word whose epoch is still being published.
+const REBASING: Uint = 1;
+
+/// Reason an acquire was denied.
+enum Denial {
+    /// Not enough tokens after applying due refills
+    Insufficient { periods: Uint, available: Uint },
+    /// The tick is older than the last refill; holds the minimum acceptable tick
+    Expired(Uint),
+}
+
+impl AtomicTokenBucketCore {
+    /// Creates a new bucket, starting full at tick 0.
+    ///
+    /// # Panics
+    /// Panics if any parameter is 0, or if `capacity` is above `Uint::MAX / 2`.
+    pub fn new(capacity: Uint, refill_interval: Uint, refill_amount: Uint) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(capacity <= Uint::MAX / 2, "capacity must be at most Uint::MAX / 2");
+        assert!(refill_interval > 0, "refill_interval must be greater than 0");
+        assert!(refill_amount > 0, "refill_amount must be greater than 0");
+        let max_offset = (Uint::MAX / 2 - capacity) / (capacity + 1);
+        AtomicTokenBucketCore {
+            capacity,
+            refill_interval,
+            refill_amount,
+            max_offset,
+            // A rebased offset is below this, so it always fits
+            epoch_periods: max_offset / 2 + 1,
+            epoch: AtomicU64::new(0),
+            state: AtomicU64::new(capacity << 1),
+        }
+    }
+
+    /// Splits a state word into `(offset, available)`.
+    #[inline(always)]
+    fn unpack(&self, word: Uint) -> (Uint, Uint) {
+        let word = word >> 1;
+        (word / (self.capacity + 1), word % (self.capacity + 1))
+    }
+
+    /// Packs `(offset, available)` into a state word.
+    #[inline(always)]
+    fn pack(&self, offset: Uint, available: Uint) -> Uint {
+        (offset * (self.capacity + 1) + available) << 1
+    }
+
+    /// Loads the state word together with the epoch it counts periods from,
+    /// waiting out a rebase in progress.
+    fn load(&self) -> (Uint, Uint) {
+        loop {
+            let word = self.state.load(Ordering::Acquire);
+            if word & REBASING == 0 {
+                let epoch = self.epoch.load(Ordering::Acquire);
+                // A rebase between the two loads would have changed the word
+                if self.state.load(Ordering::Acquire) == word {
+                    return (word, epoch);
+                }
+            }
+            thread::yield_now();
+        }
+    }
+
+    /// Applies the refills due at `tick` to a state word, without storing it.
+    ///
+    /// # Returns
+    /// * `Ok((periods, available))` - The refilled state, with `periods` counted from tick 0
+    /// * `Err(min_acceptable_tick)` - If `tick` is older than the last refill
+    #[inline(always)]
+    fn refilled(&self, word: Uint, epoch: Uint, tick: Uint) -> Result<(Uint, Uint), Uint> {
+        let (offset, available) = self.unpack(word);
+        let periods = epoch * self.epoch_periods + offset;
+        let last_refill_tick = periods * self.refill_interval;
+        if tick < last_refill_tick {
+            return Err(last_refill_tick);
+        }
+
+        let now_periods = tick / self.refill_interval;
+        let refilled = (now_periods - periods).saturating_mul(self.refill_amount);
+        Ok((now_periods, available.saturating_add(refilled).min(self.capacity)))
+    }
+
+    /// Refills the state to `tick` and takes `tokens` if enough are available,
+    /// retrying until the update is published.
+    fn acquire(&self, tick: Uint, tokens: Uint) -> Result<(), Denial> {
+        loop {
+            let (current, epoch) = self.load();
+            let (periods, available) = self.refilled(current, epoch, tick).map_err(Denial::Expired)?;
+            let (available, result) = if tokens <= available {
+                (available - tokens, Ok(()))
+            } else {
+                // Still publish the refill, so the last refill tick advances as in TokenBucketCore
+                (available, Err(Denial::Insufficient { periods, available }))
+            };
+
+            let offset = periods - epoch * self.epoch_periods;
+            if offset <= self.max_offset {
+                let next = self.pack(offset, available);
+                if next == current {
+                    return result;
+                }
+                if self.state.compare_exchange_weak(current, next, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+                    return result;
+                }
+            } else {
+                let epoch = periods / self.epoch_periods;
+                let next = self.pack(periods - epoch * self.epoch_periods, available);
+                if self.state.compare_exchange_weak(current, next | REBASING, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+                    // Nobody else writes a word marked as rebasing
+                    self.epoch.store(epoch, Ordering::Release);
+                    self.state.store(next, Ordering::Release);
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Attempts to acquire the specified number of tokens at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If tokens were successfully acquired
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If insufficient tokens are available
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last refill
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        self.acquire(tick, tokens).map_err(|denial| match denial {
+            Denial::Insufficient { .. } => SimpleRateLimitError::InsufficientCapacity,
+            Denial::Expired(_) => SimpleRateLimitError::ExpiredTick,
+        })
+    }
+
+    /// Attempts to acquire tokens at the given tick, returning detailed diagnostics on failure.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        if tokens > self.capacity {
+            // An expired tick takes precedence, as in TokenBucketCore
+            let (word, epoch) = self.load();
+            if let Err(min_acceptable_tick) = self.refilled(word, epoch, tick) {
+                return Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick });
+            }
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: tokens,
+                capacity: self.capacity,
+            });
+        }
+
+        self.acquire(tick, tokens).map_err(|denial| match denial {
+            Denial::Insufficient { periods, available } => {
+                let shortfall = tokens - available;
+                let needed_refills = (shortfall + self.refill_amount - 1) / self.refill_amount;
+                let next_refill_tick = (periods + 1).saturating_mul(self.refill_interval);
+                VerboseRateLimitError::InsufficientCapacity {
+                    acquiring: tokens,
+                    available,
+                    retry_after_ticks: (needed_refills - 1) * self.refill_interval + next_refill_tick.saturating_sub(tick),
+                    available_next_refill: available.saturating_add(self.refill_amount).min(self.capacity),
+                }
+            }
+            Denial::Expired(min_acceptable_tick) => VerboseRateLimitError::ExpiredTick { min_acceptable_tick },
+        })
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `refill_amount / refill_interval`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.refill_amount, self.refill_interval)
+    }
+
+    /// Returns the number of tokens available at the given tick.
+    ///
+    /// The refill due at `tick` is computed on the current state without storing it.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let (word, epoch) = self.load();
+        let (_, available) = self.refilled(word, epoch, tick)
+            .map_err(|_| SimpleRateLimitError::ExpiredTick)?;
+        Ok(available)
+    }
+}
+
+impl RateLimitCore for AtomicTokenBucketCore {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::{Mutex, PoisonError};
+use crate::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+
+/// Core implementation of an in-flight (concurrency) limiter with auto-expiring holds.
+///
+/// Unlike the rate limiting cores, which count how many tokens were acquired
+/// within a period, this core counts how many holds are active at once. Each
+/// successful acquire returns a [`HoldGuard`] that occupies one slot until it is
+/// passed to [`release`](Self::release) or until it is older than
+/// `max_hold_ticks`, whichever comes first. Auto-expiry protects the limiter from
+/// callers that crash or forget to release.
+///
+/// # Algorithm Behavior
+///
+/// - A hold acquired at tick `a` is active at every tick `t` with `a >= t - max_hold_ticks`
+/// - Expired holds are dropped lazily at the start of each operation
+/// - An acquire is admitted if fewer than `capacity` holds are active
+/// - Releasing an expired or already released hold is a no-op
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::ConcurrencyLimiterCore;
+///
+/// // At most 2 requests in flight, each hold expires after 100 ticks
+/// let limiter = ConcurrencyLimiterCore::new(2, 100);
+///
+/// let first = limiter.acquire_at(0).unwrap();
+/// let _second = limiter.acquire_at(5).unwrap();
+/// assert!(limiter.acquire_at(10).is_err());
+///
+/// // Releasing a hold frees its slot immediately
+/// assert!(limiter.release(first));
+/// assert!(limiter.acquire_at(10).is_ok());
+/// ```
+pub struct ConcurrencyLimiterCore {
+    /// Maximum number of holds active at the same time
+    capacity: Uint,
+    /// Number of ticks after which a hold is released automatically
+    max_hold_ticks: Uint,
+    /// Internal state protected by mutex for thread safety
+    state: Mutex<ConcurrencyLimiterCoreState>,
+}
+
+/// Internal state of the concurrency limiter
+struct ConcurrencyLimiterCoreState {
+    /// Active holds as `(id, acquired_tick)` pairs
+    holds: Vec<(u64, Uint)>,
+    /// Identifier assigned to the next hold
+    next_id: u64,
+    /// Latest tick seen by an acquire (used to reject time going backwards)
+    last_tick: Uint,
+}
+
+/// A slot held in a [`ConcurrencyLimiterCore`].
+///
+/// Pass it to [`ConcurrencyLimiterCore::release`] when the request completes.
+/// Dropping it without releasing leaves the slot occupied until it auto-expires.
+#[must_use = "an unreleased hold occupies a slot until it expires"]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HoldGuard {
+    /// Identifier of the hold within its limiter
+    id: u64,
+    /// Tick at which the hold was acquired
+    acquired_tick: Uint,
+}
+
+impl HoldGuard {
+    /// Returns the tick at which the hold was acquired.
+    pub fn acquired_tick(&self) -> Uint {
+        self.acquired_tick
+    }
+}
+
+impl ConcurrencyLimiterCore {
+    /// Creates a new concurrency limiter.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - Maximum number of holds active at the same time
+    /// * `max_hold_ticks` - Number of ticks after which a hold is released automatically
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `max_hold_ticks` is zero.
+    pub fn new(capacity: Uint, max_hold_ticks: Uint) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(max_hold_ticks > 0, "max_hold_ticks must be greater than 0");
+
+        ConcurrencyLimiterCore {
+            capacity,
+            max_hold_ticks,
+            state: Mutex::new(ConcurrencyLimiterCoreState {
+                holds: Vec::new(),
+                next_id: 0,
+                last_tick: 0,
+            }),
+        }
+    }
+
+    /// Drops holds that have expired by `tick`.
+    #[inline(always)]
+    fn expire_holds(&self, state: &mut ConcurrencyLimiterCoreState, tick: Uint) {
+        let oldest_active = tick.saturating_sub(self.max_hold_ticks);
+        state.holds.retain(|&(_, acquired_tick)| acquired_tick >= oldest_active);
+    }
+
+    /// Attempts to take one slot at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(HoldGuard)` - If fewer than `capacity` holds were active
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If all slots are held
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last acquire
+    pub fn acquire_at(&self, tick: Uint) -> Result<HoldGuard, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.last_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+        state.last_tick = tick;
+
+        self.expire_holds(&mut state, tick);
+        if state.holds.len() as Uint >= self.capacity {
+            return Err(SimpleRateLimitError::InsufficientCapacity);
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.holds.push((id, tick));
+        Ok(HoldGuard { id, acquired_tick: tick })
+    }
+
+    /// Attempts to take one slot at the given tick, returning detailed diagnostics on failure.
+    ///
+    /// When all slots are held, `retry_after_ticks` is the time until the oldest
+    /// hold auto-expires and `available_next_refill` the number of holds expiring
+    /// then; an explicit release may free a slot sooner.
+    pub fn acquire_verbose_at(&self, tick: Uint) -> Result<HoldGuard, VerboseRateLimitError> {
+        let mut state = self.state.try_lock()
+            .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
+
+        if tick < state.last_tick {
+            return Err(VerboseRateLimitError::ExpiredTick {
+                min_acceptable_tick: state.last_tick,
+            });
+        }
+        state.last_tick = tick;
+
+        self.expire_holds(&mut state, tick);
+        if state.holds.len() as Uint >= self.capacity {
+            let oldest = state.holds.iter().map(|&(_, acquired_tick)| acquired_tick).min().unwrap_or(tick);
+            let expiring = state.holds.iter().filter(|&&(_, acquired_tick)| acquired_tick == oldest).count();
+            return Err(VerboseRateLimitError::InsufficientCapacity {
+                acquiring: 1,
+                available: 0,
+                retry_after_ticks: oldest.saturating_add(self.max_hold_ticks).saturating_add(1) - tick,
+                available_next_refill: expiring as Uint,
+            });
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.holds.push((id, tick));
+        Ok(HoldGuard { id, acquired_tick: tick })
+    }
+
+    /// Frees the slot held by `guard` before it expires.
+    ///
+    /// The guard is consumed, so a release must not be lost to contention:
+    /// unlike the other operations, this one waits for the internal lock.
+    ///
+    /// # Returns
+    /// * `true` - If the hold was active and has been released
+    /// * `false` - If the hold had already expired
+    pub fn release(&self, guard: HoldGuard) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        match state.holds.iter().position(|&(id, _)| id == guard.id) {
+            Some(index) => {
+                state.holds.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of holds active at the given tick, dropping expired ones.
+    ///
+    /// # Returns
+    /// * `Ok(active)` - Number of active holds
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last acquire
+    pub fn active_holds(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.last_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        self.expire_holds(&mut state, tick);
+        Ok(state.holds.len() as Uint)
+    }
+
+    /// Returns the number of slots free at the given tick, dropping expired holds.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.active_holds(tick).map(|active| self.capacity.saturating_sub(active))
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint};
+
+/// Core implementation of a limit on distinct event IDs per fixed window.
+///
+/// Each acquire names an event ID. The first occurrence of an ID in a window
+/// counts toward `capacity`; repeats of an ID already admitted in the same
+/// window are free. This limits how many different events are processed per
+/// window regardless of how often each is retried or duplicated.
+///
+/// # Algorithm Behavior
+///
+/// - Time is divided into windows of `window_ticks`, as in the fixed window counter
+/// - A new ID is admitted if fewer than `capacity` distinct IDs were admitted in
+///   the window, and is then remembered; a remembered ID is always admitted
+/// - At a window boundary the count and the remembered IDs are cleared
+///
+/// # Bounded Memory
+///
+/// At most `max_tracked` IDs are remembered per window. When a new ID is admitted
+/// with the set full, the oldest remembered ID is forgotten, so a later repeat of
+/// it is charged again as if it were new. The distinct count itself is kept
+/// separately and is exact, so forgetting an ID can only make the limit stricter,
+/// never admit more than `capacity` new IDs per window. With `max_tracked` at
+/// least `capacity` nothing is ever forgotten, since no more than `capacity` IDs
+/// are admitted per window.
+///
+/// Because every acquire names an ID, this type exposes its own methods instead
+/// of implementing [`RateLimitCore`](crate::rate_limit::RateLimitCore).
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::DistinctWindowCore;
+///
+/// // 2 distinct events per window of 10 ticks
+/// let core = DistinctWindowCore::new(2, 10, 16);
+///
+/// assert_eq!(core.try_acquire_at(7, 0), Ok(()));
+/// assert_eq!(core.try_acquire_at(9, 1), Ok(()));
+/// // Repeats are free, a third distinct ID is not
+/// assert_eq!(core.try_acquire_at(7, 2), Ok(()));
+/// assert!(core.try_acquire_at(11, 3).is_err());
+/// ```
+pub struct DistinctWindowCore {
+    /// Maximum number of distinct IDs admitted per window
+    capacity: Uint,
+    /// Duration of each window in ticks
+    window_ticks: Uint,
+    /// Maximum number of IDs remembered per window
+    max_tracked: usize,
+    /// Internal state protected by mutex for thread safety
+    state: Mutex<DistinctWindowCoreState>,
+}
+
+/// Internal state of the distinct window core
+struct DistinctWindowCoreState {
+    /// Start tick of the current window
+    start_tick: Uint,
+    /// Distinct IDs admitted in the current window
+    count: Uint,
+    /// IDs remembered in the current window
+    tracked: HashSet<u64>,
+    /// Remembered IDs from oldest to newest admission, for eviction
+    order: VecDeque<u64>,
+}
+
+impl DistinctWindowCore {
+    /// Creates a new distinct window core.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - Maximum number of distinct IDs per window
+    /// * `window_ticks` - Duration of each window in ticks
+    /// * `max_tracked` - Maximum number of IDs remembered per window
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parameter is zero.
+    pub fn new(capacity: Uint, window_ticks: Uint, max_tracked: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(window_ticks > 0, "window_ticks must be greater than 0");
+        assert!(max_tracked > 0, "max_tracked must be greater than 0");
+
+        DistinctWindowCore {
+            capacity,
+            window_ticks,
+            max_tracked,
+            state: Mutex::new(DistinctWindowCoreState {
+                start_tick: 0,
+                count: 0,
+                tracked: HashSet::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Moves the state to the window containing `tick`.
+    #[inline(always)]
+    fn advance_window(&self, state: &mut DistinctWindowCoreState, tick: Uint) {
+        let current_window = tick / self.window_ticks;
+        if current_window > state.start_tick / self.window_ticks {
+            state.count = 0;
+            state.tracked.clear();
+            state.order.clear();
+            state.start_tick = current_window * self.window_ticks;
+        }
+    }
+
+    /// Attempts to admit event `id` at the given tick.
+    ///
+    /// # Parameters
+    /// * `id` - Identifier of the event
+    /// * `tick` - Current time tick for the operation
+    ///
+    /// # Returns
+    /// * `Ok(())` - If `id` was already admitted in this window, or is new and within capacity
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If `id` is new and the window has no room
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is before the current window
+    pub fn try_acquire_at(&self, id: u64, tick: Uint) -> SimpleRateLimitResult {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.start_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        self.advance_window(&mut state, tick);
+
+        if state.tracked.contains(&id) {
+            return Ok(());
+        }
+        if state.count >= self.capacity {
+            return Err(SimpleRateLimitError::InsufficientCapacity);
+        }
+
+        state.count += 1;
+        if state.order.len() >= self.max_tracked {
+            if let Some(oldest) = state.order.pop_front() {
+                state.tracked.remove(&oldest);
+            }
+        }
+        state.tracked.insert(id);
+        state.order.push_back(id);
+        Ok(())
+    }
+
+    /// Returns how many more distinct IDs can be admitted in the window containing `tick`.
+    ///
+    /// # Returns
+    /// * `Ok(remaining)` - `capacity` minus the distinct IDs admitted in the window
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is before the current window
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.start_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        self.advance_window(&mut state, tick);
+        Ok(self.capacity - state.count)
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Mutex;
-use crate::{rate_limit::RateLimitCore, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+use crate::{rate_limit::{RateLimitCore, Reconfigure, RefundableCore, UnsyncRateLimit, reduced_allowance}, cores::UnsyncCore, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError, AcquireCode, TransitionInfo, InvalidConfig, StateDecodeError};
+use crate::cores::state_bytes::{StateReader, StateWriter, KIND_FIXED_WINDOW};
 
 /// Core implementation of the fixed window counter rate limiting algorithm.
 ///
@@ -45,16 +46,44 @@ pub struct FixedWindowCounterCore {
     capacity: Uint,
     /// Duration of each window in ticks
     window_ticks: Uint,
+    /// Whether the previous window's count is weighted into admission decisions
+    smoothing: bool,
+    /// Overshoot beyond capacity admitted once per window
+    grace: Uint,
     /// Internal state protected by mutex for thread safety
     state: Mutex<FixedWindowCounterCoreState>,
 }
 
+/// Inclusive tick range of one fixed window, reported by
+/// [`FixedWindowCounterCore::try_acquire_verbose_windowed_at`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowBounds {
+    /// First tick of the window
+    pub start_tick: Uint,
+    /// Last tick of the window, `start_tick + window_ticks - 1`
+    pub end_tick: Uint,
+}
+
+impl WindowBounds {
+    /// Returns the first tick of the next window, when the count resets.
+    pub fn reset_tick(&self) -> Uint {
+        self.end_tick.saturating_add(1)
+    }
+}
+
 /// Internal state of the fixed window counter
+#[derive(Clone, Default)]
 struct FixedWindowCounterCoreState {
     /// Current count of tokens used in the active window
     count: Uint,
+    /// Count of the window immediately before the active one (only used when smoothing)
+    prev_count: Uint,
     /// Tick when the current window started
     start_tick: Uint,
+    /// Whether the current window has already used its grace overshoot
+    grace_used: bool,
+    /// How far behind the minimum acceptable tick the last `ExpiredTick` rejection was
+    last_expired_delta: Option<Uint>,
 }
 
 /// Core trait implementation for the fixed window counter.
@@ -120,21 +149,49 @@ impl RateLimitCore for FixedWindowCounterCore {
         self.capacity_remaining(tick)
     }
 
-    /// Returns the number of tokens that can still be acquired without exceeding capacity.
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
     ///
-    /// # Arguments
-    ///
-    /// * `tick` - Current time tick for leak calculation.
+    /// This method is a wrapper that calls the main `max_acquirable_at` logic.
+    #[inline(always)]
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.max_acquirable_at(tick)
+    }
+
+    /// Attempts to acquire tokens and reports the state transitions the call performed.
     ///
-    /// # Returns
+    /// This method is a wrapper that calls the main `try_acquire_detailed_at` logic.
+    #[inline(always)]
+    fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        self.try_acquire_detailed_at(tick, tokens)
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction.
     ///
-    /// The number of tokens currently available for acquisition, or 0 if error.
+    /// This method is a wrapper that calls the main `steady_state_allowance` logic.
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
+
+impl RefundableCore for FixedWindowCounterCore {
     #[inline(always)]
-    fn capacity_remaining_or_0(&self, tick: Uint) -> Uint {
-        self.capacity_remaining_or_0(tick)
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.refund_at(tick, tokens)
     }
 }
 
+impl Reconfigure for FixedWindowCounterCore {
+    type Config = FixedWindowCounterCoreConfig;
+
+    /// Returns a counter with `config` that carries over this counter's state at `tick`.
+    ///
+    /// This method is a wrapper that calls the main `reconfigured` logic.
+    #[inline(always)]
+    fn reconfigured(&self, config: FixedWindowCounterCoreConfig, tick: Uint) -> Result<Self, SimpleRateLimitError> {
+        self.reconfigured(config, tick)
+    }
+}
 
 impl FixedWindowCounterCore {
     /// Creates a new fixed window counter with the specified parameters.
@@ -155,19 +212,197 @@ impl FixedWindowCounterCore {
     /// let counter = FixedWindowCounterCore::new(50, 20);
     /// ```
     pub fn new(capacity: Uint, window_ticks: Uint) -> Self {
+        Self::new_with_smoothing(capacity, window_ticks, false)
+    }
+
+    /// Creates a new fixed window counter, optionally with boundary smoothing.
+    ///
+    /// With `smoothing` enabled, the count of the immediately preceding window
+    /// is weighted by how much of it still overlaps a sliding window ending at
+    /// the current tick, the same weighting [`ApproximateSlidingWindowCore`]
+    /// uses. This curtails the burst of up to `2 * capacity` that a plain fixed
+    /// window allows across a boundary, at the cost of one extra counter and no
+    /// per-bucket storage. With `smoothing` disabled this is identical to [`new`](Self::new).
+    ///
+    /// [`ApproximateSlidingWindowCore`]: crate::cores::ApproximateSlidingWindowCore
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `window_ticks` is zero.
+    pub fn new_with_smoothing(capacity: Uint, window_ticks: Uint, smoothing: bool) -> Self {
         assert!(capacity > 0, "capacity must be greater than 0");
         assert!(window_ticks > 0, "window_ticks must be greater than 0");
         
         FixedWindowCounterCore {
             capacity,
             window_ticks,
+            smoothing,
+            grace: 0,
             state: Mutex::new(FixedWindowCounterCoreState {
                 count: 0,
+                prev_count: 0,
                 start_tick: 0, // First window starts at tick 0
+                grace_used: false,
+                last_expired_delta: None,
             }),
         }
     }
 
+    /// Creates a new fixed window counter, returning an error instead of panicking on invalid parameters.
+    ///
+    /// Accepts exactly the parameters [`new`](Self::new) accepts.
+    ///
+    /// # Returns
+    /// * `Ok(FixedWindowCounterCore)` - If every parameter is non-zero
+    /// * `Err(InvalidConfig)` - The first zero parameter, in argument order
+    pub fn try_new(capacity: Uint, window_ticks: Uint) -> Result<Self, InvalidConfig> {
+        if capacity == 0 {
+            return Err(InvalidConfig::ZeroCapacity);
+        }
+        if window_ticks == 0 {
+            return Err(InvalidConfig::ZeroWindowTicks);
+        }
+        Ok(Self::new(capacity, window_ticks))
+    }
+
+    /// Creates a new fixed window counter that tolerates one small overshoot per window.
+    ///
+    /// Once per window, a request that does not fit in the remaining capacity is
+    /// still admitted if it fits within `grace` tokens beyond it. The window's
+    /// count then exceeds `capacity`, so the window temporarily admits up to
+    /// `capacity + grace` tokens; after that it is enforced strictly until the
+    /// next window, which gets a fresh grace. A single request larger than
+    /// `capacity` is still rejected with `BeyondCapacity`. With `grace` 0 this
+    /// is identical to [`new`](Self::new).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `window_ticks` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rate_guard_core::cores::FixedWindowCounterCore;
+    ///
+    /// let counter = FixedWindowCounterCore::new_with_grace(10, 60, 2);
+    /// assert_eq!(counter.try_acquire_at(0, 9), Ok(()));
+    /// // Two over capacity: admitted once
+    /// assert_eq!(counter.try_acquire_at(1, 3), Ok(()));
+    /// assert!(counter.try_acquire_at(2, 1).is_err());
+    /// ```
+    pub fn new_with_grace(capacity: Uint, window_ticks: Uint, grace: Uint) -> Self {
+        let mut counter = Self::new(capacity, window_ticks);
+        counter.grace = grace;
+        counter
+    }
+
+    /// Moves the state to the window containing `tick`, if that window is later.
+    ///
+    /// The finished window's count is kept as `prev_count` only when the new
+    /// window immediately follows it; otherwise nothing overlaps and it is dropped.
+    #[inline(always)]
+    fn advance_window(&self, state: &mut FixedWindowCounterCoreState, tick: Uint) {
+        let current_window = tick / self.window_ticks;
+        let state_window = state.start_tick / self.window_ticks;
+
+        if current_window > state_window {
+            state.prev_count = if current_window == state_window + 1 { state.count } else { 0 };
+            state.count = 0;
+            state.start_tick = current_window * self.window_ticks;
+            state.grace_used = false;
+        }
+    }
+
+    /// Brings the state up to `tick`, moving it to a later window if `tick`
+    /// falls in one. Every path that updates the counter goes through here.
+    ///
+    /// # Returns
+    /// * `Ok(rotated)` - Whether the state moved to a new window
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If `tick` is older than the current window start
+    #[inline(always)]
+    fn update_locked(&self, state: &mut FixedWindowCounterCoreState, tick: Uint) -> Result<bool, SimpleRateLimitError> {
+        self.check_tick_locked(state, tick)?;
+
+        let start_before = state.start_tick;
+        self.advance_window(state, tick);
+        Ok(state.start_tick != start_before)
+    }
+
+    /// Rejects a `tick` older than the current window start.
+    #[inline(always)]
+    fn check_tick_locked(&self, state: &mut FixedWindowCounterCoreState, tick: Uint) -> SimpleRateLimitResult {
+        // Prevent time from going backwards within the current window
+        if tick < state.start_tick {
+            state.last_expired_delta = Some(state.start_tick - tick);
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of tokens acquirable at `tick` from an up-to-date state.
+    ///
+    /// Without smoothing this is `capacity - count`. With smoothing, the previous
+    /// window contributes `prev_count * overlap`, where `overlap` is how many of
+    /// its ticks still fall in the sliding window `[tick - window_ticks + 1, tick]`,
+    /// and the result is the largest `k` with
+    /// `(count + k) * window_ticks + prev_count * overlap <= capacity * window_ticks`.
+    #[inline(always)]
+    fn available(&self, state: &FixedWindowCounterCoreState, tick: Uint) -> Uint {
+        if !self.smoothing {
+            return self.capacity.saturating_sub(state.count);
+        }
+
+        let elapsed = tick.saturating_sub(state.start_tick);
+        let overlap = (self.window_ticks - 1).saturating_sub(elapsed);
+        let used = state.prev_count.saturating_mul(overlap)
+            .saturating_add(state.count.saturating_mul(self.window_ticks));
+        self.capacity.saturating_mul(self.window_ticks).saturating_sub(used) / self.window_ticks
+    }
+
+    /// Adds `tokens` to an up-to-date state if they fit in the remaining capacity,
+    /// or within the grace overshoot if the window has not used it yet.
+    ///
+    /// # Returns
+    /// Whether the tokens were admitted.
+    #[inline(always)]
+    fn admit(&self, state: &mut FixedWindowCounterCoreState, tick: Uint, tokens: Uint) -> bool {
+        let available = self.available(state, tick);
+        if tokens > available {
+            if state.grace_used || tokens > available.saturating_add(self.grace) {
+                return false;
+            }
+            state.grace_used = true;
+        }
+        state.count += tokens;
+        true
+    }
+
+    /// Computes how many ticks to wait until `tokens` would be admitted,
+    /// given an up-to-date state that currently denies them.
+    #[inline(always)]
+    fn retry_after(&self, state: &FixedWindowCounterCoreState, tick: Uint, tokens: Uint) -> Uint {
+        let next_window_tick = state.start_tick.saturating_add(self.window_ticks);
+        if !self.smoothing {
+            return next_window_tick.saturating_sub(tick);
+        }
+
+        let budget = self.capacity.saturating_mul(self.window_ticks);
+        let current = state.count.saturating_add(tokens).saturating_mul(self.window_ticks);
+        if current <= budget && state.prev_count > 0 {
+            // Fits later in this window, once the previous window's weight has shrunk enough
+            let max_overlap = (budget - current) / state.prev_count;
+            let elapsed = (self.window_ticks - 1).saturating_sub(max_overlap);
+            return state.start_tick.saturating_add(elapsed).saturating_sub(tick);
+        }
+
+        // Only fits in the next window, where the current count becomes the weighted one
+        let elapsed = match (self.capacity - tokens).saturating_mul(self.window_ticks).checked_div(state.count) {
+            Some(max_overlap) => (self.window_ticks - 1).saturating_sub(max_overlap),
+            None => 0,
+        };
+        next_window_tick.saturating_add(elapsed).saturating_sub(tick)
+    }
+
     /// Attempts to acquire the specified number of tokens at the given tick.
     ///
     /// This method first determines which window the current tick belongs to,
@@ -206,25 +441,17 @@ impl FixedWindowCounterCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        // Prevent time from going backwards within the current window
-        if tick < state.start_tick {
-            return Err(SimpleRateLimitError::ExpiredTick);
-        }
-
-        // Calculate which window the current tick belongs to
-        let current_window = tick / self.window_ticks;
-        let state_window = state.start_tick / self.window_ticks;
+        self.try_acquire_at_locked(&mut state, tick, tokens)
+    }
 
-        // Check if we've moved to a new window
-        if current_window > state_window {
-            // Reset counter and update window start time
-            state.count = 0;
-            state.start_tick = current_window * self.window_ticks;
-        }
+    /// Body of [`try_acquire_at`](Self::try_acquire_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_at_locked(&self, state: &mut FixedWindowCounterCoreState, tick: Uint,tokens: Uint) -> SimpleRateLimitResult {
+        self.update_locked(state, tick)?;
 
         // Check if we can accommodate the requested tokens within capacity
-        if tokens <= self.capacity.saturating_sub(state.count) {
-            state.count += tokens;
+        if self.admit(state, tick, tokens) {
             Ok(())
         } else {
             Err(SimpleRateLimitError::InsufficientCapacity)
@@ -256,7 +483,8 @@ impl FixedWindowCounterCore {
     /// * `Err(VerboseRateLimitError::ContentionFailure)` – Lock could not be acquired
     /// * `Err(VerboseRateLimitError::ExpiredTick)` – Provided tick is older than current window
     /// * `Err(VerboseRateLimitError::BeyondCapacity)` – Requested tokens exceed the configured capacity
-    /// * `Err(VerboseRateLimitError::InsufficientCapacity)` – Not enough tokens available in current window
+    /// * `Err(VerboseRateLimitError::InsufficientCapacity)` – Not enough tokens available in current window;
+    ///   `available_next_refill` is what the next window starts with
     ///
     /// # Example
     /// ```
@@ -283,11 +511,49 @@ impl FixedWindowCounterCore {
         let mut state = self.state.try_lock()
             .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
 
-        if tick < state.start_tick {
-            return Err(VerboseRateLimitError::ExpiredTick {
-                min_acceptable_tick: state.start_tick,
-            });
+        self.try_acquire_verbose_at_locked(&mut state, tick, tokens)
+    }
+
+    /// Returns the bounds of the window containing `tick`.
+    ///
+    /// Windows are aligned to multiples of `window_ticks`, so this does not
+    /// depend on the counter state.
+    pub fn window_bounds_at(&self, tick: Uint) -> WindowBounds {
+        let start_tick = tick - tick % self.window_ticks;
+        WindowBounds {
+            start_tick,
+            end_tick: start_tick.saturating_add(self.window_ticks - 1),
         }
+    }
+
+    /// Attempts to acquire tokens like `try_acquire_verbose_at`, and also
+    /// returns the bounds of the window containing `tick`.
+    ///
+    /// The bounds let callers report an absolute reset time, such as an
+    /// `X-RateLimit-Reset` header, alongside the decision.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rate_guard_core::cores::FixedWindowCounterCore;
+    ///
+    /// let counter = FixedWindowCounterCore::new(10, 60);
+    /// let (result, window) = counter.try_acquire_verbose_windowed_at(75, 4);
+    /// assert_eq!(result, Ok(()));
+    /// assert_eq!((window.start_tick, window.end_tick), (60, 119));
+    /// assert_eq!(window.reset_tick(), 120);
+    /// ```
+    pub fn try_acquire_verbose_windowed_at(&self, tick: Uint, tokens: Uint) -> (VerboseRateLimitResult, WindowBounds) {
+        (self.try_acquire_verbose_at(tick, tokens), self.window_bounds_at(tick))
+    }
+
+    /// Body of [`try_acquire_verbose_at`](Self::try_acquire_verbose_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_verbose_at_locked(&self, state: &mut FixedWindowCounterCoreState, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.check_tick_locked(state, tick).map_err(|_| VerboseRateLimitError::ExpiredTick {
+            min_acceptable_tick: state.start_tick,
+        })?;
 
         if tokens > self.capacity {
             return Err(VerboseRateLimitError::BeyondCapacity {
@@ -296,31 +562,110 @@ impl FixedWindowCounterCore {
             });
         }
 
-        let current_window = tick / self.window_ticks;
-        let state_window = state.start_tick / self.window_ticks;
+        self.advance_window(state, tick);
 
-        if current_window > state_window {
-            // New window → reset
-            state.count = 0;
-            state.start_tick = current_window * self.window_ticks;
-        }
-
-        if tokens <= self.capacity.saturating_sub(state.count) {
-            state.count += tokens;
+        if self.admit(state, tick, tokens) {
             Ok(())
         } else {
-            let available = self.capacity.saturating_sub(state.count);
-            let next_window_tick = (current_window + 1) * self.window_ticks;
-            let retry_after_ticks = next_window_tick.saturating_sub(tick);
+            let available = self.available(state, tick);
+            let retry_after_ticks = self.retry_after(state, tick, tokens);
+
+            // The next refill is the start of the next window
+            let mut next = state.clone();
+            let next_start = state.start_tick.saturating_add(self.window_ticks);
+            self.advance_window(&mut next, next_start);
 
             Err(VerboseRateLimitError::InsufficientCapacity {
                 acquiring: tokens,
                 available,
                 retry_after_ticks,
+                available_next_refill: self.available(&next, next_start),
             })
         }
     }
 
+    /// Attempts to acquire tokens like `try_acquire_at`, and reports whether this
+    /// call moved the counter to a new window.
+    ///
+    /// `window_rotated` is set when `tick` falls in a later window than the
+    /// current one, resetting the count. `refilled` and `leaked` are always 0.
+    ///
+    /// # Returns
+    /// * `Ok(TransitionInfo)` - With `allowed` set to whether the tokens were acquired;
+    ///   a request denied for insufficient capacity is reported here, not as an error
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    pub fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        if tokens == 0 {
+            return Ok(TransitionInfo { allowed: true, ..TransitionInfo::default() });
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let window_rotated = self.update_locked(&mut state, tick)?;
+        Ok(TransitionInfo {
+            allowed: self.admit(&mut state, tick, tokens),
+            window_rotated,
+            ..TransitionInfo::default()
+        })
+    }
+
+    /// Attempts to acquire tokens at the given tick, returning a plain [`AcquireCode`].
+    ///
+    /// This is the fast-path `try_acquire_at` with its result mapped to a `Copy`
+    /// integer code, intended for FFI layers that cannot carry a Rust `Result`.
+    ///
+    /// # Arguments
+    /// * `tick` - Current time tick
+    /// * `tokens` - Number of tokens to acquire
+    #[inline(always)]
+    pub fn try_acquire_code_at(&self, tick: Uint, tokens: Uint) -> AcquireCode {
+        AcquireCode::from(self.try_acquire_at(tick, tokens))
+    }
+
+    /// Returns previously acquired tokens to the current window.
+    ///
+    /// The refund is credited to the window containing `tick`. If `tick` has
+    /// moved into a new window the counter is reset first, so tokens consumed in
+    /// an earlier window are never credited against the new one.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick for the operation
+    /// * `tokens` - Number of tokens to give back
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were returned (zero tokens is a no-op)
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current window start
+    pub fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        self.update_locked(&mut state, tick)?;
+
+        state.count = state.count.saturating_sub(tokens);
+        Ok(())
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `capacity / window_ticks`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.capacity, self.window_ticks)
+    }
 
     /// Gets the current remaining token capacity in the current window.
     /// 
@@ -343,24 +688,165 @@ impl FixedWindowCounterCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        // Prevent time from going backwards within the current window
-        if tick < state.start_tick {
-            return Err(SimpleRateLimitError::ExpiredTick);
+        self.capacity_remaining_locked(&mut state, tick)
+    }
+
+    /// Renders the capacity and the remaining capacity at `tick` as Prometheus
+    /// gauges labeled with `name`. See [`metrics`](crate::metrics) for the format.
+    ///
+    /// # Returns
+    /// * `Ok(text)` - The rendered lines, ending with a newline
+    /// * `Err(_)` - The error of `capacity_remaining` at `tick`
+    #[cfg(feature = "metrics")]
+    pub fn render_prometheus(&self, name: &str, tick: Uint) -> Result<String, SimpleRateLimitError> {
+        let remaining = self.capacity_remaining(tick)?;
+        Ok(crate::metrics::render_gauges(name, self.capacity, remaining))
+    }
+
+    /// Body of [`capacity_remaining`](Self::capacity_remaining) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn capacity_remaining_locked(&self, state: &mut FixedWindowCounterCoreState, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.update_locked(state, tick)?;
+
+        // Return remaining capacity in current window
+        Ok(self.available(state, tick))
+    }
+
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
+    ///
+    /// This is the capacity left in the window containing `tick`, i.e. the same
+    /// value as `capacity_remaining`, plus the [grace](Self::new_with_grace)
+    /// overshoot while the window has not used it, capped at the capacity.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick
+    ///
+    /// # Returns
+    /// * `Ok(max_tokens)` - `try_acquire_at(tick, max_tokens)` succeeds and `max_tokens + 1` does not
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let available = self.capacity_remaining_locked(&mut state, tick)?;
+        if state.grace_used {
+            Ok(available)
+        } else {
+            Ok(available.saturating_add(self.grace).min(self.capacity))
         }
+    }
 
-        // Calculate which window the current tick belongs to
-        let current_window = tick / self.window_ticks;
-        let state_window = state.start_tick / self.window_ticks;
+    /// Returns the tokens admitted so far in the window containing `tick`.
+    ///
+    /// This is the counterpart of [`capacity_remaining`](Self::capacity_remaining):
+    /// without smoothing the two always sum to the capacity, except after a
+    /// [grace](Self::new_with_grace) overshoot, when this exceeds it. With
+    /// smoothing, the previous window's weighted share is not included here,
+    /// so the sum can fall short of the capacity. The window is not advanced.
+    ///
+    /// # Returns
+    /// * `Ok(used)` - Tokens counted in the current window, 0 if `tick` starts a new one
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn used_in_window(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        self.check_tick_locked(&mut state, tick)?;
 
-        // Check if we've moved to a new window
-        if current_window > state_window {
-            // Reset counter and update window start time
-            state.count = 0;
-            state.start_tick = current_window * self.window_ticks;
+        if tick / self.window_ticks > state.start_tick / self.window_ticks {
+            Ok(0)
+        } else {
+            Ok(state.count)
         }
+    }
 
-        // Return remaining capacity in current window
-        Ok(self.capacity.saturating_sub(state.count))
+    /// Returns the counter to its freshly constructed state.
+    ///
+    /// Both the current and the previous window counts are cleared and the
+    /// window restarts at tick 0.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the state was reset
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn reset(&self) -> Result<(), SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        state.count = 0;
+        state.prev_count = 0;
+        state.start_tick = 0;
+        state.grace_used = false;
+        state.last_expired_delta = None;
+        Ok(())
+    }
+
+    /// Serializes the counter state into a compact binary buffer.
+    ///
+    /// The layout is the header described in [`cores`](crate::cores#binary-persistence)
+    /// followed by the current count, the previous window's count, the
+    /// current window's start tick and whether the current window has used its
+    /// grace overshoot (1) or not (0).
+    ///
+    /// # Returns
+    /// * `Ok(bytes)` - The serialized state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let mut writer = StateWriter::new(KIND_FIXED_WINDOW, 4);
+        writer.uint(state.count);
+        writer.uint(state.prev_count);
+        writer.uint(state.start_tick);
+        writer.uint(Uint::from(state.grace_used));
+        Ok(writer.finish())
+    }
+
+    /// Restores a counter built from `config` with the state in `bytes`,
+    /// as produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Returns
+    /// * `Ok(counter)` - The restored counter
+    /// * `Err(StateDecodeError)` - If the header does not match this build and core,
+    ///   the buffer has the wrong length, a count exceeds the configured capacity plus grace,
+    ///   or the grace flag is not 0 or 1, or is 0 while the count exceeds the capacity
+    ///
+    /// # Panics
+    /// Panics if `capacity` or `window_size` in `config` is zero, like the `From` conversion.
+    pub fn from_bytes(config: FixedWindowCounterCoreConfig, bytes: &[u8]) -> Result<Self, StateDecodeError> {
+        let mut reader = StateReader::new(bytes, KIND_FIXED_WINDOW)?;
+        let count = reader.uint()?;
+        let prev_count = reader.uint()?;
+        let start_tick = reader.uint()?;
+        let grace_used = match reader.uint()? {
+            0 => false,
+            1 => true,
+            _ => return Err(StateDecodeError::InvalidState),
+        };
+        reader.finish()?;
+
+        let mut counter: Self = config.into();
+        let max_count = counter.capacity.saturating_add(counter.grace);
+        // Only the grace overshoot can push a count past capacity
+        if count > max_count || prev_count > max_count || (count > counter.capacity && !grace_used) {
+            return Err(StateDecodeError::InvalidState);
+        }
+        let state = counter.state.get_mut().unwrap();
+        state.count = count;
+        state.prev_count = prev_count;
+        state.start_tick = start_tick;
+        state.grace_used = grace_used;
+        Ok(counter)
     }
 
     /// Returns the number of tokens that can still be acquired without exceeding capacity.
@@ -377,6 +863,27 @@ impl FixedWindowCounterCore {
         self.capacity_remaining(tick).unwrap_or(0)
     }
 
+    /// Returns how far backwards the most recently rejected tick was.
+    ///
+    /// `SimpleRateLimitError::ExpiredTick` carries no payload, to keep the fast path
+    /// small and existing comparisons against it working. When a tick is rejected as
+    /// expired, the core records `min_acceptable_tick - tick`, where the minimum
+    /// acceptable tick is the current window start, and this accessor reports it.
+    /// The value is kept until the next expired rejection overwrites it.
+    ///
+    /// # Returns
+    /// * `Ok(Some(delta))` - Distance of the last `ExpiredTick` rejection behind the minimum acceptable tick
+    /// * `Ok(None)` - If no tick has been rejected as expired
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    pub fn last_rejected_expired_delta(&self) -> Result<Option<Uint>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        Ok(state.last_expired_delta)
+    }
+
     /// Gets the current remaining capacity without updating window state.
     ///
     /// This method simply returns the remaining tokens that can be acquired in
@@ -396,6 +903,32 @@ impl FixedWindowCounterCore {
         Ok(self.capacity.saturating_sub(state.count))
     }
 
+    /// Gets the remaining capacity at a specific tick without updating window state.
+    ///
+    /// If `tick` falls in a later window than the stored one, the counter would be
+    /// reset by the next operation, so full `capacity` is reported. The reset itself
+    /// is not committed. Otherwise the stored window's remaining capacity is returned.
+    ///
+    /// # Parameters
+    /// * `tick` - Time tick to project the window state to
+    ///
+    /// # Returns
+    /// * `Ok(remaining_capacity)` - Remaining capacity in the window containing `tick`
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    #[inline(always)]
+    pub fn current_capacity_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        // Project a copy so the transition is not committed
+        let mut projected = state.clone();
+        self.advance_window(&mut projected, tick);
+
+        Ok(self.available(&projected, tick))
+    }
+
     /// Returns the current remaining capacity
     /// This method is a convenience wrapper around `current_capacity`
     /// that returns 0 if the capacity is not available.
@@ -404,21 +937,127 @@ impl FixedWindowCounterCore {
         self.current_capacity().unwrap_or(0)
     }
 
+    /// Returns a counter with `config` that carries over this counter's state at `tick`.
+    ///
+    /// The tokens counted in the window containing `tick` stay counted, clamped
+    /// to the new capacity, in the new window containing `tick`. If the window
+    /// length changes, tokens from before the new window's start may still be
+    /// counted until it ends, so a reload never frees capacity early. The
+    /// previous window's count, used for smoothing, carries over only if the
+    /// window length is unchanged. This counter is not modified.
+    ///
+    /// # Returns
+    /// * `Ok(counter)` - The reconfigured counter
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is before the current window
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    ///
+    /// # Panics
+    /// Panics if `config` has a zero capacity or window size.
+    pub fn reconfigured(&self, config: FixedWindowCounterCoreConfig, tick: Uint) -> Result<Self, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        if tick < state.start_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        // Work on a copy so this counter is left exactly as it was
+        let mut migrated = state.clone();
+        self.advance_window(&mut migrated, tick);
+
+        let mut counter = FixedWindowCounterCore::from(config);
+        migrated.count = migrated.count.min(counter.capacity);
+        migrated.prev_count = if counter.window_ticks == self.window_ticks {
+            migrated.prev_count.min(counter.capacity)
+        } else {
+            0
+        };
+        migrated.start_tick = tick / counter.window_ticks * counter.window_ticks;
+        *counter.state.get_mut().unwrap() = migrated;
+        Ok(counter)
+    }
+}
+
+impl FixedWindowCounterCore {
+    /// Converts this core into an [`UnsyncCore`] for single-threaded use.
+    ///
+    /// The current state is kept. The returned limiter makes the same decisions
+    /// as this core without taking the lock.
+    pub fn into_unsync(self) -> UnsyncCore<Self> {
+        UnsyncCore { core: self }
+    }
+
+    /// Runs `f` on the state without locking, for [`UnsyncCore`].
+    fn with_state_mut<R>(&mut self, f: impl FnOnce(&Self, &mut FixedWindowCounterCoreState) -> R) -> R {
+        let mut state = std::mem::take(self.state.get_mut().unwrap_or_else(|e| e.into_inner()));
+        let result = f(self, &mut state);
+        *self.state.get_mut().unwrap_or_else(|e| e.into_inner()) = state;
+        result
+    }
+}
+
+/// Single-threaded implementation, sharing the locked path's logic.
+impl UnsyncRateLimit for UnsyncCore<FixedWindowCounterCore> {
+    fn try_acquire_at(&mut self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        if tokens > self.core.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_at_locked(state, tick, tokens))
+    }
+
+    fn try_acquire_verbose_at(&mut self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_verbose_at_locked(state, tick, tokens))
+    }
+
+    fn capacity_remaining(&mut self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.core.with_state_mut(|core, state| core.capacity_remaining_locked(state, tick))
+    }
 }
 
 /// Configuration structure for creating a `FixedWindowCounterCore` limiter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FixedWindowCounterCoreConfig {
     /// Maximum number of actions allowed per window.
     pub capacity: Uint,
     /// Number of ticks that define the fixed window length.
     pub window_size: Uint,
+    /// Weight the previous window's count to curtail boundary bursts.
+    /// See [`FixedWindowCounterCore::new_with_smoothing`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub smoothing: bool,
+    /// Overshoot beyond capacity admitted once per window.
+    /// See [`FixedWindowCounterCore::new_with_grace`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grace: Uint,
 }
 
 impl FixedWindowCounterCoreConfig {
-    /// Creates a new configuration instance.
+    /// Creates a new configuration instance without smoothing or grace.
     pub fn new(capacity: Uint, window_size: Uint) -> Self {
-        Self { capacity, window_size }
+        Self { capacity, window_size, smoothing: false, grace: 0 }
+    }
+
+    /// Sets whether the previous window's count is weighted in.
+    ///
+    /// Prefer `new` plus setters over a struct literal: a literal must name
+    /// every field, so it breaks whenever a field is added.
+    pub fn with_smoothing(mut self, smoothing: bool) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Sets the overshoot beyond capacity admitted once per window.
+    pub fn with_grace(mut self, grace: Uint) -> Self {
+        self.grace = grace;
+        self
     }
 }
 
@@ -436,10 +1075,7 @@ impl From<FixedWindowCounterCoreConfig> for FixedWindowCounterCore {
     /// ```
     /// use rate_guard_core::cores::{FixedWindowCounterCore, FixedWindowCounterCoreConfig};
     ///
-    /// let config = FixedWindowCounterCoreConfig {
-    ///     capacity: 100,
-    ///     window_size: 60,
-    /// };
+    /// let config = FixedWindowCounterCoreConfig::new(100, 60).with_grace(5);
     ///
     /// let limiter = FixedWindowCounterCore::from(config);
     /// ```
@@ -452,10 +1088,14 @@ impl From<FixedWindowCounterCoreConfig> for FixedWindowCounterCore {
     /// let limiter: FixedWindowCounterCore = FixedWindowCounterCoreConfig {
     ///     capacity: 100,
     ///     window_size: 60,
+    ///     smoothing: false,
+    ///     grace: 0,
     /// }.into();
     /// ```
     #[inline(always)]
     fn from(config: FixedWindowCounterCoreConfig) -> Self {
-        FixedWindowCounterCore::new(config.capacity, config.window_size)
+        let mut counter = FixedWindowCounterCore::new_with_smoothing(config.capacity, config.window_size, config.smoothing);
+        counter.grace = config.grace;
+        counter
     }
 }
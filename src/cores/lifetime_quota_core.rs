@@ -0,0 +1,219 @@
+use std::sync::Mutex;
+use crate::{rate_limit::{RateLimitCore, RefundableCore}, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+
+/// Core implementation of a non-renewing lifetime quota.
+///
+/// The quota starts at `total` tokens and only counts down: there is no refill
+/// and no window, and the tick passed to each call is ignored. This fits limits
+/// such as "1000 requests for the whole free trial". The quota can be replaced
+/// with [`reset_quota`](Self::reset_quota), for example on a plan upgrade.
+///
+/// # Algorithm Behavior
+///
+/// - An acquire is admitted if `tokens <= remaining`, and decrements `remaining`
+/// - Requests larger than `total` fail with `BeyondCapacity`
+/// - Once exhausted, requests fail until the quota is reset or tokens are refunded
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::LifetimeQuotaCore;
+///
+/// let quota = LifetimeQuotaCore::new(1000);
+/// assert_eq!(quota.try_acquire_at(0, 999), Ok(()));
+/// assert!(quota.try_acquire_at(u64::MAX.into(), 2).is_err());
+/// assert_eq!(quota.capacity_remaining(0), Ok(1));
+/// ```
+pub struct LifetimeQuotaCore {
+    /// Internal state protected by mutex for thread safety
+    state: Mutex<LifetimeQuotaCoreState>,
+}
+
+/// Internal state of the lifetime quota
+struct LifetimeQuotaCoreState {
+    /// Size of the quota
+    total: Uint,
+    /// Tokens not yet consumed
+    remaining: Uint,
+}
+
+impl RateLimitCore for LifetimeQuotaCore {
+    /// Attempts to acquire the specified number of tokens from the quota.
+    ///
+    /// This method is a wrapper that calls the main `try_acquire_at` logic.
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens from the quota with detailed error reporting.
+    ///
+    /// This method is a wrapper that calls the main `try_acquire_verbose_at` logic.
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the number of tokens left in the quota.
+    ///
+    /// This method is a wrapper that calls the main `capacity_remaining` logic.
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+}
+
+impl RefundableCore for LifetimeQuotaCore {
+    /// This method is a wrapper that calls the main `refund_at` logic.
+    #[inline(always)]
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.refund_at(tick, tokens)
+    }
+}
+
+impl LifetimeQuotaCore {
+    /// Creates a new lifetime quota of `total` tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `total` is zero.
+    pub fn new(total: Uint) -> Self {
+        assert!(total > 0, "total must be greater than 0");
+        LifetimeQuotaCore {
+            state: Mutex::new(LifetimeQuotaCoreState { total, remaining: total }),
+        }
+    }
+
+    /// Attempts to acquire tokens from the quota. The tick is ignored.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were taken from the quota
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If fewer than `tokens` remain
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the quota total
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    #[inline(always)]
+    pub fn try_acquire_at(&self, _tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tokens > state.total {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        if tokens <= state.remaining {
+            state.remaining -= tokens;
+            Ok(())
+        } else {
+            Err(SimpleRateLimitError::InsufficientCapacity)
+        }
+    }
+
+    /// Attempts to acquire tokens from the quota, returning detailed diagnostics
+    /// on failure. The tick is ignored.
+    ///
+    /// Since the quota never refills, an `InsufficientCapacity` denial reports
+    /// `retry_after_ticks: Uint::MAX` and `available_next_refill` equal to `available`.
+    #[inline(always)]
+    pub fn try_acquire_verbose_at(&self, _tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = self.state.try_lock()
+            .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
+
+        if tokens > state.total {
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: tokens,
+                capacity: state.total,
+            });
+        }
+
+        if tokens <= state.remaining {
+            state.remaining -= tokens;
+            Ok(())
+        } else {
+            Err(VerboseRateLimitError::InsufficientCapacity {
+                acquiring: tokens,
+                available: state.remaining,
+                retry_after_ticks: Uint::MAX,
+                available_next_refill: state.remaining,
+            })
+        }
+    }
+
+    /// Returns tokens to the quota, clamped so it never exceeds its total. The tick is ignored.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were returned
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn refund_at(&self, _tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        state.remaining = state.remaining.saturating_add(tokens).min(state.total);
+        Ok(())
+    }
+
+    /// Returns the number of tokens left in the quota. The tick is ignored.
+    ///
+    /// # Returns
+    /// * `Ok(remaining)` - Tokens not yet consumed
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn capacity_remaining(&self, _tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        Ok(state.remaining)
+    }
+
+    /// Renders the quota size and the remaining quota as Prometheus gauges
+    /// labeled with `name`. See [`metrics`](crate::metrics) for the format.
+    ///
+    /// # Returns
+    /// * `Ok(text)` - The rendered lines, ending with a newline
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If the lock could not be acquired
+    #[cfg(feature = "metrics")]
+    pub fn render_prometheus(&self, name: &str, _tick: Uint) -> Result<String, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        Ok(crate::metrics::render_gauges(name, state.total, state.remaining))
+    }
+
+    /// Replaces the quota with a fresh one of `new_total` tokens.
+    ///
+    /// Both the total and the remaining tokens are set to `new_total`; usage
+    /// under the previous quota is forgotten.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the quota was replaced
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_total` is zero.
+    pub fn reset_quota(&self, new_total: Uint) -> Result<(), SimpleRateLimitError> {
+        assert!(new_total > 0, "total must be greater than 0");
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        state.total = new_total;
+        state.remaining = new_total;
+        Ok(())
+    }
+}
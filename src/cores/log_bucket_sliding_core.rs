@@ -0,0 +1,327 @@
+use std::sync::Mutex;
+use crate::rate_limit::{reduced_allowance, RateLimitCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Buckets kept per level, enough for every bucket a window sum can reach.
+const SLOTS_PER_LEVEL: usize = 8;
+
+/// Core implementation of a sliding window over exponentially sized buckets.
+///
+/// A [`SlidingWindowCounterCore`](crate::cores::SlidingWindowCounterCore) with
+/// fine buckets over a long window needs one counter per bucket: a day-long
+/// window at one-second resolution is 86,400 buckets. This core keeps buckets
+/// of `bucket_ticks`, `2 * bucket_ticks`, `4 * bucket_ticks` and so on, up to
+/// the largest power-of-two multiple that fits in the window, so the same
+/// window needs a few buckets per doubling, about `8 * log2(window / bucket)`.
+///
+/// # Algorithm Behavior
+///
+/// - Every acquire is counted at each level, in the bucket of that level's size
+///   that contains its tick; buckets are aligned to multiples of their size
+/// - A window sum walks back from `tick` over adjacent buckets, using the
+///   largest bucket that is aligned and no larger than its distance from
+///   `tick`, until it reaches the window start `tick - window_ticks + 1`
+/// - A request is admitted if the sum plus the request fits in `capacity`
+///
+/// # Precision Profile
+///
+/// Recent usage is exact: the ticks just behind `tick` are summed at the
+/// finest resolution, and each bucket is at most as long as its distance from
+/// `tick`, so resolution falls off gradually with age. At the window start the
+/// sum includes the whole bucket that straddles it, which may be as long as
+/// the window itself. Tokens acquired before the window start may therefore
+/// still count, for up to one more window, so this core can deny more than an
+/// exact sliding log would. It never admits more: usage over any
+/// `window_ticks` span stays within `capacity`.
+///
+/// Ticks older than the start of the finest bucket last used are rejected
+/// with `ExpiredTick`.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::LogBucketSlidingCore;
+///
+/// // 1000 tokens per day of 86,400 ticks, with one-tick recent resolution
+/// let core = LogBucketSlidingCore::new(1000, 86_400, 1);
+/// assert!(core.bucket_count() < 200);
+///
+/// assert_eq!(core.try_acquire_at(0, 600), Ok(()));
+/// assert!(core.try_acquire_at(1_000, 500).is_err());
+/// // Tokens leave the sum within two windows of being acquired
+/// assert_eq!(core.try_acquire_at(172_800, 1000), Ok(()));
+/// ```
+pub struct LogBucketSlidingCore {
+    /// Maximum number of tokens allowed within the sliding window
+    capacity: Uint,
+    /// Length of the sliding window in ticks
+    window_ticks: Uint,
+    /// Duration of the finest buckets in ticks
+    bucket_ticks: Uint,
+    /// Number of bucket sizes, each twice the previous
+    levels: usize,
+    /// Internal state protected by mutex for thread safety
+    state: Mutex<LogBucketSlidingCoreState>,
+}
+
+/// Internal state of the log-bucket sliding window
+struct LogBucketSlidingCoreState {
+    /// Token counts, `SLOTS_PER_LEVEL` circular slots per level
+    counts: Vec<Uint>,
+    /// Start tick of each slot, to tell its current bucket from a stale one
+    starts: Vec<Uint>,
+    /// Start tick of the newest finest bucket used; older ticks are expired
+    newest_start: Uint,
+}
+
+impl LogBucketSlidingCore {
+    /// Creates a new log-bucket sliding window.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - Maximum number of tokens within any window
+    /// * `window_ticks` - Length of the sliding window in ticks
+    /// * `bucket_ticks` - Duration of the finest buckets, the resolution of recent usage
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parameter is zero, or if `bucket_ticks` exceeds `window_ticks`.
+    pub fn new(capacity: Uint, window_ticks: Uint, bucket_ticks: Uint) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(window_ticks > 0, "window_ticks must be greater than 0");
+        assert!(bucket_ticks > 0, "bucket_ticks must be greater than 0");
+        assert!(bucket_ticks <= window_ticks, "bucket_ticks must not exceed window_ticks");
+
+        let mut levels = 1;
+        let mut width = bucket_ticks;
+        while width <= window_ticks / 2 {
+            width *= 2;
+            levels += 1;
+        }
+
+        LogBucketSlidingCore {
+            capacity,
+            window_ticks,
+            bucket_ticks,
+            levels,
+            state: Mutex::new(LogBucketSlidingCoreState {
+                counts: vec![0; levels * SLOTS_PER_LEVEL],
+                starts: vec![0; levels * SLOTS_PER_LEVEL],
+                newest_start: 0,
+            }),
+        }
+    }
+
+    /// Returns the number of bucket counters kept, across all levels.
+    pub fn bucket_count(&self) -> usize {
+        self.levels * SLOTS_PER_LEVEL
+    }
+
+    /// Returns the length of the sliding window in ticks.
+    pub fn window_ticks(&self) -> Uint {
+        self.window_ticks
+    }
+
+    /// Returns the size in ticks of the buckets at `level`.
+    #[inline(always)]
+    fn width(&self, level: usize) -> Uint {
+        self.bucket_ticks << level
+    }
+
+    /// Returns the slot index of the bucket at `level` starting at `start`.
+    #[inline(always)]
+    fn slot(&self, level: usize, start: Uint) -> usize {
+        level * SLOTS_PER_LEVEL + ((start / self.width(level)) % SLOTS_PER_LEVEL as Uint) as usize
+    }
+
+    /// Returns the tokens counted in the bucket at `level` starting at `start`.
+    #[inline(always)]
+    fn bucket(&self, state: &LogBucketSlidingCoreState, level: usize, start: Uint) -> Uint {
+        let slot = self.slot(level, start);
+        if state.starts[slot] == start {
+            state.counts[slot]
+        } else {
+            0
+        }
+    }
+
+    /// Adds `tokens` at `tick` to the bucket containing it at every level.
+    fn record(&self, state: &mut LogBucketSlidingCoreState, tick: Uint, tokens: Uint) {
+        for level in 0..self.levels {
+            let width = self.width(level);
+            let start = tick / width * width;
+            let slot = self.slot(level, start);
+            if state.starts[slot] != start {
+                state.starts[slot] = start;
+                state.counts[slot] = 0;
+            }
+            state.counts[slot] = state.counts[slot].saturating_add(tokens);
+        }
+    }
+
+    /// Returns the tokens counted in the window ending at `tick`.
+    ///
+    /// Walks back from the finest bucket containing `tick`, each step using the
+    /// largest bucket that ends at the current boundary and is no larger than
+    /// its distance from `tick`.
+    fn used(&self, state: &LogBucketSlidingCoreState, tick: Uint) -> Uint {
+        let window_start = tick.saturating_sub(self.window_ticks - 1);
+        let mut boundary = tick / self.bucket_ticks * self.bucket_ticks;
+        let mut used = self.bucket(state, 0, boundary);
+
+        while boundary > window_start {
+            let age = tick - boundary + 1;
+            let mut level = 0;
+            while level + 1 < self.levels {
+                let width = self.width(level + 1);
+                if boundary % width != 0 || width > age {
+                    break;
+                }
+                level += 1;
+            }
+            boundary -= self.width(level);
+            used = used.saturating_add(self.bucket(state, level, boundary));
+        }
+        used
+    }
+
+    /// Rejects ticks older than the newest finest bucket and records `tick` as seen.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If `tick` is acceptable
+    /// * `Err(min_acceptable_tick)` - If `tick` is expired
+    #[inline(always)]
+    fn advance(&self, state: &mut LogBucketSlidingCoreState, tick: Uint) -> Result<(), Uint> {
+        if tick < state.newest_start {
+            return Err(state.newest_start);
+        }
+        state.newest_start = tick / self.bucket_ticks * self.bucket_ticks;
+        Ok(())
+    }
+
+    /// Attempts to acquire the specified number of tokens at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens fit in the window
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If the window has no room for `tokens`
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the newest finest bucket
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        self.advance(&mut state, tick).map_err(|_| SimpleRateLimitError::ExpiredTick)?;
+
+        if self.used(&state, tick).saturating_add(tokens) > self.capacity {
+            return Err(SimpleRateLimitError::InsufficientCapacity);
+        }
+        self.record(&mut state, tick, tokens);
+        Ok(())
+    }
+
+    /// Attempts to acquire tokens at the given tick, returning detailed diagnostics on failure.
+    ///
+    /// `retry_after_ticks` is found by binary search over the window sums ahead,
+    /// and `available_next_refill` is the room at the next tick.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = self.state.try_lock()
+            .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
+
+        self.advance(&mut state, tick)
+            .map_err(|min_acceptable_tick| VerboseRateLimitError::ExpiredTick { min_acceptable_tick })?;
+
+        if tokens > self.capacity {
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: tokens,
+                capacity: self.capacity,
+            });
+        }
+
+        let used = self.used(&state, tick);
+        if used.saturating_add(tokens) <= self.capacity {
+            self.record(&mut state, tick, tokens);
+            return Ok(());
+        }
+
+        // Every bucket counted now has left the window once the window start has
+        // passed the largest bucket after `tick`, which bounds the search.
+        let mut lo = tick;
+        let mut hi = tick
+            .saturating_add(self.window_ticks)
+            .saturating_add(self.width(self.levels - 1));
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.used(&state, mid).saturating_add(tokens) <= self.capacity {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        Err(VerboseRateLimitError::InsufficientCapacity {
+            acquiring: tokens,
+            available: self.capacity.saturating_sub(used),
+            retry_after_ticks: hi - tick,
+            available_next_refill: self.capacity.saturating_sub(self.used(&state, tick.saturating_add(1))),
+        })
+    }
+
+    /// Returns the number of tokens that fit in the window ending at `tick`.
+    ///
+    /// # Returns
+    /// * `Ok(remaining)` - `capacity` minus the tokens counted in the window
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the newest finest bucket
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        self.advance(&mut state, tick).map_err(|_| SimpleRateLimitError::ExpiredTick)?;
+        Ok(self.capacity.saturating_sub(self.used(&state, tick)))
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `capacity / window_ticks`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.capacity, self.window_ticks)
+    }
+}
+
+impl RateLimitCore for LogBucketSlidingCore {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
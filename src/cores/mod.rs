@@ -10,6 +10,18 @@
 //! - **[`FixedWindowCounterCore`]** - Simple window-based counting with reset at boundaries
 //! - **[`SlidingWindowCounterCore`]** - Accurate sliding window using multiple buckets
 //! - **[`ApproximateSlidingWindowCore`]** - Memory-efficient approximate sliding window
+//! - **[`NestedFixedWindowCore`]** - Coarse window enforced as the rolling sum of fine fixed windows
+//! - **[`ConcurrencyLimiterCore`]** - Limits requests in flight, with auto-expiring holds
+//! - **[`LifetimeQuotaCore`]** - Non-renewing quota that only counts down
+//! - **[`ScoreDecayCore`]** - Limits an exponentially decaying sum of per-request scores
+//! - **[`WeightedSemaphoreCore`]** - Limits the total weight in flight, released when permits drop
+//! - **[`WeightedClassesCore`]** - Shares one capacity across classes in proportion to their weights
+//! - **[`DistinctWindowCore`]** - Limits distinct event IDs per window, admitting repeats for free
+//! - **[`LogBucketSlidingCore`]** - Sliding window over exponentially sized buckets, for long windows
+//! - **[`UnsyncCore`]** - Lock-free form of a core for single-threaded use, via `into_unsync`
+//! - **`TokenBucketShared`** - Token bucket over caller-provided atomics, for cross-process sharing (`u64` ticks only)
+//! - **`TokenBucketSeqlock`** - Token bucket whose reads never lock or contend, via a seqlock (`u64` ticks only)
+//! - **`AtomicTokenBucketCore`** - Token bucket updated by compare-and-swap, never failing with contention (`u64` ticks only)
 //!
 //! # Algorithm Comparison
 //!
@@ -20,19 +32,53 @@
 //! | Sliding Window | Medium | High | Smooth bursts | Accurate limiting |
 //! | Approximate SW | Low | Good | Good | Efficient approximation |
 //!
+//! [`recommend::choose`](crate::recommend::choose) applies this table to a target rate.
+//!
+//! # Binary Persistence
+//!
+//! The token bucket, fixed window, sliding window and approximate sliding window
+//! cores provide `to_bytes` and `from_bytes` for a compact, dependency-free
+//! snapshot of their state: a version byte, the tick width, a core kind byte,
+//! then each field as a little-endian `Uint`. The configuration is not stored;
+//! `from_bytes` takes it separately.
+//!
+//! The token bucket also offers `snapshot` and `restore`, which capture the
+//! configuration together with the state in a [`TokenBucketSnapshot`].
+//!
+//! With the `serde` feature, the config structs of those four cores derive
+//! `Serialize` and `Deserialize`, with `Uint` fields as plain integers, so a
+//! configuration can be loaded from a file. The fixed window's `smoothing` and
+//! `grace` may be omitted and default to off.
+//!
 //! # Thread Safety
 //!
 //! All cores use internal mutexes and provide thread-safe operations through
 //! the `try_acquire_at` method, which may return `ContentionFailure` if the
 //! lock cannot be acquired immediately.
+//!
+//! # Equal-Tick Ordering
+//!
+//! Concurrent calls with the same tick are decided in whatever order they take
+//! the lock. For a set of same-tick requests of equal size, every order admits
+//! the same number of them and leaves the same state behind; only which
+//! callers win differs. Zero-token requests (always admitted) and requests
+//! beyond capacity (always denied) do not affect this. Requests of different
+//! sizes are admitted first fit in call order, so their admitted count can
+//! depend on the order.
+
+pub(crate) mod state_bytes;
 
 pub mod token_bucket_core;
 pub use token_bucket_core::TokenBucketCore;
+pub use token_bucket_core::RefillPolicy;
+pub use token_bucket_core::transfer_capacity;
 pub use token_bucket_core::TokenBucketCoreConfig;
+pub use token_bucket_core::TokenBucketSnapshot;
 
 pub mod fixed_window_counter_core;
 pub use fixed_window_counter_core::FixedWindowCounterCore;
 pub use fixed_window_counter_core::FixedWindowCounterCoreConfig;
+pub use fixed_window_counter_core::WindowBounds;
 
 pub mod sliding_window_counter_core;
 pub use sliding_window_counter_core::SlidingWindowCounterCore;
@@ -40,4 +86,49 @@ pub use sliding_window_counter_core::SlidingWindowCounterCoreConfig;
 
 pub mod approximate_sliding_window_core;
 pub use approximate_sliding_window_core::ApproximateSlidingWindowCore;
-pub use approximate_sliding_window_core::ApproximateSlidingWindowCoreConfig;
\ No newline at end of file
+pub use approximate_sliding_window_core::ApproximateSlidingWindowCoreConfig;
+pub use approximate_sliding_window_core::ExplainedRateLimitError;
+pub use approximate_sliding_window_core::WindowBreakdown;
+
+pub mod nested_fixed_window_core;
+pub use nested_fixed_window_core::NestedFixedWindowCore;
+
+pub mod concurrency_limiter_core;
+pub use concurrency_limiter_core::ConcurrencyLimiterCore;
+pub use concurrency_limiter_core::HoldGuard;
+
+pub mod lifetime_quota_core;
+pub use lifetime_quota_core::LifetimeQuotaCore;
+
+pub mod score_decay_core;
+pub use score_decay_core::ScoreDecayCore;
+
+pub mod unsync_core;
+pub use unsync_core::UnsyncCore;
+
+pub mod weighted_semaphore_core;
+pub use weighted_semaphore_core::{Permit, WeightedSemaphoreCore};
+
+pub mod weighted_classes_core;
+pub use weighted_classes_core::WeightedClassesCore;
+
+pub mod distinct_window_core;
+pub use distinct_window_core::DistinctWindowCore;
+
+pub mod log_bucket_sliding_core;
+pub use log_bucket_sliding_core::LogBucketSlidingCore;
+
+#[cfg(not(feature = "tick-u128"))]
+pub mod token_bucket_shared;
+#[cfg(not(feature = "tick-u128"))]
+pub use token_bucket_shared::TokenBucketShared;
+
+#[cfg(not(feature = "tick-u128"))]
+pub mod token_bucket_seqlock;
+#[cfg(not(feature = "tick-u128"))]
+pub use token_bucket_seqlock::TokenBucketSeqlock;
+
+#[cfg(not(feature = "tick-u128"))]
+pub mod atomic_token_bucket_core;
+#[cfg(not(feature = "tick-u128"))]
+pub use atomic_token_bucket_core::AtomicTokenBucketCore;
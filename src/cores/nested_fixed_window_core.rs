@@ -0,0 +1,143 @@
+use crate::cores::SlidingWindowCounterCore;
+use crate::rate_limit::{RateLimitCore, RefundableCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Core implementation of a coarse fixed window built from fine fixed windows.
+///
+/// The coarse window of `fine_ticks * coarse_multiple` ticks is never stored as
+/// its own counter. Instead the core keeps `coarse_multiple` integer counters,
+/// one per fine window, and admits a request if the rolling sum of the fine
+/// windows overlapping the last coarse window, plus the request, stays within
+/// `capacity`. To enforce "100 per 10s" with 1s fine windows, use
+/// `NestedFixedWindowCore::new(100, 1_000, 10)` with millisecond ticks.
+///
+/// # Compared to other cores
+///
+/// - A single [`FixedWindowCounterCore`](crate::cores::FixedWindowCounterCore)
+///   resets all at once, so up to twice its capacity can pass across a boundary.
+///   Here only the oldest fine window leaves the sum at each fine boundary, so
+///   a boundary burst is bounded by one fine window's worth of tokens.
+/// - [`SlidingWindowCounterCore`] has identical semantics: this core is that
+///   algorithm with `bucket_ticks = fine_ticks` and
+///   `bucket_count = coarse_multiple`, and delegates to it. Use this type when
+///   the configuration is naturally phrased as N fine windows per coarse one.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::NestedFixedWindowCore;
+///
+/// // 10 tokens per 40 ticks, counted in 4 fine windows of 10 ticks
+/// let core = NestedFixedWindowCore::new(10, 10, 4);
+/// assert_eq!(core.try_acquire_at(35, 10), Ok(()));
+///
+/// // A fixed window of 40 ticks would reset at tick 40; here the tokens
+/// // from fine window [30, 39] still count until tick 70
+/// assert!(core.try_acquire_at(40, 1).is_err());
+/// assert_eq!(core.try_acquire_at(70, 10), Ok(()));
+/// ```
+pub struct NestedFixedWindowCore {
+    /// Rolling sum over the fine windows, one bucket per fine window
+    windows: SlidingWindowCounterCore,
+    /// Duration of each fine window in ticks
+    fine_ticks: Uint,
+    /// Number of fine windows in the coarse window
+    coarse_multiple: Uint,
+}
+
+impl NestedFixedWindowCore {
+    /// Creates a new nested fixed window core with all fine counters empty.
+    ///
+    /// # Parameters
+    /// * `capacity` - Maximum number of tokens allowed within the coarse window
+    /// * `fine_ticks` - Duration of each fine window in ticks
+    /// * `coarse_multiple` - Number of fine windows in the coarse window
+    ///
+    /// # Panics
+    /// Panics if any parameter is zero.
+    pub fn new(capacity: Uint, fine_ticks: Uint, coarse_multiple: Uint) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(fine_ticks > 0, "fine_ticks must be greater than 0");
+        assert!(coarse_multiple > 0, "coarse_multiple must be greater than 0");
+
+        NestedFixedWindowCore {
+            windows: SlidingWindowCounterCore::new(capacity, fine_ticks, coarse_multiple),
+            fine_ticks,
+            coarse_multiple,
+        }
+    }
+
+    /// Attempts to acquire tokens against the rolling sum of the fine windows.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens fit within the coarse window's capacity
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If the fine windows are too full
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.windows.try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens, returning detailed diagnostics on failure.
+    ///
+    /// `retry_after_ticks` counts until enough of the oldest fine windows have
+    /// left the coarse window, as for [`SlidingWindowCounterCore`].
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.windows.try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the tokens left in the coarse window at `tick`.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.windows.capacity_remaining(tick)
+    }
+
+    /// Returns `(capacity, fine_ticks * coarse_multiple)` as a reduced fraction.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.windows.steady_state_allowance()
+    }
+
+    /// Returns the duration of each fine window in ticks.
+    pub fn fine_ticks(&self) -> Uint {
+        self.fine_ticks
+    }
+
+    /// Returns the number of fine windows in the coarse window.
+    pub fn coarse_multiple(&self) -> Uint {
+        self.coarse_multiple
+    }
+
+    /// Returns the duration of the coarse window in ticks.
+    pub fn coarse_ticks(&self) -> Uint {
+        self.fine_ticks.saturating_mul(self.coarse_multiple)
+    }
+}
+
+impl RateLimitCore for NestedFixedWindowCore {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
+
+impl RefundableCore for NestedFixedWindowCore {
+    #[inline(always)]
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.windows.refund_at(tick, tokens)
+    }
+}
@@ -0,0 +1,340 @@
+use std::sync::Mutex;
+use crate::{rate_limit::{scaled_allowance, RateLimitCore}, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+
+/// Fractional bits of the accumulated score.
+const SCORE_FRAC_BITS: u32 = 32;
+
+/// `1.0` in the Q64 fixed-point format of decay factors.
+const Q64_ONE: u128 = 1 << 64;
+
+/// Widens a `Uint` to `u128`; a no-op with `tick-u128`.
+#[inline(always)]
+#[allow(clippy::unnecessary_cast)]
+fn widen(value: Uint) -> u128 {
+    value as u128
+}
+
+/// Narrows a `u128` to `Uint`, saturating at `Uint::MAX`; a no-op with `tick-u128`.
+#[inline(always)]
+#[allow(clippy::unnecessary_cast)]
+fn narrow(value: u128) -> Uint {
+    value.min(Uint::MAX as u128) as Uint
+}
+
+/// Multiplies `value` by a Q64 factor no larger than `Q64_ONE`, rounding down.
+#[inline(always)]
+fn mul_q64(value: u128, factor: u128) -> u128 {
+    let high = value >> 64;
+    let low = value & (Q64_ONE - 1);
+    high * factor + ((low * factor) >> 64)
+}
+
+/// Raises a Q64 factor no larger than `Q64_ONE` to the power `exp`, rounding down.
+fn pow_q64(mut base: u128, mut exp: u128) -> u128 {
+    let mut result = Q64_ONE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_q64(result, base);
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul_q64(base, base);
+        }
+    }
+    result
+}
+
+/// Core implementation of a limiter over an exponentially decaying score.
+///
+/// Each request carries a "suspicion score" instead of a token count. Scores
+/// accumulate and decay exponentially with the configured half-life, and a
+/// request is rejected if adding its score would push the decayed total above
+/// `threshold`. This suits abuse detection, where requests differ in how much
+/// they should count and bad behavior should be forgiven gradually.
+///
+/// # Algorithm Behavior
+///
+/// - The accumulated score halves every `half_life_ticks`
+/// - On each call the score is first decayed to `tick`, then `score` is added
+///   if the total stays within `threshold`; rejected scores are not added
+/// - Decay uses integer fixed-point arithmetic only (a Q64 per-tick factor and
+///   32 fractional bits of score), so results are identical on every platform.
+///   Whole half-lives are applied as exact halvings
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::ScoreDecayCore;
+///
+/// let core = ScoreDecayCore::new(100, 10);
+/// assert_eq!(core.try_acquire_at(0, 80), Ok(()));
+/// assert!(core.try_acquire_at(0, 30).is_err());
+///
+/// // One half-life later the score has halved
+/// assert_eq!(core.current_score(10), Ok(40));
+/// assert_eq!(core.try_acquire_at(10, 30), Ok(()));
+/// ```
+pub struct ScoreDecayCore {
+    /// Maximum accumulated score
+    threshold: Uint,
+    /// Ticks for the accumulated score to halve
+    half_life_ticks: Uint,
+    /// Per-tick decay factor in Q64, chosen so that `factor^half_life_ticks` is one half
+    tick_factor: u128,
+    /// Internal state protected by mutex for thread safety
+    state: Mutex<ScoreDecayCoreState>,
+}
+
+/// Internal state of the score decay core
+struct ScoreDecayCoreState {
+    /// Accumulated score with `SCORE_FRAC_BITS` fractional bits, as of `last_tick`
+    score: u128,
+    /// Tick of the last operation
+    last_tick: Uint,
+}
+
+impl RateLimitCore for ScoreDecayCore {
+    /// Attempts to add `tokens` as a score at the given tick.
+    ///
+    /// This method is a wrapper that calls the main `try_acquire_at` logic.
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to add `tokens` as a score with detailed error reporting.
+    ///
+    /// This method is a wrapper that calls the main `try_acquire_verbose_at` logic.
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the largest score that would be admitted at the given tick.
+    ///
+    /// This method is a wrapper that calls the main `capacity_remaining` logic.
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    /// Returns the score the decay removes per tick at the threshold.
+    ///
+    /// This method is a wrapper that calls the main `steady_state_allowance` logic.
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
+
+impl ScoreDecayCore {
+    /// Creates a new score decay core.
+    ///
+    /// # Parameters
+    ///
+    /// * `threshold` - Maximum accumulated score
+    /// * `half_life_ticks` - Ticks for the accumulated score to halve
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parameter is zero.
+    pub fn new(threshold: Uint, half_life_ticks: Uint) -> Self {
+        assert!(threshold > 0, "threshold must be greater than 0");
+        assert!(half_life_ticks > 0, "half_life_ticks must be greater than 0");
+
+        // Smallest Q64 factor whose `half_life_ticks`-th power is at least one half
+        let half = Q64_ONE / 2;
+        let (mut lo, mut hi) = (0u128, Q64_ONE);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if pow_q64(mid, widen(half_life_ticks)) >= half {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        ScoreDecayCore {
+            threshold,
+            half_life_ticks,
+            tick_factor: lo,
+            state: Mutex::new(ScoreDecayCoreState { score: 0, last_tick: 0 }),
+        }
+    }
+
+    /// Converts a whole score to the internal fixed-point representation.
+    #[inline(always)]
+    fn to_fixed(score: Uint) -> u128 {
+        widen(score).saturating_mul(1 << SCORE_FRAC_BITS)
+    }
+
+    /// Decays a fixed-point score over `elapsed` ticks.
+    #[inline(always)]
+    fn decay(&self, score: u128, elapsed: Uint) -> u128 {
+        let halvings = elapsed / self.half_life_ticks;
+        if score == 0 || halvings >= 128 {
+            return 0;
+        }
+        let remainder = elapsed % self.half_life_ticks;
+        mul_q64(score >> halvings, pow_q64(self.tick_factor, widen(remainder)))
+    }
+
+    /// Returns the largest whole score admitted on top of a fixed-point score.
+    #[inline(always)]
+    fn remaining(&self, score: u128) -> Uint {
+        let room = Self::to_fixed(self.threshold).saturating_sub(score) >> SCORE_FRAC_BITS;
+        narrow(room)
+    }
+
+    /// Returns the fixed-point score decayed to `tick`, without modifying the state.
+    fn decayed_score_at(&self, tick: Uint) -> Result<u128, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.last_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+        Ok(self.decay(state.score, tick - state.last_tick))
+    }
+
+    /// Attempts to add `score` to the accumulated score at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the decayed total plus `score` is within the threshold
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If it would exceed the threshold
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `score` alone exceeds the threshold
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn try_acquire_at(&self, tick: Uint, score: Uint) -> SimpleRateLimitResult {
+        if score == 0 {
+            return Ok(());
+        }
+        if score > self.threshold {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        // Prevent time from going backwards
+        if tick < state.last_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        state.score = self.decay(state.score, tick - state.last_tick);
+        state.last_tick = tick;
+
+        if score <= self.remaining(state.score) {
+            state.score = state.score.saturating_add(Self::to_fixed(score));
+            Ok(())
+        } else {
+            Err(SimpleRateLimitError::InsufficientCapacity)
+        }
+    }
+
+    /// Attempts to add `score` to the accumulated score, returning detailed
+    /// diagnostics on failure.
+    ///
+    /// On `InsufficientCapacity`, `retry_after_ticks` is the number of ticks until
+    /// decay alone makes room for `score`.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, score: Uint) -> VerboseRateLimitResult {
+        if score == 0 {
+            return Ok(());
+        }
+        if score > self.threshold {
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: score,
+                capacity: self.threshold,
+            });
+        }
+
+        let mut state = self.state.try_lock()
+            .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
+
+        if tick < state.last_tick {
+            return Err(VerboseRateLimitError::ExpiredTick {
+                min_acceptable_tick: state.last_tick,
+            });
+        }
+
+        state.score = self.decay(state.score, tick - state.last_tick);
+        state.last_tick = tick;
+
+        let available = self.remaining(state.score);
+        if score <= available {
+            state.score = state.score.saturating_add(Self::to_fixed(score));
+            return Ok(());
+        }
+
+        // The decayed score only shrinks, so binary search the first admitting tick.
+        // After 128 half-lives it has decayed to zero, which bounds the search.
+        let mut lo: Uint = 0;
+        let mut hi = self.half_life_ticks.saturating_mul(128);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if score <= self.remaining(self.decay(state.score, mid)) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        Err(VerboseRateLimitError::InsufficientCapacity {
+            acquiring: score,
+            available,
+            retry_after_ticks: hi,
+            available_next_refill: self.remaining(self.decay(state.score, 1)),
+        })
+    }
+
+    /// Returns the largest score that would be admitted at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(remaining)` - `threshold` minus the decayed score, rounded down
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let decayed = self.decayed_score_at(tick)?;
+        Ok(self.remaining(decayed))
+    }
+
+    /// Returns the score the decay removes per tick once the accumulated score
+    /// is at the threshold, as a reduced `(score, ticks)` fraction.
+    ///
+    /// This is `threshold * (1 - f)` for the per-tick decay factor `f`, the
+    /// same factor the decay applies, so `2^(-1/half_life_ticks)` rounded up in
+    /// Q64. The factor is rounded to fewer bits if the fraction does not fit in a `Uint`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        // Halve both sides so that `Q64_ONE` fits in a `u64`
+        let removed = narrow((Q64_ONE - self.tick_factor) >> 1);
+        scaled_allowance((self.threshold, 1), removed, narrow(Q64_ONE >> 1))
+    }
+
+    /// Renders the capacity and the remaining capacity at `tick` as Prometheus
+    /// gauges labeled with `name`. See [`metrics`](crate::metrics) for the format.
+    ///
+    /// # Returns
+    /// * `Ok(text)` - The rendered lines, ending with a newline
+    /// * `Err(_)` - The error of `capacity_remaining` at `tick`
+    #[cfg(feature = "metrics")]
+    pub fn render_prometheus(&self, name: &str, tick: Uint) -> Result<String, SimpleRateLimitError> {
+        let remaining = self.capacity_remaining(tick)?;
+        Ok(crate::metrics::render_gauges(name, self.threshold, remaining))
+    }
+
+    /// Returns the accumulated score decayed to the given tick, rounded up.
+    ///
+    /// # Returns
+    /// * `Ok(score)` - The decayed score
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn current_score(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let decayed = self.decayed_score_at(tick)?;
+        let whole = decayed.saturating_add((1 << SCORE_FRAC_BITS) - 1) >> SCORE_FRAC_BITS;
+        Ok(narrow(whole))
+    }
+}
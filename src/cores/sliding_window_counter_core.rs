@@ -1,5 +1,6 @@
 use std::sync::Mutex;
-use crate::{rate_limit::RateLimitCore, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+use crate::{rate_limit::{RateLimitCore, RefundableCore, UnsyncRateLimit, reduced_allowance}, cores::UnsyncCore, SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError, AcquireCode, TransitionInfo, InvalidConfig, StateDecodeError};
+use crate::cores::state_bytes::{StateReader, StateWriter, KIND_SLIDING_WINDOW};
 
 /// Core implementation of the sliding window counter rate limiting algorithm.
 ///
@@ -23,6 +24,15 @@ use crate::{rate_limit::RateLimitCore, SimpleRateLimitResult, SimpleRateLimitErr
 ///
 /// Only buckets whose start time falls within this range contribute to the total count.
 ///
+/// # Approximation Bound
+///
+/// Counting whole buckets never counts more than an exact sliding log would. It
+/// misses only the tokens acquired in the single bucket that straddles the window
+/// start, whose start tick has already left the window while some of its ticks
+/// have not. Usage over any exact `window_size` span can therefore exceed
+/// `capacity` by at most the tokens of one bucket; smaller `bucket_ticks`
+/// tightens the bound.
+///
 /// # Example
 ///
 /// ```rust
@@ -54,6 +64,7 @@ pub struct SlidingWindowCounterCore {
 }
 
 /// Internal state of the sliding window counter
+#[derive(Default)]
 struct SlidingWindowCounterCoreState {
     /// Token counts for each bucket (circular array)
     buckets: Vec<Uint>,
@@ -61,9 +72,10 @@ struct SlidingWindowCounterCoreState {
     bucket_start_ticks: Vec<Uint>,
     /// Index of the most recently used bucket
     last_bucket_index: usize,
+    /// How far behind the minimum acceptable tick the last `ExpiredTick` rejection was
+    last_expired_delta: Option<Uint>,
 }
 
-
 /// Core trait implementation for the fixed window counter.
 /// This provides the basic operations needed by the rate limiter core trait.
 impl RateLimitCore for SlidingWindowCounterCore {
@@ -97,7 +109,6 @@ impl RateLimitCore for SlidingWindowCounterCore {
         self.try_acquire_verbose_at(tick, tokens)
     }
 
-
     /// Returns the number of tokens that can still be acquired without exceeding capacity.
     /// 
     /// # Arguments
@@ -108,17 +119,35 @@ impl RateLimitCore for SlidingWindowCounterCore {
         self.capacity_remaining(tick)
     }
 
-    /// Returns the number of tokens that can still be acquired without exceeding capacity.
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
     ///
-    /// # Arguments
-    ///
-    /// * `tick` - Current time tick for leak calculation.
+    /// This method is a wrapper that calls the main `max_acquirable_at` logic.
+    #[inline(always)]
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.max_acquirable_at(tick)
+    }
+
+    /// Attempts to acquire tokens and reports the state transitions the call performed.
     ///
-    /// # Returns
+    /// This method is a wrapper that calls the main `try_acquire_detailed_at` logic.
+    #[inline(always)]
+    fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        self.try_acquire_detailed_at(tick, tokens)
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction.
     ///
-    /// The number of tokens currently available for acquisition, or 0 if error.
-    fn capacity_remaining_or_0(&self, tick: Uint) -> Uint {
-        self.capacity_remaining_or_0(tick)
+    /// This method is a wrapper that calls the main `steady_state_allowance` logic.
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
+
+impl RefundableCore for SlidingWindowCounterCore {
+    #[inline(always)]
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.refund_at(tick, tokens)
     }
 }
 
@@ -131,7 +160,8 @@ impl SlidingWindowCounterCore {
     /// * `bucket_count` - Number of buckets in the sliding window
     ///
     /// # Panics
-    /// Panics if any parameter is zero, as this would create an invalid configuration.
+    /// Panics if any parameter is zero, as this would create an invalid configuration,
+    /// or if `bucket_count` buckets cannot be allocated.
     ///
     /// # Example
     ///
@@ -141,20 +171,52 @@ impl SlidingWindowCounterCore {
     /// let counter = SlidingWindowCounterCore::new(100, 10, 5);
     /// ```
     pub fn new(capacity: Uint, bucket_ticks: Uint, bucket_count: Uint) -> Self {
-        assert!(capacity > 0, "capacity must be greater than 0");
-        assert!(bucket_ticks > 0, "bucket_ticks must be greater than 0");
-        assert!(bucket_count > 0, "bucket_count must be greater than 0");
-        
-        SlidingWindowCounterCore {
+        Self::try_new(capacity, bucket_ticks, bucket_count).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Allocates `bucket_count` zeroed slots, or returns `None` if the count
+    /// does not fit in a `usize` or the allocation fails.
+    fn zeroed_buckets(bucket_count: Uint) -> Option<Vec<Uint>> {
+        let len = usize::try_from(bucket_count).ok()?;
+        let mut buckets = Vec::new();
+        buckets.try_reserve_exact(len).ok()?;
+        buckets.resize(len, 0);
+        Some(buckets)
+    }
+
+    /// Creates a new sliding window counter, returning an error instead of panicking on invalid parameters.
+    ///
+    /// Accepts exactly the parameters [`new`](Self::new) accepts.
+    ///
+    /// # Returns
+    /// * `Ok(SlidingWindowCounterCore)` - If every parameter is non-zero and the buckets were allocated
+    /// * `Err(InvalidConfig::BucketCountTooLarge { .. })` - If `bucket_count` buckets cannot be allocated
+    /// * `Err(InvalidConfig)` - Otherwise the first zero parameter, in argument order
+    pub fn try_new(capacity: Uint, bucket_ticks: Uint, bucket_count: Uint) -> Result<Self, InvalidConfig> {
+        if capacity == 0 {
+            return Err(InvalidConfig::ZeroCapacity);
+        }
+        if bucket_ticks == 0 {
+            return Err(InvalidConfig::ZeroBucketTicks);
+        }
+        if bucket_count == 0 {
+            return Err(InvalidConfig::ZeroBucketCount);
+        }
+        let too_large = InvalidConfig::BucketCountTooLarge { bucket_count };
+        let buckets = Self::zeroed_buckets(bucket_count).ok_or_else(|| too_large.clone())?;
+        let bucket_start_ticks = Self::zeroed_buckets(bucket_count).ok_or(too_large)?;
+
+        Ok(SlidingWindowCounterCore {
             capacity,
             bucket_ticks,
             bucket_count,
             state: Mutex::new(SlidingWindowCounterCoreState {
-                buckets: vec![0; bucket_count as usize],
-                bucket_start_ticks: vec![0; bucket_count as usize],
+                buckets,
+                bucket_start_ticks,
                 last_bucket_index: 0,
+                last_expired_delta: None,
             }),
-        }
+        })
     }
 
     /// Calculates the total window size in ticks.
@@ -166,6 +228,76 @@ impl SlidingWindowCounterCore {
         self.bucket_ticks.saturating_mul(self.bucket_count)
     }
 
+    /// Returns the slot index and start tick of the bucket `tick` falls into.
+    ///
+    /// The bucket number is reduced modulo `bucket_count` before narrowing to
+    /// `usize`, so the slot stays correct for ticks beyond `usize::MAX`.
+    #[inline(always)]
+    fn bucket_of(&self, tick: Uint) -> (usize, Uint) {
+        let bucket_number = tick / self.bucket_ticks;
+        ((bucket_number % self.bucket_count) as usize, bucket_number * self.bucket_ticks)
+    }
+
+    /// Rejects a `tick` older than the most recently used bucket.
+    ///
+    /// Only checked once a bucket has been used, since a fresh counter has
+    /// every start tick at 0.
+    #[inline(always)]
+    fn check_tick_locked(&self, state: &mut SlidingWindowCounterCoreState, tick: Uint) -> SimpleRateLimitResult {
+        let last_start = state.bucket_start_ticks[state.last_bucket_index];
+        if last_start > 0 && tick < last_start {
+            state.last_expired_delta = Some(last_start - tick);
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+        Ok(())
+    }
+
+    /// Brings the bucket `tick` falls into up to date, lazily resetting it if
+    /// it still holds an older cycle. Every path that updates the counter goes
+    /// through here.
+    ///
+    /// # Returns
+    /// * `Ok((index, rotated))` - The bucket's slot, and whether it was reset
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last recorded operation
+    #[inline(always)]
+    fn update_locked(&self, state: &mut SlidingWindowCounterCoreState, tick: Uint) -> Result<(usize, bool), SimpleRateLimitError> {
+        self.check_tick_locked(state, tick)?;
+        Ok(self.reset_bucket_locked(state, tick))
+    }
+
+    /// Lazily resets the bucket a `tick` already checked by
+    /// [`check_tick_locked`](Self::check_tick_locked) falls into.
+    ///
+    /// # Returns
+    /// The bucket's slot, and whether it was reset.
+    #[inline(always)]
+    fn reset_bucket_locked(&self, state: &mut SlidingWindowCounterCoreState, tick: Uint) -> (usize, bool) {
+        let (index, start_tick) = self.bucket_of(tick);
+        let rotated = state.bucket_start_ticks[index] != start_tick;
+        if rotated {
+            state.buckets[index] = 0;
+            state.bucket_start_ticks[index] = start_tick;
+        }
+        (index, rotated)
+    }
+
+    /// Counts `tokens` into the bucket at `index` if the sliding window at
+    /// `tick` has room for them. Shared by every acquire path.
+    ///
+    /// # Returns
+    /// Whether the tokens were admitted, and the capacity available before the request.
+    #[inline(always)]
+    fn admit_locked(&self, state: &mut SlidingWindowCounterCoreState, index: usize, tick: Uint, tokens: Uint) -> (bool, Uint) {
+        let total = self.count_tokens_in_valid_buckets_within_sliding_window(state, tick);
+        let available = self.capacity.saturating_sub(total);
+        let admitted = tokens <= available;
+        if admitted {
+            state.buckets[index] += tokens;
+            state.last_bucket_index = index;
+        }
+        (admitted, available)
+    }
+
     /// Attempts to acquire the specified number of tokens at the given tick.
     ///
     /// This method determines which bucket the current tick belongs to, performs
@@ -204,32 +336,16 @@ impl SlidingWindowCounterCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        // Prevent time from going backwards (only check if we have previous data)
-        if state.bucket_start_ticks[state.last_bucket_index] > 0 && 
-           tick < state.bucket_start_ticks[state.last_bucket_index] {
-            return Err(SimpleRateLimitError::ExpiredTick);
-        }
-
-        // Determine which bucket this tick belongs to
-        let current_bucket_index = ((tick / self.bucket_ticks) as usize) % (self.bucket_count as usize);
-        let current_bucket_start_tick = (tick / self.bucket_ticks) * self.bucket_ticks;
-
-        // Lazy reset: if this bucket's start time is different, it's a new bucket cycle
-        if state.bucket_start_ticks[current_bucket_index] != current_bucket_start_tick {
-            state.buckets[current_bucket_index] = 0;
-            state.bucket_start_ticks[current_bucket_index] = current_bucket_start_tick;
-        }
-
-        // Calculate the sliding window range
-        let window_start_tick = tick.saturating_sub(self.window_ticks());
+        self.try_acquire_at_locked(&mut state, tick, tokens)
+    }
 
-        // Count tokens in all valid buckets within the sliding window
-        let total = self.count_tokens_in_valid_buckets_within_sliding_window(&state, tick, window_start_tick);
+    /// Body of [`try_acquire_at`](Self::try_acquire_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_at_locked(&self, state: &mut SlidingWindowCounterCoreState, tick: Uint,tokens: Uint) -> SimpleRateLimitResult {
+        let (index, _) = self.update_locked(state, tick)?;
 
-        // Check if we can accommodate the requested tokens
-        if total <= self.capacity.saturating_sub(tokens) {
-            state.buckets[current_bucket_index] += tokens;
-            state.last_bucket_index = current_bucket_index;
+        if self.admit_locked(state, index, tick, tokens).0 {
             Ok(())
         } else {
             Err(SimpleRateLimitError::InsufficientCapacity)
@@ -248,7 +364,7 @@ impl SlidingWindowCounterCore {
     /// * `Err(VerboseRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
     /// * `Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick })` - If the tick is older than the last recorded operation
     /// * `Err(VerboseRateLimitError::BeyondCapacity { acquiring, capacity })` - If the requested tokens exceed the maximum capacity
-    /// * `Err(VerboseRateLimitError::InsufficientCapacity { acquiring, available, retry_after_ticks })` - If there are not enough tokens available, but suggests how long to wait before retrying
+    /// * `Err(VerboseRateLimitError::InsufficientCapacity { .. })` - If there are not enough tokens available, with how long to wait before retrying and the tokens available once the oldest bucket leaves the window
     #[inline(always)]
     pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
         if tokens == 0 {
@@ -258,16 +374,17 @@ impl SlidingWindowCounterCore {
         let mut state = self.state.try_lock()
             .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
 
-        let window_start_tick = tick.saturating_sub(self.window_ticks());
+        self.try_acquire_verbose_at_locked(&mut state, tick, tokens)
+    }
 
+    /// Body of [`try_acquire_verbose_at`](Self::try_acquire_verbose_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_verbose_at_locked(&self, state: &mut SlidingWindowCounterCoreState, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
         // Reject if time has gone backwards
-        if state.bucket_start_ticks[state.last_bucket_index] > 0 &&
-            tick < state.bucket_start_ticks[state.last_bucket_index]
-        {
-            return Err(VerboseRateLimitError::ExpiredTick {
-                min_acceptable_tick: state.bucket_start_ticks[state.last_bucket_index],
-            });
-        }
+        self.check_tick_locked(state, tick).map_err(|_| VerboseRateLimitError::ExpiredTick {
+            min_acceptable_tick: state.bucket_start_ticks[state.last_bucket_index],
+        })?;
 
         // Reject if acquiring more than capacity
         if tokens > self.capacity {
@@ -277,50 +394,40 @@ impl SlidingWindowCounterCore {
             });
         }
 
-        // Determine current bucket index
-        let bucket_ticks = self.bucket_ticks;
-        let bucket_count = self.bucket_count as usize;
-        let current_bucket_idx = ((tick / bucket_ticks) % self.bucket_count as Uint) as usize;
-        let current_bucket_start_tick = (tick / bucket_ticks) * bucket_ticks;
-
-        // Reset current bucket if entering new time slot
-        if state.bucket_start_ticks[current_bucket_idx] != current_bucket_start_tick {
-            state.buckets[current_bucket_idx] = 0;
-            state.bucket_start_ticks[current_bucket_idx] = current_bucket_start_tick;
-        }
-
-        state.last_bucket_index = current_bucket_idx;
-
-        // ----- Phase 1: calculate total used tokens in current window -----
-        let mut total_used = 0;
-        let mut valid_indices = Vec::with_capacity(bucket_count);
-
-        for i in 0..bucket_count {
-            let idx = i;
-            let ts = state.bucket_start_ticks[idx];
-            if ts >= window_start_tick && ts <= tick {
-                total_used += state.buckets[idx];
-                valid_indices.push(idx);
-            }
-        }
-
-        let available = self.capacity.saturating_sub(total_used);
+        let (current_bucket_idx, _) = self.reset_bucket_locked(state, tick);
 
-        if tokens <= available {
-            state.buckets[current_bucket_idx] += tokens;
+        // ----- Phase 1: admit against the tokens used in the current window -----
+        let (admitted, available) = self.admit_locked(state, current_bucket_idx, tick, tokens);
+        if admitted {
             return Ok(());
         }
 
         // ----- Phase 2: simulate expiration to estimate retry -----
-        let mut released = 0;
+        // Buckets leave the window in start-tick order. Walking the ring once from
+        // the slot after the current bucket visits the in-window buckets in that
+        // order, oldest first, ending with the current bucket.
+        let bucket_count = self.bucket_count as usize;
+        let mut released: Uint = 0;
         let mut retry_after_ticks = self.window_ticks(); // fallback to full window
+        let mut available_next_refill = available;
+        let mut first = true;
 
-        for (i, &idx) in valid_indices.iter().enumerate() {
-            released += state.buckets[idx];
+        for offset in 1..=bucket_count {
+            let idx = (current_bucket_idx + offset) % bucket_count;
+            let ts = state.bucket_start_ticks[idx];
+            let bucket_tokens = state.buckets[idx];
+            if bucket_tokens == 0 || !self.is_bucket_in_window(ts, tick) {
+                continue;
+            }
 
-            let remaining = available + released;
-            if remaining >= tokens {
-                retry_after_ticks = ((i + 1) as Uint) * bucket_ticks;
+            if first {
+                // The next refill is the earliest bucket leaving the window
+                available_next_refill = available.saturating_add(bucket_tokens).min(self.capacity);
+                first = false;
+            }
+            released = released.saturating_add(bucket_tokens);
+            if available.saturating_add(released) >= tokens {
+                retry_after_ticks = ts.saturating_add(self.window_ticks()) - tick;
                 break;
             }
         }
@@ -329,21 +436,30 @@ impl SlidingWindowCounterCore {
             acquiring: tokens,
             available,
             retry_after_ticks,
+            available_next_refill,
         })
     }
 
+    /// Returns whether a bucket starting at `start_tick` still counts at `tick`.
+    ///
+    /// A bucket counts while `tick - start_tick < window_ticks`, so it expires at
+    /// `start_tick + window_ticks`. That is also the first tick that maps back to
+    /// the bucket's slot, where the lazy reset reuses it, so this predicate agrees
+    /// with what acquires observe after the reset.
+    #[inline(always)]
+    fn is_bucket_in_window(&self, start_tick: Uint, tick: Uint) -> bool {
+        start_tick <= tick && tick - start_tick < self.window_ticks()
+    }
 
     /// Counts the total number of tokens currently present in valid buckets
-    /// within the sliding window defined by `window_start_tick` and `tick`.
+    /// within the sliding window ending at `tick`.
     ///
-    /// Only buckets whose start time falls within the inclusive range
-    /// `[window_start_tick, tick]` are considered valid and included in the total.
+    /// Only buckets accepted by `is_bucket_in_window` are included in the total.
     /// This ensures that expired or future buckets are excluded from the calculation.
     ///
     /// # Parameters
     /// * `state` - A reference to the internal bucket state
     /// * `tick` - The current tick (inclusive upper bound of the sliding window)
-    /// * `window_start_tick` - The oldest tick included in the window (inclusive lower bound)
     ///
     /// # Returns
     /// Returns the total number of tokens in all buckets that fall within the current sliding window.
@@ -352,18 +468,100 @@ impl SlidingWindowCounterCore {
         &self,
         state: &SlidingWindowCounterCoreState,
         tick: Uint,
-        window_start_tick: Uint,
     ) -> Uint {
-        let mut total = 0;
+        let mut total: Uint = 0;
         for i in 0..(self.bucket_count as usize) {
-            let start_tick = state.bucket_start_ticks[i];
-            if start_tick >= window_start_tick && start_tick <= tick {
-                total += state.buckets[i];
+            if self.is_bucket_in_window(state.bucket_start_ticks[i], tick) {
+                total = total.saturating_add(state.buckets[i]);
             }
         }
         total
     }
 
+    /// Attempts to acquire tokens like `try_acquire_at`, and reports whether this
+    /// call rotated into a new bucket.
+    ///
+    /// `window_rotated` is set when the bucket slot for `tick` held an older
+    /// bucket and was lazily reset. `refilled` and `leaked` are always 0.
+    ///
+    /// # Returns
+    /// * `Ok(TransitionInfo)` - With `allowed` set to whether the tokens were acquired;
+    ///   a request denied for insufficient capacity is reported here, not as an error
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    pub fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        if tokens == 0 {
+            return Ok(TransitionInfo { allowed: true, ..TransitionInfo::default() });
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let (index, window_rotated) = self.update_locked(&mut state, tick)?;
+        Ok(TransitionInfo {
+            allowed: self.admit_locked(&mut state, index, tick, tokens).0,
+            window_rotated,
+            ..TransitionInfo::default()
+        })
+    }
+
+    /// Attempts to acquire tokens at the given tick, returning a plain [`AcquireCode`].
+    ///
+    /// This is the fast-path `try_acquire_at` with its result mapped to a `Copy`
+    /// integer code, intended for FFI layers that cannot carry a Rust `Result`.
+    ///
+    /// # Arguments
+    /// * `tick` - Current time tick
+    /// * `tokens` - Number of tokens to acquire
+    #[inline(always)]
+    pub fn try_acquire_code_at(&self, tick: Uint, tokens: Uint) -> AcquireCode {
+        AcquireCode::from(self.try_acquire_at(tick, tokens))
+    }
+
+    /// Returns previously acquired tokens to the sliding window.
+    ///
+    /// The refund is credited to the bucket containing `tick`, which is lazily
+    /// reset first if it still holds an older cycle. Refunds larger than the
+    /// bucket's count are clamped to zero.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick for the operation
+    /// * `tokens` - Number of tokens to give back
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were returned (zero tokens is a no-op)
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last recorded operation
+    pub fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let (current_bucket_index, _) = self.update_locked(&mut state, tick)?;
+
+        state.buckets[current_bucket_index] = state.buckets[current_bucket_index].saturating_sub(tokens);
+        state.last_bucket_index = current_bucket_index;
+        Ok(())
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `capacity / (bucket_ticks * bucket_count)`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.capacity, self.window_ticks())
+    }
+
     /// Gets the current remaining token capacity in the sliding window.
     ///
     /// This method updates bucket states based on current tick (performs lazy reset
@@ -385,33 +583,177 @@ impl SlidingWindowCounterCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        // Prevent time from going backwards (only check if we have previous data)
-        if state.bucket_start_ticks[state.last_bucket_index] > 0 && 
-           tick < state.bucket_start_ticks[state.last_bucket_index] {
-            return Err(SimpleRateLimitError::ExpiredTick);
-        }
+        self.capacity_remaining_locked(&mut state, tick)
+    }
 
-        // Determine which bucket this tick belongs to
-        let current_bucket_index = ((tick / self.bucket_ticks) as usize) % (self.bucket_count as usize);
-        let current_bucket_start_tick = (tick / self.bucket_ticks) * self.bucket_ticks;
+    /// Renders the capacity and the remaining capacity at `tick` as Prometheus
+    /// gauges labeled with `name`. See [`metrics`](crate::metrics) for the format.
+    ///
+    /// # Returns
+    /// * `Ok(text)` - The rendered lines, ending with a newline
+    /// * `Err(_)` - The error of `capacity_remaining` at `tick`
+    #[cfg(feature = "metrics")]
+    pub fn render_prometheus(&self, name: &str, tick: Uint) -> Result<String, SimpleRateLimitError> {
+        let remaining = self.capacity_remaining(tick)?;
+        Ok(crate::metrics::render_gauges(name, self.capacity, remaining))
+    }
 
-        // Lazy reset: if this bucket's start time is different, it's a new bucket cycle
-        if state.bucket_start_ticks[current_bucket_index] != current_bucket_start_tick {
-            state.buckets[current_bucket_index] = 0;
-            state.bucket_start_ticks[current_bucket_index] = current_bucket_start_tick;
-        }
+    /// Body of [`capacity_remaining`](Self::capacity_remaining) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn capacity_remaining_locked(&self, state: &mut SlidingWindowCounterCoreState, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let total_used = self.tokens_in_window_locked(state, tick)?;
+
+        // Return remaining capacity
+        Ok(self.capacity.saturating_sub(total_used))
+    }
 
-        // Calculate the sliding window range
-        let window_start_tick = tick.saturating_sub(self.window_ticks());
+    /// Returns the tokens counted in the sliding window at `tick`, after the
+    /// lazy reset of the bucket `tick` falls into.
+    fn tokens_in_window_locked(&self, state: &mut SlidingWindowCounterCoreState, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let (current_bucket_index, _) = self.update_locked(state, tick)?;
 
         // Count tokens in all valid buckets within the sliding window
-        let total_used = self.count_tokens_in_valid_buckets_within_sliding_window(&state, tick, window_start_tick);
+        let total_used = self.count_tokens_in_valid_buckets_within_sliding_window(state, tick);
 
         // Update last bucket index for future ExpiredTick checks
         state.last_bucket_index = current_bucket_index;
 
-        // Return remaining capacity
-        Ok(self.capacity.saturating_sub(total_used))
+        Ok(total_used)
+    }
+
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
+    ///
+    /// This is the capacity left in the sliding window ending at `tick`, i.e. the same value as `capacity_remaining`.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick
+    ///
+    /// # Returns
+    /// * `Ok(max_tokens)` - `try_acquire_at(tick, max_tokens)` succeeds and `max_tokens + 1` does not
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    #[inline(always)]
+    pub fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    /// Returns the current request rate, in tokens per window.
+    ///
+    /// This is the total of the tokens counted in the sliding window at `tick`,
+    /// which is the rate over the last `bucket_ticks * bucket_count` ticks. It
+    /// rises as tokens are acquired and falls as whole buckets leave the window,
+    /// which makes it a smooth load signal, for example for autoscaling. Like
+    /// `capacity_remaining`, it performs the lazy reset of the bucket `tick`
+    /// falls into but does not consume any tokens.
+    ///
+    /// # Returns
+    /// * `Ok(rate)` - Tokens counted in the sliding window at `tick`
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If `tick` is older than the last bucket used
+    pub fn current_rate(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        self.tokens_in_window_locked(&mut state, tick)
+    }
+
+    /// Returns the tokens admitted so far in the sliding window ending at `tick`.
+    ///
+    /// This is the exact counterpart of [`capacity_remaining`](Self::capacity_remaining);
+    /// the two always sum to the capacity. Buckets that have slid out of the
+    /// window are skipped rather than reset, so nothing is committed.
+    ///
+    /// # Returns
+    /// * `Ok(used)` - Sum of the buckets within the sliding window
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn used_in_window(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        self.check_tick_locked(&mut state, tick)?;
+
+        Ok(self.count_tokens_in_valid_buckets_within_sliding_window(&state, tick))
+    }
+
+    /// Returns the counter to its freshly constructed state.
+    ///
+    /// Every bucket is cleared and restarts at tick 0. The bucket storage is
+    /// reused rather than reallocated.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the state was reset
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn reset(&self) -> Result<(), SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        state.buckets.iter_mut().for_each(|count| *count = 0);
+        state.bucket_start_ticks.iter_mut().for_each(|start| *start = 0);
+        state.last_bucket_index = 0;
+        state.last_expired_delta = None;
+        Ok(())
+    }
+
+    /// Serializes the counter state into a compact binary buffer.
+    ///
+    /// The layout is the header described in [`cores`](crate::cores#binary-persistence)
+    /// followed by the bucket count, the index of the most recently used bucket,
+    /// and then the count and start tick of each bucket in order.
+    ///
+    /// # Returns
+    /// * `Ok(bytes)` - The serialized state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let mut writer = StateWriter::new(KIND_SLIDING_WINDOW, 2 + 2 * state.buckets.len());
+        writer.uint(state.buckets.len() as Uint);
+        writer.uint(state.last_bucket_index as Uint);
+        for (&count, &start) in state.buckets.iter().zip(state.bucket_start_ticks.iter()) {
+            writer.uint(count);
+            writer.uint(start);
+        }
+        Ok(writer.finish())
+    }
+
+    /// Restores a counter built from `config` with the state in `bytes`,
+    /// as produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Returns
+    /// * `Ok(counter)` - The restored counter
+    /// * `Err(StateDecodeError)` - If the header does not match this build and core,
+    ///   the buffer has the wrong length, or the bucket count or index does not
+    ///   match `config`
+    ///
+    /// # Panics
+    /// Panics if any field of `config` is zero, like the `From` conversion.
+    pub fn from_bytes(config: SlidingWindowCounterCoreConfig, bytes: &[u8]) -> Result<Self, StateDecodeError> {
+        let mut reader = StateReader::new(bytes, KIND_SLIDING_WINDOW)?;
+        let bucket_count = reader.uint()?;
+        let last_bucket_index = reader.uint()?;
+        if bucket_count != config.bucket_count || last_bucket_index >= bucket_count {
+            return Err(StateDecodeError::InvalidState);
+        }
+
+        let mut counter: Self = config.into();
+        let state = counter.state.get_mut().unwrap();
+        for i in 0..state.buckets.len() {
+            state.buckets[i] = reader.uint()?;
+            state.bucket_start_ticks[i] = reader.uint()?;
+        }
+        reader.finish()?;
+        state.last_bucket_index = last_bucket_index as usize;
+        Ok(counter)
     }
 
     /// Returns the number of tokens that can still be acquired without exceeding capacity.
@@ -428,6 +770,27 @@ impl SlidingWindowCounterCore {
         self.capacity_remaining(tick).unwrap_or(0)
     }
 
+    /// Returns how far backwards the most recently rejected tick was.
+    ///
+    /// `SimpleRateLimitError::ExpiredTick` carries no payload, to keep the fast path
+    /// small and existing comparisons against it working. When a tick is rejected as
+    /// expired, the core records `min_acceptable_tick - tick`, where the minimum
+    /// acceptable tick is the start of the most recently used bucket, and this accessor reports it.
+    /// The value is kept until the next expired rejection overwrites it.
+    ///
+    /// # Returns
+    /// * `Ok(Some(delta))` - Distance of the last `ExpiredTick` rejection behind the minimum acceptable tick
+    /// * `Ok(None)` - If no tick has been rejected as expired
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    pub fn last_rejected_expired_delta(&self) -> Result<Option<Uint>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        Ok(state.last_expired_delta)
+    }
+
     /// Gets the current remaining capacity without updating bucket states.
     ///
     /// This method returns the remaining capacity in the current sliding window
@@ -470,15 +833,48 @@ impl SlidingWindowCounterCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        // Calculate the sliding window range
-        let window_start_tick = tick.saturating_sub(self.window_ticks());
-
         // Count tokens in all valid buckets within the sliding window (without updates)
-        let total_used = self.count_tokens_in_valid_buckets_within_sliding_window(&state, tick, window_start_tick);
+        let total_used = self.count_tokens_in_valid_buckets_within_sliding_window(&state, tick);
 
         Ok(self.capacity.saturating_sub(total_used))
     }
 
+    /// Reports when the oldest bucket in the sliding window expires and how much it frees.
+    ///
+    /// A bucket starting at `start` is counted while `tick < start + window_ticks`, so it
+    /// stops counting at `start + window_ticks`. This method finds the non-empty
+    /// bucket with the earliest start tick inside the window at `tick` and reports that
+    /// expiry tick together with the tokens it holds. Like `current_capacity_at`, it
+    /// does not update any bucket state.
+    ///
+    /// # Parameters
+    /// * `tick` - Time tick for sliding window calculation
+    ///
+    /// # Returns
+    /// * `Ok(Some((expiry_tick, tokens_freed)))` - The oldest non-empty bucket in the window
+    /// * `Ok(None)` - If no tokens are counted in the window
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    pub fn next_expiry(&self, tick: Uint) -> Result<Option<(Uint, Uint)>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let mut oldest: Option<(Uint, Uint)> = None;
+        for i in 0..(self.bucket_count as usize) {
+            let start_tick = state.bucket_start_ticks[i];
+            let tokens = state.buckets[i];
+            if tokens == 0 || !self.is_bucket_in_window(start_tick, tick) {
+                continue;
+            }
+            if oldest.map_or(true, |(oldest_start, _)| start_tick < oldest_start) {
+                oldest = Some((start_tick, tokens));
+            }
+        }
+
+        let window_ticks = self.window_ticks();
+        Ok(oldest.map(|(start_tick, tokens)| (start_tick.saturating_add(window_ticks), tokens)))
+    }
 
     /// Returns the current remaining capacity
     /// This method is a convenience wrapper around `current_capacity`
@@ -489,8 +885,51 @@ impl SlidingWindowCounterCore {
     }
 }
 
+impl SlidingWindowCounterCore {
+    /// Converts this core into an [`UnsyncCore`] for single-threaded use.
+    ///
+    /// The current state is kept. The returned limiter makes the same decisions
+    /// as this core without taking the lock.
+    pub fn into_unsync(self) -> UnsyncCore<Self> {
+        UnsyncCore { core: self }
+    }
+
+    /// Runs `f` on the state without locking, for [`UnsyncCore`].
+    fn with_state_mut<R>(&mut self, f: impl FnOnce(&Self, &mut SlidingWindowCounterCoreState) -> R) -> R {
+        let mut state = std::mem::take(self.state.get_mut().unwrap_or_else(|e| e.into_inner()));
+        let result = f(self, &mut state);
+        *self.state.get_mut().unwrap_or_else(|e| e.into_inner()) = state;
+        result
+    }
+}
+
+/// Single-threaded implementation, sharing the locked path's logic.
+impl UnsyncRateLimit for UnsyncCore<SlidingWindowCounterCore> {
+    fn try_acquire_at(&mut self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        if tokens > self.core.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_at_locked(state, tick, tokens))
+    }
+
+    fn try_acquire_verbose_at(&mut self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_verbose_at_locked(state, tick, tokens))
+    }
+
+    fn capacity_remaining(&mut self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.core.with_state_mut(|core, state| core.capacity_remaining_locked(state, tick))
+    }
+}
+
 /// Configuration structure for creating a `SlidingWindowCounterCore` limiter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlidingWindowCounterCoreConfig {
     /// Maximum number of tokens allowed in the sliding window.
     pub capacity: Uint,
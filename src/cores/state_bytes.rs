@@ -0,0 +1,98 @@
+//! Compact binary layout shared by the cores' `to_bytes` / `from_bytes`.
+//!
+//! Every buffer starts with a three-byte header followed by the core's fields,
+//! each encoded as a little-endian `Uint`:
+//!
+//! | Offset | Size | Content |
+//! |--------|------|---------|
+//! | 0 | 1 | Format version ([`STATE_FORMAT_VERSION`]) |
+//! | 1 | 1 | Tick width in bytes (8 for `tick-u64`, 16 for `tick-u128`) |
+//! | 2 | 1 | Core kind |
+//! | 3 | n × width | Fields, in the order documented on each core |
+
+use core::mem::size_of;
+use crate::{StateDecodeError, Uint};
+
+/// Version of the binary state layout written by this build.
+pub(crate) const STATE_FORMAT_VERSION: u8 = 2;
+
+/// Width of an encoded `Uint` in bytes.
+const TICK_WIDTH: usize = size_of::<Uint>();
+
+/// Core kind byte of [`TokenBucketCore`](crate::cores::TokenBucketCore).
+pub(crate) const KIND_TOKEN_BUCKET: u8 = 1;
+/// Core kind byte of [`FixedWindowCounterCore`](crate::cores::FixedWindowCounterCore).
+pub(crate) const KIND_FIXED_WINDOW: u8 = 2;
+/// Core kind byte of [`SlidingWindowCounterCore`](crate::cores::SlidingWindowCounterCore).
+pub(crate) const KIND_SLIDING_WINDOW: u8 = 3;
+/// Core kind byte of [`ApproximateSlidingWindowCore`](crate::cores::ApproximateSlidingWindowCore).
+pub(crate) const KIND_APPROXIMATE_SLIDING_WINDOW: u8 = 4;
+
+/// Appends a header and fields to a buffer.
+pub(crate) struct StateWriter {
+    bytes: Vec<u8>,
+}
+
+impl StateWriter {
+    /// Starts a buffer for `fields` fields of a core of the given kind.
+    pub(crate) fn new(kind: u8, fields: usize) -> Self {
+        let mut bytes = Vec::with_capacity(3 + fields * TICK_WIDTH);
+        bytes.extend_from_slice(&[STATE_FORMAT_VERSION, TICK_WIDTH as u8, kind]);
+        StateWriter { bytes }
+    }
+
+    /// Appends one field.
+    pub(crate) fn uint(&mut self, value: Uint) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Returns the finished buffer.
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Validates a header and reads fields from a buffer.
+pub(crate) struct StateReader<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> StateReader<'a> {
+    /// Checks the header of `bytes` against this build and the expected core kind.
+    pub(crate) fn new(bytes: &'a [u8], kind: u8) -> Result<Self, StateDecodeError> {
+        if bytes.len() < 3 {
+            return Err(StateDecodeError::InvalidLength);
+        }
+        if bytes[0] != STATE_FORMAT_VERSION {
+            return Err(StateDecodeError::UnsupportedVersion(bytes[0]));
+        }
+        if bytes[1] as usize != TICK_WIDTH {
+            return Err(StateDecodeError::TickWidthMismatch { expected: TICK_WIDTH as u8, found: bytes[1] });
+        }
+        if bytes[2] != kind {
+            return Err(StateDecodeError::CoreMismatch { expected: kind, found: bytes[2] });
+        }
+        Ok(StateReader { rest: &bytes[3..] })
+    }
+
+    /// Reads the next field.
+    pub(crate) fn uint(&mut self) -> Result<Uint, StateDecodeError> {
+        if self.rest.len() < TICK_WIDTH {
+            return Err(StateDecodeError::InvalidLength);
+        }
+        let (field, rest) = self.rest.split_at(TICK_WIDTH);
+        let mut raw = [0u8; TICK_WIDTH];
+        raw.copy_from_slice(field);
+        self.rest = rest;
+        Ok(Uint::from_le_bytes(raw))
+    }
+
+    /// Checks that every byte of the buffer was read.
+    pub(crate) fn finish(self) -> Result<(), StateDecodeError> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(StateDecodeError::InvalidLength)
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::sync::Mutex;
-use crate::{SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
-use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError, AcquireCode, TransitionInfo, InvalidConfig, StateDecodeError};
+use crate::cores::state_bytes::{StateReader, StateWriter, KIND_TOKEN_BUCKET};
+use crate::cores::UnsyncCore;
+use crate::rate_limit::{RateLimitCore, Reconfigure, RefundableCore, UnsyncRateLimit, reduced_allowance};
 
 /// Core implementation of the token bucket rate limiting algorithm.
 ///
@@ -41,16 +43,61 @@ pub struct TokenBucketCore {
     refill_interval: Uint,
     /// Number of tokens added in each refill event
     refill_amount: Uint,
+    /// Maximum number of tokens a single operation may add, however long the bucket sat idle
+    accrual_cap: Option<Uint>,
+    /// Whether `capacity_remaining` commits the refill it computes
+    refill_policy: RefillPolicy,
     /// Internal state protected by mutex for thread safety
     state: Mutex<TokenBucketCoreState>,
 }
 
+/// When a [`TokenBucketCore`] commits the refill accrued since its last update.
+///
+/// Acquires always refill the bucket before taking tokens. The policy only
+/// decides whether `capacity_remaining` does too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RefillPolicy {
+    /// Reads are pure: `capacity_remaining` reports the refilled level without
+    /// storing it or advancing the last refill tick. This is the default.
+    OnWrite,
+    /// `capacity_remaining` stores the refill it computes, like an acquire of
+    /// zero tokens. This was the behavior before the policy was configurable.
+    OnReadAndWrite,
+}
+
+impl Default for RefillPolicy {
+    fn default() -> Self {
+        RefillPolicy::OnWrite
+    }
+}
+
 /// Internal state of the token bucket
+#[derive(Clone, Default)]
 struct TokenBucketCoreState {
     /// Current number of tokens available in the bucket
     available: Uint,
     /// Tick when the last refill occurred (used for calculating elapsed time)
     last_refill_tick: Uint,
+    /// How far behind the minimum acceptable tick the last `ExpiredTick` rejection was
+    last_expired_delta: Option<Uint>,
+    /// Time-weighted fill level accumulator, if fill tracking is enabled
+    fill: Option<FillAccumulator>,
+    /// Fractional part of the level in permille, less than one token and 0 when full
+    permille_credit: Uint,
+}
+
+/// Permille (thousandths) in one whole token.
+const PERMILLE_PER_TOKEN: Uint = 1000;
+
+/// Running integral of the bucket level over time
+#[derive(Clone, Copy)]
+struct FillAccumulator {
+    /// Tick up to which the level has been integrated
+    last_tick: Uint,
+    /// Sum of `available * elapsed_ticks` over all integrated intervals
+    weighted_sum: Uint,
+    /// Total ticks integrated
+    elapsed_ticks: Uint,
 }
 
 impl RateLimitCore for TokenBucketCore {
@@ -98,8 +145,9 @@ impl RateLimitCore for TokenBucketCore {
     }
 
     /// Gets the current number of tokens remaining in the bucket.
-    /// This method updates the bucket state based on elapsed time (performs refill),
-    /// then returns the current number of available tokens.
+    /// This method computes the refill due at `tick` and returns the resulting
+    /// number of available tokens. Under the default [`RefillPolicy::OnWrite`]
+    /// the refill is not stored; under [`RefillPolicy::OnReadAndWrite`] it is.
     /// # Arguments
     /// * `tick` - Current time tick for refill calculation
     /// # Returns
@@ -111,16 +159,47 @@ impl RateLimitCore for TokenBucketCore {
         self.capacity_remaining(tick)
     }
 
-    /// Returns the number of tokens that can still be acquired without exceeding capacity.
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
     ///
-    /// # Arguments
-    /// * `tick` - Current time tick for refill calculation
+    /// This method is a wrapper that calls the main `max_acquirable_at` logic.
+    #[inline(always)]
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.max_acquirable_at(tick)
+    }
+
+    /// Attempts to acquire tokens and reports the state transitions the call performed.
     ///
-    /// # Returns
-    /// Number of available tokens or 0 if error.
+    /// This method is a wrapper that calls the main `try_acquire_detailed_at` logic.
+    #[inline(always)]
+    fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        self.try_acquire_detailed_at(tick, tokens)
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction.
+    ///
+    /// This method is a wrapper that calls the main `steady_state_allowance` logic.
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
+
+impl RefundableCore for TokenBucketCore {
+    #[inline(always)]
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.refund_at(tick, tokens)
+    }
+}
+
+impl Reconfigure for TokenBucketCore {
+    type Config = TokenBucketCoreConfig;
+
+    /// Returns a bucket with `config` that carries over this bucket's state at `tick`.
+    ///
+    /// This method is a wrapper that calls the main `reconfigured` logic.
     #[inline(always)]
-    fn capacity_remaining_or_0(&self, tick: Uint) -> Uint {
-        self.capacity_remaining_or_0(tick)
+    fn reconfigured(&self, config: TokenBucketCoreConfig, tick: Uint) -> Result<Self, SimpleRateLimitError> {
+        self.reconfigured(config, tick)
     }
 }
 
@@ -153,13 +232,242 @@ impl TokenBucketCore {
             capacity,
             refill_interval,
             refill_amount,
+            accrual_cap: None,
+            refill_policy: RefillPolicy::default(),
             state: Mutex::new(TokenBucketCoreState {
                 available: capacity, // Bucket starts full
                 last_refill_tick: 0,
+                last_expired_delta: None,
+                fill: None,
+                permille_credit: 0,
             }),
         }
     }
 
+    /// Creates a new token bucket whose refill per operation is capped.
+    ///
+    /// The bucket refills lazily when it is used, adding `refill_amount` for every
+    /// `refill_interval` elapsed since the last refill. With `accrual_cap` set,
+    /// one such refill adds at most `accrual_cap` tokens, so
+    /// `available = min(capacity, previous_available + min(refilled, accrual_cap))`.
+    /// After a long idle period the bucket therefore does not jump straight back
+    /// to `capacity`, which discourages "idle, then megaburst" clients. Under
+    /// steady traffic, where each operation refills fewer than `accrual_cap`
+    /// tokens, the bucket behaves like [`new`](Self::new). `None` disables the cap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parameter is zero, including `accrual_cap: Some(0)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rate_guard_core::cores::TokenBucketCore;
+    ///
+    /// let bucket = TokenBucketCore::new_with_accrual_cap(100, 10, 10, Some(20));
+    /// assert_eq!(bucket.try_acquire_at(0, 100), Ok(()));
+    /// // A long idle period adds at most 20 tokens
+    /// assert_eq!(bucket.capacity_remaining(1000), Ok(20));
+    /// ```
+    pub fn new_with_accrual_cap(capacity: Uint, refill_interval: Uint, refill_amount: Uint, accrual_cap: Option<Uint>) -> Self {
+        assert!(accrual_cap != Some(0), "accrual_cap must be greater than 0");
+        let mut bucket = Self::new(capacity, refill_interval, refill_amount);
+        bucket.accrual_cap = accrual_cap;
+        bucket
+    }
+
+    /// Creates a new token bucket with the given [`RefillPolicy`].
+    ///
+    /// [`new`](Self::new) uses [`RefillPolicy::OnWrite`]; pass
+    /// [`RefillPolicy::OnReadAndWrite`] to have `capacity_remaining` commit
+    /// refills as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parameter is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rate_guard_core::cores::{RefillPolicy, TokenBucketCore};
+    ///
+    /// let bucket = TokenBucketCore::new_with_refill_policy(10, 5, 1, RefillPolicy::OnReadAndWrite);
+    /// assert_eq!(bucket.capacity_remaining(20), Ok(10));
+    /// // The read advanced the bucket to tick 20
+    /// assert!(bucket.try_acquire_at(10, 1).is_err());
+    /// ```
+    pub fn new_with_refill_policy(capacity: Uint, refill_interval: Uint, refill_amount: Uint, refill_policy: RefillPolicy) -> Self {
+        let mut bucket = Self::new(capacity, refill_interval, refill_amount);
+        bucket.refill_policy = refill_policy;
+        bucket
+    }
+
+    /// Returns the number of tokens added by `refill_times` elapsed intervals,
+    /// limited by the accrual cap if one is configured.
+    #[inline(always)]
+    fn refill_tokens(&self, refill_times: Uint) -> Uint {
+        let refilled = refill_times.saturating_mul(self.refill_amount);
+        match self.accrual_cap {
+            Some(cap) => refilled.min(cap),
+            None => refilled,
+        }
+    }
+
+    /// Adds whole `tokens` to the bucket, capped at the capacity.
+    ///
+    /// The permille credit is the fractional part of the level, so a bucket
+    /// filled to capacity keeps none: the level never exceeds `capacity`.
+    #[inline(always)]
+    fn add_tokens(&self, state: &mut TokenBucketCoreState, tokens: Uint) {
+        if tokens >= self.capacity - state.available {
+            state.available = self.capacity;
+            state.permille_credit = 0;
+        } else {
+            state.available += tokens;
+        }
+    }
+
+    /// Creates a new token bucket that tracks its time-weighted average fill level.
+    ///
+    /// Behaves exactly like [`new`](Self::new), and additionally integrates the
+    /// number of available tokens over time for [`average_fill`](Self::average_fill).
+    ///
+    /// # Panics
+    ///
+    /// Panics if any parameter is zero.
+    pub fn new_with_fill_tracking(capacity: Uint, refill_interval: Uint, refill_amount: Uint) -> Self {
+        let bucket = Self::new(capacity, refill_interval, refill_amount);
+        bucket.state.lock().unwrap().fill = Some(FillAccumulator {
+            last_tick: 0,
+            weighted_sum: 0,
+            elapsed_ticks: 0,
+        });
+        bucket
+    }
+
+    /// Integrates the level over the ticks since the last integration.
+    ///
+    /// Between operations the level follows the lazy refill exactly: it holds
+    /// `available` until the next refill boundary and then rises by each refill,
+    /// up to the capacity. Every operation that changes `available` or the last
+    /// refill tick calls this first, at the tick it changes them, so the area
+    /// integrated is the level an observer would have seen tick by tick.
+    #[inline(always)]
+    fn integrate_fill(&self, state: &mut TokenBucketCoreState, tick: Uint) {
+        let last_tick = match state.fill {
+            Some(fill) if tick > fill.last_tick => fill.last_tick,
+            _ => return,
+        };
+        let (available, last_refill_tick) = (state.available, state.last_refill_tick);
+        // A bucket restored past the last integration held `available` until its last refill
+        let before_refill = last_refill_tick.saturating_sub(last_tick).min(tick - last_tick);
+        let from = last_tick.max(last_refill_tick);
+        let area = available
+            .saturating_mul(before_refill)
+            .saturating_add(self.fill_area_since_refill(state, tick).saturating_sub(self.fill_area_since_refill(state, from)));
+
+        if let Some(fill) = state.fill.as_mut() {
+            fill.weighted_sum = fill.weighted_sum.saturating_add(area);
+            fill.elapsed_ticks = fill.elapsed_ticks.saturating_add(tick - last_tick);
+            fill.last_tick = tick;
+        }
+    }
+
+    /// Returns the integral of the level from the last refill tick to `tick`,
+    /// assuming no operation in between. `tick` must not be before the last refill.
+    ///
+    /// After `k` refill events the level is `min(available + k * refill_amount, limit)`,
+    /// where `limit` is the capacity, or `available` plus the accrual cap if that is lower.
+    fn fill_area_since_refill(&self, state: &TokenBucketCoreState, tick: Uint) -> Uint {
+        let available = state.available;
+        let limit = match self.accrual_cap {
+            Some(cap) => available.saturating_add(cap).min(self.capacity),
+            None => self.capacity,
+        };
+        let level = |k: Uint| available.saturating_add(k.saturating_mul(self.refill_amount)).min(limit);
+
+        let elapsed = tick - state.last_refill_tick;
+        let (intervals, partial) = (elapsed / self.refill_interval, elapsed % self.refill_interval);
+        // Levels of the first `rising` whole intervals are below the limit
+        let rising = if limit > available {
+            intervals.min((limit - available - 1) / self.refill_amount + 1)
+        } else {
+            0
+        };
+        let triangle = if rising % 2 == 0 {
+            (rising / 2).saturating_mul(rising.saturating_sub(1))
+        } else {
+            rising.saturating_mul((rising - 1) / 2)
+        };
+        let whole_intervals = available
+            .saturating_mul(rising)
+            .saturating_add(self.refill_amount.saturating_mul(triangle))
+            .saturating_add(limit.saturating_mul(intervals - rising));
+        whole_intervals
+            .saturating_mul(self.refill_interval)
+            .saturating_add(level(intervals).saturating_mul(partial))
+    }
+
+    /// Creates a new token bucket, returning an error instead of panicking on invalid parameters.
+    ///
+    /// This is the lenient form: like [`new`](Self::new), it only rejects zero
+    /// parameters. A `refill_amount` larger than `capacity` is accepted, and each
+    /// refill is simply capped at `capacity`. Use [`try_new_strict`](Self::try_new_strict)
+    /// to treat that as a misconfiguration.
+    ///
+    /// # Returns
+    /// * `Ok(TokenBucketCore)` - If every parameter is non-zero
+    /// * `Err(InvalidConfig)` - The first zero parameter, in argument order
+    pub fn try_new(capacity: Uint, refill_interval: Uint, refill_amount: Uint) -> Result<Self, InvalidConfig> {
+        if capacity == 0 {
+            return Err(InvalidConfig::ZeroCapacity);
+        }
+        if refill_interval == 0 {
+            return Err(InvalidConfig::ZeroRefillInterval);
+        }
+        if refill_amount == 0 {
+            return Err(InvalidConfig::ZeroRefillAmount);
+        }
+        Ok(Self::new(capacity, refill_interval, refill_amount))
+    }
+
+    /// Creates a new token bucket with strict validation.
+    ///
+    /// In addition to the checks of [`try_new`](Self::try_new), this rejects a
+    /// `refill_amount` larger than `capacity`. Such a bucket works, but part of
+    /// every refill is discarded by the cap, which usually means the parameters
+    /// were mixed up.
+    ///
+    /// # Returns
+    /// * `Ok(TokenBucketCore)` - If the parameters are valid
+    /// * `Err(InvalidConfig::RefillExceedsCapacity { .. })` - If `refill_amount > capacity`
+    /// * `Err(InvalidConfig)` - Any error [`try_new`](Self::try_new) would return
+    pub fn try_new_strict(capacity: Uint, refill_interval: Uint, refill_amount: Uint) -> Result<Self, InvalidConfig> {
+        if capacity > 0 && refill_amount > capacity {
+            return Err(InvalidConfig::RefillExceedsCapacity { refill_amount, capacity });
+        }
+        Self::try_new(capacity, refill_interval, refill_amount)
+    }
+
+    /// Creates a new token bucket from a configuration, returning an error
+    /// instead of panicking on invalid fields.
+    ///
+    /// This is the fallible form of `TokenBucketCore::from(config)`, for
+    /// configurations that come from outside the program.
+    ///
+    /// # Returns
+    /// * `Ok(TokenBucketCore)` - If the configuration is valid
+    /// * `Err(InvalidConfig::ZeroAccrualCap)` - If `accrual_cap` is `Some(0)`
+    /// * `Err(InvalidConfig)` - Any error [`try_new`](Self::try_new) would return
+    pub fn try_from_config(config: TokenBucketCoreConfig) -> Result<Self, InvalidConfig> {
+        let mut bucket = Self::try_new(config.capacity, config.refill_interval, config.refill_amount)?;
+        if config.accrual_cap == Some(0) {
+            return Err(InvalidConfig::ZeroAccrualCap);
+        }
+        bucket.accrual_cap = config.accrual_cap;
+        Ok(bucket)
+    }
+
     /// Attempts to acquire the specified number of tokens at the given tick.
     ///
     /// This method first calculates how many tokens should have been added since the
@@ -192,27 +500,73 @@ impl TokenBucketCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
+        self.try_acquire_at_locked(&mut state, tick, tokens)
+    }
+
+    /// Commits the refill due at `tick` to the state.
+    ///
+    /// Every path that brings the bucket up to date goes through here.
+    ///
+    /// # Returns
+    /// * `Ok(refilled)` - The tokens the refill added, after the capacity cap
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If `tick` is older than the last refill
+    fn refill_locked(&self, state: &mut TokenBucketCoreState, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.check_tick_locked(state, tick)?;
+        Ok(self.apply_refill_locked(state, tick))
+    }
+
+    /// Rejects a `tick` older than the last refill.
+    #[inline(always)]
+    fn check_tick_locked(&self, state: &mut TokenBucketCoreState, tick: Uint) -> SimpleRateLimitResult {
         // Prevent time from going backwards
         if tick < state.last_refill_tick {
+            state.last_expired_delta = Some(state.last_refill_tick - tick);
             return Err(SimpleRateLimitError::ExpiredTick);
         }
+        Ok(())
+    }
+
+    /// Commits the refill due at a `tick` already checked by
+    /// [`check_tick_locked`](Self::check_tick_locked), returning the tokens it added.
+    #[inline(always)]
+    fn apply_refill_locked(&self, state: &mut TokenBucketCoreState, tick: Uint) -> Uint {
+        self.integrate_fill(state, tick);
 
         // Calculate how many tokens should be added based on elapsed time
         let elapsed_ticks = tick - state.last_refill_tick;
         let refill_times = elapsed_ticks / self.refill_interval;
-        let total_refilled = refill_times.saturating_mul(self.refill_amount);
-        
+        let total_refilled = self.refill_tokens(refill_times);
+
         // Apply the refill, capped at bucket capacity
-        state.available = (state.available.saturating_add(total_refilled)).min(self.capacity);
-        
+        let before = state.available;
+        self.add_tokens(state, total_refilled);
+
         // Update last refill tick to align with actual refill timing
         if refill_times > 0 {
-            state.last_refill_tick = state.last_refill_tick + (refill_times * self.refill_interval);
+            state.last_refill_tick += refill_times * self.refill_interval;
         }
+        state.available - before
+    }
 
-        // Check if we have sufficient tokens available
+    /// Takes `tokens` from a bucket already refilled up to date, if it holds
+    /// them. Shared by every whole-token acquire path.
+    #[inline(always)]
+    fn admit_locked(state: &mut TokenBucketCoreState, tokens: Uint) -> bool {
         if tokens <= state.available {
             state.available -= tokens;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Body of [`try_acquire_at`](Self::try_acquire_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_at_locked(&self, state: &mut TokenBucketCoreState, tick: Uint,tokens: Uint) -> SimpleRateLimitResult {
+        self.refill_locked(state, tick)?;
+
+        if Self::admit_locked(state, tokens) {
             Ok(())
         } else {
             Err(SimpleRateLimitError::InsufficientCapacity)
@@ -245,7 +599,8 @@ impl TokenBucketCore {
     /// * `Err(VerboseRateLimitError::ExpiredTick)` – If the provided tick is older than the last refill
     /// * `Err(VerboseRateLimitError::BeyondCapacity)` – If the requested amount exceeds the bucket's max capacity
     /// * `Err(VerboseRateLimitError::InsufficientCapacity)` – If not enough tokens are currently available,
-    ///     includes how many are available and how long to wait (in ticks) before retrying.
+    ///   includes how many are available, how long to wait (in ticks) before retrying, and
+    ///   `available_next_refill`, the tokens available after one more refill.
     ///
     /// # Example
     /// ```
@@ -272,11 +627,16 @@ impl TokenBucketCore {
         let mut state = self.state.try_lock()
             .map_err(|_| VerboseRateLimitError::ContentionFailure)?;
 
-        if tick < state.last_refill_tick {
-            return Err(VerboseRateLimitError::ExpiredTick {
-                min_acceptable_tick: state.last_refill_tick,
-            });
-        }
+        self.try_acquire_verbose_at_locked(&mut state, tick, tokens)
+    }
+
+    /// Body of [`try_acquire_verbose_at`](Self::try_acquire_verbose_at) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn try_acquire_verbose_at_locked(&self, state: &mut TokenBucketCoreState, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.check_tick_locked(state, tick).map_err(|_| VerboseRateLimitError::ExpiredTick {
+            min_acceptable_tick: state.last_refill_tick,
+        })?;
 
         if tokens > self.capacity {
             return Err(VerboseRateLimitError::BeyondCapacity {
@@ -285,26 +645,18 @@ impl TokenBucketCore {
             });
         }
 
-        let elapsed_ticks = tick - state.last_refill_tick;
-        let refill_times = elapsed_ticks / self.refill_interval;
-        let total_refilled = refill_times.saturating_mul(self.refill_amount);
-
-        state.available = (state.available + total_refilled).min(self.capacity);
+        self.apply_refill_locked(state, tick);
 
-        if refill_times > 0 {
-            state.last_refill_tick += refill_times * self.refill_interval;
-        }
-
-        if tokens <= state.available {
-            state.available -= tokens;
+        if Self::admit_locked(state, tokens) {
             Ok(())
         } else {
-
             // Step 3: retry-after estimation
             let available = state.available;
             let shortfall = tokens.saturating_sub(available);
             debug_assert!(shortfall > 0);
 
+            // With an accrual cap below the shortfall, one refill cannot cover it and
+            // this is only a lower bound: the caller needs more than one retry.
             let needed_refills = (shortfall + self.refill_amount - 1) / self.refill_amount; //ceil(shortfall / refill_amount)
             debug_assert!(needed_refills >= 1);
 
@@ -316,22 +668,165 @@ impl TokenBucketCore {
                 acquiring: tokens,
                 available,
                 retry_after_ticks,
+                available_next_refill: available.saturating_add(self.refill_tokens(1)).min(self.capacity),
             })
         }
     }
 
+    /// Attempts to acquire a fractional cost, expressed in permille (thousandths)
+    /// of a token, at the given tick.
+    ///
+    /// The bucket level is kept in permille: the whole tokens plus a credit of
+    /// less than one token, the change left when a fractional cost was paid
+    /// from a whole token. A cost is admitted if it does not exceed the level,
+    /// so a cost of 300 permille consumes exactly 0.3 of a token, and three such
+    /// acquires fit in one token.
+    ///
+    /// The level never exceeds the capacity: a refill or refund that fills the
+    /// bucket absorbs the credit. `capacity_remaining` reports the level in
+    /// whole tokens, rounded down.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick for the operation
+    /// * `permille` - Cost in thousandths of a token
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the cost was covered
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `permille` exceeds the capacity in permille
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If credit and available tokens do not cover it
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    pub fn try_acquire_permille_at(&self, tick: Uint, permille: Uint) -> SimpleRateLimitResult {
+        if permille == 0 {
+            return Ok(());
+        }
+
+        if permille > self.capacity.saturating_mul(PERMILLE_PER_TOKEN) {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        self.refill_locked(&mut state, tick)?;
+
+        if permille <= state.permille_credit {
+            state.permille_credit -= permille;
+            return Ok(());
+        }
+
+        // Withdraw ceil(shortfall / 1000) whole tokens and keep the change
+        let shortfall = permille - state.permille_credit;
+        let whole_tokens = shortfall / PERMILLE_PER_TOKEN + Uint::from(shortfall % PERMILLE_PER_TOKEN != 0);
+        if whole_tokens <= state.available {
+            state.available -= whole_tokens;
+            // whole_tokens * 1000 - shortfall, without overflowing near Uint::MAX
+            state.permille_credit = (PERMILLE_PER_TOKEN - shortfall % PERMILLE_PER_TOKEN) % PERMILLE_PER_TOKEN;
+            Ok(())
+        } else {
+            Err(SimpleRateLimitError::InsufficientCapacity)
+        }
+    }
+
     /// Gets the current number of tokens remaining in the bucket.
-    /// This method updates the bucket state based on elapsed time (performs refill),
-    /// then returns the current number of available tokens.
+    /// This method computes the refill due at `tick` and returns the resulting
+    /// number of available tokens. Under the default [`RefillPolicy::OnWrite`]
+    /// the refill is not stored; under [`RefillPolicy::OnReadAndWrite`] it is.
     #[inline]
     pub fn tokens_in_bucket(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
         self.capacity_remaining(tick)
     }
 
+    /// Attempts to acquire tokens like `try_acquire_at`, and reports the refill
+    /// this call performed.
+    ///
+    /// `refilled` is the number of tokens the lazy refill actually added, after
+    /// the capacity cap. `window_rotated` and `leaked` are always `false` and 0.
+    ///
+    /// # Returns
+    /// * `Ok(TransitionInfo)` - With `allowed` set to whether the tokens were acquired;
+    ///   a request denied for insufficient capacity is reported here, not as an error
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last operation
+    pub fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        if tokens == 0 {
+            return Ok(TransitionInfo { allowed: true, ..TransitionInfo::default() });
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let refilled = self.refill_locked(&mut state, tick)?;
+        Ok(TransitionInfo {
+            allowed: Self::admit_locked(&mut state, tokens),
+            refilled,
+            ..TransitionInfo::default()
+        })
+    }
+
+    /// Attempts to acquire tokens at the given tick, returning a plain [`AcquireCode`].
+    ///
+    /// This is the fast-path `try_acquire_at` with its result mapped to a `Copy`
+    /// integer code, intended for FFI layers that cannot carry a Rust `Result`.
+    ///
+    /// # Arguments
+    /// * `tick` - Current time tick
+    /// * `tokens` - Number of tokens to acquire
+    #[inline(always)]
+    pub fn try_acquire_code_at(&self, tick: Uint, tokens: Uint) -> AcquireCode {
+        AcquireCode::from(self.try_acquire_at(tick, tokens))
+    }
+
+    /// Returns previously acquired tokens to the bucket.
+    ///
+    /// The bucket is first refilled up to `tick`, then `tokens` are added back.
+    /// Refunding beyond capacity is silently clamped to `capacity`.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick for the operation
+    /// * `tokens` - Number of tokens to give back
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were returned (zero tokens is a no-op)
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last refill
+    pub fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        // Bring the bucket up to date before crediting the refund
+        self.refill_locked(&mut state, tick)?;
+
+        self.add_tokens(&mut state, tokens);
+        Ok(())
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `refill_amount / refill_interval`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.refill_amount, self.refill_interval)
+    }
+
     /// Gets the current remaining token capacity.
     ///
-    /// This method updates the bucket state based on elapsed time (performs refill),
-    /// then returns the current number of available tokens.
+    /// This method computes the refill due at `tick` and returns the resulting
+    /// number of available tokens. Under the default [`RefillPolicy::OnWrite`]
+    /// the refill is not stored; under [`RefillPolicy::OnReadAndWrite`] it is.
     ///
     /// # Parameters
     /// * `tick` - Current time tick for refill calculation
@@ -348,28 +843,313 @@ impl TokenBucketCore {
             Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
         };
 
-        // Prevent time from going backwards
-        if tick < state.last_refill_tick {
-            return Err(SimpleRateLimitError::ExpiredTick);
-        }
+        self.capacity_remaining_locked(&mut state, tick)
+    }
 
-        // Calculate how many tokens should be added based on elapsed time
+    /// Renders the capacity and the remaining capacity at `tick` as Prometheus
+    /// gauges labeled with `name`. See [`metrics`](crate::metrics) for the format.
+    ///
+    /// # Returns
+    /// * `Ok(text)` - The rendered lines, ending with a newline
+    /// * `Err(_)` - The error of `capacity_remaining` at `tick`
+    #[cfg(feature = "metrics")]
+    pub fn render_prometheus(&self, name: &str, tick: Uint) -> Result<String, SimpleRateLimitError> {
+        let remaining = self.capacity_remaining(tick)?;
+        Ok(crate::metrics::render_gauges(name, self.capacity, remaining))
+    }
+
+    /// Returns the tokens available at `tick` and the whole refill intervals
+    /// elapsed since the last refill, without committing either. `tick` must
+    /// not be before the last refill tick.
+    #[inline(always)]
+    fn projected_refill(&self, state: &TokenBucketCoreState, tick: Uint) -> (Uint, Uint) {
         let elapsed_ticks = tick - state.last_refill_tick;
         let refill_times = elapsed_ticks / self.refill_interval;
-        let total_refilled = refill_times.saturating_mul(self.refill_amount);
-        
-        // Apply the refill, capped at bucket capacity
-        state.available = (state.available.saturating_add(total_refilled)).min(self.capacity);
-        
-        // Update last refill tick to align with actual refill timing
-        if refill_times > 0 {
-            state.last_refill_tick = state.last_refill_tick + (refill_times * self.refill_interval);
+        let total_refilled = self.refill_tokens(refill_times);
+        (state.available.saturating_add(total_refilled).min(self.capacity), refill_times)
+    }
+
+    /// Returns a bucket with `config` that carries over this bucket's state at `tick`.
+    ///
+    /// The refill due at `tick` under the current parameters is applied first,
+    /// then the available tokens are clamped to the new capacity, so shrinking
+    /// the capacity never hands out more than it allows and growing it does
+    /// not grant a free burst. The new refill interval is measured from the
+    /// last refill. The refill policy, fill tracking and permille credit carry
+    /// over unchanged; this bucket is not modified.
+    ///
+    /// # Returns
+    /// * `Ok(bucket)` - The reconfigured bucket
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last refill
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    ///
+    /// # Panics
+    /// Panics if `config` has a zero capacity, refill interval or refill amount.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rate_guard_core::cores::{TokenBucketCore, TokenBucketCoreConfig};
+    ///
+    /// let bucket = TokenBucketCore::new(100, 10, 10);
+    /// assert_eq!(bucket.try_acquire_at(0, 40), Ok(()));
+    ///
+    /// let smaller = bucket.reconfigured(TokenBucketCoreConfig::new(50, 10, 5), 0).unwrap();
+    /// assert_eq!(smaller.capacity_remaining(0), Ok(50));
+    /// ```
+    pub fn reconfigured(&self, config: TokenBucketCoreConfig, tick: Uint) -> Result<Self, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        // Work on a copy so this bucket is left exactly as it was
+        let mut migrated = state.clone();
+        self.refill_locked(&mut migrated, tick)?;
+
+        let mut bucket = TokenBucketCore::from(config);
+        bucket.refill_policy = self.refill_policy;
+        if migrated.available >= bucket.capacity {
+            migrated.available = bucket.capacity;
+            migrated.permille_credit = 0;
         }
+        *bucket.state.get_mut().unwrap() = migrated;
+        Ok(bucket)
+    }
 
-        // Return current available token count
-        Ok(state.available)
+    /// Returns how many refill events it takes, from `tick`, to fill the bucket:
+    /// `ceil((capacity - available) / refill_amount)`, with `available` as
+    /// of `tick`.
+    ///
+    /// This is the debt a pipelining caller has run up, in refill cycles; see
+    /// [`ticks_until_full`](Self::ticks_until_full) for the same in ticks. Both
+    /// assume no further acquires and a full `refill_amount` per event. Nothing
+    /// is committed, whatever the [`RefillPolicy`].
+    ///
+    /// # Returns
+    /// * `Ok(cycles)` - 0 if the bucket is full at `tick`
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last refill
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rate_guard_core::cores::TokenBucketCore;
+    ///
+    /// let bucket = TokenBucketCore::new(10, 5, 3);
+    /// assert_eq!(bucket.try_acquire_at(0, 8), Ok(()));
+    /// assert_eq!(bucket.refill_cycles_behind(0), Ok(3));
+    /// ```
+    pub fn refill_cycles_behind(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        self.check_tick_locked(&mut state, tick)?;
+
+        let (available, _) = self.projected_refill(&state, tick);
+        Ok(self.cycles_to_fill(available))
+    }
+
+    /// Returns how many ticks after `tick` the bucket becomes full.
+    ///
+    /// The first refill event lands at the next interval boundary after the
+    /// last refill, and each later one `refill_interval` ticks after that, so
+    /// with `cycles = refill_cycles_behind(tick)` the result lies in
+    /// `((cycles - 1) * refill_interval, cycles * refill_interval]`, and is 0
+    /// exactly when `cycles` is 0. Nothing is committed.
+    ///
+    /// # Returns
+    /// * `Ok(ticks)` - 0 if the bucket is full at `tick`
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last refill
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn ticks_until_full(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        self.check_tick_locked(&mut state, tick)?;
+
+        let (available, refill_times) = self.projected_refill(&state, tick);
+        let cycles = self.cycles_to_fill(available);
+        if cycles == 0 {
+            return Ok(0);
+        }
+        let next_refill_tick = state
+            .last_refill_tick
+            .saturating_add(refill_times.saturating_add(1).saturating_mul(self.refill_interval));
+        Ok((next_refill_tick - tick).saturating_add((cycles - 1).saturating_mul(self.refill_interval)))
+    }
+
+    /// Returns the bucket to its freshly constructed state.
+    ///
+    /// The bucket is refilled to capacity and its refill clock restarts at
+    /// tick 0, so ticks earlier than a previous acquire are accepted again.
+    /// Fill tracking, if enabled, starts over as well.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the state was reset
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn reset(&self) -> Result<(), SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        state.available = self.capacity;
+        state.last_refill_tick = 0;
+        state.last_expired_delta = None;
+        state.permille_credit = 0;
+        if let Some(fill) = state.fill.as_mut() {
+            *fill = FillAccumulator {
+                last_tick: 0,
+                weighted_sum: 0,
+                elapsed_ticks: 0,
+            };
+        }
+        Ok(())
+    }
+
+    /// Returns the refill events needed to bring `available` tokens up to capacity.
+    #[inline(always)]
+    fn cycles_to_fill(&self, available: Uint) -> Uint {
+        let deficit = self.capacity - available;
+        deficit / self.refill_amount + Uint::from(deficit % self.refill_amount != 0)
+    }
+
+    /// Body of [`capacity_remaining`](Self::capacity_remaining) once the state is held exclusively,
+    /// shared by the locked path and [`UnsyncCore`].
+    #[inline(always)]
+    fn capacity_remaining_locked(&self, state: &mut TokenBucketCoreState, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.check_tick_locked(state, tick)?;
+
+        // Only commit the refill if reads are allowed to advance the bucket
+        if self.refill_policy == RefillPolicy::OnReadAndWrite {
+            self.apply_refill_locked(state, tick);
+            return Ok(state.available);
+        }
+
+        Ok(self.projected_refill(state, tick).0)
+    }
+
+    /// Returns the largest number of tokens a single acquire at `tick` would be granted.
+    ///
+    /// This is the tokens available after refilling up to `tick`, i.e. the same value as `capacity_remaining`.
+    ///
+    /// # Parameters
+    /// * `tick` - Current time tick
+    ///
+    /// # Returns
+    /// * `Ok(max_tokens)` - `try_acquire_at(tick, max_tokens)` succeeds and `max_tokens + 1` does not
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the current state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    #[inline(always)]
+    pub fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    /// Serializes the bucket state into a compact binary buffer.
+    ///
+    /// The layout is the header described in [`cores`](crate::cores#binary-persistence)
+    /// followed by `available`, the last refill tick and the permille credit.
+    /// Fill tracking and `ExpiredTick` diagnostics are not persisted.
+    ///
+    /// # Returns
+    /// * `Ok(bytes)` - The serialized state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let mut writer = StateWriter::new(KIND_TOKEN_BUCKET, 3);
+        writer.uint(state.available);
+        writer.uint(state.last_refill_tick);
+        writer.uint(state.permille_credit);
+        Ok(writer.finish())
+    }
+
+    /// Restores a bucket built from `config` with the state in `bytes`,
+    /// as produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Returns
+    /// * `Ok(bucket)` - The restored bucket
+    /// * `Err(StateDecodeError)` - If the header does not match this build and core,
+    ///   the buffer has the wrong length, or the state exceeds the configured capacity
+    ///
+    /// # Panics
+    /// Panics if any field of `config` is zero, like the `From` conversion.
+    pub fn from_bytes(config: TokenBucketCoreConfig, bytes: &[u8]) -> Result<Self, StateDecodeError> {
+        let mut reader = StateReader::new(bytes, KIND_TOKEN_BUCKET)?;
+        let available = reader.uint()?;
+        let last_refill_tick = reader.uint()?;
+        let permille_credit = reader.uint()?;
+        reader.finish()?;
+
+        let mut bucket: Self = config.into();
+        // The credit is the fractional part of the level, which never exceeds the capacity
+        if available > bucket.capacity || permille_credit >= PERMILLE_PER_TOKEN || (available == bucket.capacity && permille_credit > 0) {
+            return Err(StateDecodeError::InvalidState);
+        }
+        let state = bucket.state.get_mut().unwrap();
+        state.available = available;
+        state.last_refill_tick = last_refill_tick;
+        state.permille_credit = permille_credit;
+        Ok(bucket)
     }
 
+    /// Captures the bucket's configuration and state as a [`TokenBucketSnapshot`].
+    ///
+    /// Unlike [`to_bytes`](Self::to_bytes), the snapshot carries the configuration,
+    /// so [`restore`](Self::restore) needs nothing else. The refill policy, fill
+    /// tracking and `ExpiredTick` diagnostics are not captured.
+    ///
+    /// # Returns
+    /// * `Ok(snapshot)` - The bucket's configuration and state
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn snapshot(&self) -> Result<TokenBucketSnapshot, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        Ok(TokenBucketSnapshot {
+            config: TokenBucketCoreConfig {
+                capacity: self.capacity,
+                refill_interval: self.refill_interval,
+                refill_amount: self.refill_amount,
+                accrual_cap: self.accrual_cap,
+            },
+            available: state.available,
+            last_refill_tick: state.last_refill_tick,
+            permille_credit: state.permille_credit,
+        })
+    }
+
+    /// Rebuilds a bucket from a [`TokenBucketSnapshot`].
+    ///
+    /// The bucket continues from the captured state: refills accrue from
+    /// `last_refill_tick`, so time that passed while the service was down is
+    /// credited as usual, and ticks before it are rejected. A snapshot that was
+    /// edited or written by hand is clamped: `available` to the capacity and the
+    /// permille credit to less than one token, or to none if the bucket is full.
+    ///
+    /// # Panics
+    /// Panics if any field of `snapshot.config` is zero, like the `From` conversion.
+    pub fn restore(snapshot: TokenBucketSnapshot) -> Self {
+        let mut bucket: Self = snapshot.config.into();
+        let capacity = bucket.capacity;
+        let state = bucket.state.get_mut().unwrap();
+        state.available = snapshot.available.min(capacity);
+        state.last_refill_tick = snapshot.last_refill_tick;
+        state.permille_credit = if state.available == capacity {
+            0
+        } else {
+            snapshot.permille_credit.min(PERMILLE_PER_TOKEN - 1)
+        };
+        bucket
+    }
 
     /// Returns the number of tokens that can still be acquired without exceeding capacity.
     ///
@@ -383,6 +1163,51 @@ impl TokenBucketCore {
         self.capacity_remaining(tick).unwrap_or(0)
     }
 
+    /// Returns how far backwards the most recently rejected tick was.
+    ///
+    /// `SimpleRateLimitError::ExpiredTick` carries no payload, to keep the fast path
+    /// small and existing comparisons against it working. When a tick is rejected as
+    /// expired, the core records `min_acceptable_tick - tick`, where the minimum
+    /// acceptable tick is the last refill tick, and this accessor reports it.
+    /// The value is kept until the next expired rejection overwrites it.
+    ///
+    /// # Returns
+    /// * `Ok(Some(delta))` - Distance of the last `ExpiredTick` rejection behind the minimum acceptable tick
+    /// * `Ok(None)` - If no tick has been rejected as expired
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    pub fn last_rejected_expired_delta(&self) -> Result<Option<Uint>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        Ok(state.last_expired_delta)
+    }
+
+    /// Returns the time-weighted average number of available tokens.
+    ///
+    /// Requires a bucket created with [`new_with_fill_tracking`](Self::new_with_fill_tracking).
+    /// The level is integrated over time, refills included, up to the tick of the
+    /// last operation that changed the bucket, and the average is the integral
+    /// divided by the ticks integrated (rounded down).
+    ///
+    /// # Returns
+    /// * `Ok(Some(average))` - The average fill level since tick 0, or the current
+    ///   level if no ticks have been integrated yet
+    /// * `Ok(None)` - If fill tracking is disabled
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    pub fn average_fill(&self) -> Result<Option<Uint>, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        Ok(state.fill.as_ref().map(|fill| match fill.elapsed_ticks {
+            0 => state.available,
+            elapsed_ticks => fill.weighted_sum / elapsed_ticks,
+        }))
+    }
+
     /// Gets the current token capacity without updating refill state.
     ///
     /// This method returns the current number of tokens in the bucket without
@@ -402,6 +1227,51 @@ impl TokenBucketCore {
         Ok(state.available)
     }
 
+    /// Gets the tokens that would be available at a specific tick without updating refill state.
+    ///
+    /// The refill due at `tick` is computed on the stored state and not committed,
+    /// regardless of the [`RefillPolicy`], so future ticks can be polled without
+    /// advancing the bucket. A tick before the last refill reports the stored tokens.
+    ///
+    /// # Parameters
+    /// * `tick` - Time tick to project the bucket to
+    ///
+    /// # Returns
+    /// * `Ok(available_tokens)` - Tokens in the bucket at `tick`
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    #[inline(always)]
+    pub fn current_capacity_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let (available, _) = self.projected_refill(&state, tick.max(state.last_refill_tick));
+        Ok(available)
+    }
+
+    /// Returns the tick of the next refill event after the stored state.
+    ///
+    /// This is `last_refill_tick + refill_interval`, computed from the stored
+    /// state like [`current_capacity`](Self::current_capacity): refills that are
+    /// already due but not yet committed are not taken into account. If the
+    /// stored bucket is full, no refill is pending and `last_refill_tick` itself
+    /// is returned, which is never later than any tick the bucket accepts.
+    ///
+    /// # Returns
+    /// * `Ok(tick)` - Tick at which the next refill is added, or `last_refill_tick` if full
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - Unable to acquire internal lock
+    pub fn next_refill_tick(&self) -> Result<Uint, SimpleRateLimitError> {
+        let state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if state.available >= self.capacity {
+            return Ok(state.last_refill_tick);
+        }
+        Ok(state.last_refill_tick.saturating_add(self.refill_interval))
+    }
 
     /// Returns the current remaining capacity
     /// This method is a convenience wrapper around `current_capacity`
@@ -412,8 +1282,51 @@ impl TokenBucketCore {
     }
 }
 
+impl TokenBucketCore {
+    /// Converts this core into an [`UnsyncCore`] for single-threaded use.
+    ///
+    /// The current state is kept. The returned limiter makes the same decisions
+    /// as this core without taking the lock.
+    pub fn into_unsync(self) -> UnsyncCore<Self> {
+        UnsyncCore { core: self }
+    }
+
+    /// Runs `f` on the state without locking, for [`UnsyncCore`].
+    fn with_state_mut<R>(&mut self, f: impl FnOnce(&Self, &mut TokenBucketCoreState) -> R) -> R {
+        let mut state = std::mem::take(self.state.get_mut().unwrap_or_else(|e| e.into_inner()));
+        let result = f(self, &mut state);
+        *self.state.get_mut().unwrap_or_else(|e| e.into_inner()) = state;
+        result
+    }
+}
+
+/// Single-threaded implementation, sharing the locked path's logic.
+impl UnsyncRateLimit for UnsyncCore<TokenBucketCore> {
+    fn try_acquire_at(&mut self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        if tokens > self.core.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_at_locked(state, tick, tokens))
+    }
+
+    fn try_acquire_verbose_at(&mut self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+        self.core.with_state_mut(|core, state| core.try_acquire_verbose_at_locked(state, tick, tokens))
+    }
+
+    fn capacity_remaining(&mut self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.core.with_state_mut(|core, state| core.capacity_remaining_locked(state, tick))
+    }
+}
+
 /// Configuration structure for creating a `TokenBucketCore` limiter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TokenBucketCoreConfig {
     /// Maximum number of tokens the bucket can hold.
     pub capacity: Uint,
@@ -421,17 +1334,49 @@ pub struct TokenBucketCoreConfig {
     pub refill_interval: Uint,
     /// Number of tokens added per interval.
     pub refill_amount: Uint,
+    /// Maximum tokens added by one refill; see [`TokenBucketCore::new_with_accrual_cap`].
+    pub accrual_cap: Option<Uint>,
+}
+
+/// Configuration and state of a [`TokenBucketCore`], for persistence across restarts.
+///
+/// Taken by [`TokenBucketCore::snapshot`] and turned back into a bucket by
+/// [`TokenBucketCore::restore`]. With the `serde` feature it derives
+/// `Serialize` and `Deserialize`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenBucketSnapshot {
+    /// Parameters of the bucket.
+    pub config: TokenBucketCoreConfig,
+    /// Tokens in the bucket as of the last refill.
+    pub available: Uint,
+    /// Tick of the last refill.
+    pub last_refill_tick: Uint,
+    /// Fractional part of the level in thousandths of a token, left over from
+    /// permille acquires; always 0 when `available` equals the capacity.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub permille_credit: Uint,
 }
 
 impl TokenBucketCoreConfig {
-    /// Creates a new configuration instance.
+    /// Creates a new configuration instance without an accrual cap.
     pub fn new(capacity: Uint, refill_interval: Uint, refill_amount: Uint) -> Self {
         Self {
             capacity,
             refill_interval,
             refill_amount,
+            accrual_cap: None,
         }
     }
+
+    /// Sets the maximum tokens added by one refill.
+    ///
+    /// Prefer `new` plus setters over a struct literal: a literal must name
+    /// every field, so it breaks whenever a field is added.
+    pub fn with_accrual_cap(mut self, accrual_cap: Option<Uint>) -> Self {
+        self.accrual_cap = accrual_cap;
+        self
+    }
 }
 
 impl From<TokenBucketCoreConfig> for TokenBucketCore {
@@ -452,6 +1397,7 @@ impl From<TokenBucketCoreConfig> for TokenBucketCore {
     ///     capacity: 100,
     ///     refill_interval: 10,
     ///     refill_amount: 5,
+    ///     accrual_cap: None,
     /// };
     ///
     /// let limiter = TokenBucketCore::from(config);
@@ -466,10 +1412,77 @@ impl From<TokenBucketCoreConfig> for TokenBucketCore {
     ///     capacity: 100,
     ///     refill_interval: 10,
     ///     refill_amount: 5,
+    ///     accrual_cap: None,
     /// }.into();
     /// ```
     #[inline(always)]
     fn from(config: TokenBucketCoreConfig) -> Self {
-        TokenBucketCore::new(config.capacity, config.refill_interval, config.refill_amount)
+        TokenBucketCore::new_with_accrual_cap(config.capacity, config.refill_interval, config.refill_amount, config.accrual_cap)
+    }
+}
+
+/// Moves `tokens` of available capacity from one token bucket to another.
+///
+/// Both buckets are first refilled up to `tick`. The tokens are then removed
+/// from `from` and added to `to`, capped at `to`'s capacity; the part that does
+/// not fit is dropped and returned. Both locks are held for the whole transfer,
+/// so no acquire observes the tokens in both buckets or in neither. The locks
+/// are taken in address order, and with `try_lock` like every other operation,
+/// so concurrent transfers in opposite directions cannot deadlock.
+///
+/// Transferring from a bucket to itself changes nothing and returns `Ok(0)`
+/// once the bucket is known to hold `tokens`.
+///
+/// # Returns
+/// * `Ok(overflow)` - The tokens that did not fit in `to`; `tokens - overflow` were moved
+/// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If `from` holds fewer than `tokens`
+/// * `Err(SimpleRateLimitError::ExpiredTick)` - If `tick` is older than either bucket's last refill
+/// * `Err(SimpleRateLimitError::ContentionFailure)` - If either lock could not be acquired
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::{transfer_capacity, TokenBucketCore};
+///
+/// let a = TokenBucketCore::new(100, 10, 10);
+/// let b = TokenBucketCore::new(100, 10, 10);
+/// b.try_acquire_at(0, 50).unwrap();
+///
+/// assert_eq!(transfer_capacity(&a, &b, 0, 30), Ok(0));
+/// assert_eq!(a.capacity_remaining(0), Ok(70));
+/// assert_eq!(b.capacity_remaining(0), Ok(80));
+/// ```
+pub fn transfer_capacity(from: &TokenBucketCore, to: &TokenBucketCore, tick: Uint, tokens: Uint) -> Result<Uint, SimpleRateLimitError> {
+    if std::ptr::eq(from, to) {
+        let mut state = from.state.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+        from.refill_locked(&mut state, tick)?;
+        return if tokens <= state.available {
+            Ok(0)
+        } else {
+            Err(SimpleRateLimitError::InsufficientCapacity)
+        };
     }
+
+    // Lock in address order
+    let from_first = (from as *const TokenBucketCore) < (to as *const TokenBucketCore);
+    let (first, second) = if from_first { (from, to) } else { (to, from) };
+    let mut first_state = first.state.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+    let mut second_state = second.state.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+    let (from_state, to_state) = if from_first {
+        (&mut *first_state, &mut *second_state)
+    } else {
+        (&mut *second_state, &mut *first_state)
+    };
+
+    from.refill_locked(from_state, tick)?;
+    to.refill_locked(to_state, tick)?;
+
+    if tokens > from_state.available {
+        return Err(SimpleRateLimitError::InsufficientCapacity);
+    }
+
+    from_state.available -= tokens;
+    let accepted = tokens.min(to.capacity - to_state.available);
+    to.add_tokens(to_state, accepted);
+    Ok(tokens - accepted)
 }
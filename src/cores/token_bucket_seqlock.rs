@@ -0,0 +1,258 @@
+//! Token bucket whose reads never take a lock.
+//!
+//! Only available when `Uint` is `u64` (i.e. without the `tick-u128` feature),
+//! because the state is stored in [`AtomicU64`] words.
+
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::sync::Mutex;
+use crate::{SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+use crate::rate_limit::{reduced_allowance, RateLimitCore};
+
+/// Token bucket with lock-free reads, for monitoring at high frequency.
+///
+/// Acquires behave like [`TokenBucketCore`](crate::cores::TokenBucketCore):
+/// they take a lightweight writer lock with `try_lock` and return
+/// `ContentionFailure` if another acquire holds it. Reads
+/// (`capacity_remaining` and `current_capacity`) never take that lock. They
+/// read the state under a sequence counter instead, which a writer makes odd
+/// while it updates the state and even again once done; a reader that sees an
+/// odd counter, or a counter that changed during its read, simply reads again.
+///
+/// # Consistency
+///
+/// - A read always returns a value computed from one consistent snapshot, the
+///   state as left by a completed acquire; a half-applied update is never seen
+/// - Reads never block writers and never return `ContentionFailure`; they may
+///   retry while an acquire is in progress
+/// - Reads do not apply refills to the stored state: `capacity_remaining`
+///   computes the refill due at `tick` on its snapshot, so repeated reads at any
+///   tick leave the bucket exactly as it was
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketSeqlock;
+///
+/// let bucket = TokenBucketSeqlock::new(10, 5, 2);
+/// assert_eq!(bucket.try_acquire_at(0, 8), Ok(()));
+/// assert_eq!(bucket.capacity_remaining(0), Ok(2));
+/// assert_eq!(bucket.capacity_remaining(5), Ok(4));
+/// ```
+pub struct TokenBucketSeqlock {
+    /// Maximum number of tokens the bucket can hold
+    capacity: Uint,
+    /// Number of ticks between each refill event
+    refill_interval: Uint,
+    /// Number of tokens added in each refill event
+    refill_amount: Uint,
+    /// Sequence counter; odd while a write is in progress
+    seq: AtomicU64,
+    /// Current number of tokens available in the bucket
+    available: AtomicU64,
+    /// Tick when the last refill occurred
+    last_refill_tick: AtomicU64,
+    /// Serializes writers; readers never take it
+    write_lock: Mutex<()>,
+}
+
+/// Outcome of one locked acquire attempt.
+enum Acquire {
+    /// The tokens were taken
+    Taken,
+    /// Not enough tokens after applying due refills
+    Insufficient { available: Uint, last_refill_tick: Uint },
+    /// The tick is older than the last refill; holds the minimum acceptable tick
+    Expired(Uint),
+    /// Another acquire holds the writer lock
+    Contended,
+}
+
+impl TokenBucketSeqlock {
+    /// Creates a new bucket, starting full at tick 0.
+    ///
+    /// # Panics
+    /// Panics if any parameter is 0.
+    pub fn new(capacity: Uint, refill_interval: Uint, refill_amount: Uint) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(refill_interval > 0, "refill_interval must be greater than 0");
+        assert!(refill_amount > 0, "refill_amount must be greater than 0");
+        TokenBucketSeqlock {
+            capacity,
+            refill_interval,
+            refill_amount,
+            seq: AtomicU64::new(0),
+            available: AtomicU64::new(capacity),
+            last_refill_tick: AtomicU64::new(0),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Reads a consistent `(available, last_refill_tick)` snapshot without locking.
+    fn snapshot(&self) -> (Uint, Uint) {
+        loop {
+            let before = self.seq.load(Ordering::Acquire);
+            if before & 1 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+            let available = self.available.load(Ordering::Relaxed);
+            let last_refill_tick = self.last_refill_tick.load(Ordering::Relaxed);
+            fence(Ordering::Acquire);
+            if self.seq.load(Ordering::Relaxed) == before {
+                return (available, last_refill_tick);
+            }
+        }
+    }
+
+    /// Publishes a new state. Must only be called while holding `write_lock`.
+    fn publish(&self, available: Uint, last_refill_tick: Uint) {
+        let seq = self.seq.load(Ordering::Relaxed);
+        self.seq.store(seq.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+        self.available.store(available, Ordering::Relaxed);
+        self.last_refill_tick.store(last_refill_tick, Ordering::Relaxed);
+        self.seq.store(seq.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Applies the refills due at `tick` to a snapshot.
+    ///
+    /// # Returns
+    /// * `Ok((available, last_refill_tick))` - The refilled snapshot
+    /// * `Err(min_acceptable_tick)` - If `tick` is older than the last refill
+    #[inline(always)]
+    fn refilled(&self, (available, last_refill_tick): (Uint, Uint), tick: Uint) -> Result<(Uint, Uint), Uint> {
+        if tick < last_refill_tick {
+            return Err(last_refill_tick);
+        }
+        let refill_times = (tick - last_refill_tick) / self.refill_interval;
+        let available = available.saturating_add(refill_times.saturating_mul(self.refill_amount)).min(self.capacity);
+        Ok((available, last_refill_tick + refill_times * self.refill_interval))
+    }
+
+    /// Refills and takes `tokens` under the writer lock.
+    fn acquire(&self, tick: Uint, tokens: Uint) -> Acquire {
+        let _guard = match self.write_lock.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Acquire::Contended,
+        };
+
+        // Holding the writer lock, nothing else can change the state
+        let current = (self.available.load(Ordering::Relaxed), self.last_refill_tick.load(Ordering::Relaxed));
+        let (available, last_refill_tick) = match self.refilled(current, tick) {
+            Ok(refilled) => refilled,
+            Err(min_acceptable_tick) => return Acquire::Expired(min_acceptable_tick),
+        };
+
+        if tokens <= available {
+            self.publish(available - tokens, last_refill_tick);
+            Acquire::Taken
+        } else {
+            if (available, last_refill_tick) != current {
+                self.publish(available, last_refill_tick);
+            }
+            Acquire::Insufficient { available, last_refill_tick }
+        }
+    }
+
+    /// Attempts to acquire the specified number of tokens at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If tokens were successfully acquired
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If insufficient tokens are available
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last refill
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If another acquire is in progress
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        match self.acquire(tick, tokens) {
+            Acquire::Taken => Ok(()),
+            Acquire::Insufficient { .. } => Err(SimpleRateLimitError::InsufficientCapacity),
+            Acquire::Expired(_) => Err(SimpleRateLimitError::ExpiredTick),
+            Acquire::Contended => Err(SimpleRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Attempts to acquire tokens at the given tick, returning detailed diagnostics on failure.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        if tokens > self.capacity {
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: tokens,
+                capacity: self.capacity,
+            });
+        }
+
+        match self.acquire(tick, tokens) {
+            Acquire::Taken => Ok(()),
+            Acquire::Insufficient { available, last_refill_tick } => {
+                let shortfall = tokens - available;
+                let needed_refills = (shortfall + self.refill_amount - 1) / self.refill_amount;
+                let next_refill_tick = last_refill_tick + self.refill_interval;
+                Err(VerboseRateLimitError::InsufficientCapacity {
+                    acquiring: tokens,
+                    available,
+                    retry_after_ticks: (needed_refills - 1) * self.refill_interval + next_refill_tick.saturating_sub(tick),
+                    available_next_refill: available.saturating_add(self.refill_amount).min(self.capacity),
+                })
+            }
+            Acquire::Expired(min_acceptable_tick) => Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick }),
+            Acquire::Contended => Err(VerboseRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `refill_amount / refill_interval`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.refill_amount, self.refill_interval)
+    }
+
+    /// Returns the number of tokens available at the given tick, without locking.
+    ///
+    /// The refills due at `tick` are included in the result but not stored.
+    ///
+    /// # Returns
+    /// * `Ok(available)` - Tokens available at `tick`
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If `tick` is older than the last refill
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.refilled(self.snapshot(), tick)
+            .map(|(available, _)| available)
+            .map_err(|_| SimpleRateLimitError::ExpiredTick)
+    }
+
+    /// Returns the number of tokens in the bucket as of the last acquire, without locking.
+    pub fn current_capacity(&self) -> Uint {
+        self.snapshot().0
+    }
+}
+
+impl RateLimitCore for TokenBucketSeqlock {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
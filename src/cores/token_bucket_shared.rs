@@ -0,0 +1,240 @@
+//! Token bucket whose state lives in caller-provided atomics.
+//!
+//! Only available when `Uint` is `u64` (i.e. without the `tick-u128` feature),
+//! because the state is stored in [`AtomicU64`] words.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::{SimpleRateLimitResult, SimpleRateLimitError, Uint, VerboseRateLimitResult, VerboseRateLimitError};
+use crate::rate_limit::{reduced_allowance, RateLimitCore};
+
+/// Token bucket operating on externally owned atomics, for sharing one limit
+/// across processes.
+///
+/// Instead of owning its state behind a mutex, the bucket borrows two
+/// [`AtomicU64`] words: the number of available tokens and the tick of the last
+/// refill. Placing both words in shared memory (for example a POSIX `shm_open`
+/// mapping) lets separate processes construct their own `TokenBucketShared`
+/// over the same words and enforce a single limit together. The algorithm is
+/// the same as [`TokenBucketCore`](crate::cores::TokenBucketCore).
+///
+/// # Memory layout
+///
+/// - Two naturally aligned, 8-byte `AtomicU64` words; they need not be adjacent
+/// - `available` holds the token count, `last_refill_tick` the tick of the last refill
+/// - The words must be initialized exactly once, before any process acquires,
+///   with [`TokenBucketShared::initialize`] (or equivalently `capacity` and `0`)
+/// - All processes must use the same `capacity`, `refill_interval`, `refill_amount`
+///   and the same tick source; nothing in the shared words records them
+///
+/// # Ordering
+///
+/// Updates are lock-free compare-and-swap loops. A refill is first claimed by
+/// advancing `last_refill_tick`, then credited to `available`, with
+/// acquire/release ordering on both words. A concurrent caller that observes the
+/// advanced tick before the credit may be denied spuriously; it never admits
+/// more than the bucket holds. Unlike the mutex-based cores, contention never
+/// surfaces as `ContentionFailure`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::AtomicU64;
+/// use rate_guard_core::cores::TokenBucketShared;
+///
+/// let available = AtomicU64::new(0);
+/// let last_refill_tick = AtomicU64::new(0);
+///
+/// let a = TokenBucketShared::new(10, 5, 1, &available, &last_refill_tick);
+/// a.initialize();
+/// let b = TokenBucketShared::new(10, 5, 1, &available, &last_refill_tick);
+///
+/// assert_eq!(a.try_acquire_at(0, 6), Ok(()));
+/// assert!(b.try_acquire_at(0, 6).is_err());
+/// assert_eq!(b.try_acquire_at(0, 4), Ok(()));
+/// ```
+pub struct TokenBucketShared<'a> {
+    /// Maximum number of tokens the bucket can hold
+    capacity: Uint,
+    /// Number of ticks between each refill event
+    refill_interval: Uint,
+    /// Number of tokens added in each refill event
+    refill_amount: Uint,
+    /// Externally owned count of available tokens
+    available: &'a AtomicU64,
+    /// Externally owned tick of the last refill
+    last_refill_tick: &'a AtomicU64,
+}
+
+impl<'a> TokenBucketShared<'a> {
+    /// Creates a new bucket view over externally owned state.
+    ///
+    /// This does not touch the atomics; see [`initialize`](Self::initialize).
+    ///
+    /// # Panics
+    /// Panics if any parameter is 0.
+    pub fn new(
+        capacity: Uint,
+        refill_interval: Uint,
+        refill_amount: Uint,
+        available: &'a AtomicU64,
+        last_refill_tick: &'a AtomicU64,
+    ) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(refill_interval > 0, "refill_interval must be greater than 0");
+        assert!(refill_amount > 0, "refill_amount must be greater than 0");
+        TokenBucketShared {
+            capacity,
+            refill_interval,
+            refill_amount,
+            available,
+            last_refill_tick,
+        }
+    }
+
+    /// Resets the shared state to a full bucket at tick 0.
+    ///
+    /// Call this once, from one process, before the bucket is used.
+    pub fn initialize(&self) {
+        self.last_refill_tick.store(0, Ordering::Release);
+        self.available.store(self.capacity, Ordering::Release);
+    }
+
+    /// Applies any refills due at `tick` to the shared state.
+    ///
+    /// # Returns
+    /// * `Ok(last_refill_tick)` - The last refill tick after the update
+    /// * `Err(min_acceptable_tick)` - If `tick` is older than the last refill
+    fn refill(&self, tick: Uint) -> Result<Uint, Uint> {
+        loop {
+            let last = self.last_refill_tick.load(Ordering::Acquire);
+            if tick < last {
+                return Err(last);
+            }
+
+            let refill_times = (tick - last) / self.refill_interval;
+            if refill_times == 0 {
+                return Ok(last);
+            }
+
+            // Claim the refill by advancing the tick; exactly one caller wins it
+            let new_last = last + refill_times * self.refill_interval;
+            if self.last_refill_tick
+                .compare_exchange(last, new_last, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+            {
+                continue;
+            }
+
+            let total_refilled = refill_times.saturating_mul(self.refill_amount);
+            let mut current = self.available.load(Ordering::Acquire);
+            loop {
+                let updated = current.saturating_add(total_refilled).min(self.capacity);
+                match self.available.compare_exchange_weak(current, updated, Ordering::AcqRel, Ordering::Acquire) {
+                    Ok(_) => return Ok(new_last),
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+    }
+
+    /// Takes `tokens` from the shared pool if enough are available.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were taken
+    /// * `Err(available)` - The observed token count if it was insufficient
+    fn take(&self, tokens: Uint) -> Result<(), Uint> {
+        let mut current = self.available.load(Ordering::Acquire);
+        loop {
+            if tokens > current {
+                return Err(current);
+            }
+            match self.available.compare_exchange_weak(current, current - tokens, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return Ok(()),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Attempts to acquire the specified number of tokens at the given tick.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If tokens were successfully acquired
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If insufficient tokens are available
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is older than the last refill
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        self.refill(tick).map_err(|_| SimpleRateLimitError::ExpiredTick)?;
+        self.take(tokens).map_err(|_| SimpleRateLimitError::InsufficientCapacity)
+    }
+
+    /// Attempts to acquire tokens at the given tick, returning detailed diagnostics on failure.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        let last_refill_tick = self.refill(tick)
+            .map_err(|min_acceptable_tick| VerboseRateLimitError::ExpiredTick { min_acceptable_tick })?;
+
+        if tokens > self.capacity {
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: tokens,
+                capacity: self.capacity,
+            });
+        }
+
+        self.take(tokens).map_err(|available| {
+            let shortfall = tokens - available;
+            let needed_refills = (shortfall + self.refill_amount - 1) / self.refill_amount;
+            let next_refill_tick = last_refill_tick + self.refill_interval;
+            VerboseRateLimitError::InsufficientCapacity {
+                acquiring: tokens,
+                available,
+                retry_after_ticks: (needed_refills - 1) * self.refill_interval + next_refill_tick.saturating_sub(tick),
+                available_next_refill: available.saturating_add(self.refill_amount).min(self.capacity),
+            }
+        })
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction,
+    /// `refill_amount / refill_interval`.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        reduced_allowance(self.refill_amount, self.refill_interval)
+    }
+
+    /// Returns the number of tokens available at the given tick, applying due refills.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.refill(tick).map_err(|_| SimpleRateLimitError::ExpiredTick)?;
+        Ok(self.available.load(Ordering::Acquire))
+    }
+}
+
+impl<'a> RateLimitCore for TokenBucketShared<'a> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
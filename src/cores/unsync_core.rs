@@ -0,0 +1,38 @@
+//! Lock-free, single-threaded form of the mutex-backed cores.
+
+/// A core converted for single-threaded use.
+///
+/// Created by `into_unsync` on [`TokenBucketCore`](crate::cores::TokenBucketCore),
+/// [`FixedWindowCounterCore`](crate::cores::FixedWindowCounterCore),
+/// [`SlidingWindowCounterCore`](crate::cores::SlidingWindowCounterCore) and
+/// [`ApproximateSlidingWindowCore`](crate::cores::ApproximateSlidingWindowCore).
+/// It implements [`UnsyncRateLimit`](crate::rate_limit::UnsyncRateLimit), which
+/// reaches the core state through `&mut self` instead of `try_lock`, so it runs
+/// the same algorithm without any synchronization cost.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::rate_limit::UnsyncRateLimit;
+///
+/// let mut limiter = TokenBucketCore::new(10, 5, 2).into_unsync();
+/// assert!(limiter.try_acquire_at(0, 10).is_ok());
+/// assert!(limiter.try_acquire_at(0, 1).is_err());
+/// ```
+#[derive(Debug)]
+pub struct UnsyncCore<C> {
+    pub(crate) core: C,
+}
+
+impl<C> UnsyncCore<C> {
+    /// Converts back into the locked core, keeping the current state.
+    pub fn into_inner(self) -> C {
+        self.core
+    }
+
+    /// Returns a reference to the wrapped core.
+    pub fn inner(&self) -> &C {
+        &self.core
+    }
+}
@@ -0,0 +1,269 @@
+use std::sync::Mutex;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint};
+
+/// Core implementation of weighted fair sharing of one capacity across classes.
+///
+/// Several priority classes draw from one shared capacity per window. Each class
+/// has a weight, and its guaranteed share is `capacity * weight / total_weight`.
+/// While the pool has room, a class may always use its guaranteed share, and
+/// beyond that it may keep acquiring as long as its part of the recent consumption
+/// does not exceed its weight's part among the classes currently active. A class
+/// alone can use the whole pool, while under saturation each class converges to
+/// its weighted share.
+///
+/// # Algorithm Behavior
+///
+/// - Time is divided into windows of `window_ticks`, as in the fixed window counter
+/// - Recent consumption of a class decays linearly over the following window: at
+///   tick `t`, the previous window's count is weighted by how many of its ticks
+///   still fall in `[t - window_ticks + 1, t]`
+/// - An acquire is admitted if the pool has room and the class stays within either
+///   its guaranteed share or its weighted part of the recent consumption
+///
+/// Because every acquire names a class, this type exposes its own methods instead
+/// of implementing [`RateLimitCore`](crate::rate_limit::RateLimitCore).
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::WeightedClassesCore;
+///
+/// // 100 tokens per 10 ticks shared 50/30/20
+/// let core = WeightedClassesCore::new(100, 10, vec![50, 30, 20]);
+///
+/// // A class alone may use the whole pool
+/// assert_eq!(core.try_acquire_at(2, 0, 60), Ok(()));
+///
+/// // Once class 0 is active, class 2 is held to its weighted part
+/// assert_eq!(core.try_acquire_at(0, 0, 40), Ok(()));
+/// assert!(core.try_acquire_at(2, 15, 1).is_err());
+/// assert!(core.try_acquire_at(0, 15, 1).is_ok());
+/// ```
+pub struct WeightedClassesCore {
+    /// Maximum number of tokens acquirable per window across all classes
+    capacity: Uint,
+    /// Duration of each window in ticks
+    window_ticks: Uint,
+    /// Weight of each class
+    weights: Vec<Uint>,
+    /// Sum of all weights
+    total_weight: Uint,
+    /// Internal state protected by mutex for thread safety
+    state: Mutex<WeightedClassesCoreState>,
+}
+
+/// Internal state of the weighted classes core
+struct WeightedClassesCoreState {
+    /// Start tick of the current window
+    start_tick: Uint,
+    /// Tokens acquired by each class in the current window
+    counts: Vec<Uint>,
+    /// Tokens acquired by each class in the previous window
+    prev_counts: Vec<Uint>,
+}
+
+impl WeightedClassesCore {
+    /// Creates a new weighted classes core.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - Maximum number of tokens per window across all classes
+    /// * `window_ticks` - Duration of each window in ticks
+    /// * `weights` - Weight of each class; class `i` is addressed by index `i`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `window_ticks` is zero, if `weights` is empty, or
+    /// if any weight is zero.
+    pub fn new(capacity: Uint, window_ticks: Uint, weights: Vec<Uint>) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(window_ticks > 0, "window_ticks must be greater than 0");
+        assert!(!weights.is_empty(), "weights must not be empty");
+        assert!(weights.iter().all(|&w| w > 0), "weights must be greater than 0");
+
+        let total_weight = weights.iter().fold(0 as Uint, |sum, &w| sum.saturating_add(w));
+        let class_count = weights.len();
+        WeightedClassesCore {
+            capacity,
+            window_ticks,
+            weights,
+            total_weight,
+            state: Mutex::new(WeightedClassesCoreState {
+                start_tick: 0,
+                counts: vec![0; class_count],
+                prev_counts: vec![0; class_count],
+            }),
+        }
+    }
+
+    /// Returns the number of classes.
+    pub fn class_count(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Moves the state to the window containing `tick`.
+    #[inline(always)]
+    fn advance_window(&self, state: &mut WeightedClassesCoreState, tick: Uint) {
+        let current_window = tick / self.window_ticks;
+        let state_window = state.start_tick / self.window_ticks;
+
+        if current_window > state_window {
+            if current_window == state_window + 1 {
+                std::mem::swap(&mut state.prev_counts, &mut state.counts);
+            } else {
+                state.prev_counts.iter_mut().for_each(|c| *c = 0);
+            }
+            state.counts.iter_mut().for_each(|c| *c = 0);
+            state.start_tick = current_window * self.window_ticks;
+        }
+    }
+
+    /// Returns the recent consumption of `class` in units of `tokens * window_ticks`.
+    #[inline(always)]
+    fn recent_units(&self, state: &WeightedClassesCoreState, class: usize, tick: Uint) -> Uint {
+        let elapsed = tick.saturating_sub(state.start_tick);
+        let overlap = (self.window_ticks - 1).saturating_sub(elapsed);
+        state.prev_counts[class].saturating_mul(overlap)
+            .saturating_add(state.counts[class].saturating_mul(self.window_ticks))
+    }
+
+    /// Returns the guaranteed share of `class` in units of `tokens * window_ticks`.
+    #[inline(always)]
+    fn share_units(&self, class: usize) -> Uint {
+        self.capacity.saturating_mul(self.window_ticks).saturating_mul(self.weights[class]) / self.total_weight
+    }
+
+    /// Returns the number of tokens `class` may acquire at `tick` from an up-to-date state.
+    ///
+    /// With `R` the class's recent consumption, `T` the total, `G` its guaranteed
+    /// share and `W` the weight of the active classes (those with recent consumption,
+    /// plus `class`), the largest `k` such that `T + k <= capacity` and either
+    /// `R + k <= G` or `(R + k) * W <= weight * (T + k)`.
+    #[inline(always)]
+    fn available(&self, state: &WeightedClassesCoreState, class: usize, tick: Uint) -> Uint {
+        let capacity_units = self.capacity.saturating_mul(self.window_ticks);
+        let weight = self.weights[class];
+        let mut total_units: Uint = 0;
+        let mut active_weight = weight;
+        for other in 0..self.weights.len() {
+            let recent = self.recent_units(state, other, tick);
+            total_units = total_units.saturating_add(recent);
+            if other != class && recent > 0 {
+                active_weight = active_weight.saturating_add(self.weights[other]);
+            }
+        }
+        let own_units = self.recent_units(state, class, tick);
+
+        let pool = capacity_units.saturating_sub(total_units) / self.window_ticks;
+        let within_share = self.share_units(class).saturating_sub(own_units) / self.window_ticks;
+        let within_ratio = if active_weight == weight {
+            pool
+        } else {
+            // (R + k) * W <= weight * (T + k)  <=>  k * (W - weight) <= weight * T - R * W
+            weight.saturating_mul(total_units)
+                .saturating_sub(own_units.saturating_mul(active_weight))
+                / (active_weight - weight).saturating_mul(self.window_ticks)
+        };
+        pool.min(within_share.max(within_ratio))
+    }
+
+    /// Attempts to acquire tokens for `class` at the given tick.
+    ///
+    /// # Parameters
+    /// * `class` - Index of the class, as in the `weights` passed to `new`
+    /// * `tick` - Current time tick for the operation
+    /// * `tokens` - Number of tokens to acquire
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tokens were successfully acquired
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If the pool or the class's share is exhausted
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the shared capacity
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is before the current window
+    ///
+    /// # Panics
+    /// Panics if `class` is not less than [`class_count`](Self::class_count).
+    pub fn try_acquire_at(&self, class: usize, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        assert!(class < self.weights.len(), "class out of range");
+
+        if tokens == 0 {
+            return Ok(());
+        }
+
+        if tokens > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.start_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        self.advance_window(&mut state, tick);
+
+        if tokens <= self.available(&state, class, tick) {
+            state.counts[class] += tokens;
+            Ok(())
+        } else {
+            Err(SimpleRateLimitError::InsufficientCapacity)
+        }
+    }
+
+    /// Returns the number of tokens `class` could acquire at `tick`.
+    ///
+    /// # Returns
+    /// * `Ok(tokens)` - The largest acquire that would succeed for `class`
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is before the current window
+    ///
+    /// # Panics
+    /// Panics if `class` is not less than [`class_count`](Self::class_count).
+    pub fn capacity_remaining(&self, class: usize, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        assert!(class < self.weights.len(), "class out of range");
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.start_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        self.advance_window(&mut state, tick);
+        Ok(self.available(&state, class, tick))
+    }
+
+    /// Returns an equal split of the capacity among the classes active at `tick`.
+    ///
+    /// A class is active if it has recent consumption, i.e. it acquired tokens in
+    /// the current window or in the part of the previous window still overlapping
+    /// `[tick - window_ticks + 1, tick]`. The result is `capacity / active`, or
+    /// the whole capacity while no class is active. Weights are ignored; this is
+    /// meant for telling clients their rough allotment under contention.
+    ///
+    /// # Returns
+    /// * `Ok(tokens)` - The capacity divided by the number of active classes (at least 1)
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If the tick is before the current window
+    pub fn fair_share(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if tick < state.start_tick {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+
+        self.advance_window(&mut state, tick);
+        let active = (0..self.weights.len())
+            .filter(|&class| self.recent_units(&state, class, tick) > 0)
+            .count();
+        Ok(self.capacity / (active.max(1) as Uint))
+    }
+}
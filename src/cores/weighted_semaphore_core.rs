@@ -0,0 +1,127 @@
+use std::sync::{Mutex, PoisonError};
+use crate::{SimpleRateLimitError, Uint};
+
+/// Core implementation of a weighted semaphore limiting concurrent in-flight weight.
+///
+/// Where [`ConcurrencyLimiterCore`](crate::cores::ConcurrencyLimiterCore) counts
+/// holds, this core sums their weights, so heavy operations can take a larger
+/// share of the limit than light ones. Each successful acquire returns a
+/// [`Permit`] that keeps its weight in flight until it is dropped. There is no
+/// tick and no auto-expiry: weight is released only by dropping the permit.
+///
+/// # Algorithm Behavior
+///
+/// - An acquire of `weight` is admitted if `in_flight + weight <= capacity`
+/// - Dropping a permit subtracts its weight from `in_flight`
+/// - Acquires use `try_lock` and may return `ContentionFailure`; dropping a
+///   permit waits for the lock, so a release is never lost
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::WeightedSemaphoreCore;
+///
+/// let semaphore = WeightedSemaphoreCore::new(10);
+/// let heavy = semaphore.acquire(8).unwrap();
+/// assert!(semaphore.acquire(3).is_err());
+///
+/// drop(heavy);
+/// assert!(semaphore.acquire(3).is_ok());
+/// ```
+#[derive(Debug)]
+pub struct WeightedSemaphoreCore {
+    /// Maximum total weight in flight at the same time
+    capacity: Uint,
+    /// Total weight of the live permits, protected by mutex for thread safety
+    in_flight: Mutex<Uint>,
+}
+
+/// Weight held in a [`WeightedSemaphoreCore`], released when dropped.
+#[must_use = "dropping a permit releases its weight immediately"]
+#[derive(Debug)]
+pub struct Permit<'a> {
+    /// Semaphore the weight is held in
+    semaphore: &'a WeightedSemaphoreCore,
+    /// Weight held by this permit
+    weight: Uint,
+}
+
+impl<'a> Permit<'a> {
+    /// Returns the weight held by this permit.
+    pub fn weight(&self) -> Uint {
+        self.weight
+    }
+}
+
+impl<'a> Drop for Permit<'a> {
+    fn drop(&mut self) {
+        let mut in_flight = self.semaphore.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+        *in_flight = in_flight.saturating_sub(self.weight);
+    }
+}
+
+impl WeightedSemaphoreCore {
+    /// Creates a new weighted semaphore with nothing in flight.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - Maximum total weight in flight at the same time
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: Uint) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+
+        WeightedSemaphoreCore {
+            capacity,
+            in_flight: Mutex::new(0),
+        }
+    }
+
+    /// Attempts to put `weight` in flight.
+    ///
+    /// # Returns
+    /// * `Ok(Permit)` - If `in_flight + weight <= capacity`; the weight is held until the permit is dropped
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `weight` exceeds the capacity
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If too much weight is in flight
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn acquire(&self, weight: Uint) -> Result<Permit<'_>, SimpleRateLimitError> {
+        if weight > self.capacity {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        let mut in_flight = match self.in_flight.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        if weight > self.capacity - *in_flight {
+            return Err(SimpleRateLimitError::InsufficientCapacity);
+        }
+
+        *in_flight += weight;
+        Ok(Permit { semaphore: self, weight })
+    }
+
+    /// Returns the total weight currently in flight.
+    ///
+    /// # Returns
+    /// * `Ok(in_flight)` - The weight held by live permits
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn in_flight(&self) -> Result<Uint, SimpleRateLimitError> {
+        match self.in_flight.try_lock() {
+            Ok(guard) => Ok(*guard),
+            Err(_) => Err(SimpleRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Returns the weight that can still be acquired.
+    ///
+    /// # Returns
+    /// * `Ok(available)` - `capacity - in_flight`
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    pub fn capacity_remaining(&self) -> Result<Uint, SimpleRateLimitError> {
+        self.in_flight().map(|in_flight| self.capacity - in_flight)
+    }
+}
@@ -20,10 +20,16 @@ pub type SimpleRateLimitResult = Result<(), SimpleRateLimitError>;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VerboseRateLimitError {
     /// Not enough tokens available.
+    ///
+    /// `available_next_refill` is the number of tokens available once the
+    /// limiter next regains capacity, if nothing else is acquired meanwhile.
+    /// Compared with `acquiring`, it tells a caller whether waiting for the
+    /// next refill is enough or the request should shrink.
     InsufficientCapacity {
         acquiring: Uint,
         available: Uint,
         retry_after_ticks: Uint,
+        available_next_refill: Uint,
     },
     /// Request permanently exceeds the configured capacity.
     BeyondCapacity {
@@ -41,6 +47,189 @@ pub enum VerboseRateLimitError {
 /// Result type for verbose rate limiting.
 pub type VerboseRateLimitResult = Result<(), VerboseRateLimitError>;
 
+/// Error returned by fallible constructors for invalid limiter parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidConfig {
+    /// `capacity` is zero.
+    ZeroCapacity,
+    /// `refill_interval` is zero.
+    ZeroRefillInterval,
+    /// `refill_amount` is zero.
+    ZeroRefillAmount,
+    /// `window_ticks` is zero.
+    ZeroWindowTicks,
+    /// `bucket_ticks` is zero.
+    ZeroBucketTicks,
+    /// `bucket_count` is zero.
+    ZeroBucketCount,
+    /// `accrual_cap` is `Some(0)`.
+    ZeroAccrualCap,
+    /// `bucket_count` buckets cannot be allocated.
+    BucketCountTooLarge {
+        bucket_count: Uint,
+    },
+    /// `refill_amount` is larger than `capacity` (strict validation only).
+    RefillExceedsCapacity {
+        refill_amount: Uint,
+        capacity: Uint,
+    },
+}
+
+/// Error returned when restoring a core from bytes produced by its `to_bytes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateDecodeError {
+    /// The buffer was written with a format version this build does not read.
+    UnsupportedVersion(u8),
+    /// The buffer was written by a build with a different tick width, in bytes.
+    TickWidthMismatch { expected: u8, found: u8 },
+    /// The buffer holds the state of a different kind of core.
+    CoreMismatch { expected: u8, found: u8 },
+    /// The buffer is shorter or longer than its layout requires.
+    InvalidLength,
+    /// The decoded state does not fit the given configuration.
+    InvalidState,
+}
+
+/// Plain integer outcome of an acquire attempt, suitable for FFI boundaries.
+///
+/// This is a `Copy`, `#[repr(u8)]` mirror of [`SimpleRateLimitResult`], so the
+/// outcome can be handed to C callers without exposing a Rust `Result`.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AcquireCode {
+    /// The request was allowed.
+    Allowed = 0,
+    /// Not enough tokens available right now.
+    InsufficientCapacity = 1,
+    /// Request permanently exceeds the configured capacity.
+    BeyondCapacity = 2,
+    /// Failed due to lock contention.
+    Contention = 3,
+    /// Provided tick is too old.
+    ExpiredTick = 4,
+}
+
+/// Readiness of an acquire, for building futures by hand.
+///
+/// Returned by [`RateLimitCore::poll_acquire_at`](crate::rate_limit::RateLimitCore::poll_acquire_at),
+/// which folds every outcome into whether the caller has its tokens, should
+/// wait and poll again, or should give up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AcquirePoll {
+    /// The tokens were acquired.
+    Ready,
+    /// The tokens are not available yet; poll again after this many ticks.
+    Pending(Uint),
+    /// The request can never be admitted, because it exceeds the capacity.
+    Rejected,
+}
+
+/// State transitions observed by a detailed acquire.
+///
+/// Returned by [`RateLimitCore::try_acquire_detailed_at`](crate::rate_limit::RateLimitCore::try_acquire_detailed_at),
+/// which makes the lazy refill and window bookkeeping of an acquire visible for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TransitionInfo {
+    /// Whether the tokens were acquired.
+    pub allowed: bool,
+    /// Tokens added to a token bucket by this call's refill, after the capacity cap.
+    pub refilled: Uint,
+    /// Tokens drained by a leak event. None of the current cores leak, so this is 0.
+    pub leaked: Uint,
+    /// Whether this call moved the core to a new window or bucket, resetting its count.
+    pub window_rotated: bool,
+}
+
+/// Verbose error tagged with the label of the limiter that produced it.
+///
+/// Returned by combinators built from [`Labeled`](crate::combinators::Labeled)
+/// children, so a caller can tell the client which of several limits it hit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabeledRateLimitError {
+    /// Label of the denying limiter.
+    pub label: &'static str,
+    /// Error reported by the denying limiter.
+    pub error: VerboseRateLimitError,
+}
+
+impl LabeledRateLimitError {
+    /// Returns the suggested retry delay if the denial was `InsufficientCapacity`.
+    pub fn retry_after_ticks(&self) -> Option<Uint> {
+        match self.error {
+            VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. } => Some(retry_after_ticks),
+            _ => None,
+        }
+    }
+}
+
+/// Result type for labeled rate limiting.
+pub type LabeledRateLimitResult = Result<(), LabeledRateLimitError>;
+
+/// Dimension of a [`TwoDimensional`](crate::combinators::TwoDimensional) limiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    /// The request-count limiter.
+    Requests,
+    /// The byte-volume limiter.
+    Bytes,
+}
+
+/// Verbose error tagged with the dimension that denied a two-dimensional acquire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DimensionalRateLimitError {
+    /// Dimension whose limiter produced the error.
+    pub dimension: Dimension,
+    /// Error reported by that limiter.
+    pub error: VerboseRateLimitError,
+}
+
+impl DimensionalRateLimitError {
+    /// Returns the suggested retry delay if the denial was `InsufficientCapacity`.
+    pub fn retry_after_ticks(&self) -> Option<Uint> {
+        match self.error {
+            VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. } => Some(retry_after_ticks),
+            _ => None,
+        }
+    }
+}
+
+/// Result type for two-dimensional rate limiting.
+pub type DimensionalRateLimitResult = Result<(), DimensionalRateLimitError>;
+
+/// Error returned by [`ProvidedKeyed`](crate::combinators::ProvidedKeyed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyedRateLimitError {
+    /// The quota provider has no quota for the key.
+    UnknownTenant,
+    /// The quota provider returned a quota no bucket can be built from.
+    InvalidQuota(InvalidConfig),
+    /// The key's limiter denied the request.
+    Limit(SimpleRateLimitError),
+}
+
+impl From<SimpleRateLimitError> for KeyedRateLimitError {
+    fn from(error: SimpleRateLimitError) -> Self {
+        KeyedRateLimitError::Limit(error)
+    }
+}
+
+/// Result type for keyed rate limiting.
+pub type KeyedRateLimitResult = Result<(), KeyedRateLimitError>;
+
+impl From<SimpleRateLimitResult> for AcquireCode {
+    /// Maps a fast-path result onto its integer code.
+    #[inline(always)]
+    fn from(result: SimpleRateLimitResult) -> Self {
+        match result {
+            Ok(()) => AcquireCode::Allowed,
+            Err(SimpleRateLimitError::InsufficientCapacity) => AcquireCode::InsufficientCapacity,
+            Err(SimpleRateLimitError::BeyondCapacity) => AcquireCode::BeyondCapacity,
+            Err(SimpleRateLimitError::ContentionFailure) => AcquireCode::Contention,
+            Err(SimpleRateLimitError::ExpiredTick) => AcquireCode::ExpiredTick,
+        }
+    }
+}
+
 // Display trait for SimpleRateLimitError
 impl fmt::Display for SimpleRateLimitError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -59,7 +248,7 @@ impl fmt::Display for VerboseRateLimitError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use VerboseRateLimitError::*;
         match self {
-            InsufficientCapacity { acquiring, available, retry_after_ticks } => {
+            InsufficientCapacity { acquiring, available, retry_after_ticks, .. } => {
                 write!(
                     f,
                     "Insufficient capacity: tried to acquire {}, available {}, retry after {} tick(s).",
@@ -87,5 +276,89 @@ impl fmt::Display for VerboseRateLimitError {
     }
 }
 
+// Display trait for LabeledRateLimitError
+impl fmt::Display for LabeledRateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.label, self.error)
+    }
+}
+
+// Display trait for Dimension
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dimension::Requests => write!(f, "requests"),
+            Dimension::Bytes => write!(f, "bytes"),
+        }
+    }
+}
+
+// Display trait for DimensionalRateLimitError
+impl fmt::Display for DimensionalRateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.dimension, self.error)
+    }
+}
+
+// Display trait for KeyedRateLimitError
+impl fmt::Display for KeyedRateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyedRateLimitError::UnknownTenant => write!(f, "Unknown tenant: no quota is configured for the key."),
+            KeyedRateLimitError::InvalidQuota(error) => write!(f, "Invalid quota: {}", error),
+            KeyedRateLimitError::Limit(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+// Display trait for InvalidConfig
+impl fmt::Display for InvalidConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use InvalidConfig::*;
+        match self {
+            ZeroCapacity => write!(f, "capacity must be greater than 0"),
+            ZeroRefillInterval => write!(f, "refill_interval must be greater than 0"),
+            ZeroRefillAmount => write!(f, "refill_amount must be greater than 0"),
+            ZeroWindowTicks => write!(f, "window_ticks must be greater than 0"),
+            ZeroBucketTicks => write!(f, "bucket_ticks must be greater than 0"),
+            ZeroBucketCount => write!(f, "bucket_count must be greater than 0"),
+            ZeroAccrualCap => write!(f, "accrual_cap must be greater than 0"),
+            BucketCountTooLarge { bucket_count } => {
+                write!(f, "bucket_count ({}) is too large to allocate", bucket_count)
+            }
+            RefillExceedsCapacity { refill_amount, capacity } => {
+                write!(
+                    f,
+                    "refill_amount ({}) must not exceed capacity ({})",
+                    refill_amount, capacity
+                )
+            }
+        }
+    }
+}
+
+// Display trait for StateDecodeError
+impl fmt::Display for StateDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use StateDecodeError::*;
+        match self {
+            UnsupportedVersion(version) => write!(f, "unsupported state format version {}", version),
+            TickWidthMismatch { expected, found } => {
+                write!(f, "state uses {}-byte ticks, expected {}-byte ticks", found, expected)
+            }
+            CoreMismatch { expected, found } => {
+                write!(f, "state is for core kind {}, expected core kind {}", found, expected)
+            }
+            InvalidLength => write!(f, "state buffer has the wrong length"),
+            InvalidState => write!(f, "state does not match the configuration"),
+        }
+    }
+}
+
 impl std::error::Error for SimpleRateLimitError {}
 impl std::error::Error for VerboseRateLimitError {}
+impl std::error::Error for LabeledRateLimitError {}
+impl std::error::Error for DimensionalRateLimitError {}
+impl std::error::Error for KeyedRateLimitError {}
+impl std::error::Error for InvalidConfig {}
+impl std::error::Error for StateDecodeError {}
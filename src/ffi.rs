@@ -0,0 +1,55 @@
+//! C-compatible bindings for the token bucket core.
+//!
+//! This module is only compiled with the `ffi` feature. It exposes an opaque
+//! [`TokenBucketCore`] handle through `extern "C"` functions and reports
+//! acquire outcomes as plain [`AcquireCode`](crate::AcquireCode) integers.
+//!
+//! # Ownership
+//!
+//! A handle returned by [`rate_guard_token_bucket_new`] must be released with
+//! exactly one call to [`rate_guard_token_bucket_free`].
+
+use crate::cores::TokenBucketCore;
+use crate::Uint;
+
+/// Creates a new token bucket and returns an owning pointer to it.
+///
+/// Returns a null pointer if any parameter is zero.
+#[no_mangle]
+pub extern "C" fn rate_guard_token_bucket_new(
+    capacity: Uint,
+    refill_interval: Uint,
+    refill_amount: Uint,
+) -> *mut TokenBucketCore {
+    if capacity == 0 || refill_interval == 0 || refill_amount == 0 {
+        return std::ptr::null_mut();
+    }
+    Box::into_raw(Box::new(TokenBucketCore::new(capacity, refill_interval, refill_amount)))
+}
+
+/// Releases a token bucket created by [`rate_guard_token_bucket_new`].
+///
+/// Passing a null pointer is a no-op.
+///
+/// # Safety
+/// `bucket` must be null or a pointer returned by [`rate_guard_token_bucket_new`]
+/// that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rate_guard_token_bucket_free(bucket: *mut TokenBucketCore) {
+    if !bucket.is_null() {
+        drop(Box::from_raw(bucket));
+    }
+}
+
+/// Attempts to acquire `tokens` at `tick`, returning an [`AcquireCode`](crate::AcquireCode) as `u8`.
+///
+/// # Safety
+/// `bucket` must be a live pointer returned by [`rate_guard_token_bucket_new`].
+#[no_mangle]
+pub unsafe extern "C" fn rate_guard_token_bucket_try_acquire_at(
+    bucket: *const TokenBucketCore,
+    tick: Uint,
+    tokens: Uint,
+) -> u8 {
+    (*bucket).try_acquire_code_at(tick, tokens) as u8
+}
@@ -4,7 +4,7 @@
 //! ## Features
 //! - **4 Rate Limiting Algorithms**: Token Bucket, Fixed Window Counter, Sliding Window Counter, and Approximate Sliding Window  
 //! - **Thread-Safe**: All algorithms use non-blocking locks  
-//! - **Zero Dependencies**: Lightweight with no external dependencies  
+//! - **Zero Dependencies**: Lightweight with no external dependencies by default; the optional `serde` feature derives `Serialize`/`Deserialize` on the config structs  
 //! - **Flexible Time**: Works with any time unit via abstract "ticks"  
 //! - **Configurable Tick Precision**: Compile-time feature flags allow choosing `u64` (default) or `u128` for tick units  
 //! - **Rust 1.60+**: Compatible with older Rust versions  
@@ -59,6 +59,7 @@
 //!     capacity: 100,
 //!     refill_interval: 5,
 //!     refill_amount: 10,
+//!     accrual_cap: None,
 //! };
 //!
 //! let limiter: TokenBucketCore = config.into();
@@ -75,6 +76,8 @@
 //! let config = FixedWindowCounterCoreConfig {
 //!     capacity: 100,
 //!     window_size: 60,
+//!     smoothing: false,
+//!     grace: 0,
 //! };
 //!
 //! let limiter: FixedWindowCounterCore = config.into();
@@ -153,7 +156,7 @@
 //! - `ContentionFailure`: Lock was unavailable
 //! - `ExpiredTick { min_acceptable_tick }`: Time went backwards
 //! - `BeyondCapacity { acquiring, capacity }`: Requested tokens exceed max
-//! - `InsufficientCapacity { acquiring, available, retry_after_ticks }`: Not enough tokens now, but suggests how long to wait before retrying
+//! - `InsufficientCapacity { acquiring, available, retry_after_ticks, available_next_refill }`: Not enough tokens now, but suggests how long to wait before retrying and how many tokens the next refill leaves available
 //!
 //! ```Rust
 //! use rate_guard_core::{VerboseRateLimitError, VerboseRateLimitResult};
@@ -186,6 +189,14 @@
 //! let tick = my_monotonic_timer.elapsed_ticks();
 //! ```
 //!
+//! Or bind a limiter to a clock once and let it compute the tick on every call
+//! (see the [`clock`] module):
+//! ```Rust
+//! use rate_guard_core::clock::{BoundLimiter, MonotonicClock, TickScale};
+//! let limiter = BoundLimiter::new(TokenBucketCore::new(100, 10, 5), MonotonicClock::new(), TickScale::millis());
+//! limiter.acquire_now(1)?;
+//! ```
+//!
 //! ---
 //!
 //! ## Thread Safety
@@ -218,10 +229,29 @@
 pub mod types;
 pub mod cores;
 pub mod rate_limit;
+pub mod combinators;
+pub mod wrappers;
+pub mod clock;
+pub mod recommend;
 pub mod error; 
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "test-util")]
+pub mod conformance;
+#[cfg(feature = "test-util")]
+pub mod planner;
+#[cfg(feature = "test-util")]
+pub mod replay;
 
 pub use types::Uint;
 pub use error::{
     SimpleRateLimitError, VerboseRateLimitError,
     SimpleRateLimitResult, VerboseRateLimitResult,
-};
\ No newline at end of file
+    AcquireCode, AcquirePoll, TransitionInfo,
+    LabeledRateLimitError, LabeledRateLimitResult,
+    Dimension, DimensionalRateLimitError, DimensionalRateLimitResult,
+    KeyedRateLimitError, KeyedRateLimitResult,
+    InvalidConfig, StateDecodeError,
+};
@@ -0,0 +1,52 @@
+//! Prometheus / OpenMetrics text rendering for the cores.
+//!
+//! Enabled by the `metrics` feature. Each core provides
+//! `render_prometheus(name, tick)`, which renders its configured capacity and
+//! its remaining capacity at `tick` as two gauges labeled with `name`:
+//!
+//! ```text
+//! # TYPE ratelimit_capacity gauge
+//! ratelimit_capacity{name="api"} 100
+//! # TYPE ratelimit_remaining gauge
+//! ratelimit_remaining{name="api"} 42
+//! ```
+//!
+//! The output ends with a newline, so the renderings of several limiters can be
+//! concatenated into one `/metrics` response. The `# TYPE` lines are repeated
+//! for every limiter; deduplicate them when concatenating if the scraper
+//! requires it.
+
+use crate::Uint;
+
+/// Appends `value` to `out` as a label value, escaping `\`, `"` and newlines.
+fn push_label_value(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Appends one gauge, with its `# TYPE` line, to `out`.
+fn push_gauge(out: &mut String, metric: &str, name: &str, value: Uint) {
+    out.push_str("# TYPE ");
+    out.push_str(metric);
+    out.push_str(" gauge\n");
+    out.push_str(metric);
+    out.push_str("{name=\"");
+    push_label_value(out, name);
+    out.push_str("\"} ");
+    out.push_str(&value.to_string());
+    out.push('\n');
+}
+
+/// Renders the capacity and remaining-capacity gauges of one limiter.
+pub(crate) fn render_gauges(name: &str, capacity: Uint, remaining: Uint) -> String {
+    let mut out = String::new();
+    push_gauge(&mut out, "ratelimit_capacity", name, capacity);
+    push_gauge(&mut out, "ratelimit_remaining", name, remaining);
+    out
+}
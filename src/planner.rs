@@ -0,0 +1,107 @@
+//! Suggests limiter configurations from recorded traffic.
+//!
+//! This module is only compiled with the `test-util` feature. Where
+//! [`replay`](crate::replay) answers "how would this configuration have done",
+//! the planner answers the reverse question: given a recorded arrival series
+//! and a target fraction of requests to admit, it searches for the smallest
+//! configuration that meets the target by replaying the series through
+//! candidate limiters.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rate_guard_core::cores::TokenBucketCore;
+//! use rate_guard_core::planner;
+//! use rate_guard_core::replay;
+//! use rate_guard_core::Uint;
+//!
+//! // A burst of 4 single-token requests every 10 ticks
+//! let arrivals: Vec<(Uint, Uint)> = (0..20).flat_map(|i| vec![(i * 10, 1); 4]).collect();
+//! let config = planner::suggest_token_bucket(10_000, &arrivals);
+//!
+//! let report = replay::from_timestamps(&TokenBucketCore::from(config), arrivals);
+//! assert_eq!(report.denied, 0);
+//! ```
+
+use crate::cores::{TokenBucketCore, TokenBucketCoreConfig};
+use crate::replay;
+use crate::Uint;
+
+/// Basis points in a whole, the upper bound for a target fraction.
+const BPS_PER_WHOLE: Uint = 10_000;
+
+/// Returns the smallest token bucket configuration that admits at least
+/// `target_allow_fraction_bps` / 10000 of `arrivals` when they are replayed
+/// through a fresh bucket.
+///
+/// Each arrival is a `(tick, tokens)` pair, replayed as by
+/// [`replay::from_timestamps`]. "Smallest" means the smallest capacity, then
+/// the slowest refill rate at that capacity: burst size is what a limiter
+/// cannot take back once admitted, so it is minimized first. Both are found by
+/// binary search, the capacity against the fastest rate considered and the
+/// rate against the chosen capacity.
+///
+/// Rates run from 1 token per span of the series up to the total tokens
+/// requested per tick. Rates below 1 token per tick are expressed as
+/// `refill_amount = 1` with a longer `refill_interval`, so only whole
+/// fractions `1 / n` are considered; faster rates use `refill_interval = 1`.
+/// The returned configuration was replayed and met the target; an empty
+/// series yields the minimal `(1, 1, 1)` bucket.
+///
+/// # Panics
+/// Panics if `target_allow_fraction_bps` is greater than 10000.
+pub fn suggest_token_bucket(target_allow_fraction_bps: Uint, arrivals: &[(Uint, Uint)]) -> TokenBucketCoreConfig {
+    assert!(
+        target_allow_fraction_bps <= BPS_PER_WHOLE,
+        "target_allow_fraction_bps must be at most 10000"
+    );
+
+    let first_tick = arrivals.iter().map(|&(tick, _)| tick).min().unwrap_or(0);
+    let last_tick = arrivals.iter().map(|&(tick, _)| tick).max().unwrap_or(0);
+    let span = (last_tick - first_tick).saturating_add(1);
+    let total_tokens = arrivals
+        .iter()
+        .fold(0, |sum: Uint, &(_, tokens)| sum.saturating_add(tokens))
+        .max(1);
+    // Steps below `span` slow the interval down; the last step refills `total_tokens` per tick
+    let max_step = span.saturating_add(total_tokens).saturating_sub(2);
+
+    // Widened to `u128` so large series cannot overflow; a no-op cast with `tick-u128`
+    #[allow(clippy::unnecessary_cast)]
+    let meets_target = |config: TokenBucketCoreConfig| {
+        let report = replay::from_timestamps(&TokenBucketCore::from(config), arrivals.iter().copied());
+        (report.allowed as u128) * (BPS_PER_WHOLE as u128)
+            >= (target_allow_fraction_bps as u128) * (report.total as u128)
+    };
+
+    // A bucket holding every requested token admits the whole series, so the upper bounds always pass
+    let capacity = smallest_passing(1, total_tokens, |capacity| {
+        meets_target(config_at_step(capacity, span, max_step))
+    });
+    let step = smallest_passing(0, max_step, |step| meets_target(config_at_step(capacity, span, step)));
+    config_at_step(capacity, span, step)
+}
+
+/// Returns the smallest value in `low..=high` for which `passes` holds,
+/// assuming `passes(high)` holds and `passes` is monotone.
+fn smallest_passing(mut low: Uint, mut high: Uint, mut passes: impl FnMut(Uint) -> bool) -> Uint {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if passes(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    high
+}
+
+/// Returns the configuration at position `step` on the refill-rate ladder,
+/// which runs from 1 token per `span` ticks up through whole tokens per tick.
+fn config_at_step(capacity: Uint, span: Uint, step: Uint) -> TokenBucketCoreConfig {
+    if step < span {
+        TokenBucketCoreConfig::new(capacity, span - step, 1)
+    } else {
+        TokenBucketCoreConfig::new(capacity, 1, step - span + 2)
+    }
+}
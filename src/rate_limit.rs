@@ -3,8 +3,10 @@
 //! This module defines the unified trait used by all rate limiter implementations.
 //! It allows consistent use and interchangeability across token bucket, leaky bucket, window counter, and other algorithms.
 
+use std::cmp::Ordering;
+use std::thread;
 pub use crate::types::Uint;
-use crate::{SimpleRateLimitError, SimpleRateLimitResult, VerboseRateLimitResult};
+use crate::{AcquirePoll, SimpleRateLimitError, SimpleRateLimitResult, TransitionInfo, VerboseRateLimitError, VerboseRateLimitResult};
 
 /// The core trait implemented by all rate limiter algorithms.
 ///
@@ -47,7 +49,266 @@ pub trait RateLimitCore: Send + Sync {
     /// # Returns
     /// The number of tokens currently available for acquisition.
     fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError>;
+
+    /// Returns the number of tokens currently available at the given tick, or 0
+    /// if `capacity_remaining` fails (for example on contention or an expired tick).
+    ///
+    /// Cores do not need to override this.
     fn capacity_remaining_or_0(&self, tick: Uint) -> Uint {
         self.capacity_remaining(tick).unwrap_or(0)
     }
+
+    /// Returns the largest `tokens` for which `try_acquire_at(tick, tokens)` would succeed.
+    ///
+    /// Use this to size a batch. For most cores in this crate it equals
+    /// `capacity_remaining`, which the default implementation delegates to; a
+    /// [`FixedWindowCounterCore`](crate::cores::FixedWindowCounterCore) with an
+    /// unused grace overshoot reports more. Implementations whose remaining
+    /// capacity is not exactly what one request can take should override it.
+    ///
+    /// # Arguments
+    /// * `tick` – Current time tick (from the application)
+    ///
+    /// # Returns
+    /// The largest single request that would be admitted at `tick`.
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    /// Returns the long-run sustainable rate as a reduced `(tokens, ticks)` fraction.
+    ///
+    /// This is the allowance the limiter renews over time, ignoring bursts: a
+    /// token bucket reports `refill_amount / refill_interval`, a window core
+    /// `capacity / window_ticks`. Because the fraction is reduced, two limiters
+    /// with the same sustained rate report equal values, which makes
+    /// heterogeneous configurations comparable.
+    ///
+    /// The default implementation reports `(0, 1)`, meaning no renewing
+    /// allowance, which is correct for a non-renewing quota. Pass-through
+    /// wrappers report their inner limiter's allowance.
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        (0, 1)
+    }
+
+    /// Attempts to acquire tokens and reports the state transitions the call performed.
+    ///
+    /// A request denied for insufficient capacity is returned as `Ok` with
+    /// `allowed: false`. The default implementation wraps `try_acquire_at` and
+    /// reports no transitions; the cores override it with their refill and
+    /// window bookkeeping.
+    ///
+    /// # Arguments
+    /// * `tick` – Current time tick (from the application)
+    /// * `tokens` – Number of tokens to acquire
+    fn try_acquire_detailed_at(&self, tick: Uint, tokens: Uint) -> Result<TransitionInfo, SimpleRateLimitError> {
+        match self.try_acquire_at(tick, tokens) {
+            Ok(()) => Ok(TransitionInfo { allowed: true, ..TransitionInfo::default() }),
+            Err(SimpleRateLimitError::InsufficientCapacity) => Ok(TransitionInfo::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Attempts to acquire tokens and reports readiness instead of an error.
+    ///
+    /// This is the primitive for a hand-written future: `Ready` means the
+    /// tokens were consumed, `Pending(wait_ticks)` means poll again after
+    /// `wait_ticks`, and `Rejected` means the request exceeds the capacity and
+    /// will never be admitted. The default implementation makes a single
+    /// `try_acquire_verbose_at` call, so a core decides within one locked section.
+    ///
+    /// Contention is reported as `Pending(0)`, and an expired tick as pending
+    /// until the earliest acceptable tick.
+    ///
+    /// # Arguments
+    /// * `tick` – Current time tick (from the application)
+    /// * `tokens` – Number of tokens to acquire
+    fn poll_acquire_at(&self, tick: Uint, tokens: Uint) -> AcquirePoll {
+        match self.try_acquire_verbose_at(tick, tokens) {
+            Ok(()) => AcquirePoll::Ready,
+            Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. }) => AcquirePoll::Pending(retry_after_ticks),
+            Err(VerboseRateLimitError::BeyondCapacity { .. }) => AcquirePoll::Rejected,
+            Err(VerboseRateLimitError::ContentionFailure) => AcquirePoll::Pending(0),
+            Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick }) => {
+                AcquirePoll::Pending(min_acceptable_tick.saturating_sub(tick))
+            }
+        }
+    }
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(mut a: Uint, mut b: Uint) -> Uint {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Reduces `tokens / ticks` to lowest terms, for [`RateLimitCore::steady_state_allowance`].
+pub(crate) fn reduced_allowance(tokens: Uint, ticks: Uint) -> (Uint, Uint) {
+    let divisor = gcd(tokens, ticks);
+    if divisor == 0 || tokens == 0 {
+        return (0, 1);
+    }
+    (tokens / divisor, ticks / divisor)
+}
+
+/// Scales a reduced allowance by `num / den`, for wrappers that hand out a
+/// fraction of their inner limiter's capacity.
+///
+/// The result is exact and reduced when it fits in a `Uint`; otherwise the
+/// scale is rounded to fewer bits until it does.
+pub(crate) fn scaled_allowance(allowance: (Uint, Uint), mut num: Uint, mut den: Uint) -> (Uint, Uint) {
+    let (tokens, ticks) = allowance;
+    loop {
+        if tokens == 0 || num == 0 || den == 0 {
+            return (0, 1);
+        }
+        let (reduced_num, reduced_den) = reduced_allowance(num, den);
+        let (tokens_den, ticks_num) = (gcd(tokens, reduced_den), gcd(reduced_num, ticks));
+        let scaled_tokens = (tokens / tokens_den).checked_mul(reduced_num / ticks_num);
+        let scaled_ticks = (ticks / ticks_num).checked_mul(reduced_den / tokens_den);
+        match (scaled_tokens, scaled_ticks) {
+            (Some(tokens), Some(ticks)) => return (tokens, ticks),
+            _ if reduced_den == 1 => return reduced_allowance(tokens.saturating_mul(reduced_num), ticks),
+            _ => {
+                num = reduced_num >> 1;
+                den = reduced_den >> 1;
+            }
+        }
+    }
+}
+
+/// Compares two allowances exactly, without multiplying them out.
+///
+/// Compares the whole parts and recurses on the reciprocals of the remainders,
+/// like a continued fraction, so it cannot overflow.
+pub(crate) fn compare_allowance(a: (Uint, Uint), b: (Uint, Uint)) -> Ordering {
+    let (whole_a, whole_b) = (a.0 / a.1, b.0 / b.1);
+    if whole_a != whole_b {
+        return whole_a.cmp(&whole_b);
+    }
+    match (a.0 % a.1, b.0 % b.1) {
+        (0, 0) => Ordering::Equal,
+        (0, _) => Ordering::Less,
+        (_, 0) => Ordering::Greater,
+        // A larger remainder has a smaller reciprocal
+        (rest_a, rest_b) => compare_allowance((b.1, rest_b), (a.1, rest_a)),
+    }
+}
+
+/// Adds two allowances, for combinators that charge each request to one of
+/// several limiters.
+///
+/// The sum is exact and reduced when it fits in a `Uint`; otherwise the
+/// larger of the two is returned, which still bounds the sum from below.
+pub(crate) fn summed_allowance(a: (Uint, Uint), b: (Uint, Uint)) -> (Uint, Uint) {
+    let divisor = gcd(a.1, b.1);
+    let sum = a.0
+        .checked_mul(b.1 / divisor)
+        .and_then(|x| b.0.checked_mul(a.1 / divisor).and_then(|y| x.checked_add(y)));
+    match (sum, a.1.checked_mul(b.1 / divisor)) {
+        (Some(tokens), Some(ticks)) => reduced_allowance(tokens, ticks),
+        _ if compare_allowance(a, b) == Ordering::Less => b,
+        _ => a,
+    }
+}
+
+/// A rate limiter that can give back tokens taken by a previous acquire.
+///
+/// Combinators use this to roll back a partial admission when another limiter
+/// in the same decision denies the request.
+pub trait RefundableCore: RateLimitCore {
+    /// Returns `tokens` previously acquired at `tick` to the limiter.
+    ///
+    /// Refunds are credited to the window, bucket or pool that `tick` falls into,
+    /// so they are intended for rolling back an acquire made at the same tick.
+    /// Over-refunding is clamped and never raises capacity above its configured maximum.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the tokens were returned
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` if the lock could not be acquired
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` if `tick` is older than the limiter state
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult;
+}
+
+/// Gives `tokens` acquired at `tick` back to `core`, retrying while the refund
+/// is contended.
+///
+/// Combinators use this to roll back a partial admission. Contention is
+/// transient, so the refund is retried until the limiter's lock is free, and a
+/// rollback is never lost to it. The refund can then only fail with
+/// `ExpiredTick`, when another caller has already moved the limiter past
+/// `tick`; that error is returned so the combinator can report that the tokens
+/// were not given back.
+pub(crate) fn roll_back<R: RefundableCore + ?Sized>(core: &R, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+    loop {
+        match core.refund_at(tick, tokens) {
+            Err(SimpleRateLimitError::ContentionFailure) => thread::yield_now(),
+            result => return result,
+        }
+    }
+}
+
+/// Verbose counterpart of [`roll_back`].
+///
+/// A rollback that fails because the limiter has moved past `tick` is reported
+/// as `ExpiredTick`. The combinator cannot see the limiter's state, so
+/// `min_acceptable_tick` is `tick + 1`, the earliest tick that state can be at.
+pub(crate) fn roll_back_verbose<R: RefundableCore + ?Sized>(core: &R, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+    roll_back(core, tick, tokens).map_err(|_| VerboseRateLimitError::ExpiredTick {
+        min_acceptable_tick: tick.saturating_add(1),
+    })
+}
+
+/// A rate limiter that can be rebuilt with new parameters, keeping its state.
+///
+/// Used by [`Reloadable`](crate::wrappers::Reloadable) to swap configuration
+/// without losing in-flight usage.
+pub trait Reconfigure: RateLimitCore + Sized {
+    /// Parameters accepted by [`reconfigured`](Self::reconfigured).
+    type Config;
+
+    /// Returns a limiter with `config` whose state carries over this limiter's
+    /// state as of `tick`, adjusted to fit the new parameters.
+    ///
+    /// # Returns
+    /// * `Ok(limiter)` - The reconfigured limiter; `self` is left unchanged
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` if the lock could not be acquired
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` if `tick` is older than the limiter state
+    ///
+    /// # Panics
+    /// Panics if `config` is invalid, as the core's `From<Config>` conversion does.
+    fn reconfigured(&self, config: Self::Config, tick: Uint) -> Result<Self, SimpleRateLimitError>;
+}
+
+/// Single-threaded counterpart of [`RateLimitCore`].
+///
+/// Methods take `&mut self`, so implementations can skip locking entirely. Use it
+/// when a limiter is owned by one thread, such as a per-connection limiter or an
+/// event loop. Obtain one with `into_unsync` on a core; the resulting
+/// [`UnsyncCore`](crate::cores::UnsyncCore) makes exactly the same decisions as
+/// the locked core, but can never report `ContentionFailure`.
+pub trait UnsyncRateLimit {
+    /// Attempts to acquire the specified number of tokens at the given tick.
+    ///
+    /// See [`RateLimitCore::try_acquire_at`].
+    fn try_acquire_at(&mut self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult;
+
+    /// Attempts to acquire tokens at the given tick, returning detailed diagnostics.
+    ///
+    /// See [`RateLimitCore::try_acquire_verbose_at`].
+    fn try_acquire_verbose_at(&mut self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult;
+
+    /// Returns the number of tokens currently available at the given tick.
+    ///
+    /// See [`RateLimitCore::capacity_remaining`].
+    fn capacity_remaining(&mut self, tick: Uint) -> Result<Uint, SimpleRateLimitError>;
+
+    /// Returns the number of tokens currently available at the given tick, or 0
+    /// if `capacity_remaining` fails.
+    fn capacity_remaining_or_0(&mut self, tick: Uint) -> Uint {
+        self.capacity_remaining(tick).unwrap_or(0)
+    }
 }
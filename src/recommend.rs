@@ -0,0 +1,108 @@
+//! Picks a core for a target rate from the algorithm comparison table.
+//!
+//! [`choose`] encodes the guidance of the [`cores`](crate::cores) module docs
+//! as a decision and returns a constructed core. [`algorithm`] returns the
+//! decision alone:
+//!
+//! | Accuracy | Memory budget | Core |
+//! |----------|---------------|------|
+//! | [`Bursty`](Accuracy::Bursty) | any | [`TokenBucketCore`] |
+//! | [`Coarse`](Accuracy::Coarse) | any | [`FixedWindowCounterCore`] |
+//! | [`High`](Accuracy::High) | [`Low`](MemoryBudget::Low) | [`ApproximateSlidingWindowCore`] |
+//! | [`High`](Accuracy::High) | [`Medium`](MemoryBudget::Medium) | [`SlidingWindowCounterCore`] |
+//!
+//! Every recommended core sustains exactly the target rate in the long run;
+//! the choice only changes how requests may bunch up within it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rate_guard_core::recommend::{self, Accuracy, MemoryBudget};
+//!
+//! // 100 tokens per 1000 ticks, bursts allowed
+//! let limiter = recommend::choose((100, 1000), Accuracy::Bursty, MemoryBudget::Low);
+//! assert_eq!(limiter.steady_state_allowance(), (1, 10));
+//! assert_eq!(limiter.try_acquire_at(0, 100), Ok(()));
+//! ```
+
+use crate::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use crate::rate_limit::RateLimitCore;
+use crate::Uint;
+
+/// Upper bound on the buckets of a recommended [`SlidingWindowCounterCore`].
+const MAX_SLIDING_BUCKETS: Uint = 10;
+
+/// How closely admissions must follow the target rate over short spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Accuracy {
+    /// A burst of the whole per-period allowance at once is acceptable.
+    Bursty,
+    /// Up to twice the allowance across a period boundary is acceptable.
+    Coarse,
+    /// The allowance should hold over any span of one period.
+    High,
+}
+
+/// How much per-limiter state is acceptable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryBudget {
+    /// A few counters, independent of the period.
+    Low,
+    /// A small array of counters per limiter.
+    Medium,
+}
+
+/// A core [`algorithm`] can recommend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// [`TokenBucketCore`]
+    TokenBucket,
+    /// [`FixedWindowCounterCore`]
+    FixedWindow,
+    /// [`SlidingWindowCounterCore`]
+    SlidingWindow,
+    /// [`ApproximateSlidingWindowCore`]
+    ApproximateSlidingWindow,
+}
+
+/// Returns the algorithm the [module table](self) recommends for `accuracy`
+/// and `memory_budget`.
+pub fn algorithm(accuracy: Accuracy, memory_budget: MemoryBudget) -> Algorithm {
+    match (accuracy, memory_budget) {
+        (Accuracy::Bursty, _) => Algorithm::TokenBucket,
+        (Accuracy::Coarse, _) => Algorithm::FixedWindow,
+        (Accuracy::High, MemoryBudget::Low) => Algorithm::ApproximateSlidingWindow,
+        (Accuracy::High, MemoryBudget::Medium) => Algorithm::SlidingWindow,
+    }
+}
+
+/// Returns a core that admits `target_rate.0` tokens per `target_rate.1` ticks,
+/// built from the [`algorithm`] recommended for `accuracy` and `memory_budget`.
+///
+/// The recommended [`SlidingWindowCounterCore`] splits the period into the
+/// largest number of equal buckets up to 10, so a period with no small divisor
+/// gets fewer, coarser buckets.
+///
+/// # Panics
+///
+/// Panics if either part of `target_rate` is zero.
+pub fn choose(target_rate: (Uint, Uint), accuracy: Accuracy, memory_budget: MemoryBudget) -> Box<dyn RateLimitCore> {
+    let (tokens, ticks) = target_rate;
+    assert!(tokens > 0, "target tokens must be greater than 0");
+    assert!(ticks > 0, "target ticks must be greater than 0");
+
+    match algorithm(accuracy, memory_budget) {
+        Algorithm::TokenBucket => Box::new(TokenBucketCore::new(tokens, ticks, tokens)),
+        Algorithm::FixedWindow => Box::new(FixedWindowCounterCore::new(tokens, ticks)),
+        Algorithm::ApproximateSlidingWindow => Box::new(ApproximateSlidingWindowCore::new(tokens, ticks)),
+        Algorithm::SlidingWindow => {
+            let bucket_count = (1..=MAX_SLIDING_BUCKETS.min(ticks))
+                .rev()
+                .find(|count| ticks % count == 0)
+                .unwrap_or(1);
+            Box::new(SlidingWindowCounterCore::new(tokens, ticks / bucket_count, bucket_count))
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Replays recorded traffic through a limiter, for capacity planning, and
+//! replays recorded decisions without a limiter, for deterministic tests.
+//!
+//! This module is only compiled with the `test-util` feature. Export the
+//! timestamps (and token costs) of real requests, convert them to ticks, and
+//! feed them to [`from_timestamps`] with a candidate limiter to see how many of
+//! them it would have denied before rolling out a new configuration.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rate_guard_core::cores::FixedWindowCounterCore;
+//! use rate_guard_core::replay;
+//!
+//! // One "tick,tokens" line per request, as exported from a request log
+//! let csv = "0,1\n1,1\n2,1\n10,1\n";
+//! let requests = csv.lines().map(|line| {
+//!     let (tick, tokens) = line.split_once(',').unwrap();
+//!     (tick.parse().unwrap(), tokens.parse().unwrap())
+//! });
+//!
+//! let report = replay::from_timestamps(&FixedWindowCounterCore::new(2, 10), requests);
+//! assert_eq!((report.total, report.allowed, report.denied), (4, 3, 1));
+//! ```
+//!
+//! [`Recorder`] and [`Replayer`] go the other way: record the decisions a real
+//! limiter made during a session, then hand the log to a [`Replayer`] that
+//! returns exactly those decisions again, so tests of code that depends on a
+//! limiter do not depend on the limiter's internals.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Summary of a replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReplayReport {
+    /// Number of requests replayed
+    pub total: u64,
+    /// Number of requests the limiter admitted
+    pub allowed: u64,
+    /// Number of requests the limiter denied, for any reason
+    pub denied: u64,
+    /// Largest `retry_after_ticks` reported by an `InsufficientCapacity` denial, or 0
+    pub max_retry_after: Uint,
+}
+
+impl ReplayReport {
+    /// Returns the fraction of requests denied, or 0.0 for an empty replay.
+    pub fn denial_rate(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.denied as f64 / self.total as f64
+        }
+    }
+}
+
+/// Replays `(tick, tokens)` requests through `limiter` and reports the outcome.
+///
+/// Requests are sorted by tick (keeping the recorded order within a tick) and
+/// then driven through `try_acquire_verbose_at` one by one, so an export that
+/// is not strictly ordered does not show up as `ExpiredTick` denials. The
+/// limiter should be fresh; state left by earlier calls affects the report.
+pub fn from_timestamps<L, I>(limiter: &L, requests: I) -> ReplayReport
+where
+    L: RateLimitCore + ?Sized,
+    I: IntoIterator<Item = (Uint, Uint)>,
+{
+    let mut requests: Vec<(Uint, Uint)> = requests.into_iter().collect();
+    requests.sort_by_key(|&(tick, _)| tick);
+
+    let mut report = ReplayReport::default();
+    for (tick, tokens) in requests {
+        report.total += 1;
+        match limiter.try_acquire_verbose_at(tick, tokens) {
+            Ok(()) => report.allowed += 1,
+            Err(err) => {
+                report.denied += 1;
+                if let VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. } = err {
+                    report.max_retry_after = report.max_retry_after.max(retry_after_ticks);
+                }
+            }
+        }
+    }
+    report
+}
+
+/// One call recorded by a [`Recorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recorded {
+    /// A `try_acquire_at` call and its result
+    Acquire { tick: Uint, tokens: Uint, result: SimpleRateLimitResult },
+    /// A `try_acquire_verbose_at` call and its result
+    AcquireVerbose { tick: Uint, tokens: Uint, result: VerboseRateLimitResult },
+    /// A `capacity_remaining` call and its result
+    CapacityRemaining { tick: Uint, result: Result<Uint, SimpleRateLimitError> },
+}
+
+/// Delegates to an inner limiter and logs every call with its result.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::rate_limit::RateLimitCore;
+/// use rate_guard_core::replay::{Recorder, Replayer};
+///
+/// let recorder = Recorder::new(FixedWindowCounterCore::new(1, 10));
+/// let live = [recorder.try_acquire_at(0, 1), recorder.try_acquire_at(1, 1)];
+///
+/// let replayer = Replayer::new(recorder.into_log());
+/// assert_eq!([replayer.try_acquire_at(0, 1), replayer.try_acquire_at(1, 1)], live);
+/// ```
+pub struct Recorder<L: RateLimitCore> {
+    /// Limiter making the actual decisions
+    inner: L,
+    /// Calls in the order they completed
+    log: Mutex<Vec<Recorded>>,
+}
+
+impl<L: RateLimitCore> Recorder<L> {
+    /// Creates a new recorder with an empty log.
+    pub fn new(inner: L) -> Self {
+        Recorder { inner, log: Mutex::new(Vec::new()) }
+    }
+
+    /// Appends one entry to the log.
+    fn record(&self, entry: Recorded) {
+        self.log.lock().unwrap_or_else(PoisonError::into_inner).push(entry);
+    }
+
+    /// Delegates to the inner limiter and records the call.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let result = self.inner.try_acquire_at(tick, tokens);
+        self.record(Recorded::Acquire { tick, tokens, result: result.clone() });
+        result
+    }
+
+    /// Delegates to the inner limiter and records the call.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let result = self.inner.try_acquire_verbose_at(tick, tokens);
+        self.record(Recorded::AcquireVerbose { tick, tokens, result: result.clone() });
+        result
+    }
+
+    /// Delegates to the inner limiter and records the call.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let result = self.inner.capacity_remaining(tick);
+        self.record(Recorded::CapacityRemaining { tick, result: result.clone() });
+        result
+    }
+
+    /// Returns a copy of the calls recorded so far.
+    pub fn log(&self) -> Vec<Recorded> {
+        self.log.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Consumes the recorder and returns its log.
+    pub fn into_log(self) -> Vec<Recorded> {
+        self.log.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Recorder<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
+
+/// Returns the results of a [`Recorder`] log in order, without a real limiter.
+///
+/// Each call takes the next entry from the log and returns its result; the
+/// `tick` and `tokens` arguments are ignored. The replay must make the same
+/// kinds of calls in the same order as the recorded session.
+///
+/// # Panics
+///
+/// Every method panics if the log is exhausted or if the next entry was
+/// recorded by a different method, since the code under test then no longer
+/// behaves like the recorded session.
+pub struct Replayer {
+    /// Entries not yet replayed
+    log: Mutex<VecDeque<Recorded>>,
+}
+
+impl Replayer {
+    /// Creates a replayer over a recorded log.
+    pub fn new(log: Vec<Recorded>) -> Self {
+        Replayer { log: Mutex::new(log.into()) }
+    }
+
+    /// Takes the next entry from the log.
+    fn next(&self, method: &str) -> Recorded {
+        self.log
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front()
+            .unwrap_or_else(|| panic!("replay log exhausted at {}", method))
+    }
+
+    /// Returns the number of entries not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.log.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns the next recorded `try_acquire_at` result.
+    pub fn try_acquire_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        match self.next("try_acquire_at") {
+            Recorded::Acquire { result, .. } => result,
+            other => panic!("try_acquire_at replayed against {:?}", other),
+        }
+    }
+
+    /// Returns the next recorded `try_acquire_verbose_at` result.
+    pub fn try_acquire_verbose_at(&self, _tick: Uint, _tokens: Uint) -> VerboseRateLimitResult {
+        match self.next("try_acquire_verbose_at") {
+            Recorded::AcquireVerbose { result, .. } => result,
+            other => panic!("try_acquire_verbose_at replayed against {:?}", other),
+        }
+    }
+
+    /// Returns the next recorded `capacity_remaining` result.
+    pub fn capacity_remaining(&self, _tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        match self.next("capacity_remaining") {
+            Recorded::CapacityRemaining { result, .. } => result,
+            other => panic!("capacity_remaining replayed against {:?}", other),
+        }
+    }
+}
+
+impl RateLimitCore for Replayer {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+}
@@ -10,6 +10,11 @@
 //!   (Both features cannot be enabled at the same time.)
 //! - If neither feature is enabled, `u64` is used as the default type.
 
+#[cfg(not(feature = "tick-u128"))]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "tick-u128")]
+use std::{sync::{Mutex, MutexGuard, TryLockError}, thread};
+
 /// Alias for the unsigned integer type used for capacities and ticks.
 ///
 /// The type is selected at compile time using feature flags:
@@ -29,3 +34,80 @@ pub type Uint = u128;
 
 #[cfg(not(any(feature = "tick-u64", feature = "tick-u128")))]
 pub type Uint = u64;
+
+/// A `Uint` shared between threads without a blocking lock.
+///
+/// With `tick-u64` this is an [`AtomicU64`](std::sync::atomic::AtomicU64). There is no stable 128-bit
+/// atomic, so with `tick-u128` it is a mutex that is retried with `try_lock`,
+/// yielding while another thread holds it.
+pub(crate) struct AtomicUint(AtomicUintInner);
+
+#[cfg(not(feature = "tick-u128"))]
+type AtomicUintInner = AtomicU64;
+
+#[cfg(feature = "tick-u128")]
+type AtomicUintInner = Mutex<Uint>;
+
+#[cfg(not(feature = "tick-u128"))]
+impl AtomicUint {
+    pub(crate) fn new(value: Uint) -> Self {
+        AtomicUint(AtomicU64::new(value))
+    }
+
+    #[inline(always)]
+    pub(crate) fn load(&self) -> Uint {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Raises the value to `value` if it is larger.
+    #[inline(always)]
+    pub(crate) fn fetch_max(&self, value: Uint) {
+        self.0.fetch_max(value, Ordering::Relaxed);
+    }
+
+    /// Adds `value`, saturating at `Uint::MAX`.
+    #[inline(always)]
+    pub(crate) fn saturating_add(&self, value: Uint) {
+        let _ = self.0.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+            Some(current.saturating_add(value))
+        });
+    }
+}
+
+#[cfg(feature = "tick-u128")]
+impl AtomicUint {
+    pub(crate) fn new(value: Uint) -> Self {
+        AtomicUint(Mutex::new(value))
+    }
+
+    /// Locks the value, yielding while another thread holds it.
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, Uint> {
+        loop {
+            match self.0.try_lock() {
+                Ok(guard) => return guard,
+                Err(TryLockError::Poisoned(poisoned)) => return poisoned.into_inner(),
+                Err(TryLockError::WouldBlock) => thread::yield_now(),
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn load(&self) -> Uint {
+        *self.lock()
+    }
+
+    /// Raises the value to `value` if it is larger.
+    #[inline(always)]
+    pub(crate) fn fetch_max(&self, value: Uint) {
+        let mut current = self.lock();
+        *current = (*current).max(value);
+    }
+
+    /// Adds `value`, saturating at `Uint::MAX`.
+    #[inline(always)]
+    pub(crate) fn saturating_add(&self, value: Uint) {
+        let mut current = self.lock();
+        *current = current.saturating_add(value);
+    }
+}
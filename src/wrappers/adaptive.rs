@@ -0,0 +1,149 @@
+use std::sync::{Mutex, PoisonError};
+use crate::rate_limit::RateLimitCore;
+use crate::wrappers::ceiling::{assert_inner_capacity, Ceiling};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Adjusts the usable capacity of an inner limiter from reported outcomes (AIMD).
+///
+/// The wrapper keeps an adaptive `limit` between `min_limit` and `max_limit`,
+/// where `max_limit` is the inner limiter's configured capacity. Callers report
+/// how each admitted request went through [`report_outcome`](Self::report_outcome):
+/// a success raises the limit by 1 (additive increase), a failure halves it
+/// (multiplicative decrease). This is the adaptive concurrency scheme used to
+/// back off quickly when a downstream service degrades and to probe slowly
+/// for headroom once it recovers.
+///
+/// A request is admitted only if the inner limiter would admit it *and* the
+/// tokens in use after consuming it stay within `limit`, the same ceiling
+/// check as [`Margin`](crate::wrappers::Margin). A request above the current
+/// limit is `BeyondCapacity` until successes raise the limit again.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::wrappers::Adaptive;
+///
+/// let limiter = Adaptive::new(FixedWindowCounterCore::new(100, 60), 10, 100);
+/// limiter.report_outcome(false);
+/// assert_eq!(limiter.limit(), 50);
+/// assert_eq!(limiter.try_acquire_at(0, 50), Ok(()));
+/// assert!(limiter.try_acquire_at(0, 1).is_err());
+/// ```
+pub struct Adaptive<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Lowest value the adaptive limit can fall to
+    min_limit: Uint,
+    /// Highest value the adaptive limit can grow to, the inner limiter's capacity
+    max_limit: Uint,
+    /// Current adaptive limit on tokens in use
+    limit: Mutex<Uint>,
+}
+
+impl<L: RateLimitCore> Adaptive<L> {
+    /// Creates a new adaptive wrapper. The limit starts at `max_limit`.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `min_limit` - Lowest value the limit can fall to
+    /// * `max_limit` - Highest value the limit can grow to; the configured capacity of `inner`
+    ///
+    /// # Panics
+    /// Panics if `min_limit` is 0 or greater than `max_limit`, or if `max_limit`
+    /// is below the capacity of a freshly built `inner`.
+    pub fn new(inner: L, min_limit: Uint, max_limit: Uint) -> Self {
+        assert!(min_limit > 0, "min_limit must be greater than 0");
+        assert!(min_limit <= max_limit, "min_limit must not exceed max_limit");
+        assert_inner_capacity(&inner, max_limit, "max_limit must not be below the inner limiter's capacity");
+        Adaptive {
+            inner,
+            min_limit,
+            max_limit,
+            limit: Mutex::new(max_limit),
+        }
+    }
+
+    /// Adjusts the limit from the outcome of an admitted request: +1 on
+    /// success, halved on failure, clamped to `[min_limit, max_limit]`.
+    pub fn report_outcome(&self, success: bool) {
+        let mut limit = self.limit.lock().unwrap_or_else(PoisonError::into_inner);
+        *limit = if success {
+            limit.saturating_add(1).min(self.max_limit)
+        } else {
+            (*limit / 2).max(self.min_limit)
+        };
+    }
+
+    /// Returns the current adaptive limit.
+    pub fn limit(&self) -> Uint {
+        *self.limit.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the ceiling for the current limit.
+    #[inline(always)]
+    fn bound(&self) -> Ceiling {
+        Ceiling { capacity: self.max_limit, ceiling: self.limit() }
+    }
+
+    /// Attempts to acquire tokens within the current adaptive limit.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the limit and the inner limiter both admit the request
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If the limit would be exceeded
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the current limit
+    /// * Any inner error, unchanged
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.bound().try_acquire_at(&self.inner, tick, tokens)
+    }
+
+    /// Attempts to acquire tokens within the current adaptive limit,
+    /// returning detailed diagnostics on failure.
+    ///
+    /// A denial reports the tokens left under the limit as `available`, and
+    /// the inner limiter's wait until the limit admits the request as
+    /// `retry_after_ticks`, assuming the limit does not change meanwhile.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.bound().try_acquire_verbose_at(&self.inner, tick, tokens)
+    }
+
+    /// Returns the number of tokens acquirable under the current limit.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.bound().capacity_remaining(&self.inner, tick)
+    }
+
+    /// Returns the inner limiter's steady-state allowance scaled to the current limit.
+    ///
+    /// Exact for window limiters; a lower bound for token buckets, which keep
+    /// refilling at their full rate under the limit.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.bound().steady_state_allowance(&self.inner)
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Adaptive<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
@@ -0,0 +1,160 @@
+use std::thread;
+use crate::rate_limit::RateLimitCore;
+use crate::types::AtomicUint;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Lets allowlisted callers bypass an inner limiter while metering their usage.
+///
+/// Each acquire takes a `bypass` flag. A bypassing request is always admitted
+/// and its tokens are added to a separate metered total, read with
+/// [`bypassed_total`](Self::bypassed_total). Other requests are delegated to
+/// the inner limiter unchanged.
+///
+/// With `charge_inner` set, bypassing requests also consume the inner
+/// limiter's capacity, up to the largest amount it would admit, so allowlisted
+/// traffic still counts against everyone else's budget. If another caller
+/// takes that capacity first, the charge is retried with what is left. A
+/// charge that fails for another reason, such as an expired tick, is returned
+/// as the request's error and nothing is metered. Without `charge_inner`, the
+/// inner limiter never sees bypassing requests.
+///
+/// Through [`RateLimitCore`] every request is a non-bypassing one.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::wrappers::Allowlist;
+///
+/// let limiter = Allowlist::new(FixedWindowCounterCore::new(5, 60), false);
+/// assert_eq!(limiter.try_acquire_at(false, 0, 5), Ok(()));
+/// assert!(limiter.try_acquire_at(false, 0, 1).is_err());
+/// assert_eq!(limiter.try_acquire_at(true, 0, 100), Ok(()));
+/// assert_eq!(limiter.bypassed_total(), 100);
+/// ```
+pub struct Allowlist<L: RateLimitCore> {
+    /// Limiter deciding non-bypassing requests
+    inner: L,
+    /// Whether bypassing requests also consume the inner limiter's capacity
+    charge_inner: bool,
+    /// Total tokens acquired by bypassing requests
+    bypassed_total: AtomicUint,
+}
+
+impl<L: RateLimitCore> Allowlist<L> {
+    /// Creates a new allowlist wrapper with a zero bypassed total.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter deciding non-bypassing requests
+    /// * `charge_inner` - Whether bypassing requests also consume the inner limiter's capacity
+    pub fn new(inner: L, charge_inner: bool) -> Self {
+        Allowlist {
+            inner,
+            charge_inner,
+            bypassed_total: AtomicUint::new(0),
+        }
+    }
+
+    /// Charges up to `tokens` to the inner limiter, as much as it would admit.
+    ///
+    /// The amount is read with `max_acquirable_at` and then acquired; if the
+    /// acquire is contended or another caller took the capacity in between,
+    /// the amount is read again, so the charge is decided on one consistent
+    /// reading of the inner limiter.
+    fn charge(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        loop {
+            let charged = match self.inner.max_acquirable_at(tick) {
+                Ok(max) => tokens.min(max),
+                Err(SimpleRateLimitError::ContentionFailure) => {
+                    thread::yield_now();
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            if charged == 0 {
+                return Ok(());
+            }
+            match self.inner.try_acquire_at(tick, charged) {
+                Err(SimpleRateLimitError::InsufficientCapacity) | Err(SimpleRateLimitError::ContentionFailure) => {
+                    thread::yield_now()
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Charges a bypassing request to the inner limiter if configured, then meters it.
+    fn bypass(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if self.charge_inner {
+            self.charge(tick, tokens)?;
+        }
+        self.bypassed_total.saturating_add(tokens);
+        Ok(())
+    }
+
+    /// Attempts to acquire tokens, admitting bypassing requests unconditionally.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If `bypass` is set, or the inner limiter admits the request
+    /// * Any inner error, unchanged, if `bypass` is not set or charging the
+    ///   inner limiter failed
+    pub fn try_acquire_at(&self, bypass: bool, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if bypass {
+            return self.bypass(tick, tokens);
+        }
+        self.inner.try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens, admitting bypassing requests unconditionally,
+    /// and returns the inner limiter's diagnostics for other requests.
+    ///
+    /// If charging the inner limiter fails, the request is put to the inner
+    /// limiter again, which rejects it without consuming and reports its own
+    /// diagnostics.
+    pub fn try_acquire_verbose_at(&self, bypass: bool, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if bypass {
+            return match self.bypass(tick, tokens) {
+                Ok(()) => Ok(()),
+                Err(_) => self.inner.try_acquire_verbose_at(tick, tokens),
+            };
+        }
+        self.inner.try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the inner limiter's remaining capacity at `tick`.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns the total tokens acquired by bypassing requests.
+    pub fn bypassed_total(&self) -> Uint {
+        self.bypassed_total.load()
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Allowlist<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        Allowlist::try_acquire_at(self, false, tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        Allowlist::try_acquire_verbose_at(self, false, tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
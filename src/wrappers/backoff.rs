@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Suggests exponentially growing backoff to a caller that keeps getting denied.
+///
+/// The wrapper tracks consecutive `InsufficientCapacity` denials of its inner
+/// limiter. On a verbose denial the inner `retry_after_ticks` is multiplied by
+/// `2^n`, where `n` is the number of consecutive denials before this one, and
+/// capped at `max_retry_after_ticks`. The first denial therefore reports the
+/// inner hint unchanged, the second doubles it, and so on. A successful acquire
+/// resets the counter.
+///
+/// Other errors (`BeyondCapacity`, `ExpiredTick`, `ContentionFailure`) say
+/// nothing about the caller's pace and neither grow nor reset the counter.
+///
+/// The wrapper tracks a single caller; use one instance per caller.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::wrappers::Backoff;
+/// use rate_guard_core::VerboseRateLimitError;
+///
+/// let limiter = Backoff::new(FixedWindowCounterCore::new(1, 10), 1000);
+/// assert_eq!(limiter.try_acquire_verbose_at(0, 1), Ok(()));
+///
+/// let retry = |result| match result {
+///     Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. }) => retry_after_ticks,
+///     other => panic!("unexpected {:?}", other),
+/// };
+/// assert_eq!(retry(limiter.try_acquire_verbose_at(5, 1)), 5);
+/// assert_eq!(retry(limiter.try_acquire_verbose_at(5, 1)), 10);
+/// assert_eq!(retry(limiter.try_acquire_verbose_at(5, 1)), 20);
+/// ```
+pub struct Backoff<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Upper bound for the suggested `retry_after_ticks`
+    max_retry_after_ticks: Uint,
+    /// Number of `InsufficientCapacity` denials since the last success
+    consecutive_denials: AtomicU64,
+}
+
+impl<L: RateLimitCore> Backoff<L> {
+    /// Creates a new backoff wrapper.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `max_retry_after_ticks` - Cap applied to the grown retry hint
+    pub fn new(inner: L, max_retry_after_ticks: Uint) -> Self {
+        Backoff {
+            inner,
+            max_retry_after_ticks,
+            consecutive_denials: AtomicU64::new(0),
+        }
+    }
+
+    /// Attempts to acquire tokens, updating the consecutive denial counter.
+    ///
+    /// # Returns
+    /// The inner limiter's result, unchanged.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let result = self.inner.try_acquire_at(tick, tokens);
+        match result {
+            Ok(()) => self.consecutive_denials.store(0, Ordering::Relaxed),
+            Err(SimpleRateLimitError::InsufficientCapacity) => {
+                self.consecutive_denials.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Attempts to acquire tokens, growing `retry_after_ticks` on repeated denials.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the inner limiter admitted the request (the counter is reset)
+    /// * `Err(VerboseRateLimitError::InsufficientCapacity { .. })` - With `retry_after_ticks`
+    ///   multiplied by `2^n` and capped at `max_retry_after_ticks`
+    /// * Any other inner error, unchanged
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        match self.inner.try_acquire_verbose_at(tick, tokens) {
+            Ok(()) => {
+                self.consecutive_denials.store(0, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(VerboseRateLimitError::InsufficientCapacity { acquiring, available, retry_after_ticks, available_next_refill }) => {
+                let prior_denials = self.consecutive_denials.fetch_add(1, Ordering::Relaxed);
+                let factor = if prior_denials < Uint::BITS as u64 {
+                    1 << prior_denials
+                } else {
+                    Uint::MAX
+                };
+                Err(VerboseRateLimitError::InsufficientCapacity {
+                    acquiring,
+                    available,
+                    retry_after_ticks: retry_after_ticks.saturating_mul(factor).min(self.max_retry_after_ticks),
+                    available_next_refill,
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the remaining capacity of the inner limiter.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns the number of `InsufficientCapacity` denials since the last success.
+    pub fn consecutive_denials(&self) -> u64 {
+        self.consecutive_denials.load(Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Backoff<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
@@ -0,0 +1,132 @@
+//! Admission against a ceiling on the tokens in use of an inner limiter.
+//!
+//! [`Margin`](crate::wrappers::Margin),
+//! [`ScaledByReplicas`](crate::wrappers::ScaledByReplicas) and
+//! [`Adaptive`](crate::wrappers::Adaptive) all admit a request only if the
+//! inner limiter admits it *and* the tokens in use afterwards stay within a
+//! ceiling below the inner limiter's capacity. Usage is derived from the inner
+//! `capacity_remaining` as `capacity - remaining`, so the tokens the ceiling
+//! admits are the inner remaining capacity minus the tokens it withholds,
+//! `capacity - ceiling`.
+//!
+//! The ceiling check and the inner acquire are two separate calls on the inner
+//! limiter, so under concurrent use the ceiling is best effort.
+
+use crate::rate_limit::{scaled_allowance, RateLimitCore};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// A ceiling on the tokens in use of an inner limiter with a known capacity.
+#[derive(Clone, Copy)]
+pub(crate) struct Ceiling {
+    /// Configured capacity of the inner limiter
+    pub(crate) capacity: Uint,
+    /// Most tokens that may be in use after an acquire, at most `capacity`
+    pub(crate) ceiling: Uint,
+}
+
+impl Ceiling {
+    /// Returns the tokens of the inner capacity the ceiling never hands out.
+    #[inline(always)]
+    fn withheld(&self) -> Uint {
+        self.capacity.saturating_sub(self.ceiling)
+    }
+
+    /// Returns the tokens acquirable under the ceiling, given the inner
+    /// limiter's remaining capacity.
+    #[inline(always)]
+    pub(crate) fn remaining(&self, inner_remaining: Uint) -> Uint {
+        inner_remaining.saturating_sub(self.withheld())
+    }
+
+    /// Attempts to acquire tokens from `inner` within the ceiling.
+    ///
+    /// A request above the ceiling itself is `BeyondCapacity`.
+    pub(crate) fn try_acquire_at<L: RateLimitCore + ?Sized>(&self, inner: &L, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens == 0 {
+            return inner.try_acquire_at(tick, tokens);
+        }
+        if tokens > self.ceiling {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+
+        if tokens > self.remaining(inner.capacity_remaining(tick)?) {
+            return Err(SimpleRateLimitError::InsufficientCapacity);
+        }
+        inner.try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens from `inner` within the ceiling, returning
+    /// detailed diagnostics on failure.
+    ///
+    /// The ceiling admits `tokens` once the inner limiter has `tokens` plus the
+    /// withheld tokens free. On a denial the inner limiter is asked for that
+    /// amount, which it is known to be short of, so its `retry_after_ticks` is
+    /// exactly the wait until the ceiling admits the request. `available` and
+    /// `available_next_refill` are reported under the ceiling.
+    pub(crate) fn try_acquire_verbose_at<L: RateLimitCore + ?Sized>(&self, inner: &L, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens == 0 {
+            return inner.try_acquire_verbose_at(tick, tokens);
+        }
+        if tokens > self.ceiling {
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: tokens,
+                capacity: self.ceiling,
+            });
+        }
+
+        let inner_remaining = match inner.capacity_remaining(tick) {
+            Ok(remaining) => remaining,
+            // Let the inner limiter report the error with its own diagnostics
+            Err(_) => return inner.try_acquire_verbose_at(tick, tokens),
+        };
+        let available = self.remaining(inner_remaining);
+        if tokens <= available {
+            return inner.try_acquire_verbose_at(tick, tokens);
+        }
+
+        // `tokens <= ceiling`, so this never exceeds the inner capacity
+        match inner.try_acquire_verbose_at(tick, tokens + self.withheld()) {
+            Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, available_next_refill, .. }) => {
+                Err(VerboseRateLimitError::InsufficientCapacity {
+                    acquiring: tokens,
+                    available,
+                    retry_after_ticks,
+                    available_next_refill: self.remaining(available_next_refill),
+                })
+            }
+            // Only a concurrent refund can free the inner limiter at the same
+            // tick; the request was admitted, charged with the withheld tokens
+            Ok(()) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the tokens acquirable from `inner` under the ceiling.
+    pub(crate) fn capacity_remaining<L: RateLimitCore + ?Sized>(&self, inner: &L, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        Ok(self.remaining(inner.capacity_remaining(tick)?))
+    }
+
+    /// Returns the inner limiter's steady-state allowance scaled by `ceiling / capacity`.
+    ///
+    /// This is exact for window limiters, whose whole capacity renews every
+    /// window. A token bucket keeps refilling at its full rate under a ceiling,
+    /// so for a bucket this is a lower bound.
+    pub(crate) fn steady_state_allowance<L: RateLimitCore + ?Sized>(&self, inner: &L) -> (Uint, Uint) {
+        scaled_allowance(inner.steady_state_allowance(), self.ceiling, self.capacity)
+    }
+}
+
+/// Checks that `capacity` is not below the capacity of a freshly built `inner`.
+///
+/// Usage is derived as `capacity - remaining`, so a `capacity` smaller than the
+/// inner limiter's would undercount usage and let requests through above the
+/// ceiling. A fresh limiter reports its whole capacity at tick 0; a limiter
+/// whose state is already past tick 0 cannot be checked and is accepted.
+///
+/// # Panics
+/// Panics with `message` if `inner` reports more than `capacity` tokens remaining.
+pub(crate) fn assert_inner_capacity<L: RateLimitCore + ?Sized>(inner: &L, capacity: Uint, message: &str) {
+    if let Ok(remaining) = inner.capacity_remaining(0) {
+        assert!(remaining <= capacity, "{}", message);
+    }
+}
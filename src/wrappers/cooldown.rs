@@ -0,0 +1,174 @@
+use std::sync::Mutex;
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Denies every request for a fixed cooldown after the inner limiter runs out.
+///
+/// When the inner limiter denies a request with `InsufficientCapacity` and has
+/// no tokens left, the wrapper records that tick as the exhaustion tick and
+/// denies all requests
+/// until `cooldown_ticks` have elapsed from it, without consulting the inner
+/// limiter. The inner limiter keeps refilling meanwhile, so clients cannot
+/// consume each trickle of refill the moment it arrives and the limiter gets
+/// a chance to recover. After the cooldown, requests are delegated again.
+///
+/// Only capacity denials of an empty limiter start a cooldown. A request that
+/// is merely larger than what is left is denied without one, so smaller
+/// requests can still use the remaining tokens. `BeyondCapacity`,
+/// `ExpiredTick` and contention are passed through unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::Cooldown;
+///
+/// let limiter = Cooldown::new(TokenBucketCore::new(2, 1, 1), 5);
+/// assert_eq!(limiter.try_acquire_at(0, 2), Ok(()));
+/// assert!(limiter.try_acquire_at(0, 1).is_err()); // exhausted at tick 0
+/// assert!(limiter.try_acquire_at(3, 1).is_err()); // refilled, but cooling down
+/// assert_eq!(limiter.try_acquire_at(5, 1), Ok(()));
+/// ```
+pub struct Cooldown<L: RateLimitCore> {
+    /// Limiter making the admission decision outside a cooldown
+    inner: L,
+    /// Ticks to deny all requests after an exhaustion
+    cooldown_ticks: Uint,
+    /// Tick of the denial that started the current cooldown, if any
+    exhausted_at: Mutex<Option<Uint>>,
+}
+
+impl<L: RateLimitCore> Cooldown<L> {
+    /// Creates a new cooldown wrapper with no cooldown in progress.
+    ///
+    /// # Panics
+    /// Panics if `cooldown_ticks` is 0.
+    pub fn new(inner: L, cooldown_ticks: Uint) -> Self {
+        assert!(cooldown_ticks > 0, "cooldown_ticks must be greater than 0");
+        Cooldown {
+            inner,
+            cooldown_ticks,
+            exhausted_at: Mutex::new(None),
+        }
+    }
+
+    /// Returns the ticks left in the cooldown at `tick`, clearing a finished one.
+    #[inline(always)]
+    fn cooling_for(&self, exhausted_at: &mut Option<Uint>, tick: Uint) -> Option<Uint> {
+        let end = (*exhausted_at)?.saturating_add(self.cooldown_ticks);
+        if tick < end {
+            Some(end - tick)
+        } else {
+            *exhausted_at = None;
+            None
+        }
+    }
+
+    /// Attempts to acquire tokens, denying all requests during a cooldown.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If no cooldown is in progress and the inner limiter admits the request
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - During a cooldown, or if the
+    ///   inner limiter denies the request; a denial that leaves it reporting no
+    ///   remaining capacity starts a cooldown
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * Any other inner error, unchanged
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let mut exhausted_at = match self.exhausted_at.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        if self.cooling_for(&mut exhausted_at, tick).is_some() {
+            return Err(SimpleRateLimitError::InsufficientCapacity);
+        }
+
+        let result = self.inner.try_acquire_at(tick, tokens);
+        if result == Err(SimpleRateLimitError::InsufficientCapacity) && self.inner.capacity_remaining(tick) == Ok(0) {
+            *exhausted_at = Some(tick);
+        }
+        result
+    }
+
+    /// Attempts to acquire tokens, denying all requests during a cooldown, and
+    /// returns detailed diagnostics on failure.
+    ///
+    /// A denial during a cooldown reports `available: 0`, `available_next_refill: 0`
+    /// and the ticks left in the cooldown as `retry_after_ticks`. An inner capacity denial with
+    /// nothing `available` starts a cooldown and reports the later of the inner
+    /// retry and the cooldown end; any other inner denial is returned unchanged.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let mut exhausted_at = match self.exhausted_at.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(VerboseRateLimitError::ContentionFailure),
+        };
+        if let Some(remaining) = self.cooling_for(&mut exhausted_at, tick) {
+            return Err(VerboseRateLimitError::InsufficientCapacity {
+                acquiring: tokens,
+                available: 0,
+                retry_after_ticks: remaining,
+                available_next_refill: 0,
+            });
+        }
+
+        match self.inner.try_acquire_verbose_at(tick, tokens) {
+            Err(VerboseRateLimitError::InsufficientCapacity { acquiring, available: 0, retry_after_ticks, available_next_refill }) => {
+                *exhausted_at = Some(tick);
+                Err(VerboseRateLimitError::InsufficientCapacity {
+                    acquiring,
+                    available: 0,
+                    retry_after_ticks: retry_after_ticks.max(self.cooldown_ticks),
+                    available_next_refill,
+                })
+            }
+            result => result,
+        }
+    }
+
+    /// Returns 0 during a cooldown, and the inner remaining capacity otherwise.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut exhausted_at = match self.exhausted_at.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        if self.cooling_for(&mut exhausted_at, tick).is_some() {
+            return Ok(0);
+        }
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns the tick of the denial that started the current cooldown, or
+    /// `None` if none has started or the last one has been observed to end.
+    pub fn exhausted_at(&self) -> Result<Option<Uint>, SimpleRateLimitError> {
+        match self.exhausted_at.try_lock() {
+            Ok(guard) => Ok(*guard),
+            Err(_) => Err(SimpleRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Cooldown<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Runs an inner limiter in shadow mode: every decision is made, but capacity
+/// denials are counted instead of returned.
+///
+/// Each acquire is delegated to the inner limiter, which updates its state as
+/// usual. If the inner limiter denies the request for lack of capacity
+/// (`InsufficientCapacity` or `BeyondCapacity`), the wrapper increments its
+/// shadow denial counter and returns `Ok(())` anyway. This lets a new
+/// configuration be observed against real traffic before it is enforced.
+///
+/// `ExpiredTick` and `ContentionFailure` are not policy decisions and are
+/// returned unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::wrappers::DryRun;
+///
+/// let limiter = DryRun::new(FixedWindowCounterCore::new(10, 60));
+/// assert_eq!(limiter.try_acquire_at(0, 8), Ok(()));
+/// assert_eq!(limiter.try_acquire_at(0, 8), Ok(()));
+/// assert_eq!(limiter.shadow_denials(), 1);
+/// ```
+pub struct DryRun<L: RateLimitCore> {
+    /// Limiter making the shadow admission decision
+    inner: L,
+    /// Number of acquires the inner limiter would have denied
+    shadow_denials: AtomicU64,
+}
+
+impl<L: RateLimitCore> DryRun<L> {
+    /// Creates a new dry-run wrapper with a zero shadow denial count.
+    pub fn new(inner: L) -> Self {
+        DryRun {
+            inner,
+            shadow_denials: AtomicU64::new(0),
+        }
+    }
+
+    /// Attempts to acquire tokens, admitting requests the inner limiter would deny.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the inner limiter admitted the request or denied it for lack of capacity
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` / `Err(SimpleRateLimitError::ContentionFailure)` -
+    ///   Passed through from the inner limiter
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        match self.inner.try_acquire_at(tick, tokens) {
+            Err(SimpleRateLimitError::InsufficientCapacity) | Err(SimpleRateLimitError::BeyondCapacity) => {
+                self.shadow_denials.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Attempts to acquire tokens, admitting requests the inner limiter would deny.
+    ///
+    /// Behaves like `try_acquire_at`; the inner diagnostics for a shadow denial
+    /// are discarded.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        match self.inner.try_acquire_verbose_at(tick, tokens) {
+            Err(VerboseRateLimitError::InsufficientCapacity { .. })
+            | Err(VerboseRateLimitError::BeyondCapacity { .. }) => {
+                self.shadow_denials.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Returns the remaining capacity of the inner limiter.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns the number of acquires the inner limiter would have denied.
+    pub fn shadow_denials(&self) -> u64 {
+        self.shadow_denials.load(Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for DryRun<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
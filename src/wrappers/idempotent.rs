@@ -0,0 +1,187 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Deduplicates retried requests by idempotency key.
+///
+/// Clients that retry after a network failure send the same logical request
+/// twice. [`try_acquire_at_keyed`](Self::try_acquire_at_keyed) remembers each
+/// admitted key in a small LRU cache: a key admitted within `retention_ticks`
+/// is admitted again without charging the inner limiter. Keys are forgotten
+/// once they age out of the retention window or are evicted as least recently
+/// used when more than `max_keys` are tracked; such a key is charged again.
+///
+/// Only admissions are cached. A denied key made no charge, so its retry is
+/// decided by the inner limiter afresh and is admitted as soon as capacity
+/// frees up. `ContentionFailure` is never cached either.
+///
+/// Calls made through [`RateLimitCore`] carry no key and go straight to the
+/// inner limiter.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::Idempotent;
+///
+/// let limiter = Idempotent::new(TokenBucketCore::new(10, 10, 1), 128, 100);
+/// assert_eq!(limiter.try_acquire_at_keyed(42, 0, 10), Ok(()));
+/// // A retry of request 42 is not charged again
+/// assert_eq!(limiter.try_acquire_at_keyed(42, 1, 10), Ok(()));
+/// assert_eq!(limiter.inner().capacity_remaining(1), Ok(0));
+/// ```
+pub struct Idempotent<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Maximum number of keys remembered at once
+    max_keys: usize,
+    /// Ticks for which a key's admission is reused
+    retention_ticks: Uint,
+    /// Remembered admissions
+    state: Mutex<IdempotentState>,
+}
+
+/// Admitted keys and their recency order
+struct IdempotentState {
+    /// Admitted keys, with the tick they were charged at and their latest use
+    entries: HashMap<u64, IdempotentEntry>,
+    /// Keys in order of use, least recent first, tagged with the use they
+    /// record; a tag older than the key's latest use is stale and skipped
+    order: VecDeque<(u64, u64)>,
+    /// Counter tagging each use of a key
+    next_use: u64,
+}
+
+/// Cached admission of one idempotency key
+struct IdempotentEntry {
+    /// Tick at which the inner limiter was charged
+    tick: Uint,
+    /// Tag of the key's latest use in `order`
+    last_use: u64,
+}
+
+impl IdempotentState {
+    /// Records a use of `key`, making it the most recently used.
+    fn touch(&mut self, key: u64) -> u64 {
+        let tag = self.next_use;
+        self.next_use += 1;
+        self.order.push_back((key, tag));
+        tag
+    }
+
+    /// Forgets the least recently used key.
+    fn evict_oldest(&mut self) {
+        while let Some((key, tag)) = self.order.pop_front() {
+            if self.entries.get(&key).map_or(false, |entry| entry.last_use == tag) {
+                self.entries.remove(&key);
+                return;
+            }
+        }
+    }
+
+    /// Drops stale tags once they outnumber the live ones, keeping `order`
+    /// within twice the number of remembered keys.
+    fn compact(&mut self) {
+        if self.order.len() > 2 * self.entries.len() {
+            let entries = &self.entries;
+            self.order.retain(|(key, tag)| entries.get(key).map_or(false, |entry| entry.last_use == *tag));
+        }
+    }
+}
+
+impl<L: RateLimitCore> Idempotent<L> {
+    /// Creates a new idempotency wrapper.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `max_keys` - Maximum number of keys remembered at once
+    /// * `retention_ticks` - Ticks after the admission during which retries reuse it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_keys` or `retention_ticks` is zero.
+    pub fn new(inner: L, max_keys: usize, retention_ticks: Uint) -> Self {
+        assert!(max_keys > 0, "max_keys must be greater than 0");
+        assert!(retention_ticks > 0, "retention_ticks must be greater than 0");
+        Idempotent {
+            inner,
+            max_keys,
+            retention_ticks,
+            state: Mutex::new(IdempotentState {
+                entries: HashMap::with_capacity(max_keys),
+                order: VecDeque::with_capacity(max_keys),
+                next_use: 0,
+            }),
+        }
+    }
+
+    /// Attempts to acquire tokens for the request identified by `key`.
+    ///
+    /// If `key` was admitted less than `retention_ticks` before `tick`, `Ok` is
+    /// returned and the inner limiter is not called. Otherwise the inner
+    /// limiter decides, and an admission is cached. The cache stays locked
+    /// while the inner limiter runs, so concurrent retries of one key are
+    /// charged once.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the key was admitted now or within the retention window
+    /// * `Err(_)` - The inner limiter's error, which is not cached
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If the cache or the inner limiter is contended
+    pub fn try_acquire_at_keyed(&self, key: u64, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+
+        let retained = match state.entries.get(&key) {
+            Some(entry) => tick.saturating_sub(entry.tick) < self.retention_ticks,
+            None => false,
+        };
+        if retained {
+            let tag = state.touch(key);
+            if let Some(entry) = state.entries.get_mut(&key) {
+                entry.last_use = tag;
+            }
+            state.compact();
+            return Ok(());
+        }
+
+        self.inner.try_acquire_at(tick, tokens)?;
+
+        if state.entries.remove(&key).is_none() && state.entries.len() == self.max_keys {
+            state.evict_oldest();
+        }
+        let tag = state.touch(key);
+        state.entries.insert(key, IdempotentEntry { tick, last_use: tag });
+        state.compact();
+        Ok(())
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Idempotent<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.inner.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.inner.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
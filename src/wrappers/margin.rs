@@ -0,0 +1,129 @@
+use crate::rate_limit::RateLimitCore;
+use crate::wrappers::ceiling::{assert_inner_capacity, Ceiling};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Basis points in one whole (100%).
+const BPS_SCALE: Uint = 10_000;
+
+/// Keeps utilization of the inner limiter below a fraction of its capacity.
+///
+/// A request is admitted only if the inner limiter would admit it *and* the
+/// utilization after consuming it stays within `headroom_bps / 10000` of
+/// `capacity`. With `headroom_bps = 9000`, the last 10% of the inner capacity
+/// is never handed out, which keeps traffic safely under an upstream hard limit
+/// without recomputing every configured capacity.
+///
+/// Utilization is derived from the inner `capacity_remaining`, so `capacity`
+/// must match the inner limiter's configured capacity. A request larger than
+/// the ceiling itself can never be admitted and is `BeyondCapacity`. The check
+/// and the inner acquire are two separate steps; under concurrent use the
+/// margin is best effort.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::wrappers::Margin;
+///
+/// let limiter = Margin::new(FixedWindowCounterCore::new(100, 60), 100, 9000);
+/// assert_eq!(limiter.try_acquire_at(0, 90), Ok(()));
+/// assert!(limiter.try_acquire_at(0, 1).is_err());
+/// ```
+pub struct Margin<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Configured capacity of the inner limiter
+    capacity: Uint,
+    /// Maximum utilization after an acquire, in basis points of `capacity`
+    headroom_bps: Uint,
+}
+
+impl<L: RateLimitCore> Margin<L> {
+    /// Creates a new margin wrapper.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `capacity` - Configured capacity of `inner`
+    /// * `headroom_bps` - Maximum utilization in basis points (10000 = 100%)
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0, `headroom_bps` is greater than 10000, or
+    /// `inner` reports more than `capacity` tokens at tick 0.
+    pub fn new(inner: L, capacity: Uint, headroom_bps: Uint) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        assert!(headroom_bps <= BPS_SCALE, "headroom_bps must not exceed 10000");
+        assert_inner_capacity(&inner, capacity, "capacity must not be below the inner limiter's capacity");
+        Margin { inner, capacity, headroom_bps }
+    }
+
+    /// Returns the most tokens that may be in use, `headroom_bps / 10000` of `capacity`.
+    pub fn ceiling(&self) -> Uint {
+        self.capacity / BPS_SCALE * self.headroom_bps + self.capacity % BPS_SCALE * self.headroom_bps / BPS_SCALE
+    }
+
+    #[inline(always)]
+    fn bound(&self) -> Ceiling {
+        Ceiling { capacity: self.capacity, ceiling: self.ceiling() }
+    }
+
+    /// Attempts to acquire tokens while keeping utilization within the margin.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the margin and the inner limiter both admit the request
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If the margin would be exceeded
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the margin's ceiling
+    /// * Any inner error, unchanged
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.bound().try_acquire_at(&self.inner, tick, tokens)
+    }
+
+    /// Attempts to acquire tokens while keeping utilization within the margin,
+    /// returning detailed diagnostics on failure.
+    ///
+    /// A denial reports the tokens left under the margin as `available`, and
+    /// the inner limiter's wait until the margin admits the request as
+    /// `retry_after_ticks`.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.bound().try_acquire_verbose_at(&self.inner, tick, tokens)
+    }
+
+    /// Returns the number of tokens acquirable before the margin is reached.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.bound().capacity_remaining(&self.inner, tick)
+    }
+
+    /// Returns the inner limiter's steady-state allowance scaled to the margin.
+    ///
+    /// Exact for window limiters; a lower bound for token buckets, which keep
+    /// refilling at their full rate under the margin.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.bound().steady_state_allowance(&self.inner)
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Margin<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
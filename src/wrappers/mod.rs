@@ -0,0 +1,81 @@
+//! Decorators that wrap a single rate limiter.
+//!
+//! Each wrapper owns an inner [`RateLimitCore`](crate::rate_limit::RateLimitCore),
+//! delegates the admission decision to it, and adjusts either the inputs or the
+//! diagnostics around that decision. Wrappers implement `RateLimitCore`
+//! themselves, so they can be stacked and used anywhere a core is expected.
+//!
+//! # Available Wrappers
+//!
+//! - **[`Adaptive`]** - Shrinks usable capacity on reported failures and grows it on successes (AIMD)
+//! - **[`Allowlist`]** - Lets flagged callers bypass the limit while metering their usage
+//! - **[`Backoff`]** - Grows `retry_after_ticks` exponentially for a persistently denied caller
+//! - **[`Cooldown`]** - Denies every request for a fixed period after the inner limiter runs out
+//! - **[`DryRun`]** - Always admits, counting the requests the inner limiter would have denied
+//! - **[`Idempotent`]** - Charges each idempotency key once, replaying its outcome on retries
+//! - **[`Margin`]** - Keeps utilization below a configured fraction of capacity
+//! - **[`MonotonicGuard`]** - Rejects ticks below the maximum tick seen across all calls (`u64` ticks only)
+//! - **[`OnExhaust`]** - Calls a callback once each time the inner limiter goes from available to empty
+//! - **[`Pacer`]** - Schedules the part of a burst beyond current capacity at the refill rate
+//! - **[`PerRequestCap`]** - Rejects any single request above a per-request token ceiling
+//! - **[`QuantizeTick`]** - Rounds ticks down to a coarser granularity before delegating
+//! - **[`RateBand`]** - Caps the rate and flags when admissions fall below a minimum rate
+//! - **[`Reloadable`]** - Swaps in new parameters at runtime, migrating the limiter's state
+//! - **[`RetryContention`]** - Retries a bounded number of times on `ContentionFailure`
+//! - **[`ScaledByReplicas`]** - Enforces `global / replica_count` against a live replica count
+//! - **[`SignedTickAdapter`]** - Accepts signed `i64` ticks relative to a base
+
+pub mod adaptive;
+pub use adaptive::Adaptive;
+
+pub mod allowlist;
+pub use allowlist::Allowlist;
+
+pub(crate) mod ceiling;
+
+pub mod backoff;
+pub use backoff::Backoff;
+
+pub mod cooldown;
+pub use cooldown::Cooldown;
+
+pub mod dry_run;
+pub use dry_run::DryRun;
+
+pub mod idempotent;
+pub use idempotent::Idempotent;
+
+pub mod margin;
+pub use margin::Margin;
+
+#[cfg(not(feature = "tick-u128"))]
+pub mod monotonic_guard;
+#[cfg(not(feature = "tick-u128"))]
+pub use monotonic_guard::MonotonicGuard;
+
+pub mod on_exhaust;
+pub use on_exhaust::OnExhaust;
+
+pub mod pacer;
+pub use pacer::{Pacer, ReleaseSchedule};
+
+pub mod per_request_cap;
+pub use per_request_cap::PerRequestCap;
+
+pub mod quantize_tick;
+pub use quantize_tick::QuantizeTick;
+
+pub mod rate_band;
+pub use rate_band::RateBand;
+
+pub mod reloadable;
+pub use reloadable::Reloadable;
+
+pub mod retry_contention;
+pub use retry_contention::RetryContention;
+
+pub mod scaled_by_replicas;
+pub use scaled_by_replicas::ScaledByReplicas;
+
+pub mod signed_tick;
+pub use signed_tick::SignedTickAdapter;
@@ -0,0 +1,121 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Enforces monotonic time in front of an inner limiter.
+///
+/// The wrapper keeps the maximum tick seen by any acquire in an `AtomicU64`.
+/// A call with a tick below that maximum is rejected with `ExpiredTick` before
+/// the inner limiter is consulted; otherwise the maximum is advanced and the
+/// call is delegated. This gives a single, strict monotonicity check for
+/// limiters whose own check is laxer (for example, a token bucket accepts any
+/// tick at or after its last refill, not its last acquire).
+///
+/// Read-only queries such as `capacity_remaining` reject regressions too, but
+/// do not advance the maximum.
+///
+/// Only available with 64-bit ticks, since the maximum is stored in an `AtomicU64`.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::MonotonicGuard;
+/// use rate_guard_core::SimpleRateLimitError;
+///
+/// let limiter = MonotonicGuard::new(TokenBucketCore::new(100, 10, 5));
+/// assert_eq!(limiter.try_acquire_at(7, 1), Ok(()));
+/// assert_eq!(limiter.try_acquire_at(6, 1), Err(SimpleRateLimitError::ExpiredTick));
+/// assert_eq!(limiter.max_tick_seen(), 7);
+/// ```
+pub struct MonotonicGuard<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Largest tick passed to an acquire so far
+    max_tick_seen: AtomicU64,
+}
+
+impl<L: RateLimitCore> MonotonicGuard<L> {
+    /// Creates a new monotonic guard. The maximum tick seen starts at 0.
+    pub fn new(inner: L) -> Self {
+        MonotonicGuard {
+            inner,
+            max_tick_seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Advances the maximum tick seen to `tick`, or returns the current maximum
+    /// if `tick` is below it.
+    #[inline(always)]
+    fn advance(&self, tick: Uint) -> Result<(), Uint> {
+        let previous = self.max_tick_seen.fetch_max(tick, Ordering::AcqRel);
+        if tick < previous {
+            Err(previous)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Attempts to acquire tokens, rejecting ticks below the maximum seen.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the tick is not a regression and the inner limiter admits the request
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If `tick` is below the maximum tick seen
+    /// * Any inner error, unchanged
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.advance(tick).map_err(|_| SimpleRateLimitError::ExpiredTick)?;
+        self.inner.try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens, rejecting ticks below the maximum seen, and
+    /// returns detailed diagnostics on failure.
+    ///
+    /// A regression is reported as `ExpiredTick` with `min_acceptable_tick` set
+    /// to the maximum tick seen.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.advance(tick)
+            .map_err(|max| VerboseRateLimitError::ExpiredTick { min_acceptable_tick: max })?;
+        self.inner.try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the inner limiter's remaining capacity at `tick`, rejecting
+    /// ticks below the maximum seen without advancing it.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        if tick < self.max_tick_seen() {
+            return Err(SimpleRateLimitError::ExpiredTick);
+        }
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns the largest tick passed to an acquire so far, or 0 if none.
+    pub fn max_tick_seen(&self) -> Uint {
+        self.max_tick_seen.load(Ordering::Acquire)
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for MonotonicGuard<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::Mutex;
+use std::thread;
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Calls a callback once when the inner limiter runs out, for alerting.
+///
+/// The callback is edge-triggered. It receives the tick of the admitted
+/// request that left the inner limiter's remaining capacity at zero, and is
+/// not called again while the limiter stays empty. Once a later request
+/// observes the inner capacity back above zero, the wrapper re-arms, and the
+/// next request that empties the limiter calls the callback again.
+///
+/// The inner limiter makes every admission decision; the wrapper only reads
+/// its remaining capacity around each acquire. Denied requests never call the
+/// callback. A read that fails on contention is retried until the inner
+/// limiter answers, so contention never hides an exhaustion. If the read fails
+/// for another reason, such as an expired tick, that request neither fires nor
+/// re-arms the callback.
+///
+/// The callback runs after the wrapper's lock is released, on the thread of
+/// the request that emptied the limiter, so it should return quickly.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::OnExhaust;
+///
+/// let alerts = Arc::new(AtomicUsize::new(0));
+/// let counter = Arc::clone(&alerts);
+/// let limiter = OnExhaust::new(TokenBucketCore::new(3, 10, 3), move |_tick| {
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+///
+/// assert_eq!(limiter.try_acquire_at(0, 2), Ok(()));
+/// assert_eq!(limiter.try_acquire_at(0, 1), Ok(())); // empties the bucket
+/// assert!(limiter.try_acquire_at(0, 1).is_err());
+/// assert_eq!(alerts.load(Ordering::Relaxed), 1);
+/// ```
+pub struct OnExhaust<L: RateLimitCore> {
+    /// Limiter making the admission decision
+    inner: L,
+    /// Called with the tick of the request that empties the inner limiter
+    on_exhaust: Box<dyn Fn(Uint) + Send + Sync>,
+    /// Whether the callback has fired and not yet been re-armed
+    exhausted: Mutex<bool>,
+}
+
+impl<L: RateLimitCore> OnExhaust<L> {
+    /// Creates a new wrapper, armed to call `on_exhaust` on the next exhaustion.
+    pub fn new<F>(inner: L, on_exhaust: F) -> Self
+    where
+        F: Fn(Uint) + Send + Sync + 'static,
+    {
+        OnExhaust {
+            inner,
+            on_exhaust: Box::new(on_exhaust),
+            exhausted: Mutex::new(false),
+        }
+    }
+
+    /// Reads the inner remaining capacity, retrying while the inner limiter is contended.
+    fn remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        loop {
+            match self.inner.capacity_remaining(tick) {
+                Err(SimpleRateLimitError::ContentionFailure) => thread::yield_now(),
+                result => return result,
+            }
+        }
+    }
+
+    /// Runs `decide` against the inner limiter, re-arming beforehand if the
+    /// inner capacity has recovered and firing the callback if an admission
+    /// left it empty.
+    #[inline(always)]
+    fn acquire<E>(&self, tick: Uint, contention: E, decide: impl FnOnce(&L) -> Result<(), E>) -> Result<(), E> {
+        let mut exhausted = match self.exhausted.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(contention),
+        };
+        if *exhausted && matches!(self.remaining(tick), Ok(remaining) if remaining > 0) {
+            *exhausted = false;
+        }
+
+        let result = decide(&self.inner);
+        let fire = result.is_ok() && !*exhausted && self.remaining(tick) == Ok(0);
+        if fire {
+            *exhausted = true;
+        }
+        drop(exhausted);
+
+        if fire {
+            (self.on_exhaust)(tick);
+        }
+        result
+    }
+
+    /// Attempts to acquire tokens from the inner limiter, calling the callback
+    /// if the request leaves it empty.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the inner limiter admits the request
+    /// * `Err(SimpleRateLimitError::ContentionFailure)` - If unable to acquire the internal lock
+    /// * Any inner error, unchanged
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.acquire(tick, SimpleRateLimitError::ContentionFailure, |inner| inner.try_acquire_at(tick, tokens))
+    }
+
+    /// Attempts to acquire tokens from the inner limiter, calling the callback
+    /// if the request leaves it empty, and returns the inner diagnostics on failure.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.acquire(tick, VerboseRateLimitError::ContentionFailure, |inner| inner.try_acquire_verbose_at(tick, tokens))
+    }
+
+    /// Returns the inner remaining capacity.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns whether the callback has fired and the wrapper has not yet
+    /// observed the inner capacity recover.
+    pub fn is_exhausted(&self) -> Result<bool, SimpleRateLimitError> {
+        match self.exhausted.try_lock() {
+            Ok(guard) => Ok(*guard),
+            Err(_) => Err(SimpleRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for OnExhaust<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
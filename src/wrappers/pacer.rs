@@ -0,0 +1,308 @@
+use std::sync::Mutex;
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Accepts bursts by scheduling the tokens the inner limiter cannot grant yet.
+///
+/// [`try_pace_at`](Self::try_pace_at) returns a [`ReleaseSchedule`] instead of
+/// a yes/no answer, yielding `(release_tick, count)` runs that cover every
+/// requested token. Tokens the inner limiter has now are
+/// acquired immediately and released at `tick`; the rest are spread over
+/// upcoming ticks at the inner limiter's
+/// [`steady_state_allowance`](RateLimitCore::steady_state_allowance), turning
+/// the burst into a paced stream. A request is rejected only if its last
+/// release tick would lie more than `max_horizon_ticks` after `tick`.
+///
+/// Scheduled tokens are owed to the inner limiter and paid off as it refills,
+/// at the start of every later call. While tokens are owed, new requests are
+/// scheduled behind them rather than taking capacity immediately.
+///
+/// Pacing needs a known refill rate, so it is meant for token buckets and
+/// other limiters that report a steady-state allowance; with the default
+/// `(0, 1)` allowance only the immediately available tokens can be granted.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::Pacer;
+///
+/// // 2 tokens in the bucket, refilling 1 per tick
+/// let pacer = Pacer::new(TokenBucketCore::new(2, 1, 1), 10);
+/// let schedule = pacer.try_pace_at(0, 4).unwrap();
+/// assert_eq!(schedule.collect::<Vec<_>>(), vec![(0, 2), (1, 1), (2, 1)]);
+/// ```
+pub struct Pacer<L: RateLimitCore> {
+    /// Limiter whose capacity and refill rate are paced
+    inner: L,
+    /// Furthest a release tick may lie after the request's tick
+    max_horizon_ticks: Uint,
+    /// Schedule of the tokens not yet taken from the inner limiter
+    state: Mutex<PacerState>,
+}
+
+/// Internal state of the pacer
+#[derive(Debug, Default)]
+struct PacerState {
+    /// Scheduled tokens not yet acquired from the inner limiter
+    owed: Uint,
+    /// Tick the current schedule is measured from
+    origin_tick: Uint,
+    /// Tokens scheduled since `origin_tick`
+    scheduled: Uint,
+}
+
+/// Release ticks of the tokens granted by [`Pacer::try_pace_at`].
+///
+/// Iterates over `(release_tick, count)` runs in increasing tick order, the
+/// counts summing to the requested tokens. Runs are computed lazily, so a
+/// large request costs no more memory than a small one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseSchedule {
+    /// Tick of the request, at which the immediate tokens are released
+    tick: Uint,
+    /// Tokens acquired from the inner limiter right away, not yet yielded
+    immediate: Uint,
+    /// Tick the deferred tokens are scheduled from
+    origin_tick: Uint,
+    /// Pace of the deferred tokens as a `(tokens, ticks)` fraction
+    rate: (Uint, Uint),
+    /// Position in the schedule of the next deferred token, counted from 1
+    next: Uint,
+    /// Deferred tokens not yet yielded
+    deferred: Uint,
+}
+
+impl Iterator for ReleaseSchedule {
+    type Item = (Uint, Uint);
+
+    fn next(&mut self) -> Option<(Uint, Uint)> {
+        if self.immediate > 0 {
+            let run = (self.tick, self.immediate);
+            self.immediate = 0;
+            return Some(run);
+        }
+        if self.deferred == 0 {
+            return None;
+        }
+
+        let (tokens, ticks) = self.rate;
+        let offset = release_offset(self.rate, self.next);
+        // Every token scheduled up to this offset is released at the same tick
+        let through = offset.saturating_mul(tokens) / ticks;
+        let count = through.saturating_sub(self.next).saturating_add(1).min(self.deferred);
+        self.next = self.next.saturating_add(count);
+        self.deferred -= count;
+        Some((self.origin_tick.saturating_add(offset), count))
+    }
+}
+
+/// Ticks after the schedule's origin at which its `n`-th token is released.
+#[inline(always)]
+fn release_offset(rate: (Uint, Uint), n: Uint) -> Uint {
+    let (tokens, ticks) = rate;
+    let scaled = n.saturating_mul(ticks);
+    scaled / tokens + Uint::from(scaled % tokens != 0)
+}
+
+/// Why a request could not be paced
+enum Denial {
+    /// Reading the inner limiter's capacity failed
+    Read(SimpleRateLimitError),
+    /// The inner limiter denied an acquire the pacer made
+    Acquire(VerboseRateLimitError),
+    /// Tokens had to be deferred but the inner limiter reports no allowance to pace them at
+    NoRate,
+    /// The last release tick would lie beyond the horizon
+    Horizon { available: Uint, retry_after_ticks: Uint },
+}
+
+/// Returns the simple error matching a verbose one.
+fn simple(error: VerboseRateLimitError) -> SimpleRateLimitError {
+    match error {
+        VerboseRateLimitError::InsufficientCapacity { .. } => SimpleRateLimitError::InsufficientCapacity,
+        VerboseRateLimitError::BeyondCapacity { .. } => SimpleRateLimitError::BeyondCapacity,
+        VerboseRateLimitError::ExpiredTick { .. } => SimpleRateLimitError::ExpiredTick,
+        VerboseRateLimitError::ContentionFailure => SimpleRateLimitError::ContentionFailure,
+    }
+}
+
+impl<L: RateLimitCore> Pacer<L> {
+    /// Creates a new pacer with nothing scheduled.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to pace, typically a token bucket
+    /// * `max_horizon_ticks` - Furthest a release tick may lie after the request's tick;
+    ///   0 grants only the tokens available immediately
+    pub fn new(inner: L, max_horizon_ticks: Uint) -> Self {
+        Pacer {
+            inner,
+            max_horizon_ticks,
+            state: Mutex::new(PacerState::default()),
+        }
+    }
+
+    /// Pays owed tokens to the inner limiter out of what it has at `tick`,
+    /// and starts a fresh schedule once nothing is owed.
+    fn settle(&self, state: &mut PacerState, tick: Uint) -> Result<(), Denial> {
+        if state.owed > 0 {
+            let paying = self.inner.capacity_remaining(tick).map_err(Denial::Read)?.min(state.owed);
+            if paying > 0 {
+                self.inner.try_acquire_verbose_at(tick, paying).map_err(Denial::Acquire)?;
+                state.owed -= paying;
+            }
+        }
+        if state.owed == 0 {
+            state.origin_tick = tick;
+            state.scheduled = 0;
+        }
+        Ok(())
+    }
+
+    /// Shared body of the pacing entry points.
+    ///
+    /// Inner acquires are made verbose, so a denial can be returned to either
+    /// entry point with the inner limiter's own diagnostics.
+    fn pace(&self, tick: Uint, tokens: Uint) -> Result<ReleaseSchedule, Denial> {
+        if tokens == 0 {
+            return Ok(ReleaseSchedule { tick, immediate: 0, origin_tick: tick, rate: (0, 1), next: 1, deferred: 0 });
+        }
+
+        let mut state = match self.state.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => return Err(Denial::Read(SimpleRateLimitError::ContentionFailure)),
+        };
+        self.settle(&mut state, tick)?;
+
+        let immediate = if state.owed == 0 {
+            self.inner.capacity_remaining(tick).map_err(Denial::Read)?.min(tokens)
+        } else {
+            0
+        };
+        let deferred = tokens - immediate;
+
+        let rate = self.inner.steady_state_allowance();
+        if deferred > 0 {
+            if rate.0 == 0 {
+                return Err(Denial::NoRate);
+            }
+            let last = state.origin_tick.saturating_add(release_offset(rate, state.scheduled.saturating_add(deferred)));
+            let horizon = last.saturating_sub(tick);
+            if horizon > self.max_horizon_ticks {
+                return Err(Denial::Horizon {
+                    available: immediate,
+                    retry_after_ticks: horizon - self.max_horizon_ticks,
+                });
+            }
+        }
+
+        if immediate > 0 {
+            self.inner.try_acquire_verbose_at(tick, immediate).map_err(Denial::Acquire)?;
+        }
+
+        let schedule = ReleaseSchedule {
+            tick,
+            immediate,
+            origin_tick: state.origin_tick,
+            rate,
+            next: state.scheduled.saturating_add(1),
+            deferred,
+        };
+        state.owed += deferred;
+        state.scheduled += deferred;
+        Ok(schedule)
+    }
+
+    /// Schedules `tokens` and returns when they may be used.
+    ///
+    /// # Returns
+    /// * `Ok(schedule)` - `(release_tick, count)` runs covering every token;
+    ///   tokens available now are released at `tick`
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If the last release
+    ///   tick would lie more than `max_horizon_ticks` after `tick`, or tokens
+    ///   must be deferred and the inner limiter reports no steady-state allowance
+    /// * Any inner error, unchanged
+    pub fn try_pace_at(&self, tick: Uint, tokens: Uint) -> Result<ReleaseSchedule, SimpleRateLimitError> {
+        self.pace(tick, tokens).map_err(|denial| match denial {
+            Denial::Read(err) => err,
+            Denial::Acquire(err) => simple(err),
+            Denial::NoRate | Denial::Horizon { .. } => SimpleRateLimitError::InsufficientCapacity,
+        })
+    }
+
+    /// Admits `tokens` if they can be paced within the horizon, discarding the
+    /// release ticks. Use [`try_pace_at`](Self::try_pace_at) to learn them.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_pace_at(tick, tokens).map(|_| ())
+    }
+
+    /// Admits `tokens` if they can be paced within the horizon, returning
+    /// detailed diagnostics on failure.
+    ///
+    /// A horizon denial reports the tokens available immediately, also as
+    /// `available_next_refill`, and how far the schedule overshoots the horizon
+    /// as `retry_after_ticks`. Inner denials are returned unchanged. Without a
+    /// steady-state allowance nothing can be deferred, so the request is put
+    /// to the inner limiter directly and its diagnostics are returned.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        match self.pace(tick, tokens) {
+            Ok(_) => Ok(()),
+            Err(Denial::Horizon { available, retry_after_ticks }) => Err(VerboseRateLimitError::InsufficientCapacity {
+                acquiring: tokens,
+                available,
+                retry_after_ticks,
+                available_next_refill: available,
+            }),
+            Err(Denial::Acquire(err)) => Err(err),
+            Err(Denial::Read(SimpleRateLimitError::ContentionFailure)) => Err(VerboseRateLimitError::ContentionFailure),
+            // The inner limiter rejects the request again without consuming,
+            // with its own diagnostics
+            Err(Denial::Read(_)) | Err(Denial::NoRate) => self.inner.try_acquire_verbose_at(tick, tokens),
+        }
+    }
+
+    /// Returns the inner limiter's remaining capacity at `tick`, less the
+    /// tokens still owed to it.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let owed = match self.state.try_lock() {
+            Ok(state) => state.owed,
+            Err(_) => return Err(SimpleRateLimitError::ContentionFailure),
+        };
+        Ok(self.inner.capacity_remaining(tick)?.saturating_sub(owed))
+    }
+
+    /// Returns the number of scheduled tokens not yet taken from the inner limiter.
+    pub fn owed(&self) -> Result<Uint, SimpleRateLimitError> {
+        match self.state.try_lock() {
+            Ok(state) => Ok(state.owed),
+            Err(_) => Err(SimpleRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for Pacer<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
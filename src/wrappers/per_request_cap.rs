@@ -0,0 +1,118 @@
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Caps the number of tokens any single request may acquire.
+///
+/// A request for more than `max_per_request` tokens is rejected with
+/// `BeyondCapacity` before the inner limiter is consulted, regardless of how
+/// much capacity is available. Unlike the inner limiter's own `capacity` check,
+/// which bounds the whole pool, this bounds a single call, so one caller cannot
+/// drain the pool in one shot.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::PerRequestCap;
+/// use rate_guard_core::SimpleRateLimitError;
+///
+/// let limiter = PerRequestCap::new(TokenBucketCore::new(100, 10, 10), 25);
+/// assert_eq!(limiter.try_acquire_at(0, 25), Ok(()));
+/// assert_eq!(limiter.try_acquire_at(0, 26), Err(SimpleRateLimitError::BeyondCapacity));
+/// ```
+pub struct PerRequestCap<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Largest number of tokens a single request may acquire
+    max_per_request: Uint,
+}
+
+impl<L: RateLimitCore> PerRequestCap<L> {
+    /// Creates a new per-request cap wrapper.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `max_per_request` - Largest number of tokens a single request may acquire
+    ///
+    /// # Panics
+    /// Panics if `max_per_request` is 0.
+    pub fn new(inner: L, max_per_request: Uint) -> Self {
+        assert!(max_per_request > 0, "max_per_request must be greater than 0");
+        PerRequestCap { inner, max_per_request }
+    }
+
+    /// Attempts to acquire tokens, rejecting requests over the per-request cap.
+    ///
+    /// # Returns
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens > max_per_request`
+    /// * Otherwise the inner limiter's result, unchanged
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        if tokens > self.max_per_request {
+            return Err(SimpleRateLimitError::BeyondCapacity);
+        }
+        self.inner.try_acquire_at(tick, tokens)
+    }
+
+    /// Attempts to acquire tokens, rejecting requests over the per-request cap,
+    /// and returns detailed diagnostics on failure.
+    ///
+    /// A request over the cap is reported as `BeyondCapacity` with `capacity`
+    /// set to `max_per_request`.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        if tokens > self.max_per_request {
+            return Err(VerboseRateLimitError::BeyondCapacity {
+                acquiring: tokens,
+                capacity: self.max_per_request,
+            });
+        }
+        self.inner.try_acquire_verbose_at(tick, tokens)
+    }
+
+    /// Returns the remaining capacity of the inner limiter's pool.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns the largest single acquire that would succeed at `tick`, which is
+    /// never more than `max_per_request`.
+    pub fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        Ok(self.inner.max_acquirable_at(tick)?.min(self.max_per_request))
+    }
+
+    /// Returns the per-request cap.
+    pub fn max_per_request(&self) -> Uint {
+        self.max_per_request
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for PerRequestCap<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn max_acquirable_at(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.max_acquirable_at(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
@@ -0,0 +1,134 @@
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Rounds every tick down to a multiple of a granularity before delegating.
+///
+/// All ticks within one `granularity` step are passed to the inner limiter as
+/// the same tick, the start of that step. With a very fine tick source, such as
+/// nanoseconds, this keeps a sliding window from rotating buckets, and a token
+/// bucket from refilling, on every call.
+///
+/// # Precision
+///
+/// Time is only observed in whole steps: refills, window rotations and expiry
+/// happen up to `granularity - 1` ticks later than they would without
+/// quantization, so the effective limit is slightly stricter. Choose a
+/// granularity well below the limiter's refill interval or bucket size.
+/// `retry_after_ticks` and `min_acceptable_tick` in verbose errors are
+/// converted back to real ticks, rounded up to the next step boundary.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::QuantizeTick;
+///
+/// // Ticks are milliseconds, observed in 10ms steps
+/// let limiter = QuantizeTick::new(TokenBucketCore::new(1, 10, 1), 10);
+/// assert_eq!(limiter.try_acquire_at(3, 1), Ok(()));
+/// assert!(limiter.try_acquire_at(9, 1).is_err());
+/// assert_eq!(limiter.try_acquire_at(19, 1), Ok(()));
+/// assert_eq!(limiter.try_acquire_at(20, 1), Ok(()));
+/// ```
+pub struct QuantizeTick<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Size of one step, in ticks
+    granularity: Uint,
+}
+
+impl<L: RateLimitCore> QuantizeTick<L> {
+    /// Creates a new tick quantization wrapper.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `granularity` - Size of one step, in ticks
+    ///
+    /// # Panics
+    /// Panics if `granularity` is 0.
+    pub fn new(inner: L, granularity: Uint) -> Self {
+        assert!(granularity > 0, "granularity must be greater than 0");
+        QuantizeTick { inner, granularity }
+    }
+
+    /// Rounds `tick` down to a multiple of the granularity.
+    #[inline(always)]
+    pub fn quantize(&self, tick: Uint) -> Uint {
+        tick - tick % self.granularity
+    }
+
+    /// Rounds `tick` up to a multiple of the granularity, saturating at the
+    /// largest multiple representable.
+    #[inline(always)]
+    fn round_up(&self, tick: Uint) -> Uint {
+        match tick % self.granularity {
+            0 => tick,
+            rem => tick.checked_add(self.granularity - rem).unwrap_or_else(|| self.quantize(tick)),
+        }
+    }
+
+    /// Attempts to acquire tokens at the quantized tick.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.inner.try_acquire_at(self.quantize(tick), tokens)
+    }
+
+    /// Attempts to acquire tokens at the quantized tick, returning detailed
+    /// diagnostics on failure.
+    ///
+    /// Tick values in the error are converted back to real ticks.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let quantized = self.quantize(tick);
+        self.inner.try_acquire_verbose_at(quantized, tokens).map_err(|e| match e {
+            VerboseRateLimitError::InsufficientCapacity { acquiring, available, retry_after_ticks, available_next_refill } => {
+                let retry_tick = self.round_up(quantized.saturating_add(retry_after_ticks));
+                VerboseRateLimitError::InsufficientCapacity {
+                    acquiring,
+                    available,
+                    retry_after_ticks: retry_tick.saturating_sub(tick),
+                    available_next_refill,
+                }
+            }
+            VerboseRateLimitError::ExpiredTick { min_acceptable_tick } => VerboseRateLimitError::ExpiredTick {
+                min_acceptable_tick: self.round_up(min_acceptable_tick),
+            },
+            other => other,
+        })
+    }
+
+    /// Returns the remaining capacity of the inner limiter at the quantized tick.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(self.quantize(tick))
+    }
+
+    /// Returns the step size, in ticks.
+    pub fn granularity(&self) -> Uint {
+        self.granularity
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for QuantizeTick<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
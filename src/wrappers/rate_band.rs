@@ -0,0 +1,120 @@
+use crate::rate_limit::RateLimitCore;
+use crate::types::AtomicUint;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Caps the rate with an inner limiter and flags when traffic drops below a floor.
+///
+/// Admission is decided by the inner limiter alone, which enforces the maximum
+/// rate. On top of that the wrapper remembers the latest tick at which a
+/// request was admitted, and [`is_below_floor`](Self::is_below_floor) reports
+/// whether more than `floor_interval_ticks` have passed since then. The floor
+/// is an observability signal for traffic that must keep flowing, such as
+/// keep-alives; it never admits or denies anything.
+///
+/// Floor tracking starts at tick 0, as if a request had been admitted then.
+/// Recording an admission is a single atomic `fetch_max`, so it never blocks
+/// the acquire path; with `tick-u128`, which has no stable 128-bit atomic, it
+/// retries a `try_lock` on contention instead.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::RateBand;
+///
+/// let limiter = RateBand::new(TokenBucketCore::new(10, 1, 1), 5);
+/// assert_eq!(limiter.try_acquire_at(2, 1), Ok(()));
+/// assert!(!limiter.is_below_floor(7));
+/// assert!(limiter.is_below_floor(8));
+/// ```
+pub struct RateBand<L: RateLimitCore> {
+    /// Limiter enforcing the maximum rate
+    inner: L,
+    /// Longest gap between admissions before the floor is breached
+    floor_interval_ticks: Uint,
+    /// Latest tick at which a request was admitted
+    last_admitted_tick: AtomicUint,
+}
+
+impl<L: RateLimitCore> RateBand<L> {
+    /// Creates a new rate band around `inner`.
+    ///
+    /// # Panics
+    /// Panics if `floor_interval_ticks` is 0.
+    pub fn new(inner: L, floor_interval_ticks: Uint) -> Self {
+        assert!(floor_interval_ticks > 0, "floor_interval_ticks must be greater than 0");
+        RateBand {
+            inner,
+            floor_interval_ticks,
+            last_admitted_tick: AtomicUint::new(0),
+        }
+    }
+
+    /// Attempts to acquire tokens from the inner limiter, recording the tick
+    /// if the request is admitted.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let result = self.inner.try_acquire_at(tick, tokens);
+        if result.is_ok() {
+            self.last_admitted_tick.fetch_max(tick);
+        }
+        result
+    }
+
+    /// Attempts to acquire tokens from the inner limiter with detailed
+    /// diagnostics, recording the tick if the request is admitted.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let result = self.inner.try_acquire_verbose_at(tick, tokens);
+        if result.is_ok() {
+            self.last_admitted_tick.fetch_max(tick);
+        }
+        result
+    }
+
+    /// Returns the inner limiter's remaining capacity at `tick`.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.inner.capacity_remaining(tick)
+    }
+
+    /// Returns true if no request has been admitted in the
+    /// `floor_interval_ticks` ticks up to and including `tick`.
+    pub fn is_below_floor(&self, tick: Uint) -> bool {
+        tick.saturating_sub(self.last_admitted_tick()) > self.floor_interval_ticks
+    }
+
+    /// Returns the latest tick at which a request was admitted, or 0 if none.
+    pub fn last_admitted_tick(&self) -> Uint {
+        self.last_admitted_tick.load()
+    }
+
+    /// Returns the longest gap between admissions before the floor is breached.
+    pub fn floor_interval_ticks(&self) -> Uint {
+        self.floor_interval_ticks
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for RateBand<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::{Mutex, PoisonError};
+use crate::rate_limit::{RateLimitCore, Reconfigure};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Holds a limiter whose configuration can be replaced at runtime.
+///
+/// [`reload`](Self::reload) rebuilds the limiter with new parameters through
+/// [`Reconfigure`], migrating its state (for example, clamping a token
+/// bucket's available tokens to a smaller capacity), and swaps it in under one
+/// lock. Acquires always see either the old limiter or the new one, never a
+/// mix, and usage recorded before the reload keeps counting after it.
+///
+/// Acquires take the lock with `try_lock` like the cores and may return
+/// `ContentionFailure`, including while a reload is in progress. A reload
+/// waits for the lock, so a configuration change is never dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::{TokenBucketCore, TokenBucketCoreConfig};
+/// use rate_guard_core::wrappers::Reloadable;
+///
+/// let limiter = Reloadable::new(TokenBucketCore::new(100, 10, 10));
+/// assert_eq!(limiter.try_acquire_at(0, 70), Ok(()));
+///
+/// limiter.reload(TokenBucketCoreConfig::new(20, 10, 2), 0).unwrap();
+/// assert_eq!(limiter.capacity_remaining(0), Ok(20));
+/// ```
+pub struct Reloadable<L: Reconfigure> {
+    /// Limiter built from the current configuration
+    inner: Mutex<L>,
+}
+
+impl<L: Reconfigure> Reloadable<L> {
+    /// Creates a new reloadable wrapper around `inner`.
+    pub fn new(inner: L) -> Self {
+        Reloadable { inner: Mutex::new(inner) }
+    }
+
+    /// Replaces the configuration, migrating the limiter's state as of `tick`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the new limiter is in place
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If `tick` is older than the limiter state;
+    ///   the old configuration stays active
+    ///
+    /// # Panics
+    /// Panics if `config` is invalid, as the core's `From<Config>` conversion does.
+    pub fn reload(&self, config: L::Config, tick: Uint) -> SimpleRateLimitResult {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        *inner = inner.reconfigured(config, tick)?;
+        Ok(())
+    }
+
+    /// Attempts to acquire tokens from the current limiter.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        match self.inner.try_lock() {
+            Ok(inner) => inner.try_acquire_at(tick, tokens),
+            Err(_) => Err(SimpleRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Attempts to acquire tokens from the current limiter, returning detailed
+    /// diagnostics on failure.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        match self.inner.try_lock() {
+            Ok(inner) => inner.try_acquire_verbose_at(tick, tokens),
+            Err(_) => Err(VerboseRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Returns the current limiter's remaining capacity at `tick`.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        match self.inner.try_lock() {
+            Ok(inner) => inner.capacity_remaining(tick),
+            Err(_) => Err(SimpleRateLimitError::ContentionFailure),
+        }
+    }
+
+    /// Calls `f` with the current limiter, waiting for the lock.
+    pub fn with_inner<R>(&self, f: impl FnOnce(&L) -> R) -> R {
+        f(&self.inner.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Consumes the wrapper and returns the current limiter.
+    pub fn into_inner(self) -> L {
+        self.inner.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<L: Reconfigure> RateLimitCore for Reloadable<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.with_inner(|inner| inner.steady_state_allowance())
+    }
+}
@@ -0,0 +1,114 @@
+use std::hint;
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Retries the inner limiter a bounded number of times on `ContentionFailure`.
+///
+/// The mutex-backed cores never block: a call that finds the state locked fails
+/// immediately with `ContentionFailure`. Under short bursts of contention a few
+/// immediate retries almost always succeed, so this wrapper retries such calls
+/// up to `max_spins` times, calling [`std::hint::spin_loop`] between attempts.
+/// Every other result is returned unchanged, and if all retries are contended
+/// the final `ContentionFailure` is returned, so the wrapper stays non-blocking.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::TokenBucketCore;
+/// use rate_guard_core::wrappers::RetryContention;
+///
+/// let limiter = RetryContention::new(TokenBucketCore::new(100, 10, 5), 64);
+/// assert_eq!(limiter.try_acquire_at(0, 10), Ok(()));
+/// ```
+pub struct RetryContention<L: RateLimitCore> {
+    /// Limiter making the actual admission decision
+    inner: L,
+    /// Number of retries after a contended first attempt
+    max_spins: u32,
+}
+
+impl<L: RateLimitCore> RetryContention<L> {
+    /// Creates a new contention retry wrapper.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to
+    /// * `max_spins` - Retries after a contended first attempt; 0 disables retrying
+    pub fn new(inner: L, max_spins: u32) -> Self {
+        RetryContention { inner, max_spins }
+    }
+
+    /// Attempts to acquire tokens, retrying while the inner limiter is contended.
+    ///
+    /// # Returns
+    /// The first result that is not `ContentionFailure`, or `ContentionFailure`
+    /// if every attempt was contended.
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let mut spins = 0;
+        loop {
+            match self.inner.try_acquire_at(tick, tokens) {
+                Err(SimpleRateLimitError::ContentionFailure) if spins < self.max_spins => {
+                    spins += 1;
+                    hint::spin_loop();
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Attempts to acquire tokens with detailed diagnostics, retrying while the
+    /// inner limiter is contended.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let mut spins = 0;
+        loop {
+            match self.inner.try_acquire_verbose_at(tick, tokens) {
+                Err(VerboseRateLimitError::ContentionFailure) if spins < self.max_spins => {
+                    spins += 1;
+                    hint::spin_loop();
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns the remaining capacity of the inner limiter, retrying while it
+    /// is contended.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let mut spins = 0;
+        loop {
+            match self.inner.capacity_remaining(tick) {
+                Err(SimpleRateLimitError::ContentionFailure) if spins < self.max_spins => {
+                    spins += 1;
+                    hint::spin_loop();
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for RetryContention<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.inner.steady_state_allowance()
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use crate::rate_limit::RateLimitCore;
+use crate::wrappers::ceiling::{assert_inner_capacity, Ceiling};
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitResult};
+
+/// Enforces a per-replica share of a global limit, following a live replica count.
+///
+/// In an autoscaling fleet each replica should admit `global_capacity / replica_count`
+/// tokens. The replica count is shared through an `Arc<AtomicUsize>` that the
+/// caller updates as replicas come and go; every call reads it afresh, so the
+/// per-replica ceiling follows scaling events without rebuilding the limiter.
+/// A replica count of 0 is treated as 1.
+///
+/// The inner limiter does the token accounting and must be configured with
+/// `global_capacity`; this wrapper derives usage from its `capacity_remaining`
+/// and denies requests that would push usage above the current ceiling. A
+/// scale-out that lowers the ceiling below the tokens already in use denies
+/// everything until usage drains below it.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::wrappers::ScaledByReplicas;
+///
+/// let replicas = Arc::new(AtomicUsize::new(4));
+/// let limiter = ScaledByReplicas::new(FixedWindowCounterCore::new(100, 60), 100, replicas.clone());
+/// assert_eq!(limiter.try_acquire_at(0, 25), Ok(()));
+/// assert!(limiter.try_acquire_at(0, 1).is_err());
+///
+/// // Scaling in raises this replica's share
+/// replicas.store(2, Ordering::Relaxed);
+/// assert_eq!(limiter.try_acquire_at(0, 25), Ok(()));
+/// ```
+pub struct ScaledByReplicas<L: RateLimitCore> {
+    /// Limiter doing the token accounting, configured with `global_capacity`
+    inner: L,
+    /// Capacity shared by the whole fleet
+    global_capacity: Uint,
+    /// Current number of replicas, updated by the caller
+    replica_count: Arc<AtomicUsize>,
+}
+
+impl<L: RateLimitCore> ScaledByReplicas<L> {
+    /// Creates a new replica-scaled wrapper.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter to delegate decisions to, configured with `global_capacity`
+    /// * `global_capacity` - Capacity shared by the whole fleet
+    /// * `replica_count` - Live replica count, shared with whatever tracks scaling
+    ///
+    /// # Panics
+    /// Panics if `global_capacity` is 0 or `inner` reports more than
+    /// `global_capacity` tokens at tick 0.
+    pub fn new(inner: L, global_capacity: Uint, replica_count: Arc<AtomicUsize>) -> Self {
+        assert!(global_capacity > 0, "global_capacity must be greater than 0");
+        assert_inner_capacity(&inner, global_capacity, "global_capacity must not be below the inner limiter's capacity");
+        ScaledByReplicas { inner, global_capacity, replica_count }
+    }
+
+    /// Returns the current per-replica ceiling, `global_capacity / replica_count`.
+    pub fn per_replica_capacity(&self) -> Uint {
+        let replicas = self.replica_count.load(Ordering::Relaxed).max(1) as Uint;
+        self.global_capacity / replicas
+    }
+
+    /// Returns the ceiling for the current replica count.
+    #[inline(always)]
+    fn bound(&self) -> Ceiling {
+        Ceiling { capacity: self.global_capacity, ceiling: self.per_replica_capacity() }
+    }
+
+    /// Attempts to acquire tokens within the current per-replica ceiling.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the ceiling and the inner limiter both admit the request
+    /// * `Err(SimpleRateLimitError::InsufficientCapacity)` - If the ceiling would be exceeded
+    /// * `Err(SimpleRateLimitError::BeyondCapacity)` - If `tokens` exceeds the current ceiling
+    /// * Any inner error, unchanged
+    pub fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.bound().try_acquire_at(&self.inner, tick, tokens)
+    }
+
+    /// Attempts to acquire tokens within the current per-replica ceiling,
+    /// returning detailed diagnostics on failure.
+    ///
+    /// A denial reports the tokens left under the ceiling as `available`, and
+    /// the inner limiter's wait until usage drains below the ceiling as
+    /// `retry_after_ticks`, assuming the replica count does not change.
+    pub fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.bound().try_acquire_verbose_at(&self.inner, tick, tokens)
+    }
+
+    /// Returns the number of tokens acquirable under the current per-replica ceiling.
+    pub fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.bound().capacity_remaining(&self.inner, tick)
+    }
+
+    /// Returns the inner limiter's steady-state allowance scaled to the current
+    /// per-replica share.
+    ///
+    /// Exact for window limiters; a lower bound for token buckets, which keep
+    /// refilling at their full rate under the ceiling.
+    pub fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.bound().steady_state_allowance(&self.inner)
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<L: RateLimitCore> RateLimitCore for ScaledByReplicas<L> {
+    #[inline(always)]
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.try_acquire_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.try_acquire_verbose_at(tick, tokens)
+    }
+
+    #[inline(always)]
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        self.capacity_remaining(tick)
+    }
+
+    #[inline(always)]
+    fn steady_state_allowance(&self) -> (Uint, Uint) {
+        self.steady_state_allowance()
+    }
+}
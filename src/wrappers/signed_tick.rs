@@ -0,0 +1,97 @@
+use crate::rate_limit::RateLimitCore;
+use crate::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Accepts signed `i64` ticks and translates them into the unsigned tick domain.
+///
+/// The adapter holds a signed `base`; a signed tick `t` maps to the unsigned
+/// tick `t - base`. Ticks before `base` cannot be represented and are rejected
+/// with `ExpiredTick`, the same error a core reports for a tick older than its
+/// state. Choosing `base` at or below the earliest expected timestamp keeps all
+/// valid events representable, including negative ones.
+///
+/// # Example
+///
+/// ```rust
+/// use rate_guard_core::cores::FixedWindowCounterCore;
+/// use rate_guard_core::wrappers::SignedTickAdapter;
+/// use rate_guard_core::SimpleRateLimitError;
+///
+/// let limiter = SignedTickAdapter::new(FixedWindowCounterCore::new(10, 100), -1_000);
+/// assert_eq!(limiter.to_unsigned(-1_000), Some(0));
+/// assert_eq!(limiter.try_acquire_at_signed(-500, 1), Ok(()));
+/// assert_eq!(limiter.try_acquire_at_signed(-1_001, 1), Err(SimpleRateLimitError::ExpiredTick));
+/// ```
+pub struct SignedTickAdapter<L: RateLimitCore> {
+    /// Limiter receiving the translated ticks
+    inner: L,
+    /// Signed tick that maps to unsigned tick 0
+    base: i64,
+}
+
+impl<L: RateLimitCore> SignedTickAdapter<L> {
+    /// Creates a new adapter.
+    ///
+    /// # Parameters
+    /// * `inner` - Limiter operating on unsigned ticks
+    /// * `base` - Signed tick that maps to unsigned tick 0
+    pub fn new(inner: L, base: i64) -> Self {
+        SignedTickAdapter { inner, base }
+    }
+
+    /// Translates a signed tick into the inner limiter's tick domain.
+    ///
+    /// # Returns
+    /// * `Some(tick)` - `signed_tick - base`
+    /// * `None` - If `signed_tick` is before `base`
+    #[inline(always)]
+    pub fn to_unsigned(&self, signed_tick: i64) -> Option<Uint> {
+        // i128 cannot overflow for the difference of two i64 values
+        let offset = signed_tick as i128 - self.base as i128;
+        if offset < 0 {
+            None
+        } else {
+            Some(offset as Uint)
+        }
+    }
+
+    /// Attempts to acquire tokens at a signed tick.
+    ///
+    /// # Returns
+    /// * `Err(SimpleRateLimitError::ExpiredTick)` - If `signed_tick` is before `base`
+    /// * Otherwise the inner limiter's result at the translated tick
+    pub fn try_acquire_at_signed(&self, signed_tick: i64, tokens: Uint) -> SimpleRateLimitResult {
+        match self.to_unsigned(signed_tick) {
+            Some(tick) => self.inner.try_acquire_at(tick, tokens),
+            None => Err(SimpleRateLimitError::ExpiredTick),
+        }
+    }
+
+    /// Attempts to acquire tokens at a signed tick, returning detailed diagnostics on failure.
+    ///
+    /// A tick before `base` is reported as `ExpiredTick { min_acceptable_tick: 0 }`,
+    /// i.e. in the inner limiter's unsigned tick domain.
+    pub fn try_acquire_verbose_at_signed(&self, signed_tick: i64, tokens: Uint) -> VerboseRateLimitResult {
+        match self.to_unsigned(signed_tick) {
+            Some(tick) => self.inner.try_acquire_verbose_at(tick, tokens),
+            None => Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 0 }),
+        }
+    }
+
+    /// Returns the remaining capacity at a signed tick.
+    pub fn capacity_remaining_signed(&self, signed_tick: i64) -> Result<Uint, SimpleRateLimitError> {
+        match self.to_unsigned(signed_tick) {
+            Some(tick) => self.inner.capacity_remaining(tick),
+            None => Err(SimpleRateLimitError::ExpiredTick),
+        }
+    }
+
+    /// Returns the signed tick that maps to unsigned tick 0.
+    pub fn base(&self) -> i64 {
+        self.base
+    }
+
+    /// Returns a reference to the inner limiter.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+}
@@ -0,0 +1,55 @@
+use rate_guard_core::combinators::AbTest;
+use rate_guard_core::cores::TokenBucketCore;
+use rate_guard_core::SimpleRateLimitError;
+
+#[test]
+fn test_mixed_workload_tallies_disagreements() {
+    // Enforced bucket holds 10 tokens, the candidate only 6; both refill 1 token every 10 ticks
+    let limiter = AbTest::new(TokenBucketCore::new(10, 10, 1), TokenBucketCore::new(6, 10, 1));
+
+    // Both admit
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    // Enforced admits (6 left -> 3), shadow denies (2 left)
+    assert_eq!(limiter.try_acquire_at(0, 3), Ok(()));
+    // Enforced admits (3 -> 1), shadow admits (2 -> 0)
+    assert_eq!(limiter.try_acquire_at(0, 2), Ok(()));
+    // Both deny
+    assert_eq!(limiter.try_acquire_at(0, 2), Err(SimpleRateLimitError::InsufficientCapacity));
+    // Request above the shadow capacity: enforced admits later, shadow always denies
+    assert_eq!(limiter.try_acquire_at(100, 8), Ok(()));
+
+    assert_eq!(limiter.agreements(), 3);
+    assert_eq!(limiter.shadow_only_denied(), 2);
+    assert_eq!(limiter.enforced_only_denied(), 0);
+}
+
+#[test]
+fn test_enforced_only_denials_when_shadow_is_larger() {
+    let limiter = AbTest::new(TokenBucketCore::new(5, 10, 1), TokenBucketCore::new(20, 10, 1));
+    for _ in 0..4 {
+        let _ = limiter.try_acquire_at(0, 3);
+    }
+
+    // Enforced admits one 3-token request, the shadow admits all four
+    assert_eq!(limiter.agreements(), 1);
+    assert_eq!(limiter.enforced_only_denied(), 3);
+    assert_eq!(limiter.shadow_only_denied(), 0);
+    assert_eq!(limiter.capacity_remaining(0), Ok(2));
+    assert_eq!(limiter.shadow().capacity_remaining(0), Ok(8));
+}
+
+#[test]
+fn test_verbose_path_returns_enforced_decision_and_tallies() {
+    let limiter = AbTest::new(TokenBucketCore::new(10, 10, 1), TokenBucketCore::new(2, 10, 1));
+    assert_eq!(limiter.try_acquire_verbose_at(0, 5), Ok(()));
+    assert_eq!(limiter.shadow_only_denied(), 1);
+}
+
+#[test]
+fn test_expired_tick_is_not_tallied() {
+    let limiter = AbTest::new(TokenBucketCore::new(10, 10, 1), TokenBucketCore::new(6, 10, 1));
+    limiter.try_acquire_at(50, 1).unwrap();
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(limiter.agreements(), 1);
+    assert_eq!(limiter.shadow_only_denied() + limiter.enforced_only_denied(), 0);
+}
@@ -0,0 +1,72 @@
+use rate_guard_core::SimpleRateLimitError;
+use rate_guard_core::cores::{TokenBucketCore, TokenBucketCoreConfig};
+
+#[test]
+fn test_long_idle_with_and_without_cap() {
+    let uncapped = TokenBucketCore::new(100, 10, 10);
+    let capped = TokenBucketCore::new_with_accrual_cap(100, 10, 10, Some(30));
+    assert_eq!(uncapped.try_acquire_at(0, 100), Ok(()));
+    assert_eq!(capped.try_acquire_at(0, 100), Ok(()));
+
+    // 100 idle intervals: the plain bucket is full again, the capped one gained 30
+    assert_eq!(uncapped.capacity_remaining(1000), Ok(100));
+    assert_eq!(capped.capacity_remaining(1000), Ok(30));
+    assert_eq!(uncapped.try_acquire_at(1000, 100), Ok(()));
+    assert_eq!(capped.try_acquire_at(1000, 100), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(capped.try_acquire_at(1000, 30), Ok(()));
+}
+
+#[test]
+fn test_cap_applies_per_refill() {
+    let bucket = TokenBucketCore::new_with_accrual_cap(100, 10, 10, Some(30));
+    assert_eq!(bucket.try_acquire_at(0, 100), Ok(()));
+
+    // Each operation after an idle period adds at most 30
+    assert_eq!(bucket.try_acquire_at(1000, 1), Ok(()));
+    assert_eq!(bucket.capacity_remaining(1000), Ok(29));
+    assert_eq!(bucket.try_acquire_at(2000, 1), Ok(()));
+    assert_eq!(bucket.capacity_remaining(2000), Ok(58));
+    assert_eq!(bucket.try_acquire_at(3000, 1), Ok(()));
+    assert_eq!(bucket.try_acquire_at(4000, 1), Ok(()));
+    assert_eq!(bucket.capacity_remaining(4000), Ok(99));
+    assert_eq!(bucket.capacity_remaining(5000), Ok(100));
+}
+
+#[test]
+fn test_steady_traffic_is_unaffected() {
+    let uncapped = TokenBucketCore::new(50, 10, 10);
+    let capped = TokenBucketCore::new_with_accrual_cap(50, 10, 10, Some(20));
+    assert_eq!(uncapped.try_acquire_at(0, 50), Ok(()));
+    assert_eq!(capped.try_acquire_at(0, 50), Ok(()));
+    for tick in (10..200).step_by(10) {
+        assert_eq!(capped.try_acquire_at(tick, 10), uncapped.try_acquire_at(tick, 10));
+        assert_eq!(capped.capacity_remaining(tick), uncapped.capacity_remaining(tick));
+    }
+}
+
+#[test]
+fn test_config_accrual_cap() {
+    let bucket: TokenBucketCore = TokenBucketCoreConfig {
+        capacity: 100,
+        refill_interval: 10,
+        refill_amount: 10,
+        accrual_cap: Some(25),
+    }
+    .into();
+    assert_eq!(bucket.try_acquire_at(0, 100), Ok(()));
+    assert_eq!(bucket.capacity_remaining(10_000), Ok(25));
+
+    let built: TokenBucketCore = TokenBucketCoreConfig::new(100, 10, 10).with_accrual_cap(Some(25)).into();
+    assert_eq!(built.try_acquire_at(0, 100), Ok(()));
+    assert_eq!(built.capacity_remaining(10_000), Ok(25));
+
+    let plain: TokenBucketCore = TokenBucketCoreConfig::new(100, 10, 10).into();
+    assert_eq!(plain.try_acquire_at(0, 100), Ok(()));
+    assert_eq!(plain.capacity_remaining(10_000), Ok(100));
+}
+
+#[test]
+#[should_panic(expected = "accrual_cap must be greater than 0")]
+fn test_zero_accrual_cap_panics() {
+    TokenBucketCore::new_with_accrual_cap(100, 10, 10, Some(0));
+}
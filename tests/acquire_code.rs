@@ -0,0 +1,83 @@
+use rate_guard_core::{AcquireCode, SimpleRateLimitError, Uint};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+
+#[test]
+fn test_code_discriminants() {
+    assert_eq!(AcquireCode::Allowed as u8, 0);
+    assert_eq!(AcquireCode::InsufficientCapacity as u8, 1);
+    assert_eq!(AcquireCode::BeyondCapacity as u8, 2);
+    assert_eq!(AcquireCode::Contention as u8, 3);
+    assert_eq!(AcquireCode::ExpiredTick as u8, 4);
+}
+
+#[test]
+fn test_code_from_result() {
+    assert_eq!(AcquireCode::from(Ok(())), AcquireCode::Allowed);
+    assert_eq!(AcquireCode::from(Err(SimpleRateLimitError::InsufficientCapacity)), AcquireCode::InsufficientCapacity);
+    assert_eq!(AcquireCode::from(Err(SimpleRateLimitError::BeyondCapacity)), AcquireCode::BeyondCapacity);
+    assert_eq!(AcquireCode::from(Err(SimpleRateLimitError::ContentionFailure)), AcquireCode::Contention);
+    assert_eq!(AcquireCode::from(Err(SimpleRateLimitError::ExpiredTick)), AcquireCode::ExpiredTick);
+}
+
+#[test]
+fn test_token_bucket_codes() {
+    let bucket = TokenBucketCore::new(10, 10, 1);
+    assert_eq!(bucket.try_acquire_code_at(100, 10), AcquireCode::Allowed);
+    assert_eq!(bucket.try_acquire_code_at(100, 1), AcquireCode::InsufficientCapacity);
+    assert_eq!(bucket.try_acquire_code_at(100, 11), AcquireCode::BeyondCapacity);
+    assert_eq!(bucket.try_acquire_code_at(50, 1), AcquireCode::ExpiredTick);
+}
+
+#[test]
+fn test_fixed_window_codes() {
+    let counter = FixedWindowCounterCore::new(10, 10);
+    assert_eq!(counter.try_acquire_code_at(100, 10), AcquireCode::Allowed);
+    assert_eq!(counter.try_acquire_code_at(100, 1), AcquireCode::InsufficientCapacity);
+    assert_eq!(counter.try_acquire_code_at(100, 11), AcquireCode::BeyondCapacity);
+    assert_eq!(counter.try_acquire_code_at(50, 1), AcquireCode::ExpiredTick);
+}
+
+#[test]
+fn test_sliding_window_codes() {
+    let counter = SlidingWindowCounterCore::new(10, 5, 2);
+    assert_eq!(counter.try_acquire_code_at(100, 10), AcquireCode::Allowed);
+    assert_eq!(counter.try_acquire_code_at(100, 1), AcquireCode::InsufficientCapacity);
+    assert_eq!(counter.try_acquire_code_at(100, 11), AcquireCode::BeyondCapacity);
+    assert_eq!(counter.try_acquire_code_at(50, 1), AcquireCode::ExpiredTick);
+}
+
+#[test]
+fn test_approximate_sliding_window_codes() {
+    let counter = ApproximateSlidingWindowCore::new(10, 10);
+    assert_eq!(counter.try_acquire_code_at(100, 10), AcquireCode::Allowed);
+    assert_eq!(counter.try_acquire_code_at(100, 1), AcquireCode::InsufficientCapacity);
+    assert_eq!(counter.try_acquire_code_at(100, 11), AcquireCode::BeyondCapacity);
+    assert_eq!(counter.try_acquire_code_at(50, 1), AcquireCode::ExpiredTick);
+}
+
+#[test]
+fn test_ffi_token_bucket_roundtrip() {
+    use rate_guard_core::ffi::*;
+
+    let capacity: Uint = 5;
+    let bucket = rate_guard_token_bucket_new(capacity, 10, 1);
+    assert!(!bucket.is_null());
+    unsafe {
+        assert_eq!(rate_guard_token_bucket_try_acquire_at(bucket, 0, 5), AcquireCode::Allowed as u8);
+        assert_eq!(rate_guard_token_bucket_try_acquire_at(bucket, 0, 1), AcquireCode::InsufficientCapacity as u8);
+        assert_eq!(rate_guard_token_bucket_try_acquire_at(bucket, 0, 6), AcquireCode::BeyondCapacity as u8);
+        assert_eq!(rate_guard_token_bucket_try_acquire_at(bucket, 10, 1), AcquireCode::Allowed as u8);
+        assert_eq!(rate_guard_token_bucket_try_acquire_at(bucket, 0, 1), AcquireCode::ExpiredTick as u8);
+        rate_guard_token_bucket_free(bucket);
+    }
+}
+
+#[test]
+fn test_ffi_rejects_zero_parameters() {
+    use rate_guard_core::ffi::*;
+
+    assert!(rate_guard_token_bucket_new(0, 10, 1).is_null());
+    unsafe { rate_guard_token_bucket_free(std::ptr::null_mut()) };
+}
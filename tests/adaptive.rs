@@ -0,0 +1,103 @@
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::wrappers::Adaptive;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+#[test]
+fn test_failures_decrease_limit() {
+    let limiter = Adaptive::new(FixedWindowCounterCore::new(64, 10), 4, 64);
+    assert_eq!(limiter.limit(), 64);
+
+    let mut previous = limiter.limit();
+    for _ in 0..3 {
+        limiter.report_outcome(false);
+        assert!(limiter.limit() < previous);
+        previous = limiter.limit();
+    }
+    assert_eq!(limiter.limit(), 8);
+}
+
+#[test]
+fn test_limit_never_below_min() {
+    let limiter = Adaptive::new(FixedWindowCounterCore::new(64, 10), 4, 64);
+    for _ in 0..20 {
+        limiter.report_outcome(false);
+    }
+    assert_eq!(limiter.limit(), 4);
+}
+
+#[test]
+fn test_successes_restore_limit() {
+    let limiter = Adaptive::new(FixedWindowCounterCore::new(20, 10), 2, 20);
+    limiter.report_outcome(false);
+    limiter.report_outcome(false);
+    assert_eq!(limiter.limit(), 5);
+
+    for _ in 0..5 {
+        limiter.report_outcome(true);
+    }
+    assert_eq!(limiter.limit(), 10);
+
+    for _ in 0..50 {
+        limiter.report_outcome(true);
+    }
+    assert_eq!(limiter.limit(), 20);
+}
+
+#[test]
+fn test_acquire_enforces_adaptive_limit() {
+    let limiter = Adaptive::new(FixedWindowCounterCore::new(100, 10), 10, 100);
+    limiter.report_outcome(false);
+    limiter.report_outcome(false);
+    assert_eq!(limiter.limit(), 25);
+
+    assert_eq!(limiter.try_acquire_at(0, 20), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(5));
+    assert_eq!(limiter.try_acquire_at(0, 6), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 6),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 6, available: 5, retry_after_ticks: 10, available_next_refill: 25 })
+    );
+
+    // Growing the limit frees capacity within the same window
+    limiter.report_outcome(true);
+    assert_eq!(limiter.try_acquire_at(0, 6), Ok(()));
+}
+
+#[test]
+fn test_inner_limit_still_applies() {
+    let limiter = Adaptive::new(TokenBucketCore::new(10, 100, 1), 1, 10);
+    assert_eq!(limiter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(1, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+}
+
+#[test]
+fn test_request_above_current_limit_is_beyond_capacity() {
+    let limiter = Adaptive::new(FixedWindowCounterCore::new(100, 10), 10, 100);
+    limiter.report_outcome(false);
+    assert_eq!(limiter.try_acquire_at(0, 51), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 51),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 51, capacity: 50 })
+    );
+}
+
+#[test]
+fn test_allowance_follows_the_limit() {
+    let limiter = Adaptive::new(FixedWindowCounterCore::new(100, 10), 10, 100);
+    assert_eq!(limiter.steady_state_allowance(), (10, 1));
+    limiter.report_outcome(false);
+    assert_eq!(limiter.steady_state_allowance(), (5, 1));
+}
+
+#[test]
+#[should_panic(expected = "max_limit must not be below the inner limiter's capacity")]
+fn test_max_limit_below_inner_capacity_panics() {
+    let _ = Adaptive::new(FixedWindowCounterCore::new(100, 10), 10, 50);
+}
+
+#[test]
+#[should_panic(expected = "min_limit must not exceed max_limit")]
+fn test_min_above_max_panics() {
+    let _ = Adaptive::new(FixedWindowCounterCore::new(10, 10), 11, 10);
+}
@@ -0,0 +1,194 @@
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use rate_guard_core::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+use rate_guard_core::combinators::{AllOf, Labeled};
+use rate_guard_core::cores::{FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::{RateLimitCore, RefundableCore};
+
+// "burst": 5 tokens, 1 refilled every 10 ticks
+// "quota": 8 tokens per window of 1000 ticks
+fn create_limiter() -> AllOf {
+    AllOf::new(vec![
+        Labeled::new("burst", TokenBucketCore::new(5, 10, 1)),
+        Labeled::new("quota", FixedWindowCounterCore::new(8, 1000)),
+    ])
+}
+
+#[test]
+fn test_all_children_admit() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_labeled_at(0, 3), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(2));
+}
+
+#[test]
+fn test_burst_is_binding() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_labeled_at(0, 5), Ok(()));
+
+    let err = limiter.try_acquire_labeled_at(0, 1).unwrap_err();
+    assert_eq!(err.label, "burst");
+    assert_eq!(err.retry_after_ticks(), Some(10));
+}
+
+#[test]
+fn test_quota_is_binding_and_burst_is_rolled_back() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_labeled_at(0, 5), Ok(()));
+    // Burst refills 3 by tick 30, but only 3 quota tokens remain
+    assert_eq!(limiter.try_acquire_labeled_at(30, 3), Ok(()));
+    assert_eq!(limiter.children()[0].core().capacity_remaining(100), Ok(5));
+
+    let err = limiter.try_acquire_labeled_at(100, 1).unwrap_err();
+    assert_eq!(err.label, "quota");
+    assert_eq!(err.retry_after_ticks(), Some(900));
+    assert_eq!(
+        err.error,
+        VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 900, available_next_refill: 8 }
+    );
+
+    // The burst tokens taken before the quota denied were given back
+    assert_eq!(limiter.children()[0].core().capacity_remaining(100), Ok(5));
+}
+
+#[test]
+fn test_label_is_surfaced_for_other_errors() {
+    let limiter = AllOf::new(vec![
+        Labeled::new("small", FixedWindowCounterCore::new(10, 100)),
+        Labeled::new("tiny", SlidingWindowCounterCore::new(3, 10, 10)),
+    ]);
+    let err = limiter.try_acquire_labeled_at(0, 4).unwrap_err();
+    assert_eq!(err.label, "tiny");
+    assert_eq!(err.error, VerboseRateLimitError::BeyondCapacity { acquiring: 4, capacity: 3 });
+    assert_eq!(err.retry_after_ticks(), None);
+    assert_eq!(limiter.children()[0].core().capacity_remaining(0), Ok(10));
+}
+
+#[test]
+fn test_simple_and_trait_paths() {
+    let limiter: Box<dyn RateLimitCore> = Box::new(create_limiter());
+    assert_eq!(limiter.try_acquire_at(0, 5), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert!(matches!(
+        limiter.try_acquire_verbose_at(0, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { .. })
+    ));
+}
+
+#[test]
+fn test_labeled_error_display() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_labeled_at(0, 5), Ok(()));
+    let err = limiter.try_acquire_labeled_at(0, 1).unwrap_err();
+    assert!(err.to_string().starts_with("[burst] Insufficient capacity"));
+}
+
+#[test]
+#[should_panic(expected = "children must not be empty")]
+fn test_new_with_no_children() {
+    AllOf::new(Vec::new());
+}
+
+/// Child whose lock a test can hold from another thread.
+struct Lockable {
+    inner: Arc<FixedWindowCounterCore>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl RateLimitCore for Lockable {
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let _guard = self.lock.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+        self.inner.try_acquire_at(tick, tokens)
+    }
+
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let _guard = self.lock.try_lock().map_err(|_| VerboseRateLimitError::ContentionFailure)?;
+        self.inner.try_acquire_verbose_at(tick, tokens)
+    }
+
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let _guard = self.lock.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+        self.inner.capacity_remaining(tick)
+    }
+}
+
+impl RefundableCore for Lockable {
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let _guard = self.lock.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+        self.inner.refund_at(tick, tokens)
+    }
+}
+
+/// Child that denies every request, running `on_deny` first.
+struct Denying<F: Fn() + Send + Sync> {
+    on_deny: F,
+}
+
+impl<F: Fn() + Send + Sync> RateLimitCore for Denying<F> {
+    fn try_acquire_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        (self.on_deny)();
+        Err(SimpleRateLimitError::InsufficientCapacity)
+    }
+
+    fn try_acquire_verbose_at(&self, _tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        (self.on_deny)();
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: tokens, available: 0, retry_after_ticks: 1, available_next_refill: 0 })
+    }
+
+    fn capacity_remaining(&self, _tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        Ok(0)
+    }
+}
+
+impl<F: Fn() + Send + Sync> RefundableCore for Denying<F> {
+    fn refund_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        Ok(())
+    }
+}
+
+/// Has another thread take `lock` and hold it for a while.
+fn hold_from_another_thread(lock: &Arc<Mutex<()>>) {
+    let lock = Arc::clone(lock);
+    let (locked_tx, locked_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _guard = lock.lock().unwrap();
+        locked_tx.send(()).unwrap();
+        thread::sleep(Duration::from_millis(50));
+    });
+    locked_rx.recv().unwrap();
+}
+
+#[test]
+fn test_rollback_waits_out_a_held_child_lock() {
+    let first = Arc::new(FixedWindowCounterCore::new(10, 100));
+    let lock = Arc::new(Mutex::new(()));
+    let held = Arc::clone(&lock);
+    // The first child's lock is held when the second child's denial triggers the rollback
+    let limiter = AllOf::new(vec![
+        Labeled::new("first", Lockable { inner: Arc::clone(&first), lock }),
+        Labeled::new("second", Denying { on_deny: move || hold_from_another_thread(&held) }),
+    ]);
+
+    let err = limiter.try_acquire_labeled_at(0, 3).unwrap_err();
+    assert_eq!(err.label, "second");
+    assert_eq!(first.capacity_remaining(0), Ok(10));
+
+    assert_eq!(limiter.try_acquire_at(0, 3), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(first.capacity_remaining(0), Ok(10));
+}
+
+#[test]
+fn test_failed_rollback_is_reported_with_its_label() {
+    let first = Arc::new(FixedWindowCounterCore::new(10, 100));
+    let other_caller = Arc::clone(&first);
+    // Another caller moves the first child's window on before the rollback
+    let limiter = AllOf::new(vec![
+        Labeled::new("first", Lockable { inner: Arc::clone(&first), lock: Arc::new(Mutex::new(())) }),
+        Labeled::new("second", Denying { on_deny: move || assert!(other_caller.try_acquire_at(100, 1).is_ok()) }),
+    ]);
+
+    let err = limiter.try_acquire_labeled_at(0, 3).unwrap_err();
+    assert_eq!(err.label, "first");
+    assert_eq!(err.error, VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 1 });
+}
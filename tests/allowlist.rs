@@ -0,0 +1,74 @@
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::Allowlist;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+#[test]
+fn test_bypass_always_succeeds_and_is_counted() {
+    let limiter = Allowlist::new(FixedWindowCounterCore::new(5, 60), false);
+    assert_eq!(limiter.try_acquire_at(false, 0, 5), Ok(()));
+
+    for _ in 0..4 {
+        assert_eq!(limiter.try_acquire_at(true, 0, 3), Ok(()));
+    }
+    assert_eq!(limiter.try_acquire_verbose_at(true, 0, 10), Ok(()));
+    // Even a request beyond the inner capacity
+    assert_eq!(limiter.try_acquire_at(true, 0, 100), Ok(()));
+    assert_eq!(limiter.bypassed_total(), 122);
+}
+
+#[test]
+fn test_non_bypass_obeys_limit() {
+    let limiter = Allowlist::new(FixedWindowCounterCore::new(5, 60), false);
+    assert_eq!(limiter.try_acquire_at(false, 0, 4), Ok(()));
+    assert_eq!(limiter.try_acquire_at(false, 0, 2), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(false, 0, 6),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 6, capacity: 5 })
+    );
+    assert_eq!(limiter.bypassed_total(), 0);
+
+    // The trait path never bypasses
+    assert_eq!(RateLimitCore::try_acquire_at(&limiter, 0, 2), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_bypass_leaves_inner_untouched_by_default() {
+    let limiter = Allowlist::new(FixedWindowCounterCore::new(5, 60), false);
+    assert_eq!(limiter.try_acquire_at(true, 0, 4), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(5));
+}
+
+#[test]
+fn test_bypass_charges_inner_when_configured() {
+    let limiter = Allowlist::new(TokenBucketCore::new(10, 100, 1), true);
+    assert_eq!(limiter.try_acquire_at(true, 0, 4), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(6));
+
+    // A bypass larger than what is left drains the inner limiter
+    assert_eq!(limiter.try_acquire_at(true, 0, 8), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.try_acquire_at(false, 0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.bypassed_total(), 12);
+}
+
+#[test]
+fn test_failed_charge_is_reported_and_not_metered() {
+    let limiter = Allowlist::new(TokenBucketCore::new(10, 100, 1), true);
+    assert_eq!(limiter.try_acquire_at(true, 200, 4), Ok(()));
+
+    assert_eq!(limiter.try_acquire_at(true, 100, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert!(matches!(limiter.try_acquire_verbose_at(true, 100, 1), Err(VerboseRateLimitError::ExpiredTick { .. })));
+    assert_eq!(limiter.bypassed_total(), 4);
+    assert_eq!(limiter.capacity_remaining(200), Ok(6));
+}
+
+#[test]
+fn test_charge_uses_the_largest_admissible_amount() {
+    // Only 7 of the 20 fit; the charge takes all of them
+    let limiter = Allowlist::new(FixedWindowCounterCore::new(10, 60), true);
+    assert_eq!(limiter.try_acquire_at(false, 0, 3), Ok(()));
+    assert_eq!(limiter.try_acquire_at(true, 0, 20), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.bypassed_total(), 20);
+}
@@ -0,0 +1,95 @@
+use rate_guard_core::cores::ApproximateSlidingWindowCore;
+use rate_guard_core::Uint;
+
+/// Minimal linear congruential generator for deterministic pseudo-random inputs.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: Uint) -> Uint {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Uint::from(self.0 >> 33) % bound
+    }
+}
+
+/// Brute-force sliding log of every admitted acquire.
+struct SlidingLog {
+    window_ticks: Uint,
+    entries: Vec<(Uint, Uint)>,
+}
+
+impl SlidingLog {
+    /// Tokens acquired within the exact window `[tick - window_ticks + 1, tick]`.
+    fn exact_usage(&self, tick: Uint) -> Uint {
+        self.entries
+            .iter()
+            .filter(|&&(t, _)| tick - t < self.window_ticks)
+            .map(|&(_, tokens)| tokens)
+            .sum()
+    }
+}
+
+/// Returns the largest (over-count, under-count) seen over randomized traffic.
+fn observed_errors(capacity: Uint, window_ticks: Uint, rng: &mut Lcg) -> (Uint, Uint) {
+    let core = ApproximateSlidingWindowCore::new(capacity, window_ticks);
+    let mut log = SlidingLog { window_ticks, entries: Vec::new() };
+    let (mut over, mut under) = (0, 0);
+
+    let mut tick: Uint = 0;
+    for _ in 0..2_000 {
+        tick += if rng.next(8) == 0 { rng.next(window_ticks * 3) } else { rng.next(2) };
+
+        let estimated = capacity - core.capacity_remaining(tick).unwrap();
+        let exact = log.exact_usage(tick);
+        over = over.max(estimated.saturating_sub(exact));
+        under = under.max(exact.saturating_sub(estimated));
+
+        let tokens = 1 + rng.next(capacity);
+        if core.try_acquire_at(tick, tokens).is_ok() {
+            log.entries.push((tick, tokens));
+        }
+    }
+    (over, under)
+}
+
+#[test]
+fn test_bound_covers_randomized_traffic() {
+    let mut rng = Lcg(7);
+    for &(capacity, window_ticks) in &[(10, 4), (100, 10), (7, 1), (50, 7), (3, 20)] {
+        let core = ApproximateSlidingWindowCore::new(capacity, window_ticks);
+        let bound = core.max_error_bound();
+        let (over, under) = observed_errors(capacity, window_ticks, &mut rng);
+        let params = format!("capacity={} window_ticks={}", capacity, window_ticks);
+        assert!(over <= bound, "{} over={} bound={}", params, over, bound);
+        assert!(under <= bound, "{} under={} bound={}", params, under, bound);
+    }
+}
+
+#[test]
+fn test_bound_is_reached_by_over_count() {
+    let core = ApproximateSlidingWindowCore::new(10, 4);
+    assert_eq!(core.max_error_bound(), 8);
+
+    // All 10 tokens on the first tick of window [4, 7]; at tick 8 they have left
+    // the exact window, but the estimate still counts 3/4 of them, rounded up
+    assert_eq!(core.try_acquire_at(4, 10), Ok(()));
+    let estimated = 10 - core.capacity_remaining(8).unwrap();
+    assert_eq!(estimated, 8);
+}
+
+#[test]
+fn test_under_count_stays_within_bound() {
+    let core = ApproximateSlidingWindowCore::new(10, 4);
+
+    // All 10 tokens on the last tick of window [4, 7]; at tick 10 they are all in
+    // the exact window [7, 10], but the estimate counts only 1/4 of them
+    assert_eq!(core.try_acquire_at(7, 10), Ok(()));
+    let estimated = 10 - core.capacity_remaining(10).unwrap();
+    assert_eq!(estimated, 3);
+    assert!(10 - estimated <= core.max_error_bound());
+}
+
+#[test]
+fn test_single_tick_window_is_exact() {
+    assert_eq!(ApproximateSlidingWindowCore::new(100, 1).max_error_bound(), 0);
+    assert_eq!(ApproximateSlidingWindowCore::new(5, 10).max_error_bound(), 5);
+}
@@ -1,4 +1,4 @@
-use rate_guard_core::{SimpleRateLimitError};
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
 use rate_guard_core::cores::ApproximateSlidingWindowCore;
 
 #[test]
@@ -183,3 +183,125 @@ fn test_saturating_operations() {
     assert_eq!(counter.try_acquire_at(150, 400), Ok(()));
 }
 
+
+/// Minimal linear congruential generator so the property test stays deterministic
+/// without pulling in a dependency.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: Uint) -> Uint {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Uint::from(self.0 >> 33) % bound
+    }
+}
+
+#[test]
+fn test_capacity_remaining_matches_acquire_decision() {
+    let mut rng = Lcg(0x5eed);
+
+    for _ in 0..200 {
+        let capacity = 1 + rng.next(50);
+        let window_ticks = 1 + rng.next(40);
+        let counter = ApproximateSlidingWindowCore::new(capacity, window_ticks);
+        let mut tick: Uint = 0;
+
+        for _ in 0..50 {
+            tick += rng.next(window_ticks * 2);
+            let _ = counter.try_acquire_at(tick, 1 + rng.next(capacity));
+
+            let remaining = counter.capacity_remaining(tick).unwrap();
+            assert!(remaining <= capacity);
+            if remaining < capacity {
+                assert_eq!(
+                    counter.try_acquire_at(tick, remaining + 1),
+                    Err(SimpleRateLimitError::InsufficientCapacity),
+                    "capacity={} window_ticks={} tick={} remaining={}",
+                    capacity, window_ticks, tick, remaining
+                );
+            }
+            assert_eq!(
+                counter.try_acquire_at(tick, remaining),
+                Ok(()),
+                "capacity={} window_ticks={} tick={} remaining={}",
+                capacity, window_ticks, tick, remaining
+            );
+        }
+    }
+}
+
+#[test]
+fn test_window_ticks_one_covers_single_tick() {
+    // With window_ticks = 1 the sliding window is [tick, tick]
+    let counter = ApproximateSlidingWindowCore::new(10, 1);
+
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.capacity_remaining(0), Ok(0));
+    assert_eq!(
+        counter.try_acquire_verbose_at(0, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 1, available_next_refill: 10 })
+    );
+
+    // The previous tick never overlaps the next one
+    assert_eq!(counter.current_capacity_at(1), Ok(10));
+    assert_eq!(counter.capacity_remaining(1), Ok(10));
+    assert_eq!(counter.try_acquire_at(1, 4), Ok(()));
+    assert_eq!(counter.current_capacity(), Ok(6));
+    assert_eq!(counter.try_acquire_at(2, 10), Ok(()));
+}
+
+#[test]
+fn test_exact_capacity_at_fresh_window() {
+    let counter = ApproximateSlidingWindowCore::new(10, 10);
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Empty previous window: exact capacity is admitted at any offset
+    for offset in 0..10 {
+        let counter = ApproximateSlidingWindowCore::new(10, 10);
+        assert_eq!(counter.try_acquire_at(10 + offset, 10), Ok(()), "offset {}", offset);
+    }
+}
+
+#[test]
+fn test_exact_capacity_boundary_with_overlapping_previous_window() {
+    // 4 tokens in window [0, 9]; at tick 10 + o the overlap is 9 - o
+    for offset in 0..10 {
+        let counter = ApproximateSlidingWindowCore::new(10, 10);
+        assert_eq!(counter.try_acquire_at(0, 4), Ok(()));
+
+        let overlap: Uint = 9 - offset;
+        for k in 1..=10 {
+            let expected = 4 * overlap + k * 10 <= 10 * 10;
+            let admitted = counter.try_acquire_verbose_at(10 + offset, k).is_ok();
+            assert_eq!(admitted, expected, "offset {} k {}", offset, k);
+            if admitted {
+                break;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_exact_capacity_admitted_only_when_overlap_reaches_zero() {
+    let counter = ApproximateSlidingWindowCore::new(10, 10);
+    assert_eq!(counter.try_acquire_at(0, 1), Ok(()));
+    assert_eq!(counter.try_acquire_at(18, 10), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.try_acquire_at(19, 10), Ok(()));
+
+    // Equality is admitted: 10 tokens at overlap 7 contribute 70, leaving exactly 3 * 10
+    let counter = ApproximateSlidingWindowCore::new(10, 10);
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(12, 3), Ok(()));
+    assert_eq!(counter.try_acquire_at(12, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_try_new_rejects_zero_parameters() {
+    use rate_guard_core::InvalidConfig;
+
+    assert_eq!(ApproximateSlidingWindowCore::try_new(0, 10).err(), Some(InvalidConfig::ZeroCapacity));
+    assert_eq!(ApproximateSlidingWindowCore::try_new(10, 0).err(), Some(InvalidConfig::ZeroWindowTicks));
+
+    let counter = ApproximateSlidingWindowCore::try_new(10, 5).unwrap();
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+}
@@ -1,5 +1,5 @@
-use rate_guard_core::{SimpleRateLimitError};
-use rate_guard_core::cores::ApproximateSlidingWindowCore;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::{ApproximateSlidingWindowCore, ExplainedRateLimitError, WindowBreakdown};
 
 #[test]
 fn test_contention_failure() {
@@ -61,6 +61,65 @@ fn test_weighted_contribution_calculation() {
     assert_eq!(counter.try_acquire_at(18, 50), Err(SimpleRateLimitError::InsufficientCapacity));
 }
 
+#[test]
+fn test_window_breakdown_matches_weighted_contribution() {
+    // Same scenario as test_weighted_contribution_calculation
+    let counter = ApproximateSlidingWindowCore::new(100, 10);
+    assert_eq!(counter.try_acquire_at(5, 30), Ok(()));
+    assert_eq!(counter.try_acquire_at(15, 40), Ok(()));
+    assert_eq!(counter.try_acquire_at(18, 20), Ok(()));
+
+    // Sliding window [9, 18]: window 0 overlaps at [9, 9], so its 30 tokens count 30 * 1 / 10 = 3
+    let explained = counter.try_acquire_explained_at(18, 50).unwrap_err();
+    assert_eq!(
+        explained.error,
+        VerboseRateLimitError::InsufficientCapacity { acquiring: 50, available: 37, retry_after_ticks: 3, available_next_refill: 40 }
+    );
+    assert_eq!(
+        explained.breakdown,
+        Some(WindowBreakdown {
+            current_window_tokens: 60,
+            previous_window_tokens: 30,
+            previous_window_overlap_ticks: 1,
+            previous_window_overlap_contribution: 3,
+            sliding_window_range: (9, 18),
+        })
+    );
+
+    // At tick 19 the previous window no longer overlaps
+    let breakdown = counter.window_breakdown_at(19).unwrap();
+    assert_eq!(breakdown.previous_window_overlap_ticks, 0);
+    assert_eq!(breakdown.previous_window_overlap_contribution, 0);
+    assert_eq!(breakdown.sliding_window_range, (10, 19));
+}
+
+#[test]
+fn test_explained_acquire_other_errors_have_no_breakdown() {
+    let counter = ApproximateSlidingWindowCore::new(100, 10);
+    assert_eq!(counter.try_acquire_explained_at(5, 30), Ok(()));
+    assert_eq!(
+        counter.try_acquire_explained_at(5, 101),
+        Err(Box::new(ExplainedRateLimitError {
+            error: VerboseRateLimitError::BeyondCapacity { acquiring: 101, capacity: 100 },
+            breakdown: None,
+        }))
+    );
+    assert_eq!(counter.try_acquire_at(25, 1), Ok(()));
+    let explained = counter.try_acquire_explained_at(5, 1).unwrap_err();
+    assert!(matches!(explained.error, VerboseRateLimitError::ExpiredTick { .. }));
+    assert_eq!(explained.breakdown, None);
+}
+
+#[test]
+fn test_window_breakdown_does_not_commit() {
+    let counter = ApproximateSlidingWindowCore::new(100, 10);
+    assert_eq!(counter.try_acquire_at(5, 30), Ok(()));
+    // Reading far ahead must not move the windows
+    assert_eq!(counter.window_breakdown_at(50).unwrap().current_window_tokens, 0);
+    assert_eq!(counter.window_breakdown_at(6).unwrap().current_window_tokens, 30);
+    assert_eq!(counter.try_acquire_at(6, 1), Ok(()));
+}
+
 #[test]
 fn test_no_overlap_scenario() {
     let counter = ApproximateSlidingWindowCore::new(50, 10);
@@ -52,6 +52,7 @@ fn fill_window_then_fail() {
             acquiring,
             available,
             retry_after_ticks,
+            ..
         } => {
             assert_eq!(acquiring, 1);
             assert_eq!(available, 0);
@@ -101,3 +102,21 @@ fn overlap_contribution_allows_partial_usage() {
     );
 }
 
+
+#[test]
+fn large_tokens_and_window_should_not_overflow() {
+    // capacity * window_ticks and tokens * window_ticks both exceed Uint::MAX
+    let capacity = Uint::MAX / 4;
+    let window_ticks = 1_000;
+    let limiter = new_approx_window(capacity, window_ticks);
+
+    assert_eq!(limiter.try_acquire_verbose_at(10, capacity / 2), Ok(()));
+
+    let err = limiter.try_acquire_verbose_at(20, capacity / 2).unwrap_err();
+    match err {
+        VerboseRateLimitError::InsufficientCapacity { acquiring, .. } => {
+            assert_eq!(acquiring, capacity / 2);
+        }
+        _ => panic!("Expected InsufficientCapacity"),
+    }
+}
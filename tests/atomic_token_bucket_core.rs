@@ -0,0 +1,186 @@
+#![cfg(not(feature = "tick-u128"))]
+
+use std::sync::Arc;
+use std::thread;
+use rate_guard_core::cores::{AtomicTokenBucketCore, TokenBucketCore};
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+
+#[test]
+fn test_matches_token_bucket_semantics() {
+    let bucket = AtomicTokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.try_acquire_at(4, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(bucket.try_acquire_at(5, 2), Ok(()));
+    assert_eq!(bucket.try_acquire_at(3, 1), Err(SimpleRateLimitError::ExpiredTick));
+
+    assert_eq!(
+        bucket.try_acquire_verbose_at(6, 3),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 3, available: 0, retry_after_ticks: 9, available_next_refill: 2 })
+    );
+    assert_eq!(
+        bucket.try_acquire_verbose_at(4, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 5 })
+    );
+}
+
+#[test]
+fn test_agrees_with_mutex_bucket_on_a_sequence() {
+    let atomic = AtomicTokenBucketCore::new(7, 3, 2);
+    let mutex = TokenBucketCore::new(7, 3, 2);
+
+    // Deterministic pseudo-random walk over ticks and token counts
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut tick: Uint = 0;
+    for _ in 0..2_000 {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        // Mostly move forward, sometimes step back to exercise ExpiredTick
+        tick = if seed % 10 == 0 { tick.saturating_sub(2) } else { tick + seed % 4 };
+        let tokens = (seed >> 8) % 9;
+
+        assert_eq!(atomic.try_acquire_verbose_at(tick, tokens), mutex.try_acquire_verbose_at(tick, tokens));
+        assert_eq!(atomic.capacity_remaining(tick), mutex.capacity_remaining(tick));
+    }
+}
+
+#[test]
+fn test_denial_still_advances_refill_tick() {
+    let bucket = AtomicTokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.try_acquire_at(12, 5), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(bucket.try_acquire_at(9, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(bucket.capacity_remaining(12), Ok(4));
+}
+
+#[test]
+fn test_capacity_remaining_does_not_store_refill() {
+    let bucket = AtomicTokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.capacity_remaining(100), Ok(10));
+    assert_eq!(bucket.capacity_remaining(5), Ok(2));
+}
+
+#[test]
+fn test_refills_near_the_largest_tick() {
+    let bucket = AtomicTokenBucketCore::new(10, 1, 1);
+    let start = Uint::MAX - 100;
+
+    assert_eq!(bucket.try_acquire_at(start, 10), Ok(()));
+    assert_eq!(bucket.capacity_remaining(start + 5), Ok(5));
+    assert_eq!(bucket.try_acquire_at(start + 5, 5), Ok(()));
+    assert_eq!(
+        bucket.try_acquire_verbose_at(start + 4, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: start + 5 })
+    );
+    assert_eq!(bucket.try_acquire_at(Uint::MAX, 10), Ok(()));
+}
+
+#[test]
+fn test_agrees_with_mutex_bucket_across_rebases() {
+    // The word has no room for a period offset, so every new period rebases
+    let atomic = AtomicTokenBucketCore::new(Uint::MAX / 2, 1, 3);
+    let mutex = TokenBucketCore::new(Uint::MAX / 2, 1, 3);
+    assert_eq!(atomic.try_acquire_at(0, Uint::MAX / 2), Ok(()));
+    assert_eq!(mutex.try_acquire_at(0, Uint::MAX / 2), Ok(()));
+
+    for (tick, tokens) in [(1, 2), (1, 2), (4, 8), (3, 1), (4, 1), (1_000, 5), (Uint::MAX - 1, 1)] {
+        assert_eq!(atomic.try_acquire_verbose_at(tick, tokens), mutex.try_acquire_verbose_at(tick, tokens));
+        assert_eq!(atomic.capacity_remaining(tick), mutex.capacity_remaining(tick));
+    }
+}
+
+#[test]
+fn test_concurrent_acquires_across_rebases_conserve_tokens() {
+    const TICKS: Uint = 1_000;
+    let bucket = Arc::new(AtomicTokenBucketCore::new(Uint::MAX / 2, 1, 1));
+    assert_eq!(bucket.try_acquire_at(0, Uint::MAX / 2), Ok(()));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let bucket = Arc::clone(&bucket);
+            thread::spawn(move || {
+                let mut granted = 0;
+                for tick in 1..=TICKS {
+                    match bucket.try_acquire_at(tick, 1) {
+                        Ok(()) => granted += 1,
+                        Err(SimpleRateLimitError::InsufficientCapacity | SimpleRateLimitError::ExpiredTick) => {}
+                        Err(e) => panic!("unexpected error: {:?}", e),
+                    }
+                }
+                granted
+            })
+        })
+        .collect();
+    let granted: Uint = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+    // One token is refilled per tick, each taken at most once
+    assert_eq!(granted + bucket.capacity_remaining(TICKS).unwrap(), TICKS);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be at most Uint::MAX / 2")]
+fn test_max_capacity_panics() {
+    AtomicTokenBucketCore::new(Uint::MAX / 2 + 1, 1, 1);
+}
+
+#[test]
+#[should_panic(expected = "refill_amount must be greater than 0")]
+fn test_zero_refill_amount_panics() {
+    AtomicTokenBucketCore::new(10, 1, 0);
+}
+
+/// Hammers a full bucket at one tick from several threads and returns the
+/// number of grants, failing on any error other than `InsufficientCapacity`
+/// unless `allow_contention` is set.
+fn stress<F>(acquire: F, allow_contention: bool) -> u64
+where
+    F: Fn() -> Result<(), SimpleRateLimitError> + Send + Sync + 'static,
+{
+    const THREADS: usize = 8;
+    const ATTEMPTS: usize = 5_000;
+
+    let acquire = Arc::new(acquire);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let acquire = Arc::clone(&acquire);
+            thread::spawn(move || {
+                let mut granted = 0u64;
+                for _ in 0..ATTEMPTS {
+                    match acquire() {
+                        Ok(()) => granted += 1,
+                        Err(SimpleRateLimitError::InsufficientCapacity) => {}
+                        Err(SimpleRateLimitError::ContentionFailure) if allow_contention => {}
+                        Err(e) => panic!("unexpected error: {:?}", e),
+                    }
+                }
+                granted
+            })
+        })
+        .collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+}
+
+#[test]
+fn test_concurrent_grants_are_exact_and_never_fewer_than_mutex() {
+    // Fewer tokens than attempts, so every token should be granted exactly once
+    const CAPACITY: Uint = 20_000;
+
+    let atomic = Arc::new(AtomicTokenBucketCore::new(CAPACITY, 1_000, 1));
+    let atomic_granted = {
+        let atomic = Arc::clone(&atomic);
+        stress(move || atomic.try_acquire_at(0, 1), false)
+    };
+    assert_eq!(atomic_granted, CAPACITY);
+    assert_eq!(atomic.capacity_remaining(0), Ok(0));
+
+    // The mutex bucket never over-grants, but loses requests to contention
+    let mutex = Arc::new(TokenBucketCore::new(CAPACITY, 1_000, 1));
+    let mutex_granted = {
+        let mutex = Arc::clone(&mutex);
+        stress(move || mutex.try_acquire_at(0, 1), true)
+    };
+    assert!(mutex_granted <= CAPACITY);
+    assert!(atomic_granted >= mutex_granted);
+}
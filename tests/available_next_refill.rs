@@ -0,0 +1,98 @@
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+    TokenBucketCoreConfig,
+};
+use rate_guard_core::{Uint, VerboseRateLimitError};
+
+fn available_next_refill(error: VerboseRateLimitError) -> (Uint, Uint) {
+    match error {
+        VerboseRateLimitError::InsufficientCapacity { available, available_next_refill, .. } => {
+            (available, available_next_refill)
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_token_bucket_adds_one_refill() {
+    let capacity = 10;
+    let refill_amount = 3;
+    let bucket = TokenBucketCore::new(capacity, 5, refill_amount);
+    assert_eq!(bucket.try_acquire_at(0, 9), Ok(()));
+
+    let (available, next) = available_next_refill(bucket.try_acquire_verbose_at(2, 5).unwrap_err());
+    assert_eq!(available, 1);
+    assert_eq!(next, capacity.min(available + refill_amount));
+}
+
+#[test]
+fn test_token_bucket_next_refill_clamped_to_capacity() {
+    let capacity = 10;
+    let refill_amount = 4;
+    let bucket = TokenBucketCore::new(capacity, 5, refill_amount);
+    assert_eq!(bucket.try_acquire_at(0, 2), Ok(()));
+
+    let (available, next) = available_next_refill(bucket.try_acquire_verbose_at(0, 9).unwrap_err());
+    assert_eq!(available, 8);
+    assert_eq!(next, capacity.min(available + refill_amount));
+    assert_eq!(next, capacity);
+}
+
+#[test]
+fn test_token_bucket_matches_formula_at_every_level() {
+    let (capacity, refill_amount) = (8, 3);
+    for used in 1..=capacity {
+        let bucket = TokenBucketCore::new(capacity, 10, refill_amount);
+        assert_eq!(bucket.try_acquire_at(0, used), Ok(()));
+
+        let available = capacity - used;
+        let (reported, next) = available_next_refill(bucket.try_acquire_verbose_at(1, available + 1).unwrap_err());
+        assert_eq!(reported, available);
+        assert_eq!(next, capacity.min(available + refill_amount));
+    }
+}
+
+#[test]
+fn test_token_bucket_next_refill_respects_accrual_cap() {
+    let bucket = TokenBucketCore::from(TokenBucketCoreConfig {
+        capacity: 10,
+        refill_interval: 5,
+        refill_amount: 6,
+        accrual_cap: Some(2),
+    });
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+
+    let (available, next) = available_next_refill(bucket.try_acquire_verbose_at(1, 5).unwrap_err());
+    assert_eq!((available, next), (0, 2));
+}
+
+#[test]
+fn test_fixed_window_next_refill_is_full_window() {
+    let counter = FixedWindowCounterCore::new(10, 100);
+    assert_eq!(counter.try_acquire_at(0, 7), Ok(()));
+
+    let (available, next) = available_next_refill(counter.try_acquire_verbose_at(50, 5).unwrap_err());
+    assert_eq!((available, next), (3, 10));
+}
+
+#[test]
+fn test_sliding_window_next_refill_frees_oldest_bucket() {
+    // window_ticks = 30
+    let limiter = SlidingWindowCounterCore::new(10, 10, 3);
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(limiter.try_acquire_at(10, 5), Ok(()));
+
+    let (available, next) = available_next_refill(limiter.try_acquire_verbose_at(20, 3).unwrap_err());
+    assert_eq!((available, next), (1, 5));
+}
+
+#[test]
+fn test_approximate_sliding_window_next_refill_is_next_tick() {
+    let limiter = ApproximateSlidingWindowCore::new(10, 10);
+    assert_eq!(limiter.try_acquire_at(5, 10), Ok(()));
+
+    let (available, next) = available_next_refill(limiter.try_acquire_verbose_at(12, 10).unwrap_err());
+    assert_eq!(available, limiter.capacity_remaining(12).unwrap());
+    assert_eq!(next, limiter.capacity_remaining(13).unwrap());
+    assert!(next > available);
+}
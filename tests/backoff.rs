@@ -0,0 +1,70 @@
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::wrappers::Backoff;
+
+fn retry_after(result: VerboseRateLimitResult) -> Uint {
+    match result {
+        Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. }) => retry_after_ticks,
+        other => panic!("expected InsufficientCapacity, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_backoff_grows_on_repeated_denials() {
+    // 1 token refilled every 10 ticks: the inner hint is always 10 at tick 0
+    let limiter = Backoff::new(TokenBucketCore::new(1, 10, 1), 1000);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 1), Ok(()));
+
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(0, 1)), 10);
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(0, 1)), 20);
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(0, 1)), 40);
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(0, 1)), 80);
+    assert_eq!(limiter.consecutive_denials(), 4);
+}
+
+#[test]
+fn test_backoff_is_capped() {
+    let limiter = Backoff::new(TokenBucketCore::new(1, 10, 1), 50);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 1), Ok(()));
+
+    let hints: Vec<Uint> = (0..100).map(|_| retry_after(limiter.try_acquire_verbose_at(0, 1))).collect();
+    assert_eq!(&hints[..4], &[10, 20, 40, 50]);
+    assert!(hints.iter().all(|&h| h <= 50));
+    assert_eq!(hints[99], 50);
+}
+
+#[test]
+fn test_backoff_resets_after_success() {
+    let limiter = Backoff::new(FixedWindowCounterCore::new(1, 10), 1000);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 1), Ok(()));
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(5, 1)), 5);
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(5, 1)), 10);
+
+    assert_eq!(limiter.try_acquire_verbose_at(10, 1), Ok(()));
+    assert_eq!(limiter.consecutive_denials(), 0);
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(15, 1)), 5);
+}
+
+#[test]
+fn test_simple_path_shares_the_counter() {
+    let limiter = Backoff::new(FixedWindowCounterCore::new(1, 10), 1000);
+    assert_eq!(limiter.try_acquire_at(0, 1), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.consecutive_denials(), 2);
+    assert_eq!(retry_after(limiter.try_acquire_verbose_at(0, 1)), 40);
+
+    assert_eq!(limiter.try_acquire_at(10, 1), Ok(()));
+    assert_eq!(limiter.consecutive_denials(), 0);
+}
+
+#[test]
+fn test_other_errors_do_not_touch_the_counter() {
+    let limiter = Backoff::new(FixedWindowCounterCore::new(1, 10), 1000);
+    assert_eq!(limiter.try_acquire_at(20, 1), Ok(()));
+    assert_eq!(limiter.try_acquire_at(20, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    assert_eq!(limiter.try_acquire_at(20, 2), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(limiter.consecutive_denials(), 1);
+}
@@ -0,0 +1,44 @@
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, LifetimeQuotaCore, SlidingWindowCounterCore,
+    TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+
+const CAPACITY: Uint = 10;
+
+fn cores() -> Vec<(&'static str, Box<dyn RateLimitCore>)> {
+    vec![
+        ("token_bucket", Box::new(TokenBucketCore::new(CAPACITY, 5, 2))),
+        ("fixed_window", Box::new(FixedWindowCounterCore::new(CAPACITY, 5))),
+        ("sliding_window", Box::new(SlidingWindowCounterCore::new(CAPACITY, 5, 4))),
+        ("approximate_sliding_window", Box::new(ApproximateSlidingWindowCore::new(CAPACITY, 5))),
+        ("lifetime_quota", Box::new(LifetimeQuotaCore::new(CAPACITY))),
+    ]
+}
+
+#[test]
+fn test_exactly_capacity_in_one_call_is_admitted() {
+    for (name, core) in cores() {
+        assert_eq!(core.try_acquire_at(0, CAPACITY), Ok(()), "{}", name);
+        assert_eq!(core.capacity_remaining(0), Ok(0), "{}", name);
+        assert_eq!(core.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity), "{}", name);
+    }
+    for (name, core) in cores() {
+        assert_eq!(core.try_acquire_verbose_at(0, CAPACITY), Ok(()), "{}", name);
+    }
+}
+
+#[test]
+fn test_one_over_capacity_is_beyond_capacity() {
+    for (name, core) in cores() {
+        assert_eq!(core.try_acquire_at(0, CAPACITY + 1), Err(SimpleRateLimitError::BeyondCapacity), "{}", name);
+        assert_eq!(
+            core.try_acquire_verbose_at(0, CAPACITY + 1),
+            Err(VerboseRateLimitError::BeyondCapacity { acquiring: CAPACITY + 1, capacity: CAPACITY }),
+            "{}",
+            name
+        );
+        assert_eq!(core.capacity_remaining(0), Ok(CAPACITY), "{}", name);
+    }
+}
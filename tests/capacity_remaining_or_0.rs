@@ -0,0 +1,52 @@
+use rate_guard_core::Uint;
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+
+fn cores() -> Vec<Box<dyn RateLimitCore>> {
+    vec![
+        Box::new(TokenBucketCore::new(10, 5, 2)),
+        Box::new(FixedWindowCounterCore::new(10, 5)),
+        Box::new(SlidingWindowCounterCore::new(10, 5, 4)),
+        Box::new(ApproximateSlidingWindowCore::new(10, 5)),
+    ]
+}
+
+#[test]
+fn test_default_matches_capacity_remaining() {
+    for core in cores() {
+        assert_eq!(core.try_acquire_at(20, 4), Ok(()));
+        for tick in [20, 21, 24, 30] as [Uint; 4] {
+            assert_eq!(core.capacity_remaining_or_0(tick), core.capacity_remaining(tick).unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_default_maps_errors_to_zero() {
+    for core in cores() {
+        assert_eq!(core.try_acquire_at(100, 1), Ok(()));
+        assert!(core.capacity_remaining(0).is_err());
+        assert_eq!(core.capacity_remaining_or_0(0), 0);
+    }
+}
+
+#[test]
+fn test_default_matches_inherent_method() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    let fixed = FixedWindowCounterCore::new(10, 5);
+    let sliding = SlidingWindowCounterCore::new(10, 5, 4);
+    let approx = ApproximateSlidingWindowCore::new(10, 5);
+    assert_eq!(bucket.try_acquire_at(10, 3), Ok(()));
+    assert_eq!(fixed.try_acquire_at(10, 3), Ok(()));
+    assert_eq!(sliding.try_acquire_at(10, 3), Ok(()));
+    assert_eq!(approx.try_acquire_at(10, 3), Ok(()));
+
+    for tick in [0, 10, 12] as [Uint; 3] {
+        assert_eq!(RateLimitCore::capacity_remaining_or_0(&bucket, tick), bucket.capacity_remaining_or_0(tick));
+        assert_eq!(RateLimitCore::capacity_remaining_or_0(&fixed, tick), fixed.capacity_remaining_or_0(tick));
+        assert_eq!(RateLimitCore::capacity_remaining_or_0(&sliding, tick), sliding.capacity_remaining_or_0(tick));
+        assert_eq!(RateLimitCore::capacity_remaining_or_0(&approx, tick), approx.capacity_remaining_or_0(tick));
+    }
+}
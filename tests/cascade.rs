@@ -0,0 +1,48 @@
+use rate_guard_core::{Uint, VerboseRateLimitError};
+use rate_guard_core::combinators::Cascade;
+
+const TICKS_PER_SECOND: Uint = 10;
+
+#[test]
+fn test_minute_tier_binds_before_second_tier() {
+    let limiter = Cascade::per_second(100).per_minute(250).per_hour(10_000).build(TICKS_PER_SECOND);
+
+    // Two full seconds, then the third second runs into the minute tier
+    assert_eq!(limiter.try_acquire_labeled_at(0, 100), Ok(()));
+    assert_eq!(limiter.try_acquire_labeled_at(10, 100), Ok(()));
+    assert_eq!(limiter.try_acquire_labeled_at(20, 50), Ok(()));
+
+    let err = limiter.try_acquire_labeled_at(20, 1).unwrap_err();
+    assert_eq!(err.label, "minute");
+    assert_eq!(err.retry_after_ticks(), Some(580));
+
+    // The second tier was left untouched by the denied request
+    assert_eq!(limiter.children()[0].core().capacity_remaining(20), Ok(50));
+    assert_eq!(limiter.try_acquire_labeled_at(600, 100), Ok(()));
+}
+
+#[test]
+fn test_second_tier_reported_first() {
+    let limiter = Cascade::per_second(5).per_minute(5).build(TICKS_PER_SECOND);
+    assert_eq!(limiter.try_acquire_labeled_at(0, 5), Ok(()));
+    assert_eq!(limiter.try_acquire_labeled_at(0, 1).unwrap_err().label, "second");
+}
+
+#[test]
+fn test_hour_tier() {
+    let limiter = Cascade::per_second(10).per_hour(15).build(TICKS_PER_SECOND);
+    assert_eq!(limiter.children().len(), 2);
+    assert_eq!(limiter.try_acquire_labeled_at(0, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_labeled_at(10, 5), Ok(()));
+
+    let err = limiter.try_acquire_labeled_at(20, 1).unwrap_err();
+    assert_eq!(err.label, "hour");
+    assert!(matches!(err.error, VerboseRateLimitError::InsufficientCapacity { available: 0, .. }));
+    assert_eq!(limiter.try_acquire_labeled_at(36_000, 10), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "ticks_per_second must be greater than 0")]
+fn test_zero_ticks_per_second_panics() {
+    Cascade::per_second(1).build(0);
+}
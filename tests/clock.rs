@@ -0,0 +1,70 @@
+use std::time::Duration;
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+use rate_guard_core::clock::{BoundLimiter, Clock, ManualClock, MonotonicClock, TickScale};
+use rate_guard_core::cores::TokenBucketCore;
+
+#[test]
+fn test_tick_scale_truncates_partial_ticks() {
+    let scale = TickScale::new(Duration::from_millis(100));
+    assert_eq!(scale.to_ticks(Duration::ZERO), 0);
+    assert_eq!(scale.to_ticks(Duration::from_millis(99)), 0);
+    assert_eq!(scale.to_ticks(Duration::from_millis(250)), 2);
+    assert_eq!(TickScale::secs().to_ticks(Duration::from_millis(2500)), 2);
+    assert_eq!(TickScale::millis().tick_duration(), Duration::from_millis(1));
+}
+
+#[test]
+fn test_tick_scale_saturates() {
+    let scale = TickScale::new(Duration::from_nanos(1));
+    let expected = Uint::try_from(Duration::MAX.as_nanos()).unwrap_or(Uint::MAX);
+    assert_eq!(scale.to_ticks(Duration::MAX), expected);
+}
+
+#[test]
+#[should_panic(expected = "tick duration must be greater than 0")]
+fn test_tick_scale_rejects_zero() {
+    TickScale::new(Duration::ZERO);
+}
+
+#[test]
+fn test_bound_limiter_admits_as_time_advances() {
+    // 10 tokens, refilling 5 every 10 ticks of 100ms (one second)
+    let limiter = BoundLimiter::new(
+        TokenBucketCore::new(10, 10, 5),
+        ManualClock::new(),
+        TickScale::new(Duration::from_millis(100)),
+    );
+
+    assert_eq!(limiter.acquire_now(10), Ok(()));
+    assert_eq!(limiter.acquire_now(1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    limiter.clock().advance(Duration::from_millis(400));
+    assert_eq!(limiter.now_tick(), 4);
+    assert_eq!(
+        limiter.acquire_now_verbose(1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 6, available_next_refill: 5 })
+    );
+
+    limiter.clock().advance(Duration::from_millis(600));
+    assert_eq!(limiter.now_tick(), 10);
+    assert_eq!(limiter.capacity_remaining_now(), Ok(5));
+    assert_eq!(limiter.acquire_now(5), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(10), Ok(0));
+}
+
+#[test]
+fn test_manual_clock_set_backwards_expires() {
+    let limiter = BoundLimiter::new(TokenBucketCore::new(10, 10, 5), ManualClock::new(), TickScale::millis());
+    limiter.clock().set(Duration::from_millis(30));
+    assert_eq!(limiter.acquire_now(1), Ok(()));
+
+    limiter.clock().set(Duration::from_millis(5));
+    assert_eq!(limiter.acquire_now(1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_monotonic_clock_does_not_go_backwards() {
+    let clock = MonotonicClock::new();
+    let first = clock.now();
+    assert!(clock.now() >= first);
+}
@@ -0,0 +1,98 @@
+use rate_guard_core::combinators::CompositeKeyed;
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+
+/// 10 tokens per key and 6 per method of a key, per window of 100 ticks.
+fn create_limiter() -> CompositeKeyed<&'static str, &'static str, FixedWindowCounterCore, FixedWindowCounterCore> {
+    CompositeKeyed::new(
+        |_key: &&str| FixedWindowCounterCore::new(10, 100),
+        |_key: &&str, _method: &&str| FixedWindowCounterCore::new(6, 100),
+        16,
+    )
+}
+
+#[test]
+fn test_global_budget_exhausted_by_other_method_denies_and_rolls_back() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_at(&"alice", &"POST", 0, 6), Ok(()));
+    assert_eq!(limiter.try_acquire_at(&"alice", &"PUT", 0, 4), Ok(()));
+    assert_eq!(limiter.capacity_remaining(&"alice", &"GET", 0), Ok((6, 0)));
+
+    // GET has its own budget left, but the key's global budget is spent
+    assert_eq!(limiter.try_acquire_at(&"alice", &"GET", 0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.capacity_remaining(&"alice", &"GET", 0), Ok((6, 0)));
+
+    // Once the window turns, the untouched GET budget is still whole
+    assert_eq!(limiter.try_acquire_at(&"alice", &"GET", 100, 6), Ok(()));
+}
+
+#[test]
+fn test_method_budget_denies_before_global() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_at(&"alice", &"POST", 0, 6), Ok(()));
+    assert_eq!(limiter.try_acquire_at(&"alice", &"POST", 0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.capacity_remaining(&"alice", &"POST", 0), Ok((0, 4)));
+    assert_eq!(limiter.try_acquire_at(&"alice", &"GET", 0, 4), Ok(()));
+}
+
+#[test]
+fn test_keys_are_independent() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_at(&"alice", &"POST", 0, 6), Ok(()));
+    assert_eq!(limiter.try_acquire_at(&"alice", &"GET", 0, 4), Ok(()));
+    assert_eq!(limiter.try_acquire_at(&"bob", &"POST", 0, 6), Ok(()));
+    assert_eq!(limiter.capacity_remaining(&"bob", &"GET", 0), Ok((6, 4)));
+}
+
+#[test]
+fn test_factories_receive_key_and_method() {
+    let limiter = CompositeKeyed::new(
+        |key: &u32| TokenBucketCore::new(Uint::from(*key), 10, 1),
+        |_key: &u32, method: &&str| FixedWindowCounterCore::new(if *method == "GET" { 5 } else { 1 }, 100),
+        16,
+    );
+    assert_eq!(limiter.capacity_remaining(&3, &"GET", 0), Ok((5, 3)));
+    assert_eq!(limiter.capacity_remaining(&8, &"POST", 0), Ok((1, 8)));
+}
+
+#[test]
+fn test_verbose_rolls_back_on_global_denial() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_verbose_at(&"alice", &"POST", 0, 6), Ok(()));
+    assert_eq!(limiter.try_acquire_verbose_at(&"alice", &"PUT", 0, 2), Ok(()));
+
+    let err = limiter.try_acquire_verbose_at(&"alice", &"GET", 10, 3).unwrap_err();
+    assert_eq!(
+        err,
+        VerboseRateLimitError::InsufficientCapacity { acquiring: 3, available: 2, retry_after_ticks: 90, available_next_refill: 10 }
+    );
+    assert_eq!(limiter.capacity_remaining(&"alice", &"GET", 10), Ok((6, 2)));
+}
+
+#[test]
+fn test_least_recently_used_key_is_evicted() {
+    let limiter = CompositeKeyed::new(
+        |_key: &&str| FixedWindowCounterCore::new(10, 100),
+        |_key: &&str, _method: &&str| FixedWindowCounterCore::new(6, 100),
+        2,
+    );
+    assert_eq!(limiter.try_acquire_at(&"alice", &"GET", 0, 6), Ok(()));
+    assert_eq!(limiter.try_acquire_at(&"bob", &"GET", 0, 6), Ok(()));
+    // Using alice again makes bob the least recently used
+    assert_eq!(limiter.capacity_remaining(&"alice", &"GET", 0), Ok((0, 4)));
+
+    assert_eq!(limiter.try_acquire_at(&"carol", &"GET", 0, 1), Ok(()));
+    assert_eq!(limiter.capacity_remaining(&"alice", &"GET", 0), Ok((0, 4)));
+    // bob was evicted and starts over
+    assert_eq!(limiter.capacity_remaining(&"bob", &"GET", 0), Ok((6, 10)));
+}
+
+#[test]
+#[should_panic(expected = "max_keys must be greater than 0")]
+fn test_zero_max_keys_panics() {
+    let _ = CompositeKeyed::new(
+        |_key: &&str| FixedWindowCounterCore::new(10, 100),
+        |_key: &&str, _method: &&str| FixedWindowCounterCore::new(6, 100),
+        0,
+    );
+}
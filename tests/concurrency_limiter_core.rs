@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use std::thread;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::ConcurrencyLimiterCore;
+
+#[test]
+#[should_panic(expected = "capacity must be greater than 0")]
+fn test_new_with_zero_capacity() {
+    ConcurrencyLimiterCore::new(0, 10);
+}
+
+#[test]
+#[should_panic(expected = "max_hold_ticks must be greater than 0")]
+fn test_new_with_zero_max_hold_ticks() {
+    ConcurrencyLimiterCore::new(1, 0);
+}
+
+#[test]
+fn test_capacity_limits_holds() {
+    let limiter = ConcurrencyLimiterCore::new(2, 100);
+    let _a = limiter.acquire_at(0).unwrap();
+    let _b = limiter.acquire_at(1).unwrap();
+    assert_eq!(limiter.acquire_at(2), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.active_holds(2), Ok(2));
+    assert_eq!(limiter.capacity_remaining(2), Ok(0));
+}
+
+#[test]
+fn test_explicit_release_frees_slot() {
+    let limiter = ConcurrencyLimiterCore::new(1, 100);
+    let hold = limiter.acquire_at(0).unwrap();
+    assert_eq!(hold.acquired_tick(), 0);
+    assert_eq!(limiter.acquire_at(10), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    assert!(limiter.release(hold));
+    assert_eq!(limiter.capacity_remaining(10), Ok(1));
+    let _next = limiter.acquire_at(10).unwrap();
+}
+
+#[test]
+fn test_holds_auto_expire() {
+    let limiter = ConcurrencyLimiterCore::new(1, 10);
+    let hold = limiter.acquire_at(5).unwrap();
+
+    // Still held through tick 5 + 10
+    assert_eq!(limiter.acquire_at(15), Err(SimpleRateLimitError::InsufficientCapacity));
+    // Expired at tick 16
+    let _next = limiter.acquire_at(16).unwrap();
+
+    // Releasing the expired hold does not free the new one
+    assert!(!limiter.release(hold));
+    assert_eq!(limiter.active_holds(16), Ok(1));
+}
+
+#[test]
+fn test_verbose_retry_is_oldest_expiry() {
+    let limiter = ConcurrencyLimiterCore::new(2, 10);
+    let _a = limiter.acquire_verbose_at(3).unwrap();
+    let _b = limiter.acquire_verbose_at(7).unwrap();
+    assert_eq!(
+        limiter.acquire_verbose_at(8),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 6, available_next_refill: 1 })
+    );
+    assert!(limiter.acquire_verbose_at(14).is_ok());
+}
+
+#[test]
+fn test_backwards_time_is_rejected() {
+    let limiter = ConcurrencyLimiterCore::new(2, 10);
+    let _a = limiter.acquire_at(10).unwrap();
+    assert_eq!(limiter.acquire_at(9), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(
+        limiter.acquire_verbose_at(9),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 10 })
+    );
+    assert_eq!(limiter.active_holds(9), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_release_is_never_lost_to_contention() {
+    let limiter = Arc::new(ConcurrencyLimiterCore::new(4, 1_000_000));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let limiter = Arc::clone(&limiter);
+            thread::spawn(move || {
+                for _ in 0..2_000 {
+                    // Acquires may be contended, but every release must take effect
+                    if let Ok(hold) = limiter.acquire_at(0) {
+                        assert!(limiter.release(hold));
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(limiter.active_holds(0), Ok(0));
+}
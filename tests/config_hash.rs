@@ -0,0 +1,58 @@
+use std::collections::{HashMap, HashSet};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCoreConfig, FixedWindowCounterCoreConfig,
+    SlidingWindowCounterCoreConfig, TokenBucketCoreConfig,
+};
+
+#[test]
+fn test_token_bucket_configs_deduplicate() {
+    let mut set = HashSet::new();
+    set.insert(TokenBucketCoreConfig::new(100, 10, 5));
+    set.insert(TokenBucketCoreConfig::new(100, 10, 5));
+    set.insert(TokenBucketCoreConfig::new(100, 10, 6));
+    set.insert(TokenBucketCoreConfig::new(200, 10, 5));
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn test_fixed_window_configs_deduplicate() {
+    let mut set = HashSet::new();
+    set.insert(FixedWindowCounterCoreConfig::new(100, 60));
+    set.insert(FixedWindowCounterCoreConfig::new(100, 60));
+    set.insert(FixedWindowCounterCoreConfig::new(100, 30));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_sliding_window_configs_deduplicate() {
+    let mut set = HashSet::new();
+    set.insert(SlidingWindowCounterCoreConfig::new(100, 10, 6));
+    set.insert(SlidingWindowCounterCoreConfig::new(100, 10, 6));
+    set.insert(SlidingWindowCounterCoreConfig::new(100, 6, 10));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_approximate_sliding_window_configs_deduplicate() {
+    let mut set = HashSet::new();
+    set.insert(ApproximateSlidingWindowCoreConfig::new(100, 60));
+    set.insert(ApproximateSlidingWindowCoreConfig::new(100, 60));
+    set.insert(ApproximateSlidingWindowCoreConfig::new(50, 60));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_config_as_map_key_shares_instance() {
+    let mut registry: HashMap<TokenBucketCoreConfig, usize> = HashMap::new();
+    let configs = [
+        TokenBucketCoreConfig::new(100, 10, 5),
+        TokenBucketCoreConfig::new(50, 10, 5),
+        TokenBucketCoreConfig::new(100, 10, 5),
+    ];
+    for config in configs.iter() {
+        let next_id = registry.len();
+        registry.entry(config.clone()).or_insert(next_id);
+    }
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry[&TokenBucketCoreConfig::new(100, 10, 5)], 0);
+}
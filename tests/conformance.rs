@@ -0,0 +1,10 @@
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::wrappers::Backoff;
+
+rate_guard_core::conformance!(token_bucket, 10, TokenBucketCore::new(10, 7, 3));
+rate_guard_core::conformance!(fixed_window_counter, 10, FixedWindowCounterCore::new(10, 50));
+rate_guard_core::conformance!(sliding_window_counter, 10, SlidingWindowCounterCore::new(10, 5, 4));
+rate_guard_core::conformance!(approximate_sliding_window, 10, ApproximateSlidingWindowCore::new(10, 30));
+rate_guard_core::conformance!(backoff, 10, Backoff::new(TokenBucketCore::new(10, 7, 3), 100));
@@ -0,0 +1,73 @@
+use rate_guard_core::cores::TokenBucketCore;
+use rate_guard_core::wrappers::Cooldown;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+#[test]
+fn test_denied_throughout_cooldown_while_inner_refills() {
+    let limiter = Cooldown::new(TokenBucketCore::new(5, 1, 1), 10);
+    assert_eq!(limiter.try_acquire_at(0, 5), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 5), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.exhausted_at(), Ok(Some(0)));
+
+    for tick in 1..10 {
+        assert_eq!(limiter.try_acquire_at(tick, 1), Err(SimpleRateLimitError::InsufficientCapacity), "tick {}", tick);
+        assert_eq!(limiter.capacity_remaining(tick), Ok(0));
+    }
+    // The inner bucket has refilled to capacity in the meantime
+    assert_eq!(limiter.inner().capacity_remaining(9), Ok(5));
+}
+
+#[test]
+fn test_admitted_after_cooldown() {
+    let limiter = Cooldown::new(TokenBucketCore::new(5, 1, 1), 10);
+    assert_eq!(limiter.try_acquire_at(0, 5), Ok(()));
+    assert!(limiter.try_acquire_at(0, 5).is_err());
+
+    assert_eq!(limiter.try_acquire_at(10, 5), Ok(()));
+    assert_eq!(limiter.exhausted_at(), Ok(None));
+}
+
+#[test]
+fn test_verbose_reports_cooldown_remaining() {
+    let limiter = Cooldown::new(TokenBucketCore::new(5, 1, 1), 10);
+    assert_eq!(limiter.try_acquire_at(0, 5), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 10, available_next_refill: 1 })
+    );
+    assert_eq!(
+        limiter.try_acquire_verbose_at(4, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 6, available_next_refill: 0 })
+    );
+    assert_eq!(limiter.try_acquire_verbose_at(10, 1), Ok(()));
+}
+
+#[test]
+fn test_beyond_capacity_does_not_start_cooldown() {
+    let limiter = Cooldown::new(TokenBucketCore::new(5, 1, 1), 10);
+    assert_eq!(limiter.try_acquire_at(0, 6), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.exhausted_at(), Ok(None));
+    assert_eq!(limiter.try_acquire_at(0, 5), Ok(()));
+}
+
+#[test]
+fn test_oversized_request_with_tokens_left_does_not_start_cooldown() {
+    let limiter = Cooldown::new(TokenBucketCore::new(10, 1, 1), 50);
+    assert_eq!(limiter.try_acquire_at(0, 5), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 6), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 6),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 6, available: 5, retry_after_ticks: 1, available_next_refill: 6 })
+    );
+    assert_eq!(limiter.exhausted_at(), Ok(None));
+
+    // The remaining tokens are still usable
+    assert_eq!(limiter.try_acquire_at(1, 1), Ok(()));
+    assert_eq!(limiter.capacity_remaining(1), Ok(5));
+}
+
+#[test]
+#[should_panic(expected = "cooldown_ticks must be greater than 0")]
+fn test_zero_cooldown_panics() {
+    let _ = Cooldown::new(TokenBucketCore::new(5, 1, 1), 0);
+}
@@ -0,0 +1,49 @@
+use rate_guard_core::cores::SlidingWindowCounterCore;
+use rate_guard_core::SimpleRateLimitError;
+
+#[test]
+fn test_rate_is_sum_of_filled_buckets() {
+    // Window of 4 buckets of 10 ticks
+    let core = SlidingWindowCounterCore::new(100, 10, 4);
+    assert_eq!(core.current_rate(0), Ok(0));
+
+    core.try_acquire_at(0, 5).unwrap();
+    core.try_acquire_at(12, 7).unwrap();
+    core.try_acquire_at(25, 3).unwrap();
+    core.try_acquire_at(38, 9).unwrap();
+
+    assert_eq!(core.current_rate(39), Ok(5 + 7 + 3 + 9));
+    assert_eq!(core.current_rate(39), Ok(100 - core.capacity_remaining(39).unwrap()));
+}
+
+#[test]
+fn test_rate_decays_as_buckets_expire() {
+    let core = SlidingWindowCounterCore::new(100, 10, 4);
+    core.try_acquire_at(0, 5).unwrap();
+    core.try_acquire_at(12, 7).unwrap();
+    core.try_acquire_at(25, 3).unwrap();
+    core.try_acquire_at(38, 9).unwrap();
+
+    assert_eq!(core.current_rate(40), Ok(7 + 3 + 9));
+    assert_eq!(core.current_rate(50), Ok(3 + 9));
+    assert_eq!(core.current_rate(60), Ok(9));
+    assert_eq!(core.current_rate(70), Ok(0));
+}
+
+#[test]
+fn test_rate_does_not_consume_tokens() {
+    let core = SlidingWindowCounterCore::new(10, 10, 4);
+    core.try_acquire_at(0, 4).unwrap();
+
+    for _ in 0..3 {
+        assert_eq!(core.current_rate(5), Ok(4));
+    }
+    assert_eq!(core.capacity_remaining(5), Ok(6));
+}
+
+#[test]
+fn test_rate_rejects_expired_tick() {
+    let core = SlidingWindowCounterCore::new(10, 10, 4);
+    core.try_acquire_at(30, 1).unwrap();
+    assert_eq!(core.current_rate(5), Err(SimpleRateLimitError::ExpiredTick));
+}
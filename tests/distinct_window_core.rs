@@ -0,0 +1,57 @@
+use rate_guard_core::cores::DistinctWindowCore;
+use rate_guard_core::SimpleRateLimitError;
+
+#[test]
+fn test_only_distinct_ids_count() {
+    let core = DistinctWindowCore::new(3, 100, 16);
+    for tick in 0..10 {
+        assert_eq!(core.try_acquire_at(1, tick), Ok(()));
+    }
+    assert_eq!(core.capacity_remaining(10), Ok(2));
+
+    assert_eq!(core.try_acquire_at(2, 11), Ok(()));
+    assert_eq!(core.try_acquire_at(3, 12), Ok(()));
+    assert_eq!(core.try_acquire_at(4, 13), Err(SimpleRateLimitError::InsufficientCapacity));
+    // Repeats of admitted IDs are still free once the window is full
+    for &id in &[1, 2, 3, 3, 1] {
+        assert_eq!(core.try_acquire_at(id, 14), Ok(()));
+    }
+    assert_eq!(core.capacity_remaining(14), Ok(0));
+}
+
+#[test]
+fn test_window_boundary_clears_ids() {
+    let core = DistinctWindowCore::new(2, 10, 16);
+    assert_eq!(core.try_acquire_at(1, 0), Ok(()));
+    assert_eq!(core.try_acquire_at(2, 5), Ok(()));
+    assert_eq!(core.try_acquire_at(3, 9), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    assert_eq!(core.try_acquire_at(3, 10), Ok(()));
+    // ID 1 is new again in this window
+    assert_eq!(core.try_acquire_at(1, 11), Ok(()));
+    assert_eq!(core.try_acquire_at(2, 12), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(core.try_acquire_at(2, 5), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_forgotten_ids_are_charged_again() {
+    // Only two IDs remembered, four may be admitted
+    let core = DistinctWindowCore::new(4, 100, 2);
+    assert_eq!(core.try_acquire_at(1, 0), Ok(()));
+    assert_eq!(core.try_acquire_at(2, 0), Ok(()));
+    assert_eq!(core.try_acquire_at(3, 0), Ok(()));
+    // 2 and 3 are remembered, 1 was forgotten
+    assert_eq!(core.try_acquire_at(2, 1), Ok(()));
+    assert_eq!(core.capacity_remaining(1), Ok(1));
+    assert_eq!(core.try_acquire_at(1, 2), Ok(()));
+    assert_eq!(core.capacity_remaining(2), Ok(0));
+    // The count stays exact: no new ID is admitted past capacity
+    assert_eq!(core.try_acquire_at(5, 3), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(core.try_acquire_at(1, 3), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "max_tracked must be greater than 0")]
+fn test_zero_max_tracked_panics() {
+    DistinctWindowCore::new(4, 100, 0);
+}
@@ -0,0 +1,39 @@
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::wrappers::DryRun;
+
+#[test]
+fn test_dry_run_always_admits_and_counts_denials() {
+    let limiter = DryRun::new(FixedWindowCounterCore::new(10, 60));
+
+    assert_eq!(limiter.try_acquire_at(0, 6), Ok(()));
+    assert_eq!(limiter.shadow_denials(), 0);
+
+    // The inner core would deny both of these
+    assert_eq!(limiter.try_acquire_at(0, 6), Ok(()));
+    assert_eq!(limiter.try_acquire_verbose_at(0, 20), Ok(()));
+    assert_eq!(limiter.shadow_denials(), 2);
+
+    // Admitted requests still consume inner capacity
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.try_acquire_at(0, 1), Ok(()));
+    assert_eq!(limiter.shadow_denials(), 3);
+
+    // A new window restores the inner capacity
+    assert_eq!(limiter.try_acquire_at(60, 10), Ok(()));
+    assert_eq!(limiter.shadow_denials(), 3);
+}
+
+#[test]
+fn test_dry_run_passes_through_expired_tick() {
+    let limiter = DryRun::new(TokenBucketCore::new(10, 5, 5));
+    assert_eq!(limiter.try_acquire_at(10, 1), Ok(()));
+
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 10 })
+    );
+    assert_eq!(limiter.shadow_denials(), 0);
+}
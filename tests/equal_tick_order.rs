@@ -0,0 +1,109 @@
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, LifetimeQuotaCore, NestedFixedWindowCore,
+    ScoreDecayCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::Uint;
+
+/// Minimal linear congruential generator for deterministic pseudo-random inputs.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize % bound
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.next(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+const PRELOAD_TICK: Uint = 3;
+const TICK: Uint = 20;
+const LATER_TICK: Uint = 27;
+
+/// Runs `requests` at `TICK` on a fresh, preloaded core and returns the admitted
+/// count with the remaining capacity at `TICK` and at `LATER_TICK`.
+fn run_in_order<C: RateLimitCore>(make: &dyn Fn() -> C, requests: &[Uint]) -> (usize, Uint, Uint) {
+    let core = make();
+    let _ = core.try_acquire_at(PRELOAD_TICK, 3);
+    let admitted = requests.iter().filter(|&&tokens| core.try_acquire_at(TICK, tokens).is_ok()).count();
+    (
+        admitted,
+        core.capacity_remaining(TICK).unwrap(),
+        core.capacity_remaining(LATER_TICK).unwrap(),
+    )
+}
+
+/// Asserts that every order of a multiset of equal-size requests, mixed with
+/// zero-token and over-capacity requests, admits the same number and leaves
+/// the same state.
+fn assert_order_independent<C: RateLimitCore>(name: &str, capacity: Uint, make: &dyn Fn() -> C) {
+    let mut rng = Lcg(11);
+    for size in 1..=4 {
+        let mut requests: Vec<Uint> = vec![size; 12];
+        requests.extend([0, 0, capacity + 1]);
+
+        let expected = run_in_order(make, &requests);
+        for _ in 0..50 {
+            rng.shuffle(&mut requests);
+            assert_eq!(run_in_order(make, &requests), expected, "{} size={} order={:?}", name, size, requests);
+        }
+    }
+}
+
+#[test]
+fn test_token_bucket_order_independent() {
+    assert_order_independent("token bucket", 10, &|| TokenBucketCore::new(10, 3, 2));
+}
+
+#[test]
+fn test_fixed_window_order_independent() {
+    assert_order_independent("fixed window", 10, &|| FixedWindowCounterCore::new(10, 25));
+}
+
+#[test]
+fn test_sliding_window_order_independent() {
+    assert_order_independent("sliding window", 10, &|| SlidingWindowCounterCore::new(10, 5, 4));
+}
+
+#[test]
+fn test_approximate_sliding_window_order_independent() {
+    assert_order_independent("approximate sliding window", 10, &|| ApproximateSlidingWindowCore::new(10, 24));
+}
+
+#[test]
+fn test_nested_fixed_window_order_independent() {
+    assert_order_independent("nested fixed window", 10, &|| NestedFixedWindowCore::new(10, 5, 4));
+}
+
+#[test]
+fn test_lifetime_quota_order_independent() {
+    assert_order_independent("lifetime quota", 10, &|| LifetimeQuotaCore::new(10));
+}
+
+#[test]
+fn test_score_decay_order_independent() {
+    assert_order_independent("score decay", 10, &|| ScoreDecayCore::new(10, 8));
+}
+
+#[cfg(not(feature = "tick-u128"))]
+#[test]
+fn test_token_bucket_seqlock_order_independent() {
+    use rate_guard_core::cores::TokenBucketSeqlock;
+    assert_order_independent("token bucket seqlock", 10, &|| TokenBucketSeqlock::new(10, 3, 2));
+}
+
+#[test]
+fn test_mixed_sizes_depend_on_order() {
+    // Admission is first fit in call order, so mixed sizes are not order independent:
+    // with 7 tokens left, 7 first admits one request and 3 first admits two
+    let large_first = run_in_order(&|| FixedWindowCounterCore::new(10, 25), &[7, 3, 3]);
+    let small_first = run_in_order(&|| FixedWindowCounterCore::new(10, 25), &[3, 3, 7]);
+    assert_eq!(large_first.0, 1);
+    assert_eq!(small_first.0, 2);
+}
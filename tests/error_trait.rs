@@ -0,0 +1,35 @@
+use std::error::Error;
+use rate_guard_core::cores::TokenBucketCore;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+fn acquire(bucket: &TokenBucketCore, tokens: u8) -> Result<(), Box<dyn Error>> {
+    bucket.try_acquire_at(0, tokens.into())?;
+    Ok(())
+}
+
+fn acquire_verbose(bucket: &TokenBucketCore, tokens: u8) -> Result<(), Box<dyn Error + Send + Sync>> {
+    bucket.try_acquire_verbose_at(0, tokens.into())?;
+    Ok(())
+}
+
+#[test]
+fn test_errors_propagate_into_boxed_error() {
+    let bucket = TokenBucketCore::new(10, 5, 1);
+    assert!(acquire(&bucket, 10).is_ok());
+
+    let err = acquire(&bucket, 1).unwrap_err();
+    assert_eq!(err.downcast_ref::<SimpleRateLimitError>(), Some(&SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(err.to_string(), SimpleRateLimitError::InsufficientCapacity.to_string());
+
+    let err = acquire_verbose(&bucket, 11).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<VerboseRateLimitError>(),
+        Some(&VerboseRateLimitError::BeyondCapacity { acquiring: 11, capacity: 10 })
+    );
+}
+
+#[test]
+fn test_errors_have_no_source() {
+    assert!(SimpleRateLimitError::ContentionFailure.source().is_none());
+    assert!(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 3 }.source().is_none());
+}
@@ -0,0 +1,61 @@
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+
+#[test]
+fn test_token_bucket_reports_backwards_jump() {
+    let bucket = TokenBucketCore::new(100, 10, 5);
+    assert_eq!(bucket.last_rejected_expired_delta(), Ok(None));
+
+    // Last refill tick becomes 100
+    assert_eq!(bucket.try_acquire_at(100, 1), Ok(()));
+    assert_eq!(bucket.try_acquire_at(70, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(bucket.last_rejected_expired_delta(), Ok(Some(30)));
+
+    assert_eq!(
+        bucket.try_acquire_verbose_at(95, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 100 })
+    );
+    assert_eq!(bucket.last_rejected_expired_delta(), Ok(Some(5)));
+
+    // Successful calls keep the last recorded delta
+    assert_eq!(bucket.try_acquire_at(100, 1), Ok(()));
+    assert_eq!(bucket.last_rejected_expired_delta(), Ok(Some(5)));
+}
+
+#[test]
+fn test_fixed_window_reports_backwards_jump() {
+    let counter = FixedWindowCounterCore::new(100, 10);
+    // Window starts at 100
+    assert_eq!(counter.try_acquire_at(105, 1), Ok(()));
+    assert_eq!(counter.try_acquire_at(98, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(counter.last_rejected_expired_delta(), Ok(Some(2)));
+
+    assert_eq!(counter.capacity_remaining(0), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(counter.last_rejected_expired_delta(), Ok(Some(100)));
+}
+
+#[test]
+fn test_sliding_window_reports_backwards_jump() {
+    let counter = SlidingWindowCounterCore::new(100, 10, 4);
+    // Most recent bucket starts at 100
+    assert_eq!(counter.try_acquire_at(105, 1), Ok(()));
+    assert_eq!(counter.try_acquire_at(60, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(counter.last_rejected_expired_delta(), Ok(Some(40)));
+}
+
+#[test]
+fn test_approximate_sliding_window_reports_backwards_jump() {
+    let counter = ApproximateSlidingWindowCore::new(100, 10);
+    // Latest window starts at 100
+    assert_eq!(counter.try_acquire_at(105, 1), Ok(()));
+    assert_eq!(counter.try_acquire_at(91, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(counter.last_rejected_expired_delta(), Ok(Some(9)));
+
+    assert_eq!(
+        counter.try_acquire_verbose_at(99, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 100 })
+    );
+    assert_eq!(counter.last_rejected_expired_delta(), Ok(Some(1)));
+}
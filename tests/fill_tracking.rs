@@ -0,0 +1,69 @@
+use rate_guard_core::SimpleRateLimitError;
+use rate_guard_core::cores::{RefillPolicy, TokenBucketCore};
+
+#[test]
+fn test_average_fill_full_then_empty_is_mid_range() {
+    // Refill interval far beyond the test, so the level only changes on acquire
+    let bucket = TokenBucketCore::new_with_fill_tracking(100, 1000, 10);
+
+    // Full for ticks 0..10, then drained
+    assert_eq!(bucket.try_acquire_at(10, 100), Ok(()));
+    // Empty for ticks 10..20
+    assert_eq!(bucket.try_acquire_at(20, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    assert_eq!(bucket.average_fill(), Ok(Some(50)));
+}
+
+#[test]
+fn test_average_fill_is_time_weighted() {
+    let bucket = TokenBucketCore::new_with_fill_tracking(100, 1000, 10);
+
+    // 100 for 10 ticks, 40 for 30 ticks: (1000 + 1200) / 40 = 55
+    assert_eq!(bucket.try_acquire_at(10, 60), Ok(()));
+    assert_eq!(bucket.try_acquire_verbose_at(40, 40), Ok(()));
+    assert_eq!(bucket.average_fill(), Ok(Some(55)));
+
+    // Repeated acquires at the same tick add no weight
+    assert_eq!(bucket.try_acquire_at(40, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(bucket.average_fill(), Ok(Some(55)));
+}
+
+#[test]
+fn test_average_fill_without_elapsed_time_or_tracking() {
+    let tracked = TokenBucketCore::new_with_fill_tracking(100, 10, 10);
+    assert_eq!(tracked.average_fill(), Ok(Some(100)));
+    assert_eq!(tracked.try_acquire_at(0, 30), Ok(()));
+    assert_eq!(tracked.average_fill(), Ok(Some(70)));
+
+    let untracked = TokenBucketCore::new(100, 10, 10);
+    assert_eq!(untracked.try_acquire_at(10, 30), Ok(()));
+    assert_eq!(untracked.average_fill(), Ok(None));
+}
+
+#[test]
+fn test_average_fill_counts_refills_between_acquires() {
+    let bucket = TokenBucketCore::new_with_fill_tracking(100, 10, 10);
+    assert_eq!(bucket.try_acquire_at(0, 100), Ok(()));
+
+    // 0, 10, 20, 30 and 40 tokens for 10 ticks each: 1000 / 50 = 20
+    assert_eq!(bucket.try_acquire_at(50, 1), Ok(()));
+    assert_eq!(bucket.average_fill(), Ok(Some(20)));
+
+    // 49 up to 99 over six intervals, then full for 89: (1000 + 4440 + 89000) / 1000 = 94
+    assert_eq!(bucket.try_acquire_at(1000, 1), Ok(()));
+    assert_eq!(bucket.average_fill(), Ok(Some(94)));
+}
+
+#[test]
+fn test_average_fill_integrates_refunds_and_committed_reads() {
+    let bucket = TokenBucketCore::new_with_fill_tracking(100, 1000, 10);
+    assert_eq!(bucket.try_acquire_at(0, 100), Ok(()));
+    assert_eq!(bucket.refund_at(10, 50), Ok(()));
+
+    // Empty for 10 ticks, then 50 for 10 ticks: 500 / 20 = 25
+    assert_eq!(bucket.try_acquire_at(20, 1), Ok(()));
+    assert_eq!(bucket.average_fill(), Ok(Some(25)));
+
+    let committing = TokenBucketCore::new_with_refill_policy(100, 10, 10, RefillPolicy::OnReadAndWrite);
+    assert_eq!(committing.average_fill(), Ok(None));
+}
@@ -355,4 +355,36 @@ fn test_single_tick_window() {
     
     // Window 2: tick 2 (should reset)
     assert_eq!(counter.capacity_remaining_or_0(2), 10);
-}
\ No newline at end of file
+}
+#[test]
+fn test_current_capacity_at_across_unused_windows() {
+    let counter = FixedWindowCounterCore::new(100, 10);
+    assert_eq!(counter.try_acquire_at(3, 40), Ok(()));
+
+    // Same window: reflects usage
+    assert_eq!(counter.current_capacity_at(9), Ok(60));
+
+    // Several unused windows later: projected as reset
+    assert_eq!(counter.current_capacity_at(10), Ok(100));
+    assert_eq!(counter.current_capacity_at(55), Ok(100));
+    assert_eq!(counter.current_capacity_at(1_000), Ok(100));
+
+    // The projection did not commit the reset
+    assert_eq!(counter.current_capacity(), Ok(60));
+    assert_eq!(counter.current_capacity_at(5), Ok(60));
+    assert_eq!(counter.try_acquire_at(9, 60), Ok(()));
+    assert_eq!(counter.try_acquire_at(9, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_try_new_rejects_zero_parameters() {
+    use rate_guard_core::InvalidConfig;
+
+    assert_eq!(FixedWindowCounterCore::try_new(0, 10).err(), Some(InvalidConfig::ZeroCapacity));
+    assert_eq!(FixedWindowCounterCore::try_new(10, 0).err(), Some(InvalidConfig::ZeroWindowTicks));
+    assert_eq!(FixedWindowCounterCore::try_new(0, 0).err(), Some(InvalidConfig::ZeroCapacity));
+    assert_eq!(InvalidConfig::ZeroWindowTicks.to_string(), "window_ticks must be greater than 0");
+
+    let counter = FixedWindowCounterCore::try_new(10, 5).unwrap();
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+}
@@ -50,6 +50,7 @@ fn fill_window_then_fail() {
             acquiring,
             available,
             retry_after_ticks,
+            ..
         } => {
             assert_eq!(acquiring, 1);
             assert_eq!(available, 0);
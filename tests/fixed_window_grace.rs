@@ -0,0 +1,127 @@
+use rate_guard_core::cores::{FixedWindowCounterCore, FixedWindowCounterCoreConfig};
+use rate_guard_core::{SimpleRateLimitError, StateDecodeError, VerboseRateLimitError};
+
+#[test]
+fn test_one_overshoot_within_grace_per_window() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 100, 3);
+    assert_eq!(counter.try_acquire_at(0, 8), Ok(()));
+    // Five tokens overshoot capacity by three: admitted using the grace
+    assert_eq!(counter.try_acquire_at(1, 5), Ok(()));
+    assert_eq!(counter.used_in_window(1), Ok(13));
+    assert_eq!(counter.capacity_remaining(1), Ok(0));
+    // The grace is spent, so even a single token is denied
+    assert_eq!(counter.try_acquire_at(2, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_second_overshoot_in_window_is_denied() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 100, 3);
+    assert_eq!(counter.try_acquire_at(0, 9), Ok(()));
+    assert_eq!(counter.try_acquire_at(1, 2), Ok(()));
+    assert_eq!(counter.try_acquire_at(2, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_overshoot_beyond_grace_is_denied_and_keeps_grace() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 100, 3);
+    assert_eq!(counter.try_acquire_at(0, 6), Ok(()));
+    assert_eq!(counter.try_acquire_at(1, 8), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.try_acquire_at(2, 7), Ok(()));
+}
+
+#[test]
+fn test_grace_resets_at_window_boundary() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 100, 3);
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(50, 3), Ok(()));
+    assert_eq!(counter.try_acquire_at(99, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    assert_eq!(counter.try_acquire_at(100, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(150, 3), Ok(()));
+    assert_eq!(counter.try_acquire_at(199, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_request_beyond_capacity_is_not_covered_by_grace() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 100, 3);
+    assert_eq!(counter.try_acquire_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+}
+
+#[test]
+fn test_verbose_and_detailed_paths_use_grace() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 100, 3);
+    assert_eq!(counter.try_acquire_verbose_at(0, 9), Ok(()));
+    assert_eq!(counter.try_acquire_verbose_at(10, 4), Ok(()));
+    assert_eq!(
+        counter.try_acquire_verbose_at(20, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 80, available_next_refill: 10 })
+    );
+
+    let detailed = FixedWindowCounterCore::new_with_grace(10, 100, 3);
+    assert_eq!(detailed.try_acquire_at(0, 10), Ok(()));
+    assert!(detailed.try_acquire_detailed_at(1, 2).unwrap().allowed);
+    assert!(!detailed.try_acquire_detailed_at(2, 1).unwrap().allowed);
+}
+
+#[test]
+fn test_grace_from_config_and_state_bytes() {
+    let config = FixedWindowCounterCoreConfig { capacity: 10, window_size: 100, smoothing: false, grace: 3 };
+    assert_eq!(FixedWindowCounterCoreConfig::new(10, 100).with_grace(3), config);
+    let counter = FixedWindowCounterCore::from(config.clone());
+    assert_eq!(counter.try_acquire_at(0, 12), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(0, 2), Ok(()));
+
+    // A restored counter past capacity has already used its grace
+    let restored = FixedWindowCounterCore::from_bytes(config.clone(), &counter.to_bytes().unwrap()).unwrap();
+    assert_eq!(restored.used_in_window(0), Ok(12));
+    assert_eq!(restored.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Without grace the same bytes exceed the capacity
+    assert!(FixedWindowCounterCore::from_bytes(FixedWindowCounterCoreConfig::new(10, 100), &counter.to_bytes().unwrap()).is_err());
+}
+
+#[test]
+fn test_max_acquirable_includes_unused_grace() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 60, 2);
+    // Capped at what one request may take
+    assert_eq!(counter.max_acquirable_at(0), Ok(10));
+
+    assert_eq!(counter.try_acquire_at(0, 9), Ok(()));
+    assert_eq!(counter.capacity_remaining(1), Ok(1));
+    assert_eq!(counter.max_acquirable_at(1), Ok(3));
+    assert_eq!(counter.try_acquire_at(1, 4), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.try_acquire_at(1, 2), Ok(()));
+
+    // The grace is spent for the rest of the window
+    assert_eq!(counter.max_acquirable_at(2), Ok(0));
+    assert_eq!(counter.max_acquirable_at(60), Ok(10));
+}
+
+#[test]
+fn test_spent_grace_survives_state_bytes_below_capacity() {
+    let config = FixedWindowCounterCoreConfig::new(10, 100).with_grace(3);
+    let counter = FixedWindowCounterCore::from(config.clone());
+    assert_eq!(counter.try_acquire_at(0, 9), Ok(()));
+    assert_eq!(counter.try_acquire_at(0, 3), Ok(()));
+    // A refund brings the count back under capacity, but the grace stays spent
+    assert_eq!(counter.refund_at(0, 3), Ok(()));
+    assert_eq!(counter.used_in_window(0), Ok(9));
+
+    let restored = FixedWindowCounterCore::from_bytes(config.clone(), &counter.to_bytes().unwrap()).unwrap();
+    assert_eq!(restored.to_bytes(), counter.to_bytes());
+    assert_eq!(restored.try_acquire_at(1, 2), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(restored.try_acquire_at(1, 1), Ok(()));
+
+    // A count over capacity without the grace flag cannot have been written
+    let mut bytes = FixedWindowCounterCore::from(config.clone()).to_bytes().unwrap();
+    bytes[3] = 11;
+    assert_eq!(FixedWindowCounterCore::from_bytes(config, &bytes).err(), Some(StateDecodeError::InvalidState));
+}
+
+#[test]
+fn test_zero_grace_matches_new() {
+    let counter = FixedWindowCounterCore::new_with_grace(10, 100, 0);
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
@@ -0,0 +1,110 @@
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+use rate_guard_core::cores::{FixedWindowCounterCore, FixedWindowCounterCoreConfig};
+
+#[test]
+fn test_boundary_burst_allowed_without_smoothing() {
+    let counter = FixedWindowCounterCore::new(100, 10);
+    assert_eq!(counter.try_acquire_at(9, 100), Ok(()));
+    // 200 tokens within two ticks across the boundary
+    assert_eq!(counter.try_acquire_at(10, 100), Ok(()));
+}
+
+#[test]
+fn test_boundary_burst_curtailed_with_smoothing() {
+    let counter = FixedWindowCounterCore::new_with_smoothing(100, 10, true);
+    assert_eq!(counter.try_acquire_at(9, 100), Ok(()));
+
+    // At tick 10, 9 of the previous window's 10 ticks still overlap: only 10 tokens fit
+    assert_eq!(counter.capacity_remaining(10), Ok(10));
+    assert_eq!(counter.try_acquire_at(10, 100), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.try_acquire_at(10, 10), Ok(()));
+
+    // The previous window's weight decays linearly through the current window
+    assert_eq!(counter.capacity_remaining(14), Ok(40));
+    assert_eq!(counter.capacity_remaining(19), Ok(90));
+}
+
+#[test]
+fn test_smoothing_via_config() {
+    let counter: FixedWindowCounterCore = FixedWindowCounterCoreConfig {
+        capacity: 100,
+        window_size: 10,
+        smoothing: true,
+        grace: 0,
+    }.into();
+    assert_eq!(counter.try_acquire_at(5, 100), Ok(()));
+    assert_eq!(counter.try_acquire_at(10, 11), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    let built: FixedWindowCounterCore = FixedWindowCounterCoreConfig::new(100, 10).with_smoothing(true).into();
+    assert_eq!(built.try_acquire_at(5, 100), Ok(()));
+    assert_eq!(built.try_acquire_at(10, 11), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_skipped_window_drops_previous_count() {
+    let counter = FixedWindowCounterCore::new_with_smoothing(100, 10, true);
+    assert_eq!(counter.try_acquire_at(9, 100), Ok(()));
+    // Window [10-19] is skipped entirely, so nothing overlaps window [20-29]
+    assert_eq!(counter.try_acquire_at(20, 100), Ok(()));
+}
+
+#[test]
+fn test_smoothed_current_capacity_at_does_not_commit() {
+    let counter = FixedWindowCounterCore::new_with_smoothing(100, 10, true);
+    assert_eq!(counter.try_acquire_at(5, 60), Ok(()));
+
+    assert_eq!(counter.current_capacity_at(10), Ok(46));
+    assert_eq!(counter.current_capacity_at(30), Ok(100));
+    // The stored window is unchanged
+    assert_eq!(counter.current_capacity_at(9), Ok(40));
+}
+
+#[test]
+fn test_smoothed_verbose_retry_after() {
+    let counter = FixedWindowCounterCore::new_with_smoothing(100, 10, true);
+    assert_eq!(counter.try_acquire_at(9, 100), Ok(()));
+
+    assert_eq!(
+        counter.try_acquire_verbose_at(10, 50),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 50, available: 10, retry_after_ticks: 4, available_next_refill: 100 })
+    );
+    assert_eq!(counter.try_acquire_at(13, 50), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.try_acquire_at(14, 50), Ok(()));
+}
+
+/// Minimal linear congruential generator for deterministic pseudo-random inputs.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: Uint) -> Uint {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Uint::from(self.0 >> 33) % bound
+    }
+}
+
+#[test]
+fn test_smoothed_retry_after_is_exact() {
+    let mut rng = Lcg(7);
+
+    for _ in 0..300 {
+        let capacity = 1 + rng.next(30);
+        let window_ticks = 1 + rng.next(20);
+        let counter = FixedWindowCounterCore::new_with_smoothing(capacity, window_ticks, true);
+        let mut tick: Uint = 0;
+
+        for _ in 0..20 {
+            tick += rng.next(window_ticks + 2);
+            let tokens = 1 + rng.next(capacity);
+            match counter.try_acquire_verbose_at(tick, tokens) {
+                Ok(()) => {}
+                Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. }) => {
+                    assert!(retry_after_ticks > 0);
+                    let ready = tick + retry_after_ticks;
+                    assert!(counter.current_capacity_at(ready - 1).unwrap() < tokens);
+                    assert!(counter.current_capacity_at(ready).unwrap() >= tokens);
+                }
+                Err(e) => panic!("unexpected {:?}", e),
+            }
+        }
+    }
+}
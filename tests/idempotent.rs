@@ -0,0 +1,86 @@
+use rate_guard_core::SimpleRateLimitError;
+use rate_guard_core::cores::TokenBucketCore;
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::Idempotent;
+
+#[test]
+fn test_repeated_key_is_not_charged_twice() {
+    let limiter = Idempotent::new(TokenBucketCore::new(10, 100, 1), 8, 50);
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 4), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(1, 1, 4), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(1, 49, 4), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(49), Ok(6));
+
+    assert_eq!(limiter.try_acquire_at_keyed(2, 49, 4), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(49), Ok(2));
+}
+
+#[test]
+fn test_denial_is_not_cached() {
+    let limiter = Idempotent::new(TokenBucketCore::new(10, 100, 1), 8, 50);
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 8), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(2, 0, 8), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.inner().refund_at(0, 8), Ok(()));
+    // The retry is decided afresh and charged once
+    assert_eq!(limiter.try_acquire_at_keyed(2, 1, 8), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(2, 2, 8), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(2), Ok(2));
+}
+
+#[test]
+fn test_key_past_retention_is_charged_again() {
+    let limiter = Idempotent::new(TokenBucketCore::new(10, 1000, 1), 8, 50);
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 4), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(1, 50, 4), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(50), Ok(2));
+}
+
+#[test]
+fn test_evicted_key_is_charged_again() {
+    let limiter = Idempotent::new(TokenBucketCore::new(10, 1000, 1), 2, 1000);
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 1), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(2, 0, 1), Ok(()));
+    // Touching key 1 makes key 2 the least recently used
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 1), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(3, 0, 1), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(7));
+
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 1), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(7));
+    assert_eq!(limiter.try_acquire_at_keyed(2, 0, 1), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(6));
+}
+
+#[test]
+fn test_repeated_touches_keep_lru_order() {
+    let limiter = Idempotent::new(TokenBucketCore::new(100, 1000, 1), 2, 1000);
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 1), Ok(()));
+    assert_eq!(limiter.try_acquire_at_keyed(2, 0, 1), Ok(()));
+    for _ in 0..100 {
+        assert_eq!(limiter.try_acquire_at_keyed(1, 0, 1), Ok(()));
+    }
+    assert_eq!(limiter.try_acquire_at_keyed(2, 0, 1), Ok(()));
+    // Key 1 is now the least recently used and makes room for key 3
+    assert_eq!(limiter.try_acquire_at_keyed(3, 0, 1), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(97));
+
+    assert_eq!(limiter.try_acquire_at_keyed(2, 0, 1), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(97));
+    assert_eq!(limiter.try_acquire_at_keyed(1, 0, 1), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(96));
+}
+
+#[test]
+fn test_unkeyed_calls_pass_through() {
+    let limiter = Idempotent::new(TokenBucketCore::new(10, 1000, 1), 2, 1000);
+    let core: &dyn RateLimitCore = &limiter;
+    assert_eq!(core.try_acquire_at(0, 3), Ok(()));
+    assert_eq!(core.try_acquire_at(0, 3), Ok(()));
+    assert_eq!(core.capacity_remaining(0), Ok(4));
+}
+
+#[test]
+#[should_panic(expected = "max_keys must be greater than 0")]
+fn test_zero_max_keys_panics() {
+    Idempotent::new(TokenBucketCore::new(10, 10, 1), 0, 10);
+}
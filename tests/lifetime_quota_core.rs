@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::thread;
+
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+use rate_guard_core::cores::LifetimeQuotaCore;
+use rate_guard_core::rate_limit::RateLimitCore;
+
+#[test]
+fn test_quota_exhausts_and_never_refills() {
+    let quota = LifetimeQuotaCore::new(1000);
+    for tick in 0..10 {
+        assert_eq!(quota.try_acquire_at(tick, 100), Ok(()));
+    }
+    assert_eq!(quota.capacity_remaining(0), Ok(0));
+
+    for tick in [0, 10, 1_000_000, Uint::MAX] {
+        assert_eq!(quota.try_acquire_at(tick, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+        assert_eq!(quota.capacity_remaining(tick), Ok(0));
+    }
+}
+
+#[test]
+fn test_tick_is_ignored() {
+    let quota = LifetimeQuotaCore::new(10);
+    assert_eq!(quota.try_acquire_at(500, 4), Ok(()));
+    // Going backwards is not an error for a quota
+    assert_eq!(quota.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(quota.capacity_remaining(42), Ok(2));
+}
+
+#[test]
+fn test_verbose_errors() {
+    let quota = LifetimeQuotaCore::new(10);
+    assert_eq!(
+        quota.try_acquire_verbose_at(0, 11),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 11, capacity: 10 })
+    );
+    assert_eq!(quota.try_acquire_verbose_at(0, 8), Ok(()));
+    assert_eq!(
+        quota.try_acquire_verbose_at(0, 3),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 3, available: 2, retry_after_ticks: Uint::MAX, available_next_refill: 2 })
+    );
+}
+
+#[test]
+fn test_reset_quota_and_refund() {
+    let quota = LifetimeQuotaCore::new(10);
+    assert_eq!(quota.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(quota.refund_at(0, 3), Ok(()));
+    assert_eq!(quota.capacity_remaining(0), Ok(3));
+    assert_eq!(quota.refund_at(0, 100), Ok(()));
+    assert_eq!(quota.capacity_remaining(0), Ok(10));
+
+    // Upgrading the plan starts a fresh, larger quota
+    assert_eq!(quota.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(quota.reset_quota(50), Ok(()));
+    assert_eq!(quota.capacity_remaining(0), Ok(50));
+    assert_eq!(quota.try_acquire_at(0, 50), Ok(()));
+    assert_eq!(quota.try_acquire_at(0, 51), Err(SimpleRateLimitError::BeyondCapacity));
+}
+
+#[test]
+fn test_concurrent_acquires_never_overspend() {
+    let quota = Arc::new(LifetimeQuotaCore::new(1000));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let quota = quota.clone();
+            thread::spawn(move || {
+                let mut granted: Uint = 0;
+                for _ in 0..1000 {
+                    if quota.try_acquire_at(0, 1).is_ok() {
+                        granted += 1;
+                    }
+                }
+                granted
+            })
+        })
+        .collect();
+    let granted: Uint = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+    let remaining = quota.capacity_remaining(0).unwrap();
+    assert_eq!(granted + remaining, 1000);
+    let core: &dyn RateLimitCore = quota.as_ref();
+    assert_eq!(core.capacity_remaining(0), Ok(remaining));
+}
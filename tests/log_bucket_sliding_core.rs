@@ -0,0 +1,129 @@
+use rate_guard_core::cores::{LogBucketSlidingCore, SlidingWindowCounterCore, SlidingWindowCounterCoreConfig};
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+
+/// Deterministic xorshift generator for the randomized tests.
+fn next(seed: &mut u64) -> Uint {
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 7;
+    *seed ^= *seed << 17;
+    Uint::from(*seed)
+}
+
+/// Sum of the tokens admitted within the window ending at `tick`.
+fn exact_used(log: &[(Uint, Uint)], tick: Uint, window_ticks: Uint) -> Uint {
+    let window_start = tick.saturating_sub(window_ticks - 1);
+    log.iter()
+        .filter(|&&(t, _)| t >= window_start && t <= tick)
+        .map(|&(_, tokens)| tokens)
+        .sum()
+}
+
+#[test]
+fn test_matches_uniform_window_while_usage_is_recent() {
+    const WINDOW: Uint = 1_024;
+    let log_core = LogBucketSlidingCore::new(500, WINDOW, 1);
+    // One-tick buckets make the uniform window an exact sliding log
+    let uniform = SlidingWindowCounterCore::new(500, 1, WINDOW);
+
+    let mut seed = 0x9e37_79b9_7f4a_7c15;
+    let mut tick = 0;
+    while tick < WINDOW {
+        let tokens = next(&mut seed) % 20;
+        assert_eq!(log_core.try_acquire_at(tick, tokens), uniform.try_acquire_at(tick, tokens), "tick {}", tick);
+        assert_eq!(log_core.capacity_remaining(tick), uniform.capacity_remaining(tick), "tick {}", tick);
+        tick += next(&mut seed) % 8;
+    }
+}
+
+#[test]
+fn test_distant_usage_is_conservative_and_expires_within_two_windows() {
+    const WINDOW: Uint = 1_000;
+    let log_core = LogBucketSlidingCore::new(100, WINDOW, 1);
+    let uniform = SlidingWindowCounterCore::new(100, 1, WINDOW);
+    assert_eq!(log_core.try_acquire_at(10, 60), Ok(()));
+    assert_eq!(uniform.try_acquire_at(10, 60), Ok(()));
+
+    for tick in (10..=10 + 2 * WINDOW).step_by(7) {
+        let fuzzy = log_core.capacity_remaining(tick).unwrap();
+        let exact = uniform.capacity_remaining(tick).unwrap();
+        assert!(fuzzy <= exact, "tick {}: {} > {}", tick, fuzzy, exact);
+    }
+    assert_eq!(log_core.capacity_remaining(10 + 2 * WINDOW), Ok(100));
+}
+
+#[test]
+fn test_never_admits_more_than_capacity_per_window() {
+    const WINDOW: Uint = 3_000;
+    const CAPACITY: Uint = 200;
+    let core = LogBucketSlidingCore::new(CAPACITY, WINDOW, 5);
+    let mut admitted = Vec::new();
+
+    let mut seed = 0x2545_f491_4f6c_dd1d;
+    let mut tick: Uint = 0;
+    for _ in 0..5_000 {
+        // Mix short steps with long idle gaps
+        tick += if next(&mut seed) % 50 == 0 { next(&mut seed) % (3 * WINDOW) } else { next(&mut seed) % 10 };
+        let tokens = next(&mut seed) % 30;
+
+        let remaining = core.capacity_remaining(tick).unwrap();
+        assert!(remaining <= CAPACITY - exact_used(&admitted, tick, WINDOW), "tick {}", tick);
+
+        if core.try_acquire_at(tick, tokens) == Ok(()) {
+            admitted.push((tick, tokens));
+            assert!(exact_used(&admitted, tick, WINDOW) <= CAPACITY, "tick {}", tick);
+        }
+    }
+}
+
+#[test]
+fn test_day_window_uses_logarithmic_memory() {
+    let uniform = SlidingWindowCounterCoreConfig::new(1_000, 1, 86_400);
+    let log_core = LogBucketSlidingCore::new(1_000, 86_400, 1);
+
+    let log_buckets = Uint::try_from(log_core.bucket_count()).unwrap();
+    assert_eq!(log_buckets, 17 * 8);
+    assert!(log_buckets * 500 < uniform.bucket_count);
+}
+
+#[test]
+fn test_verbose_denial_diagnostics() {
+    let core = LogBucketSlidingCore::new(10, 100, 1);
+    assert_eq!(core.try_acquire_at(0, 8), Ok(()));
+
+    match core.try_acquire_verbose_at(50, 5) {
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring, available, retry_after_ticks, available_next_refill }) => {
+            assert_eq!((acquiring, available, available_next_refill), (5, 2, 2));
+            assert!(retry_after_ticks >= 50);
+            assert_eq!(core.try_acquire_at(50 + retry_after_ticks, 5), Ok(()));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_errors() {
+    let core = LogBucketSlidingCore::new(10, 100, 4);
+    assert_eq!(core.try_acquire_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(core.try_acquire_at(0, 0), Ok(()));
+    assert_eq!(core.try_acquire_at(9, 1), Ok(()));
+    // Ticks within the newest finest bucket are still accepted
+    assert_eq!(core.try_acquire_at(8, 1), Ok(()));
+    assert_eq!(core.try_acquire_at(7, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(
+        core.try_acquire_verbose_at(7, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 8 })
+    );
+    assert_eq!(core.steady_state_allowance(), (1, 10));
+}
+
+#[test]
+#[should_panic(expected = "bucket_ticks must not exceed window_ticks")]
+fn test_bucket_larger_than_window_panics() {
+    LogBucketSlidingCore::new(10, 100, 101);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be greater than 0")]
+fn test_zero_capacity_panics() {
+    LogBucketSlidingCore::new(0, 100, 1);
+}
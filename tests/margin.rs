@@ -0,0 +1,101 @@
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::wrappers::Margin;
+
+#[test]
+fn test_margin_denies_last_ten_percent() {
+    let limiter = Margin::new(FixedWindowCounterCore::new(100, 60), 100, 9000);
+
+    assert_eq!(limiter.try_acquire_at(0, 50), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 40), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // The inner core alone would still admit the remaining 10 tokens
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(10));
+    assert_eq!(limiter.capacity_remaining(0), Ok(0));
+}
+
+#[test]
+fn test_margin_verbose_reports_margin_denial() {
+    let limiter = Margin::new(FixedWindowCounterCore::new(100, 60), 100, 9000);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 85), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 10),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 10, available: 5, retry_after_ticks: 60, available_next_refill: 90 })
+    );
+    assert_eq!(limiter.try_acquire_verbose_at(0, 5), Ok(()));
+
+    // Once the inner core itself is short, the denial is still reported under the margin
+    assert_eq!(limiter.inner().try_acquire_at(0, 10), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 60, available_next_refill: 90 })
+    );
+}
+
+#[test]
+fn test_margin_recovers_with_inner_refill() {
+    let limiter = Margin::new(TokenBucketCore::new(100, 10, 10), 100, 9000);
+    assert_eq!(limiter.try_acquire_at(0, 90), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // 10 tokens refilled: inner has 20, margin allows 10 of them
+    assert_eq!(limiter.capacity_remaining(10), Ok(10));
+    assert_eq!(limiter.try_acquire_at(10, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(10, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_verbose_retry_is_when_margin_admits() {
+    let limiter = Margin::new(TokenBucketCore::new(100, 10, 10), 100, 9000);
+    assert_eq!(limiter.try_acquire_at(0, 90), Ok(()));
+
+    // The inner bucket holds 10 tokens, all withheld by the margin, until the refill at tick 10
+    assert_eq!(
+        limiter.try_acquire_verbose_at(3, 5),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 5, available: 0, retry_after_ticks: 7, available_next_refill: 10 })
+    );
+    assert_eq!(limiter.inner().capacity_remaining(3), Ok(10));
+    assert_eq!(limiter.try_acquire_at(10, 5), Ok(()));
+}
+
+#[test]
+fn test_full_headroom_is_transparent() {
+    let limiter = Margin::new(FixedWindowCounterCore::new(100, 60), 100, 10_000);
+    assert_eq!(limiter.try_acquire_at(0, 100), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_beyond_capacity() {
+    let limiter = Margin::new(FixedWindowCounterCore::new(100, 60), 100, 9000);
+    assert_eq!(limiter.try_acquire_at(0, 101), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 0), Ok(()));
+
+    // Above the margin's ceiling but within the inner capacity: never admitted either
+    assert_eq!(limiter.try_acquire_at(0, 91), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 91),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 91, capacity: 90 })
+    );
+    assert_eq!(limiter.try_acquire_at(0, 90), Ok(()));
+}
+
+#[test]
+fn test_ceiling_does_not_overflow() {
+    let limiter = Margin::new(FixedWindowCounterCore::new(Uint::MAX, 60), Uint::MAX, 5000);
+    assert_eq!(limiter.ceiling(), Uint::MAX / 2);
+    assert_eq!(limiter.capacity_remaining(0), Ok(Uint::MAX / 2));
+}
+
+#[test]
+#[should_panic(expected = "headroom_bps must not exceed 10000")]
+fn test_new_with_excessive_headroom() {
+    Margin::new(FixedWindowCounterCore::new(100, 60), 100, 10_001);
+}
+
+#[test]
+#[should_panic(expected = "capacity must not be below the inner limiter's capacity")]
+fn test_new_with_capacity_below_inner() {
+    Margin::new(FixedWindowCounterCore::new(200, 60), 100, 9000);
+}
@@ -0,0 +1,66 @@
+use rate_guard_core::Uint;
+use rate_guard_core::combinators::{AllOf, Labeled};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::Margin;
+
+/// Minimal linear congruential generator for deterministic pseudo-random inputs.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: Uint) -> Uint {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Uint::from(self.0 >> 33) % bound
+    }
+}
+
+fn limiters() -> Vec<(&'static str, Box<dyn RateLimitCore>)> {
+    vec![
+        ("token_bucket", Box::new(TokenBucketCore::new(20, 3, 2))),
+        ("fixed_window", Box::new(FixedWindowCounterCore::new(20, 7))),
+        ("fixed_window_smoothed", Box::new(FixedWindowCounterCore::new_with_smoothing(20, 7, true))),
+        ("sliding_window", Box::new(SlidingWindowCounterCore::new(20, 3, 4))),
+        ("approximate_sliding_window", Box::new(ApproximateSlidingWindowCore::new(20, 13))),
+        ("margin", Box::new(Margin::new(FixedWindowCounterCore::new(20, 7), 20, 7500))),
+        ("all_of", Box::new(AllOf::new(vec![
+            Labeled::new("burst", TokenBucketCore::new(20, 3, 2)),
+            Labeled::new("window", SlidingWindowCounterCore::new(20, 3, 4)),
+        ]))),
+    ]
+}
+
+#[test]
+fn test_max_acquirable_matches_acquire_decision() {
+    for (name, limiter) in limiters() {
+        let mut rng = Lcg(42);
+        let mut tick: Uint = 0;
+
+        for _ in 0..300 {
+            tick += rng.next(5);
+            let _ = limiter.try_acquire_at(tick, 1 + rng.next(20));
+
+            let max = limiter.max_acquirable_at(tick).unwrap();
+            assert!(
+                limiter.try_acquire_at(tick, max + 1).is_err(),
+                "{}: acquiring {} + 1 at tick {} should fail",
+                name, max, tick
+            );
+            assert_eq!(
+                limiter.try_acquire_at(tick, max),
+                Ok(()),
+                "{}: acquiring {} at tick {} should succeed",
+                name, max, tick
+            );
+        }
+    }
+}
+
+#[test]
+fn test_max_acquirable_equals_capacity_remaining() {
+    let bucket = TokenBucketCore::new(10, 5, 1);
+    assert_eq!(bucket.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(bucket.max_acquirable_at(0), bucket.capacity_remaining(0));
+    assert_eq!(bucket.max_acquirable_at(0), Ok(6));
+}
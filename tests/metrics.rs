@@ -0,0 +1,62 @@
+#![cfg(feature = "metrics")]
+
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, LifetimeQuotaCore, ScoreDecayCore,
+    SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::SimpleRateLimitError;
+
+#[test]
+fn test_renders_capacity_and_remaining_gauges() {
+    let bucket = TokenBucketCore::new(100, 10, 5);
+    bucket.try_acquire_at(0, 58).unwrap();
+
+    assert_eq!(
+        bucket.render_prometheus("api", 0).unwrap(),
+        "# TYPE ratelimit_capacity gauge\n\
+         ratelimit_capacity{name=\"api\"} 100\n\
+         # TYPE ratelimit_remaining gauge\n\
+         ratelimit_remaining{name=\"api\"} 42\n"
+    );
+}
+
+#[test]
+fn test_every_core_renders_its_values() {
+    let fixed = FixedWindowCounterCore::new(10, 60);
+    fixed.try_acquire_at(0, 3).unwrap();
+    let sliding = SlidingWindowCounterCore::new(20, 10, 3);
+    sliding.try_acquire_at(0, 4).unwrap();
+    let approximate = ApproximateSlidingWindowCore::new(30, 60);
+    approximate.try_acquire_at(0, 5).unwrap();
+    let quota = LifetimeQuotaCore::new(40);
+    quota.try_acquire_at(0, 6).unwrap();
+    let score = ScoreDecayCore::new(50, 100);
+    score.try_acquire_at(0, 7).unwrap();
+
+    let rendered = [
+        fixed.render_prometheus("fixed", 0).unwrap(),
+        sliding.render_prometheus("sliding", 0).unwrap(),
+        approximate.render_prometheus("approximate", 0).unwrap(),
+        quota.render_prometheus("quota", 0).unwrap(),
+        score.render_prometheus("score", 0).unwrap(),
+    ];
+    let expected = [("fixed", 10, 7), ("sliding", 20, 16), ("approximate", 30, 25), ("quota", 40, 34), ("score", 50, 43)];
+    for (text, (name, capacity, remaining)) in rendered.iter().zip(expected.iter()) {
+        assert!(text.contains(&format!("ratelimit_capacity{{name=\"{}\"}} {}\n", name, capacity)), "{}", text);
+        assert!(text.contains(&format!("ratelimit_remaining{{name=\"{}\"}} {}\n", name, remaining)), "{}", text);
+    }
+}
+
+#[test]
+fn test_label_value_is_escaped() {
+    let bucket = TokenBucketCore::new(1, 1, 1);
+    let text = bucket.render_prometheus("a\"b\\c\nd", 0).unwrap();
+    assert!(text.contains("ratelimit_capacity{name=\"a\\\"b\\\\c\\nd\"} 1\n"), "{}", text);
+}
+
+#[test]
+fn test_render_reports_capacity_remaining_errors() {
+    let bucket = TokenBucketCore::new(10, 10, 1);
+    bucket.try_acquire_at(50, 1).unwrap();
+    assert_eq!(bucket.render_prometheus("api", 0), Err(SimpleRateLimitError::ExpiredTick));
+}
@@ -0,0 +1,56 @@
+#![cfg(not(feature = "tick-u128"))]
+
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::MonotonicGuard;
+
+#[test]
+fn test_monotonic_guard_rejects_regression() {
+    let limiter = MonotonicGuard::new(TokenBucketCore::new(100, 10, 5));
+    assert_eq!(limiter.try_acquire_at(7, 10), Ok(()));
+
+    // The token bucket alone accepts any tick since its last refill at 0
+    assert_eq!(limiter.inner().try_acquire_at(3, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(3, 10), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(6, 10),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 7 })
+    );
+
+    // Repeating the maximum tick is not a regression
+    assert_eq!(limiter.try_acquire_at(7, 10), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(7), Ok(70));
+}
+
+#[test]
+fn test_monotonic_guard_tracks_max_tick_seen() {
+    let limiter = MonotonicGuard::new(FixedWindowCounterCore::new(10, 100));
+    assert_eq!(limiter.max_tick_seen(), 0);
+
+    assert_eq!(limiter.try_acquire_at(5, 1), Ok(()));
+    assert_eq!(limiter.max_tick_seen(), 5);
+
+    // Denied acquires still advance the maximum
+    assert_eq!(limiter.try_acquire_at(20, 50), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.max_tick_seen(), 20);
+
+    // Rejected regressions leave it unchanged
+    assert_eq!(limiter.try_acquire_at(10, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(limiter.max_tick_seen(), 20);
+}
+
+#[test]
+fn test_monotonic_guard_queries_do_not_advance() {
+    let limiter = MonotonicGuard::new(FixedWindowCounterCore::new(10, 100));
+    assert_eq!(limiter.try_acquire_at(5, 4), Ok(()));
+
+    assert_eq!(limiter.capacity_remaining(50), Ok(6));
+    assert_eq!(limiter.max_tick_seen(), 5);
+    assert_eq!(limiter.capacity_remaining(4), Err(SimpleRateLimitError::ExpiredTick));
+
+    // Works through the trait as well
+    let core: &dyn RateLimitCore = &limiter;
+    assert_eq!(core.try_acquire_at(4, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(core.try_acquire_at(6, 1), Ok(()));
+}
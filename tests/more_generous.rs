@@ -0,0 +1,72 @@
+use rate_guard_core::combinators::MoreGenerous;
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+fn minute_or_hour() -> MoreGenerous {
+    MoreGenerous::new(vec![
+        Box::new(FixedWindowCounterCore::new(100, 60)),
+        Box::new(FixedWindowCounterCore::new(1000, 3600)),
+    ])
+}
+
+#[test]
+fn test_child_with_more_headroom_is_charged() {
+    let limiter = minute_or_hour();
+    assert_eq!(limiter.try_acquire_at(0, 30), Ok(()));
+
+    let children = limiter.children();
+    assert_eq!(children[0].capacity_remaining(0), Ok(100));
+    assert_eq!(children[1].capacity_remaining(0), Ok(970));
+}
+
+#[test]
+fn test_charge_moves_to_child_that_becomes_more_generous() {
+    let limiter = MoreGenerous::new(vec![
+        Box::new(FixedWindowCounterCore::new(10, 100)),
+        Box::new(FixedWindowCounterCore::new(8, 100)),
+    ]);
+
+    assert_eq!(limiter.most_generous_at(0), Ok((0, 10)));
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(limiter.most_generous_at(0), Ok((1, 8)));
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+
+    assert_eq!(limiter.children()[0].capacity_remaining(0), Ok(6));
+    assert_eq!(limiter.children()[1].capacity_remaining(0), Ok(4));
+}
+
+#[test]
+fn test_tie_charges_first_child() {
+    let limiter = MoreGenerous::new(vec![
+        Box::new(FixedWindowCounterCore::new(10, 100)),
+        Box::new(FixedWindowCounterCore::new(10, 100)),
+    ]);
+
+    assert_eq!(limiter.try_acquire_at(0, 3), Ok(()));
+    assert_eq!(limiter.children()[0].capacity_remaining(0), Ok(7));
+    assert_eq!(limiter.children()[1].capacity_remaining(0), Ok(10));
+}
+
+#[test]
+fn test_capacity_remaining_is_max_across_children() {
+    let limiter = minute_or_hour();
+    assert_eq!(limiter.capacity_remaining(0), Ok(1000));
+    assert_eq!(limiter.try_acquire_at(0, 950), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(100));
+}
+
+#[test]
+fn test_denies_when_most_generous_child_denies() {
+    let limiter = MoreGenerous::new(vec![
+        Box::new(TokenBucketCore::new(5, 10, 1)),
+        Box::new(FixedWindowCounterCore::new(3, 100)),
+    ]);
+
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(3));
+    assert_eq!(limiter.try_acquire_at(0, 4), Err(SimpleRateLimitError::BeyondCapacity));
+    match limiter.try_acquire_verbose_at(0, 4) {
+        Err(VerboseRateLimitError::BeyondCapacity { capacity, .. }) => assert_eq!(capacity, 3),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
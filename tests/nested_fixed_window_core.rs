@@ -0,0 +1,62 @@
+use rate_guard_core::cores::{FixedWindowCounterCore, NestedFixedWindowCore, SlidingWindowCounterCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::{SimpleRateLimitError, Uint};
+
+#[test]
+fn test_boundary_burst_admitted_by_fixed_window_only() {
+    let fixed = FixedWindowCounterCore::new(10, 40);
+    let nested = NestedFixedWindowCore::new(10, 10, 4);
+
+    // Fill the capacity just before the coarse boundary
+    assert_eq!(fixed.try_acquire_at(39, 10), Ok(()));
+    assert_eq!(nested.try_acquire_at(39, 10), Ok(()));
+
+    // The fixed window resets at 40 and lets a second full burst through
+    assert_eq!(fixed.try_acquire_at(40, 10), Ok(()));
+    assert_eq!(nested.try_acquire_at(40, 10), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_oldest_fine_window_leaves_sum() {
+    let nested = NestedFixedWindowCore::new(10, 10, 4);
+    assert_eq!(nested.try_acquire_at(5, 4), Ok(()));
+    assert_eq!(nested.try_acquire_at(15, 6), Ok(()));
+    assert_eq!(nested.capacity_remaining(39), Ok(0));
+
+    // At tick 40 only the fine window [0, 9] has left
+    assert_eq!(nested.capacity_remaining(40), Ok(4));
+    assert_eq!(nested.try_acquire_at(40, 5), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(nested.try_acquire_at(50, 5), Ok(()));
+}
+
+#[test]
+fn test_matches_sliding_window_counter() {
+    let nested = NestedFixedWindowCore::new(7, 5, 3);
+    let sliding = SlidingWindowCounterCore::new(7, 5, 3);
+
+    let calls: Vec<(Uint, Uint)> = vec![(0, 3), (4, 3), (6, 2), (9, 1), (14, 4), (16, 3), (22, 7), (40, 8), (41, 5)];
+    for (tick, tokens) in calls {
+        assert_eq!(
+            nested.try_acquire_verbose_at(tick, tokens),
+            sliding.try_acquire_verbose_at(tick, tokens),
+            "tick {} tokens {}",
+            tick,
+            tokens
+        );
+    }
+}
+
+#[test]
+fn test_accessors() {
+    let nested = NestedFixedWindowCore::new(100, 1_000, 10);
+    assert_eq!(nested.fine_ticks(), 1_000);
+    assert_eq!(nested.coarse_multiple(), 10);
+    assert_eq!(nested.coarse_ticks(), 10_000);
+    assert_eq!(RateLimitCore::steady_state_allowance(&nested), (1, 100));
+}
+
+#[test]
+#[should_panic(expected = "coarse_multiple must be greater than 0")]
+fn test_zero_coarse_multiple_panics() {
+    let _ = NestedFixedWindowCore::new(10, 10, 0);
+}
@@ -0,0 +1,118 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::OnExhaust;
+use rate_guard_core::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+
+/// Wraps `inner` with a callback recording the ticks it is called with.
+fn recording<L: RateLimitCore>(inner: L) -> (OnExhaust<L>, Arc<Mutex<Vec<Uint>>>) {
+    let fired = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&fired);
+    let limiter = OnExhaust::new(inner, move |tick| log.lock().unwrap().push(tick));
+    (limiter, fired)
+}
+
+#[test]
+fn test_fires_once_on_the_exhausting_request() {
+    let (limiter, fired) = recording(TokenBucketCore::new(5, 10, 5));
+    assert_eq!(limiter.try_acquire_at(0, 3), Ok(()));
+    assert!(fired.lock().unwrap().is_empty());
+
+    assert_eq!(limiter.try_acquire_at(1, 2), Ok(()));
+    assert_eq!(*fired.lock().unwrap(), vec![1]);
+    assert_eq!(limiter.is_exhausted(), Ok(true));
+
+    // Staying empty does not fire again
+    assert_eq!(limiter.try_acquire_at(2, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(3, 0), Ok(()));
+    assert_eq!(*fired.lock().unwrap(), vec![1]);
+}
+
+#[test]
+fn test_fires_again_only_after_recovery_then_re_exhaustion() {
+    let (limiter, fired) = recording(FixedWindowCounterCore::new(4, 10));
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(*fired.lock().unwrap(), vec![0]);
+
+    // The next window recovers the capacity and re-arms the callback
+    assert_eq!(limiter.try_acquire_at(10, 1), Ok(()));
+    assert_eq!(limiter.is_exhausted(), Ok(false));
+    assert_eq!(*fired.lock().unwrap(), vec![0]);
+
+    assert_eq!(limiter.try_acquire_at(12, 3), Ok(()));
+    assert_eq!(*fired.lock().unwrap(), vec![0, 12]);
+}
+
+#[test]
+fn test_recovery_and_exhaustion_in_one_request_fires() {
+    let (limiter, fired) = recording(FixedWindowCounterCore::new(4, 10));
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(limiter.try_acquire_at(10, 4), Ok(()));
+    assert_eq!(*fired.lock().unwrap(), vec![0, 10]);
+}
+
+#[test]
+fn test_denials_never_fire() {
+    let (limiter, fired) = recording(TokenBucketCore::new(5, 10, 5));
+    assert_eq!(limiter.try_acquire_at(0, 6), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 2),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 2, available: 1, retry_after_ticks: 10, available_next_refill: 5 })
+    );
+    assert!(fired.lock().unwrap().is_empty());
+    assert_eq!(limiter.is_exhausted(), Ok(false));
+
+    assert_eq!(limiter.try_acquire_verbose_at(0, 1), Ok(()));
+    assert_eq!(*fired.lock().unwrap(), vec![0]);
+}
+
+#[test]
+fn test_inner_makes_the_decision() {
+    let (limiter, _) = recording(TokenBucketCore::new(5, 10, 5));
+    assert_eq!(limiter.try_acquire_at(0, 2), Ok(()));
+    assert_eq!(limiter.inner().capacity_remaining(0), Ok(3));
+    assert_eq!(limiter.capacity_remaining(0), Ok(3));
+    assert_eq!(limiter.steady_state_allowance(), (1, 2));
+}
+
+/// Limiter whose capacity reads fail with contention a set number of times.
+struct ContendedReads {
+    inner: TokenBucketCore,
+    contended_reads: AtomicUsize,
+}
+
+impl RateLimitCore for ContendedReads {
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        self.inner.try_acquire_at(tick, tokens)
+    }
+
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        self.inner.try_acquire_verbose_at(tick, tokens)
+    }
+
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        if self.contended_reads.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1)).is_ok() {
+            return Err(SimpleRateLimitError::ContentionFailure);
+        }
+        self.inner.capacity_remaining(tick)
+    }
+}
+
+#[test]
+fn test_contended_reads_do_not_miss_the_alert() {
+    let (limiter, fired) = recording(ContendedReads {
+        inner: TokenBucketCore::new(5, 10, 5),
+        contended_reads: AtomicUsize::new(0),
+    });
+    limiter.inner().contended_reads.store(3, Ordering::Relaxed);
+    assert_eq!(limiter.try_acquire_at(0, 5), Ok(()));
+    assert_eq!(*fired.lock().unwrap(), vec![0]);
+
+    // The re-arming read is retried as well
+    limiter.inner().contended_reads.store(3, Ordering::Relaxed);
+    assert_eq!(limiter.try_acquire_at(10, 1), Ok(()));
+    assert_eq!(limiter.is_exhausted(), Ok(false));
+    assert_eq!(limiter.inner().contended_reads.load(Ordering::Relaxed), 0);
+}
@@ -0,0 +1,135 @@
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::{Pacer, ReleaseSchedule};
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+
+fn runs(schedule: Result<ReleaseSchedule, SimpleRateLimitError>) -> Result<Vec<(Uint, Uint)>, SimpleRateLimitError> {
+    schedule.map(Iterator::collect)
+}
+
+#[test]
+fn test_burst_paced_one_tick_apart() {
+    let bucket = TokenBucketCore::new(10, 1, 1);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    let pacer = Pacer::new(bucket, 100);
+
+    let expected: Vec<(Uint, Uint)> = (1..=10).map(|tick| (tick, 1)).collect();
+    assert_eq!(runs(pacer.try_pace_at(0, 10)), Ok(expected));
+    assert_eq!(pacer.owed(), Ok(10));
+}
+
+#[test]
+fn test_available_tokens_released_immediately() {
+    let pacer = Pacer::new(TokenBucketCore::new(3, 1, 1), 100);
+    assert_eq!(runs(pacer.try_pace_at(5, 5)), Ok(vec![(5, 3), (6, 1), (7, 1)]));
+}
+
+#[test]
+fn test_slow_refill_spreads_release_ticks() {
+    // 1 token every 4 ticks
+    let bucket = TokenBucketCore::new(4, 4, 1);
+    assert_eq!(bucket.try_acquire_at(0, 4), Ok(()));
+    let pacer = Pacer::new(bucket, 100);
+    assert_eq!(runs(pacer.try_pace_at(0, 3)), Ok(vec![(4, 1), (8, 1), (12, 1)]));
+}
+
+#[test]
+fn test_rejects_beyond_horizon_without_scheduling() {
+    let bucket = TokenBucketCore::new(10, 1, 1);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    let pacer = Pacer::new(bucket, 5);
+
+    assert_eq!(runs(pacer.try_pace_at(0, 6)), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(
+        pacer.try_acquire_verbose_at(0, 8),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 8, available: 0, retry_after_ticks: 3, available_next_refill: 0 })
+    );
+    assert_eq!(pacer.owed(), Ok(0));
+    assert_eq!(runs(pacer.try_pace_at(0, 5)), Ok(vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]));
+}
+
+#[test]
+fn test_later_requests_queue_behind_schedule() {
+    let bucket = TokenBucketCore::new(10, 1, 1);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    let pacer = Pacer::new(bucket, 100);
+
+    assert_eq!(runs(pacer.try_pace_at(0, 4)), Ok(vec![(1, 1), (2, 1), (3, 1), (4, 1)]));
+    // By tick 2 two owed tokens are paid off; new tokens follow the remaining schedule
+    assert_eq!(runs(pacer.try_pace_at(2, 2)), Ok(vec![(5, 1), (6, 1)]));
+    assert_eq!(pacer.owed(), Ok(4));
+    assert_eq!(pacer.capacity_remaining(2), Ok(0));
+}
+
+#[test]
+fn test_schedule_resets_once_paid_off() {
+    let bucket = TokenBucketCore::new(10, 1, 1);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    let pacer = Pacer::new(bucket, 100);
+
+    assert_eq!(runs(pacer.try_pace_at(0, 3)), Ok(vec![(1, 1), (2, 1), (3, 1)]));
+    // At tick 10 the bucket has refilled 10; 3 pay the debt and 7 remain
+    assert_eq!(runs(pacer.try_pace_at(10, 8)), Ok(vec![(10, 7), (11, 1)]));
+}
+
+#[test]
+fn test_unknown_rate_grants_only_available() {
+    struct NoRate(FixedWindowCounterCore);
+    impl RateLimitCore for NoRate {
+        fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> rate_guard_core::SimpleRateLimitResult {
+            self.0.try_acquire_at(tick, tokens)
+        }
+        fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> rate_guard_core::VerboseRateLimitResult {
+            self.0.try_acquire_verbose_at(tick, tokens)
+        }
+        fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+            self.0.capacity_remaining(tick)
+        }
+    }
+
+    let pacer = Pacer::new(NoRate(FixedWindowCounterCore::new(2, 10)), 100);
+    assert_eq!(runs(pacer.try_pace_at(0, 2)), Ok(vec![(0, 2)]));
+    assert_eq!(runs(pacer.try_pace_at(1, 1)), Err(SimpleRateLimitError::InsufficientCapacity));
+    // The window's own wait is reported, not a guess
+    assert_eq!(
+        pacer.try_acquire_verbose_at(1, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 9, available_next_refill: 2 })
+    );
+}
+
+#[test]
+fn test_zero_tokens_yield_empty_schedule() {
+    let pacer = Pacer::new(TokenBucketCore::new(1, 1, 1), 0);
+    assert_eq!(runs(pacer.try_pace_at(0, 0)), Ok(Vec::new()));
+    assert_eq!(pacer.try_acquire_at(0, 0), Ok(()));
+}
+
+#[test]
+fn test_fast_refill_groups_tokens_per_tick() {
+    // 5 tokens every 2 ticks
+    let bucket = TokenBucketCore::new(10, 2, 5);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    let pacer = Pacer::new(bucket, 100);
+    assert_eq!(runs(pacer.try_pace_at(0, 12)), Ok(vec![(1, 2), (2, 3), (3, 2), (4, 3), (5, 2)]));
+}
+
+#[test]
+fn test_huge_request_is_scheduled_in_few_runs() {
+    let bucket = TokenBucketCore::new(Uint::MAX, 1, Uint::MAX / 2);
+    assert_eq!(bucket.try_acquire_at(0, Uint::MAX), Ok(()));
+    let pacer = Pacer::new(bucket, 10);
+
+    let schedule: Vec<(Uint, Uint)> = pacer.try_pace_at(0, Uint::MAX).unwrap().collect();
+    assert_eq!(schedule, vec![(1, Uint::MAX / 2), (2, Uint::MAX / 2), (3, 1)]);
+}
+
+#[test]
+fn test_inner_denials_are_propagated() {
+    let pacer = Pacer::new(TokenBucketCore::new(10, 1, 1), 100);
+    assert_eq!(runs(pacer.try_pace_at(5, 1)), Ok(vec![(5, 1)]));
+    assert_eq!(runs(pacer.try_pace_at(4, 1)), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(
+        pacer.try_acquire_verbose_at(4, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 5 })
+    );
+}
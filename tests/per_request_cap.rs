@@ -0,0 +1,44 @@
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::PerRequestCap;
+
+#[test]
+fn test_request_over_cap_rejected_within_capacity() {
+    let limiter = PerRequestCap::new(TokenBucketCore::new(100, 10, 10), 25);
+
+    // The pool has 100 tokens, but a single request may take at most 25
+    assert_eq!(limiter.try_acquire_at(0, 26), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 50),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 50, capacity: 25 })
+    );
+    // Rejected requests do not touch the pool
+    assert_eq!(limiter.capacity_remaining(0), Ok(100));
+
+    for _ in 0..4 {
+        assert_eq!(limiter.try_acquire_at(0, 25), Ok(()));
+    }
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_max_acquirable_is_capped() {
+    let limiter = PerRequestCap::new(FixedWindowCounterCore::new(100, 60), 25);
+    assert_eq!(limiter.max_acquirable_at(0), Ok(25));
+    assert_eq!(limiter.try_acquire_at(0, 20), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 20), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 20), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 20), Ok(()));
+    assert_eq!(limiter.max_acquirable_at(0), Ok(20));
+
+    let core: &dyn RateLimitCore = &limiter;
+    assert_eq!(core.max_acquirable_at(0), Ok(20));
+    assert_eq!(core.capacity_remaining(0), Ok(20));
+}
+
+#[test]
+#[should_panic(expected = "max_per_request must be greater than 0")]
+fn test_zero_cap_panics() {
+    PerRequestCap::new(TokenBucketCore::new(100, 10, 10), 0);
+}
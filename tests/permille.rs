@@ -0,0 +1,105 @@
+use rate_guard_core::{SimpleRateLimitError, Uint};
+use rate_guard_core::cores::TokenBucketCore;
+
+#[test]
+fn test_permille_costs_share_one_token() {
+    let bucket = TokenBucketCore::new(1, 10, 1);
+
+    // Three 0.3 costs fit in a single token. A fourth would need 1.2 tokens, so
+    // it is the fourth, not the fifth, that a 1-token bucket denies
+    assert_eq!(bucket.try_acquire_permille_at(0, 300), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 300), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 300), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 300), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // The next refill covers the fourth cost and the fifth is paid from its change
+    assert_eq!(bucket.try_acquire_permille_at(10, 300), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(10, 300), Ok(()));
+    assert_eq!(bucket.capacity_remaining(10), Ok(0));
+}
+
+#[test]
+fn test_permille_four_costs_fit_in_two_tokens() {
+    let bucket = TokenBucketCore::new(2, 10, 1);
+    for _ in 0..4 {
+        assert_eq!(bucket.try_acquire_permille_at(0, 300), Ok(()));
+    }
+    // 1.2 tokens used: one whole token remains beyond the 0.8 credit
+    assert_eq!(bucket.capacity_remaining(0), Ok(0));
+    assert_eq!(bucket.try_acquire_permille_at(0, 800), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_permille_whole_token_reporting_is_floored() {
+    let bucket = TokenBucketCore::new(10, 10, 1);
+    assert_eq!(bucket.try_acquire_permille_at(0, 2500), Ok(()));
+    // 7.5 tokens left: 7 whole tokens plus 0.5 credit
+    assert_eq!(bucket.capacity_remaining(0), Ok(7));
+    assert_eq!(bucket.try_acquire_at(0, 7), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 500), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_permille_beyond_capacity_and_expired() {
+    let bucket = TokenBucketCore::new(2, 10, 1);
+    assert_eq!(bucket.try_acquire_permille_at(0, 2001), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(bucket.try_acquire_permille_at(0, 2000), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 0), Ok(()));
+
+    assert_eq!(bucket.try_acquire_permille_at(20, 100), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(5, 100), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_permille_fifth_quarter_is_denied() {
+    let bucket = TokenBucketCore::new(1, 10, 1);
+    for _ in 0..4 {
+        assert_eq!(bucket.try_acquire_permille_at(0, 250), Ok(()));
+    }
+    assert_eq!(bucket.try_acquire_permille_at(0, 250), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_refill_to_capacity_absorbs_the_credit() {
+    let bucket = TokenBucketCore::new(1, 10, 1);
+    for _ in 0..3 {
+        assert_eq!(bucket.try_acquire_permille_at(0, 300), Ok(()));
+    }
+
+    // 0.1 token of credit plus a whole-token refill is capped at the 1-token capacity
+    for _ in 0..10 {
+        assert_eq!(bucket.try_acquire_permille_at(10, 100), Ok(()));
+    }
+    assert_eq!(bucket.try_acquire_permille_at(10, 100), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_refund_to_capacity_absorbs_the_credit() {
+    let bucket = TokenBucketCore::new(2, 10, 1);
+    assert_eq!(bucket.try_acquire_permille_at(0, 1300), Ok(()));
+    assert_eq!(bucket.capacity_remaining(0), Ok(0));
+
+    // 0.7 token of credit: refunding one token leaves 1.7 tokens, two fill the bucket
+    assert_eq!(bucket.refund_at(0, 1), Ok(()));
+    assert_eq!(bucket.capacity_remaining(0), Ok(1));
+    assert_eq!(bucket.try_acquire_permille_at(0, 1700), Ok(()));
+    assert_eq!(bucket.refund_at(0, 2), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 2000), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_permille_near_uint_max_does_not_overflow() {
+    let bucket = TokenBucketCore::new(Uint::MAX, 10, 1);
+    assert_eq!(bucket.try_acquire_permille_at(0, Uint::MAX), Ok(()));
+
+    // ceil(Uint::MAX / 1000) whole tokens were withdrawn
+    let withdrawn = Uint::MAX / 1000 + 1;
+    assert_eq!(bucket.capacity_remaining(0), Ok(Uint::MAX - withdrawn));
+    // The change, 1000 - Uint::MAX % 1000 permille, is kept as credit
+    assert_eq!(bucket.try_acquire_at(0, Uint::MAX - withdrawn), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 1000 - Uint::MAX % 1000), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "test-util")]
+
+use rate_guard_core::cores::{TokenBucketCore, TokenBucketCoreConfig};
+use rate_guard_core::planner;
+use rate_guard_core::replay;
+use rate_guard_core::Uint;
+
+/// Steady single-token requests every 2 ticks, with a burst of 6 every 50 ticks.
+fn synthetic_arrivals() -> Vec<(Uint, Uint)> {
+    let mut arrivals = Vec::new();
+    for tick in (0..500).step_by(2) {
+        arrivals.push((tick, 1));
+        if tick % 50 == 0 {
+            arrivals.extend(vec![(tick, 1); 6]);
+        }
+    }
+    arrivals
+}
+
+fn allowed_bps(config: &TokenBucketCoreConfig, arrivals: &[(Uint, Uint)]) -> Uint {
+    let report = replay::from_timestamps(&TokenBucketCore::from(config.clone()), arrivals.iter().copied());
+    Uint::from(report.allowed * 10_000 / report.total)
+}
+
+#[test]
+fn test_suggested_config_meets_target() {
+    let arrivals = synthetic_arrivals();
+    for &target in &[5_000, 9_000, 9_900, 10_000 as Uint] {
+        let config = planner::suggest_token_bucket(target, &arrivals);
+        assert!(allowed_bps(&config, &arrivals) >= target, "target {} missed by {:?}", target, config);
+    }
+}
+
+#[test]
+fn test_suggestion_is_smaller_than_holding_everything() {
+    let arrivals = synthetic_arrivals();
+    let config = planner::suggest_token_bucket(10_000, &arrivals);
+
+    // Each burst lands on one tick with the steady request, so 7 tokens of headroom suffice
+    assert_eq!(config.capacity, 7);
+    assert!(config.capacity < arrivals.len() as Uint);
+}
+
+#[test]
+fn test_lower_target_does_not_need_more_capacity() {
+    let arrivals = synthetic_arrivals();
+    let strict = planner::suggest_token_bucket(10_000, &arrivals);
+    let loose = planner::suggest_token_bucket(5_000, &arrivals);
+    assert!(loose.capacity <= strict.capacity, "{:?} vs {:?}", loose, strict);
+}
+
+#[test]
+fn test_empty_series_yields_minimal_bucket() {
+    assert_eq!(planner::suggest_token_bucket(10_000, &[]), TokenBucketCoreConfig::new(1, 1, 1));
+}
+
+#[test]
+#[should_panic(expected = "target_allow_fraction_bps must be at most 10000")]
+fn test_target_above_whole_panics() {
+    let _ = planner::suggest_token_bucket(10_001, &[(0, 1)]);
+}
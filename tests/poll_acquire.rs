@@ -0,0 +1,65 @@
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::{
+    AcquirePoll, SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult,
+};
+
+/// Returns a fixed verbose outcome, to reach outcomes that are hard to provoke.
+struct Fixed(VerboseRateLimitError);
+
+impl RateLimitCore for Fixed {
+    fn try_acquire_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        Err(SimpleRateLimitError::ContentionFailure)
+    }
+
+    fn try_acquire_verbose_at(&self, _tick: Uint, _tokens: Uint) -> VerboseRateLimitResult {
+        Err(self.0.clone())
+    }
+
+    fn capacity_remaining(&self, _tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        Err(SimpleRateLimitError::ContentionFailure)
+    }
+}
+
+#[test]
+fn test_poll_ready_consumes_tokens() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.poll_acquire_at(0, 4), AcquirePoll::Ready);
+    assert_eq!(bucket.capacity_remaining(0), Ok(6));
+}
+
+#[test]
+fn test_poll_pending_reports_retry_after() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.poll_acquire_at(0, 10), AcquirePoll::Ready);
+    // 4 tokens need two refills of 2, at tick 10
+    assert_eq!(bucket.poll_acquire_at(0, 4), AcquirePoll::Pending(10));
+    assert_eq!(bucket.poll_acquire_at(10, 4), AcquirePoll::Ready);
+
+    let window = FixedWindowCounterCore::new(3, 10);
+    assert_eq!(window.poll_acquire_at(2, 3), AcquirePoll::Ready);
+    assert_eq!(window.poll_acquire_at(4, 1), AcquirePoll::Pending(6));
+}
+
+#[test]
+fn test_poll_rejects_beyond_capacity() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.poll_acquire_at(0, 11), AcquirePoll::Rejected);
+    assert_eq!(bucket.capacity_remaining(0), Ok(10));
+}
+
+#[test]
+fn test_poll_expired_tick_waits_for_acceptable_tick() {
+    let window = FixedWindowCounterCore::new(3, 10);
+    assert_eq!(window.poll_acquire_at(25, 1), AcquirePoll::Ready);
+    assert_eq!(window.poll_acquire_at(17, 1), AcquirePoll::Pending(3));
+
+    let expired = Fixed(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 40 });
+    assert_eq!(expired.poll_acquire_at(30, 1), AcquirePoll::Pending(10));
+}
+
+#[test]
+fn test_poll_contention_is_pending_zero() {
+    let contended = Fixed(VerboseRateLimitError::ContentionFailure);
+    assert_eq!(contended.poll_acquire_at(0, 1), AcquirePoll::Pending(0));
+}
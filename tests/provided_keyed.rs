@@ -0,0 +1,103 @@
+use std::cell::Cell;
+use rate_guard_core::combinators::{ProvidedKeyed, QuotaProvider};
+use rate_guard_core::cores::TokenBucketCoreConfig;
+use rate_guard_core::{InvalidConfig, KeyedRateLimitError, SimpleRateLimitError};
+
+/// Quotas per tenant name, counting how often it is consulted.
+struct MockProvider {
+    lookups: Cell<u32>,
+}
+
+impl QuotaProvider<String> for MockProvider {
+    fn quota_for(&self, key: &String) -> Option<TokenBucketCoreConfig> {
+        self.lookups.set(self.lookups.get() + 1);
+        match key.as_str() {
+            "free" => Some(TokenBucketCoreConfig::new(5, 10, 1)),
+            "pro" => Some(TokenBucketCoreConfig::new(50, 10, 10)),
+            "broken" => Some(TokenBucketCoreConfig::new(0, 10, 1)),
+            _ => None,
+        }
+    }
+}
+
+fn create_limiter() -> ProvidedKeyed<String, MockProvider> {
+    ProvidedKeyed::new(MockProvider { lookups: Cell::new(0) }, 16)
+}
+
+#[test]
+fn test_each_tenant_gets_its_own_quota() {
+    let limiter = create_limiter();
+    let (free, pro) = ("free".to_string(), "pro".to_string());
+
+    assert_eq!(limiter.try_acquire_at(&free, 0, 5), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_at(&free, 0, 1),
+        Err(KeyedRateLimitError::Limit(SimpleRateLimitError::InsufficientCapacity))
+    );
+    assert_eq!(limiter.try_acquire_at(&pro, 0, 50), Ok(()));
+    assert_eq!(limiter.capacity_remaining(&pro, 10), Ok(10));
+    assert_eq!(limiter.capacity_remaining(&free, 10), Ok(1));
+}
+
+#[test]
+fn test_unknown_tenant_is_denied() {
+    let limiter = create_limiter();
+    let unknown = "trial".to_string();
+    assert_eq!(limiter.try_acquire_at(&unknown, 0, 1), Err(KeyedRateLimitError::UnknownTenant));
+    assert_eq!(limiter.capacity_remaining(&unknown, 0), Err(KeyedRateLimitError::UnknownTenant));
+    assert_eq!(KeyedRateLimitError::UnknownTenant.to_string(), "Unknown tenant: no quota is configured for the key.");
+}
+
+#[test]
+fn test_quota_is_looked_up_once_per_known_key() {
+    let limiter = create_limiter();
+    let (free, unknown) = ("free".to_string(), "trial".to_string());
+    for tick in 0..3 {
+        assert_eq!(limiter.try_acquire_at(&free, tick, 1), Ok(()));
+    }
+    assert_eq!(limiter.provider().lookups.get(), 1);
+
+    // Unknown keys are not cached
+    for tick in 0..3 {
+        assert_eq!(limiter.try_acquire_at(&unknown, tick, 1), Err(KeyedRateLimitError::UnknownTenant));
+    }
+    assert_eq!(limiter.provider().lookups.get(), 4);
+}
+
+#[test]
+fn test_closure_provider() {
+    let limiter = ProvidedKeyed::new(|tenant: &u32| Some(TokenBucketCoreConfig::new((*tenant).into(), 1, 1)), 16);
+    assert_eq!(limiter.try_acquire_at(&3, 0, 3), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_at(&3, 0, 4),
+        Err(KeyedRateLimitError::Limit(SimpleRateLimitError::BeyondCapacity))
+    );
+}
+
+#[test]
+fn test_invalid_quota_is_denied_and_not_cached() {
+    let limiter = create_limiter();
+    let broken = "broken".to_string();
+    assert_eq!(limiter.try_acquire_at(&broken, 0, 1), Err(KeyedRateLimitError::InvalidQuota(InvalidConfig::ZeroCapacity)));
+    assert_eq!(limiter.capacity_remaining(&broken, 0), Err(KeyedRateLimitError::InvalidQuota(InvalidConfig::ZeroCapacity)));
+    assert_eq!(limiter.provider().lookups.get(), 2);
+    assert_eq!(
+        KeyedRateLimitError::InvalidQuota(InvalidConfig::ZeroCapacity).to_string(),
+        "Invalid quota: capacity must be greater than 0"
+    );
+
+    let zero_cap = ProvidedKeyed::new(|_: &u32| Some(TokenBucketCoreConfig::new(10, 1, 1).with_accrual_cap(Some(0))), 16);
+    assert_eq!(zero_cap.try_acquire_at(&1, 0, 1), Err(KeyedRateLimitError::InvalidQuota(InvalidConfig::ZeroAccrualCap)));
+}
+
+#[test]
+fn test_least_recently_used_bucket_is_evicted() {
+    let limiter = ProvidedKeyed::new(|_: &u32| Some(TokenBucketCoreConfig::new(5, 100, 1)), 2);
+    assert_eq!(limiter.try_acquire_at(&1, 0, 5), Ok(()));
+    assert_eq!(limiter.try_acquire_at(&2, 0, 5), Ok(()));
+    assert_eq!(limiter.try_acquire_at(&3, 0, 5), Ok(()));
+
+    // Key 1 was evicted and starts over with a full bucket; key 3 is still cached
+    assert_eq!(limiter.capacity_remaining(&1, 0), Ok(5));
+    assert_eq!(limiter.capacity_remaining(&3, 0), Ok(0));
+}
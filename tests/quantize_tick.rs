@@ -0,0 +1,52 @@
+use rate_guard_core::cores::{SlidingWindowCounterCore, TokenBucketCore};
+use rate_guard_core::wrappers::QuantizeTick;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+#[test]
+fn test_ticks_within_one_step_are_the_same_tick() {
+    // One-tick buckets: without quantization every tick would rotate a bucket
+    let limiter = QuantizeTick::new(SlidingWindowCounterCore::new(100, 1, 4), 10);
+    for tick in 10..20 {
+        limiter.try_acquire_at(tick, 1).unwrap();
+    }
+
+    // All ten acquires landed in the inner bucket at tick 10
+    assert_eq!(limiter.inner().current_rate(10), Ok(10));
+    assert_eq!(limiter.capacity_remaining(19), Ok(90));
+}
+
+#[test]
+fn test_earlier_tick_within_step_is_not_expired() {
+    let limiter = QuantizeTick::new(TokenBucketCore::new(10, 5, 1), 10);
+    limiter.try_acquire_at(17, 1).unwrap();
+    // Tick 12 is older than 17 but quantizes to the same step
+    assert_eq!(limiter.try_acquire_at(12, 1), Ok(()));
+    assert_eq!(limiter.try_acquire_at(9, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_refill_is_observed_at_step_boundaries() {
+    let limiter = QuantizeTick::new(TokenBucketCore::new(1, 5, 1), 10);
+    limiter.try_acquire_at(0, 1).unwrap();
+    // Inner refill is due at tick 5, but is not seen until the step at 10
+    assert_eq!(limiter.try_acquire_at(9, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(10, 1), Ok(()));
+}
+
+#[test]
+fn test_verbose_retry_is_in_real_ticks() {
+    let limiter = QuantizeTick::new(TokenBucketCore::new(1, 5, 1), 10);
+    limiter.try_acquire_at(0, 1).unwrap();
+
+    // Inner: retry 5 ticks after tick 0; the first step at or after 5 is 10
+    assert_eq!(
+        limiter.try_acquire_verbose_at(3, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 7, available_next_refill: 1 })
+    );
+}
+
+#[test]
+#[should_panic(expected = "granularity must be greater than 0")]
+fn test_zero_granularity_panics() {
+    QuantizeTick::new(TokenBucketCore::new(1, 5, 1), 0);
+}
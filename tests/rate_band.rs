@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::thread;
+
+use rate_guard_core::cores::{FixedWindowCounterCore, LifetimeQuotaCore, TokenBucketCore};
+use rate_guard_core::wrappers::RateBand;
+use rate_guard_core::{SimpleRateLimitError, Uint};
+
+#[test]
+fn test_below_floor_after_idle_period() {
+    let band = RateBand::new(TokenBucketCore::new(10, 1, 1), 5);
+    assert_eq!(band.try_acquire_at(10, 1), Ok(()));
+
+    assert!(!band.is_below_floor(10));
+    assert!(!band.is_below_floor(15));
+    assert!(band.is_below_floor(16));
+}
+
+#[test]
+fn test_admission_clears_floor_breach() {
+    let band = RateBand::new(TokenBucketCore::new(10, 1, 1), 5);
+    assert!(band.is_below_floor(20));
+
+    assert_eq!(band.try_acquire_at(20, 1), Ok(()));
+    assert!(!band.is_below_floor(20));
+    assert_eq!(band.last_admitted_tick(), 20);
+}
+
+#[test]
+fn test_denied_requests_do_not_count_toward_floor() {
+    let band = RateBand::new(FixedWindowCounterCore::new(1, 100), 5);
+    assert_eq!(band.try_acquire_at(1, 1), Ok(()));
+    assert_eq!(band.try_acquire_at(10, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert!(band.try_acquire_verbose_at(10, 1).is_err());
+
+    assert_eq!(band.last_admitted_tick(), 1);
+    assert!(band.is_below_floor(10));
+}
+
+#[test]
+fn test_verbose_admission_counts_toward_floor() {
+    let band = RateBand::new(TokenBucketCore::new(10, 1, 1), 5);
+    assert_eq!(band.try_acquire_verbose_at(30, 1), Ok(()));
+    assert!(!band.is_below_floor(35));
+}
+
+#[test]
+fn test_max_rate_enforced_by_inner() {
+    let band = RateBand::new(FixedWindowCounterCore::new(2, 10), 5);
+    assert_eq!(band.try_acquire_at(0, 2), Ok(()));
+    assert_eq!(band.try_acquire_at(1, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(band.capacity_remaining(1), Ok(0));
+}
+
+#[test]
+fn test_out_of_order_admission_keeps_latest_tick() {
+    let band = RateBand::new(TokenBucketCore::new(10, 100, 1), 5);
+    assert_eq!(band.try_acquire_at(20, 1), Ok(()));
+    assert_eq!(band.try_acquire_at(18, 1), Ok(()));
+    assert_eq!(band.last_admitted_tick(), 20);
+}
+
+#[test]
+#[should_panic(expected = "floor_interval_ticks must be greater than 0")]
+fn test_zero_floor_interval_panics() {
+    let _ = RateBand::new(TokenBucketCore::new(10, 1, 1), 0);
+}
+
+#[test]
+fn test_concurrent_admissions_keep_the_latest_tick() {
+    let band = Arc::new(RateBand::new(LifetimeQuotaCore::new(10_000), 5));
+    let handles: Vec<_> = (0..4)
+        .map(|thread_index: Uint| {
+            let band = band.clone();
+            thread::spawn(move || {
+                for i in 0..1000 {
+                    // The inner core's lock may be held by another thread
+                    let result = loop {
+                        match band.try_acquire_at(i * 4 + thread_index, 1) {
+                            Err(SimpleRateLimitError::ContentionFailure) => thread::yield_now(),
+                            result => break result,
+                        }
+                    };
+                    assert_eq!(result, Ok(()));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(band.last_admitted_tick(), 3999);
+}
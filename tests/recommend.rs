@@ -0,0 +1,44 @@
+use rate_guard_core::recommend::{self, Accuracy, Algorithm, MemoryBudget};
+use rate_guard_core::SimpleRateLimitError;
+
+#[test]
+fn test_high_accuracy_low_memory_is_approximate() {
+    assert_eq!(recommend::algorithm(Accuracy::High, MemoryBudget::Low), Algorithm::ApproximateSlidingWindow);
+}
+
+#[test]
+fn test_bursty_is_token_bucket() {
+    for &budget in &[MemoryBudget::Low, MemoryBudget::Medium] {
+        assert_eq!(recommend::algorithm(Accuracy::Bursty, budget), Algorithm::TokenBucket);
+    }
+    assert_eq!(recommend::algorithm(Accuracy::Coarse, MemoryBudget::Medium), Algorithm::FixedWindow);
+    assert_eq!(recommend::algorithm(Accuracy::High, MemoryBudget::Medium), Algorithm::SlidingWindow);
+}
+
+#[test]
+fn test_chosen_cores_sustain_target_rate() {
+    for &accuracy in &[Accuracy::Bursty, Accuracy::Coarse, Accuracy::High] {
+        for &budget in &[MemoryBudget::Low, MemoryBudget::Medium] {
+            for &(tokens, ticks) in &[(100, 1000), (3, 7), (1, 1)] {
+                let limiter = recommend::choose((tokens, ticks), accuracy, budget);
+                let (n, d) = limiter.steady_state_allowance();
+                assert_eq!(n * ticks, d * tokens, "{:?} {:?} {}/{}", accuracy, budget, tokens, ticks);
+                assert_eq!(limiter.try_acquire_at(0, tokens), Ok(()));
+                assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bursty_core_refills_whole_allowance() {
+    let limiter = recommend::choose((10, 100), Accuracy::Bursty, MemoryBudget::Low);
+    assert_eq!(limiter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(100, 10), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "target ticks must be greater than 0")]
+fn test_zero_ticks_panics() {
+    recommend::choose((10, 0), Accuracy::High, MemoryBudget::Low);
+}
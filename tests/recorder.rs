@@ -0,0 +1,83 @@
+#![cfg(feature = "test-util")]
+
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::replay::{Recorded, Recorder, Replayer};
+use rate_guard_core::{SimpleRateLimitError, Uint};
+
+#[test]
+fn test_replay_reproduces_recorded_session() {
+    let recorder = Recorder::new(TokenBucketCore::new(3, 5, 1));
+    let calls: Vec<(Uint, Uint)> = vec![(0, 2), (1, 2), (2, 1), (6, 2), (7, 5), (1, 1)];
+
+    let mut live = Vec::new();
+    for &(tick, tokens) in &calls {
+        live.push((
+            recorder.try_acquire_at(tick, tokens),
+            recorder.try_acquire_verbose_at(tick, tokens),
+            recorder.capacity_remaining(tick),
+        ));
+    }
+    assert_eq!(recorder.log().len(), calls.len() * 3);
+
+    let replayer = Replayer::new(recorder.into_log());
+    for (&(tick, tokens), expected) in calls.iter().zip(live) {
+        let replayed = (
+            replayer.try_acquire_at(tick, tokens),
+            replayer.try_acquire_verbose_at(tick, tokens),
+            replayer.capacity_remaining(tick),
+        );
+        assert_eq!(replayed, expected);
+    }
+    assert_eq!(replayer.remaining(), 0);
+}
+
+#[test]
+fn test_replay_ignores_arguments() {
+    let recorder = Recorder::new(FixedWindowCounterCore::new(1, 10));
+    assert_eq!(recorder.try_acquire_at(0, 1), Ok(()));
+    assert_eq!(recorder.try_acquire_at(1, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Different ticks and token counts still get the recorded results, in order
+    let replayer = Replayer::new(recorder.into_log());
+    assert_eq!(replayer.try_acquire_at(100, 50), Ok(()));
+    assert_eq!(replayer.try_acquire_at(200, 0), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_recorder_logs_arguments() {
+    let recorder = Recorder::new(FixedWindowCounterCore::new(5, 10));
+    let _ = recorder.try_acquire_at(3, 2);
+    let _ = recorder.capacity_remaining(4);
+
+    assert_eq!(
+        recorder.log(),
+        vec![
+            Recorded::Acquire { tick: 3, tokens: 2, result: Ok(()) },
+            Recorded::CapacityRemaining { tick: 4, result: Ok(3) },
+        ]
+    );
+}
+
+#[test]
+fn test_replayer_works_behind_trait_object() {
+    let recorder = Recorder::new(FixedWindowCounterCore::new(2, 10));
+    let _ = recorder.try_acquire_at(0, 3);
+
+    let replayer: Box<dyn RateLimitCore> = Box::new(Replayer::new(recorder.into_log()));
+    assert_eq!(replayer.try_acquire_at(0, 3), Err(SimpleRateLimitError::BeyondCapacity));
+}
+
+#[test]
+#[should_panic(expected = "replay log exhausted")]
+fn test_replayer_panics_when_exhausted() {
+    let replayer = Replayer::new(Vec::new());
+    let _ = replayer.try_acquire_at(0, 1);
+}
+
+#[test]
+#[should_panic(expected = "try_acquire_verbose_at replayed against")]
+fn test_replayer_panics_on_method_mismatch() {
+    let replayer = Replayer::new(vec![Recorded::Acquire { tick: 0, tokens: 1, result: Ok(()) }]);
+    let _ = replayer.try_acquire_verbose_at(0, 1);
+}
@@ -0,0 +1,72 @@
+use rate_guard_core::cores::{RefillPolicy, TokenBucketCore};
+use rate_guard_core::{SimpleRateLimitError, Uint};
+
+#[test]
+fn test_cycles_at_fill_levels() {
+    // Refills of 3 tokens every 5 ticks
+    for &(taken, cycles) in &[(0, 0), (1, 1), (3, 1), (4, 2), (6, 2), (7, 3), (10, 4)] {
+        let bucket = TokenBucketCore::new(10, 5, 3);
+        assert_eq!(bucket.try_acquire_at(0, taken), Ok(()));
+        assert_eq!(bucket.refill_cycles_behind(0), Ok(cycles), "taken {}", taken);
+    }
+}
+
+#[test]
+fn test_cycles_account_for_refill_up_to_tick() {
+    let bucket = TokenBucketCore::new(10, 5, 3);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.refill_cycles_behind(4), Ok(4));
+    // Two refills by tick 10 leave 4 tokens of debt
+    assert_eq!(bucket.refill_cycles_behind(10), Ok(2));
+    assert_eq!(bucket.refill_cycles_behind(20), Ok(0));
+}
+
+#[test]
+fn test_ticks_until_full_relates_to_cycles() {
+    let interval: Uint = 5;
+    for taken in 0..=10 {
+        for tick in 0..12 {
+            let bucket = TokenBucketCore::new(10, interval, 3);
+            assert_eq!(bucket.try_acquire_at(0, taken), Ok(()));
+
+            let cycles = bucket.refill_cycles_behind(tick).unwrap();
+            let ticks = bucket.ticks_until_full(tick).unwrap();
+            if cycles == 0 {
+                assert_eq!(ticks, 0);
+            } else {
+                assert!(ticks > (cycles - 1) * interval && ticks <= cycles * interval, "taken {} tick {}", taken, tick);
+            }
+
+            // The bucket is full exactly `ticks` later
+            assert_eq!(bucket.capacity_remaining(tick + ticks), Ok(10), "taken {} tick {}", taken, tick);
+        }
+    }
+}
+
+#[test]
+fn test_ticks_until_full_mid_interval() {
+    let bucket = TokenBucketCore::new(10, 5, 3);
+    assert_eq!(bucket.try_acquire_at(0, 7), Ok(()));
+    // Refills land at ticks 5, 10 and 15
+    assert_eq!(bucket.ticks_until_full(2), Ok(13));
+    assert_eq!(bucket.ticks_until_full(5), Ok(10));
+}
+
+#[test]
+fn test_does_not_commit_refill() {
+    let bucket = TokenBucketCore::new_with_refill_policy(10, 5, 3, RefillPolicy::OnReadAndWrite);
+    assert_eq!(bucket.try_acquire_at(2, 10), Ok(()));
+    assert_eq!(bucket.refill_cycles_behind(7), Ok(3));
+    assert_eq!(bucket.ticks_until_full(7), Ok(13));
+
+    // The refill at tick 5 was not committed, so tick 3 is still acceptable
+    assert_eq!(bucket.capacity_remaining(3), Ok(0));
+}
+
+#[test]
+fn test_expired_tick() {
+    let bucket = TokenBucketCore::new(10, 5, 3);
+    assert_eq!(bucket.try_acquire_at(12, 1), Ok(()));
+    assert_eq!(bucket.refill_cycles_behind(9), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(bucket.ticks_until_full(9), Err(SimpleRateLimitError::ExpiredTick));
+}
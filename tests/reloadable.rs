@@ -0,0 +1,117 @@
+use rate_guard_core::cores::{
+    FixedWindowCounterCore, FixedWindowCounterCoreConfig, TokenBucketCore, TokenBucketCoreConfig,
+};
+use rate_guard_core::rate_limit::{RateLimitCore, Reconfigure};
+use rate_guard_core::wrappers::Reloadable;
+use rate_guard_core::SimpleRateLimitError;
+
+#[test]
+fn test_token_bucket_reload_to_smaller_capacity_clamps() {
+    let limiter = Reloadable::new(TokenBucketCore::new(100, 10, 10));
+    assert_eq!(limiter.try_acquire_at(0, 30), Ok(()));
+
+    limiter.reload(TokenBucketCoreConfig::new(50, 10, 5), 5).unwrap();
+    assert_eq!(limiter.capacity_remaining(5), Ok(50));
+    assert_eq!(limiter.try_acquire_at(5, 51), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.try_acquire_at(5, 50), Ok(()));
+
+    // The new refill rate applies from the last refill at tick 0
+    assert_eq!(limiter.capacity_remaining(10), Ok(5));
+}
+
+#[test]
+fn test_token_bucket_reload_to_larger_capacity_keeps_usage() {
+    let limiter = Reloadable::new(TokenBucketCore::new(100, 10, 10));
+    assert_eq!(limiter.try_acquire_at(0, 70), Ok(()));
+
+    limiter.reload(TokenBucketCoreConfig::new(500, 10, 10), 0).unwrap();
+    // No free burst: the 30 tokens left stay 30, and the bucket can now grow past 100
+    assert_eq!(limiter.capacity_remaining(0), Ok(30));
+    assert_eq!(limiter.capacity_remaining(100), Ok(130));
+}
+
+#[test]
+fn test_token_bucket_reload_applies_due_refill_first() {
+    let limiter = Reloadable::new(TokenBucketCore::new(100, 10, 10));
+    assert_eq!(limiter.try_acquire_at(0, 100), Ok(()));
+
+    // Two refills of 10 are due at tick 25 under the old configuration
+    limiter.reload(TokenBucketCoreConfig::new(100, 1, 1), 25).unwrap();
+    assert_eq!(limiter.capacity_remaining(25), Ok(25));
+}
+
+#[test]
+fn test_fixed_window_reload_carries_window_count() {
+    let limiter = Reloadable::new(FixedWindowCounterCore::new(10, 100));
+    assert_eq!(limiter.try_acquire_at(10, 8), Ok(()));
+
+    limiter.reload(FixedWindowCounterCoreConfig::new(5, 100), 20).unwrap();
+    assert_eq!(limiter.capacity_remaining(20), Ok(0));
+    assert_eq!(limiter.capacity_remaining(100), Ok(5));
+
+    limiter.reload(FixedWindowCounterCoreConfig::new(20, 100), 100).unwrap();
+    assert_eq!(limiter.try_acquire_at(100, 20), Ok(()));
+}
+
+#[test]
+fn test_fixed_window_reload_to_shorter_window() {
+    let limiter = Reloadable::new(FixedWindowCounterCore::new(10, 100));
+    assert_eq!(limiter.try_acquire_at(10, 6), Ok(()));
+
+    // The usage moves into the new window [30, 39] and is released at 40
+    limiter.reload(FixedWindowCounterCoreConfig::new(10, 10), 35).unwrap();
+    assert_eq!(limiter.capacity_remaining(35), Ok(4));
+    assert_eq!(limiter.capacity_remaining(40), Ok(10));
+    assert_eq!(limiter.try_acquire_at(29, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_reload_with_expired_tick_keeps_old_config() {
+    let limiter = Reloadable::new(FixedWindowCounterCore::new(10, 100));
+    assert_eq!(limiter.try_acquire_at(150, 1), Ok(()));
+
+    assert_eq!(
+        limiter.reload(FixedWindowCounterCoreConfig::new(1, 100), 50),
+        Err(SimpleRateLimitError::ExpiredTick)
+    );
+    assert_eq!(limiter.capacity_remaining(150), Ok(9));
+}
+
+#[test]
+fn test_reconfigured_leaves_original_unchanged() {
+    let bucket = TokenBucketCore::new(100, 10, 10);
+    assert_eq!(bucket.try_acquire_at(0, 30), Ok(()));
+
+    let smaller = Reconfigure::reconfigured(&bucket, TokenBucketCoreConfig::new(10, 10, 1), 0).unwrap();
+    assert_eq!(smaller.capacity_remaining(0), Ok(10));
+    assert_eq!(bucket.capacity_remaining(0), Ok(70));
+}
+
+#[test]
+fn test_reload_mid_flight_from_threads() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let limiter = Arc::new(Reloadable::new(TokenBucketCore::new(1_000, 1, 10)));
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let limiter = Arc::clone(&limiter);
+            thread::spawn(move || {
+                for tick in 0..200 {
+                    let _ = limiter.try_acquire_at(tick, 3);
+                }
+            })
+        })
+        .collect();
+    for capacity in [50, 2_000, 10, 500] {
+        // Workers may have moved the bucket past tick 0, which leaves the old configuration active
+        let _ = limiter.reload(TokenBucketCoreConfig::new(capacity, 1, 10), 0);
+    }
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let remaining = limiter.capacity_remaining(1_000).unwrap();
+    assert!(remaining <= 2_000);
+    assert_eq!(RateLimitCore::steady_state_allowance(&*limiter), (10, 1));
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "test-util")]
+
+use rate_guard_core::Uint;
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::replay::{self, ReplayReport};
+
+#[test]
+fn test_replay_counts_fixed_sequence() {
+    // Window of 10 ticks, 3 tokens per window
+    let requests: Vec<(Uint, Uint)> = vec![(0, 1), (1, 1), (2, 1), (3, 1), (9, 2), (10, 2), (11, 2), (25, 4)];
+    let report = replay::from_timestamps(&FixedWindowCounterCore::new(3, 10), requests);
+
+    // (3,1) and (9,2) are denied until tick 10; (11,2) until tick 20; (25,4) is beyond capacity
+    assert_eq!(
+        report,
+        ReplayReport { total: 8, allowed: 4, denied: 4, max_retry_after: 9 }
+    );
+    assert_eq!(report.denial_rate(), 0.5);
+}
+
+#[test]
+fn test_replay_sorts_by_tick() {
+    let limiter = TokenBucketCore::new(2, 10, 2);
+    let report = replay::from_timestamps(&limiter, vec![(20, 1), (0, 2), (10, 2)]);
+    assert_eq!(report, ReplayReport { total: 3, allowed: 3, denied: 0, max_retry_after: 0 });
+}
+
+#[test]
+fn test_empty_replay() {
+    let report = replay::from_timestamps(&FixedWindowCounterCore::new(3, 10), Vec::new());
+    assert_eq!(report, ReplayReport::default());
+    assert_eq!(report.denial_rate(), 0.0);
+}
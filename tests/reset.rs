@@ -0,0 +1,59 @@
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::SimpleRateLimitError;
+
+/// Drains `limiter` at a late tick, resets it, and checks it behaves like a fresh one.
+fn assert_resets<L: RateLimitCore>(limiter: &L, reset: impl Fn(&L) -> Result<(), SimpleRateLimitError>) {
+    assert_eq!(limiter.try_acquire_at(100, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(100, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::ExpiredTick));
+
+    assert_eq!(reset(limiter), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(10));
+    assert_eq!(limiter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_token_bucket_reset() {
+    assert_resets(&TokenBucketCore::new(10, 5, 1), TokenBucketCore::reset);
+}
+
+#[test]
+fn test_fixed_window_reset() {
+    assert_resets(&FixedWindowCounterCore::new(10, 20), FixedWindowCounterCore::reset);
+}
+
+#[test]
+fn test_fixed_window_reset_clears_smoothing_history() {
+    let counter = FixedWindowCounterCore::new_with_smoothing(10, 20, true);
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(20, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    assert_eq!(counter.reset(), Ok(()));
+    assert_eq!(counter.try_acquire_at(20, 10), Ok(()));
+}
+
+#[test]
+fn test_sliding_window_reset() {
+    assert_resets(&SlidingWindowCounterCore::new(10, 5, 4), SlidingWindowCounterCore::reset);
+}
+
+#[test]
+fn test_approximate_sliding_window_reset() {
+    assert_resets(&ApproximateSlidingWindowCore::new(10, 20), ApproximateSlidingWindowCore::reset);
+}
+
+#[test]
+fn test_reset_restarts_fill_tracking() {
+    let bucket = TokenBucketCore::new_with_fill_tracking(10, 5, 1);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.try_acquire_at(4, 0), Ok(()));
+    assert_eq!(bucket.average_fill(), Ok(Some(0)));
+
+    assert_eq!(bucket.reset(), Ok(()));
+    assert_eq!(bucket.try_acquire_at(4, 0), Ok(()));
+    assert_eq!(bucket.average_fill(), Ok(Some(10)));
+}
@@ -0,0 +1,80 @@
+use rate_guard_core::{Uint, VerboseRateLimitError};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+
+/// Minimal linear congruential generator for deterministic pseudo-random inputs.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: Uint) -> Uint {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Uint::from(self.0 >> 33) % bound
+    }
+}
+
+/// Drives `make()` limiters with random traffic and checks that every
+/// `retry_after_ticks` hint is exact: retrying at `tick + retry_after_ticks`
+/// succeeds, and (when the hint is above one tick) retrying one tick earlier
+/// still fails.
+fn check_retry_after_is_exact<L: RateLimitCore>(capacity: Uint, make: impl Fn(&mut Lcg) -> L) {
+    let mut rng = Lcg(1234);
+
+    for _ in 0..200 {
+        let limiter = make(&mut rng);
+        let mut tick: Uint = 0;
+
+        for _ in 0..30 {
+            tick += rng.next(8);
+            let tokens = 1 + rng.next(capacity);
+            match limiter.try_acquire_verbose_at(tick, tokens) {
+                Ok(()) => {}
+                Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. }) => {
+                    assert!(retry_after_ticks > 0);
+                    let ready = tick + retry_after_ticks;
+                    if retry_after_ticks > 1 {
+                        assert!(
+                            limiter.max_acquirable_at(ready - 1).unwrap() < tokens,
+                            "retry_after_ticks {} at tick {} for {} tokens is too long",
+                            retry_after_ticks, tick, tokens
+                        );
+                    }
+                    assert_eq!(
+                        limiter.try_acquire_verbose_at(ready, tokens),
+                        Ok(()),
+                        "retry_after_ticks {} at tick {} for {} tokens is too short",
+                        retry_after_ticks, tick, tokens
+                    );
+                    tick = ready;
+                }
+                Err(e) => panic!("unexpected {:?}", e),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_token_bucket_retry_after_is_exact() {
+    check_retry_after_is_exact(20, |rng| TokenBucketCore::new(20, 1 + rng.next(6), 1 + rng.next(5)));
+}
+
+#[test]
+fn test_fixed_window_retry_after_is_exact() {
+    check_retry_after_is_exact(20, |rng| FixedWindowCounterCore::new(20, 1 + rng.next(15)));
+}
+
+#[test]
+fn test_smoothed_fixed_window_retry_after_is_exact() {
+    check_retry_after_is_exact(20, |rng| FixedWindowCounterCore::new_with_smoothing(20, 1 + rng.next(15), true));
+}
+
+#[test]
+fn test_sliding_window_retry_after_is_exact() {
+    check_retry_after_is_exact(20, |rng| SlidingWindowCounterCore::new(20, 1 + rng.next(5), 1 + rng.next(5)));
+}
+
+#[test]
+fn test_approximate_sliding_window_retry_after_is_exact() {
+    check_retry_after_is_exact(20, |rng| ApproximateSlidingWindowCore::new(20, 1 + rng.next(15)));
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use rate_guard_core::{SimpleRateLimitError, SimpleRateLimitResult, Uint, VerboseRateLimitError, VerboseRateLimitResult};
+use rate_guard_core::cores::TokenBucketCore;
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::RetryContention;
+
+/// Limiter that reports contention for its first `contended` calls, then admits.
+struct ContendedThenOk {
+    contended: u64,
+    calls: AtomicU64,
+}
+
+impl ContendedThenOk {
+    fn new(contended: u64) -> Self {
+        ContendedThenOk { contended, calls: AtomicU64::new(0) }
+    }
+
+    fn is_contended(&self) -> bool {
+        self.calls.fetch_add(1, Ordering::Relaxed) < self.contended
+    }
+}
+
+impl RateLimitCore for ContendedThenOk {
+    fn try_acquire_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        if self.is_contended() { Err(SimpleRateLimitError::ContentionFailure) } else { Ok(()) }
+    }
+
+    fn try_acquire_verbose_at(&self, _tick: Uint, _tokens: Uint) -> VerboseRateLimitResult {
+        if self.is_contended() { Err(VerboseRateLimitError::ContentionFailure) } else { Ok(()) }
+    }
+
+    fn capacity_remaining(&self, _tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        if self.is_contended() { Err(SimpleRateLimitError::ContentionFailure) } else { Ok(7) }
+    }
+}
+
+#[test]
+fn test_retries_hide_transient_contention() {
+    let limiter = RetryContention::new(ContendedThenOk::new(3), 3);
+    assert_eq!(limiter.try_acquire_at(0, 1), Ok(()));
+    assert_eq!(limiter.inner().calls.load(Ordering::Relaxed), 4);
+
+    let limiter = RetryContention::new(ContendedThenOk::new(2), 3);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 1), Ok(()));
+    let limiter = RetryContention::new(ContendedThenOk::new(2), 3);
+    assert_eq!(limiter.capacity_remaining(0), Ok(7));
+}
+
+#[test]
+fn test_gives_up_after_max_spins() {
+    let limiter = RetryContention::new(ContendedThenOk::new(10), 3);
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::ContentionFailure));
+    assert_eq!(limiter.inner().calls.load(Ordering::Relaxed), 4);
+
+    let limiter = RetryContention::new(ContendedThenOk::new(1), 0);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 1), Err(VerboseRateLimitError::ContentionFailure));
+}
+
+#[test]
+fn test_other_results_pass_through_without_retry() {
+    let limiter = RetryContention::new(TokenBucketCore::new(10, 10, 1), 5);
+    assert_eq!(limiter.try_acquire_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.try_acquire_at(20, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(20, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_concurrent_acquires_see_no_contention() {
+    let limiter = Arc::new(RetryContention::new(TokenBucketCore::new(1_000_000, 1, 1), 1_000_000));
+    let contended = Arc::new(AtomicU64::new(0));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let limiter = limiter.clone();
+            let contended = contended.clone();
+            thread::spawn(move || {
+                for _ in 0..2_000 {
+                    match limiter.try_acquire_at(0, 1) {
+                        Ok(()) => {}
+                        Err(SimpleRateLimitError::ContentionFailure) => {
+                            contended.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(e) => panic!("unexpected {:?}", e),
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // Every attempt got through the lock and consumed exactly one token
+    let failures = contended.load(Ordering::Relaxed);
+    assert_eq!(failures, 0);
+    assert_eq!(limiter.capacity_remaining(0), Ok(1_000_000 - 8_000));
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::wrappers::ScaledByReplicas;
+
+fn limiter(replicas: usize) -> (ScaledByReplicas<FixedWindowCounterCore>, Arc<AtomicUsize>) {
+    let count = Arc::new(AtomicUsize::new(replicas));
+    (ScaledByReplicas::new(FixedWindowCounterCore::new(120, 100), 120, count.clone()), count)
+}
+
+#[test]
+fn test_per_replica_ceiling_follows_replica_count() {
+    let (limiter, replicas) = limiter(4);
+    assert_eq!(limiter.per_replica_capacity(), 30);
+    assert_eq!(limiter.capacity_remaining(0), Ok(30));
+    assert_eq!(limiter.try_acquire_at(0, 30), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Scale in: the share grows to 60 and the 30 already used still counts
+    replicas.store(2, Ordering::Relaxed);
+    assert_eq!(limiter.capacity_remaining(0), Ok(30));
+    assert_eq!(limiter.try_acquire_at(0, 30), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Scale out: usage is already above the new ceiling of 20
+    replicas.store(6, Ordering::Relaxed);
+    assert_eq!(limiter.capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // A new window frees the inner limiter; the ceiling is now 20
+    assert_eq!(limiter.try_acquire_at(100, 20), Ok(()));
+    assert_eq!(limiter.try_acquire_at(100, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_request_above_ceiling_is_beyond_capacity() {
+    let (limiter, replicas) = limiter(3);
+    assert_eq!(limiter.try_acquire_at(0, 41), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 41),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 41, capacity: 40 })
+    );
+    replicas.store(1, Ordering::Relaxed);
+    assert_eq!(limiter.try_acquire_at(0, 41), Ok(()));
+}
+
+#[test]
+fn test_zero_replicas_treated_as_one() {
+    let (limiter, _) = limiter(0);
+    assert_eq!(limiter.per_replica_capacity(), 120);
+    assert_eq!(limiter.try_acquire_at(0, 120), Ok(()));
+}
+
+#[test]
+fn test_verbose_ceiling_denial() {
+    let (limiter, _) = limiter(4);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 25), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 10),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 10, available: 5, retry_after_ticks: 100, available_next_refill: 30 })
+    );
+}
+
+#[test]
+fn test_verbose_retry_waits_for_usage_to_drain() {
+    let count = Arc::new(AtomicUsize::new(2));
+    let limiter = ScaledByReplicas::new(TokenBucketCore::new(100, 10, 10), 100, count);
+    assert_eq!(limiter.try_acquire_verbose_at(0, 50), Ok(()));
+    // 50 of the 100 are in use and the ceiling is 50; 5 more need one refill
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 5),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 5, available: 0, retry_after_ticks: 10, available_next_refill: 10 })
+    );
+    assert_eq!(limiter.try_acquire_at(10, 5), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "global_capacity must not be below the inner limiter's capacity")]
+fn test_new_with_global_capacity_below_inner() {
+    ScaledByReplicas::new(FixedWindowCounterCore::new(120, 100), 100, Arc::new(AtomicUsize::new(1)));
+}
@@ -0,0 +1,45 @@
+use rate_guard_core::{SimpleRateLimitError, Uint};
+use rate_guard_core::combinators::Scheduled;
+use rate_guard_core::cores::FixedWindowCounterCore;
+use rate_guard_core::rate_limit::RateLimitCore;
+
+const PEAK_START: Uint = 100;
+
+fn limiter() -> Scheduled<FixedWindowCounterCore, FixedWindowCounterCore> {
+    Scheduled::new(
+        FixedWindowCounterCore::new(5, 1000),
+        FixedWindowCounterCore::new(50, 1000),
+        |tick| tick >= PEAK_START,
+    )
+}
+
+#[test]
+fn test_routes_by_schedule() {
+    let limiter = limiter();
+    assert_eq!(limiter.try_acquire_at(99, 20), Ok(()));
+    assert_eq!(limiter.off_peak().capacity_remaining(99), Ok(30));
+    assert_eq!(limiter.peak().capacity_remaining(99), Ok(5));
+
+    assert_eq!(limiter.try_acquire_at(100, 5), Ok(()));
+    assert_eq!(limiter.try_acquire_at(100, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.peak().capacity_remaining(100), Ok(0));
+    assert_eq!(limiter.off_peak().capacity_remaining(100), Ok(30));
+}
+
+#[test]
+fn test_switch_does_not_move_consumption() {
+    let limiter = limiter();
+    // Off-peak usage before the switch is not charged to the peak limiter
+    assert_eq!(limiter.try_acquire_at(90, 50), Ok(()));
+    assert_eq!(limiter.capacity_remaining(100), Ok(5));
+    assert_eq!(limiter.try_acquire_at(100, 5), Ok(()));
+}
+
+#[test]
+fn test_trait_object_follows_schedule() {
+    let limiter: Box<dyn RateLimitCore> = Box::new(limiter());
+    assert_eq!(limiter.capacity_remaining(0), Ok(50));
+    assert_eq!(limiter.capacity_remaining(PEAK_START), Ok(5));
+    assert!(limiter.try_acquire_verbose_at(PEAK_START, 6).is_err());
+    assert_eq!(limiter.try_acquire_verbose_at(PEAK_START, 5), Ok(()));
+}
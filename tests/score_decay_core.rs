@@ -0,0 +1,95 @@
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+use rate_guard_core::cores::ScoreDecayCore;
+use rate_guard_core::rate_limit::RateLimitCore;
+
+#[test]
+fn test_throttles_high_scores_and_recovers() {
+    let core = ScoreDecayCore::new(100, 10);
+    assert_eq!(core.try_acquire_at(0, 40), Ok(()));
+    assert_eq!(core.try_acquire_at(1, 40), Ok(()));
+    assert_eq!(core.try_acquire_at(2, 40), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(core.try_acquire_at(3, 40), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Rejected scores were not added; the total keeps decaying
+    let score = core.current_score(3).unwrap();
+    assert!((60..80).contains(&score), "score {}", score);
+    assert_eq!(core.try_acquire_at(12, 40), Ok(()));
+
+    // After many half-lives everything is forgiven
+    assert_eq!(core.current_score(2000), Ok(0));
+    assert_eq!(core.capacity_remaining(2000), Ok(100));
+}
+
+#[test]
+fn test_whole_half_lives_halve_exactly() {
+    let core = ScoreDecayCore::new(1000, 8);
+    assert_eq!(core.try_acquire_at(0, 800), Ok(()));
+    assert_eq!(core.current_score(8), Ok(400));
+    assert_eq!(core.current_score(16), Ok(200));
+    assert_eq!(core.current_score(24), Ok(100));
+}
+
+#[test]
+fn test_decay_is_monotonic_and_close_to_exponential() {
+    let core = ScoreDecayCore::new(1_000_000, 100);
+    assert_eq!(core.try_acquire_at(0, 1_000_000), Ok(()));
+    let mut previous = Uint::MAX;
+    for tick in 0..=300 {
+        let score = core.current_score(tick).unwrap();
+        assert!(score <= previous);
+        let expected = 1_000_000.0 * 0.5f64.powf(tick as f64 / 100.0);
+        assert!((score as f64 - expected).abs() <= 2.0, "tick {} score {} expected {}", tick, score, expected);
+        previous = score;
+    }
+}
+
+#[test]
+fn test_reads_do_not_change_the_outcome() {
+    let read = ScoreDecayCore::new(100, 7);
+    let unread = ScoreDecayCore::new(100, 7);
+    assert_eq!(read.try_acquire_at(0, 90), Ok(()));
+    assert_eq!(unread.try_acquire_at(0, 90), Ok(()));
+    for tick in 0..20 {
+        let _ = read.current_score(tick);
+        let _ = read.capacity_remaining(tick);
+    }
+    assert_eq!(read.current_score(20), unread.current_score(20));
+}
+
+#[test]
+fn test_verbose_errors() {
+    let core = ScoreDecayCore::new(100, 10);
+    assert_eq!(
+        core.try_acquire_verbose_at(0, 101),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 101, capacity: 100 })
+    );
+    assert_eq!(core.try_acquire_verbose_at(0, 80), Ok(()));
+
+    // 80 * 2^(-t/10) <= 60 first holds at t = 5
+    assert_eq!(
+        core.try_acquire_verbose_at(0, 40),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 40, available: 20, retry_after_ticks: 5, available_next_refill: 25 })
+    );
+    assert_eq!(core.try_acquire_at(4, 40), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(core.try_acquire_at(5, 40), Ok(()));
+
+    assert_eq!(core.try_acquire_at(4, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(
+        core.try_acquire_verbose_at(4, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 5 })
+    );
+}
+
+#[test]
+fn test_trait_object() {
+    let core: Box<dyn RateLimitCore> = Box::new(ScoreDecayCore::new(10, 1));
+    assert_eq!(core.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(core.capacity_remaining(0), Ok(0));
+    assert_eq!(core.capacity_remaining(1), Ok(5));
+}
+
+#[test]
+#[should_panic(expected = "half_life_ticks must be greater than 0")]
+fn test_zero_half_life_panics() {
+    ScoreDecayCore::new(10, 0);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "serde")]
+
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCoreConfig, FixedWindowCounterCoreConfig, SlidingWindowCounterCoreConfig,
+    TokenBucketCore, TokenBucketCoreConfig,
+};
+
+#[test]
+fn test_token_bucket_config_round_trip() {
+    let config = TokenBucketCoreConfig { capacity: 100, refill_interval: 10, refill_amount: 5, accrual_cap: Some(20) };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"capacity":100,"refill_interval":10,"refill_amount":5,"accrual_cap":20}"#);
+    assert_eq!(serde_json::from_str::<TokenBucketCoreConfig>(&json).unwrap(), config);
+
+    let bucket = TokenBucketCore::from(serde_json::from_str::<TokenBucketCoreConfig>(&json).unwrap());
+    assert_eq!(bucket.try_acquire_at(0, 100), Ok(()));
+}
+
+#[test]
+fn test_window_configs_round_trip() {
+    let fixed = FixedWindowCounterCoreConfig { capacity: 10, window_size: 60, smoothing: true, grace: 2 };
+    let json = serde_json::to_string(&fixed).unwrap();
+    assert_eq!(serde_json::from_str::<FixedWindowCounterCoreConfig>(&json).unwrap(), fixed);
+
+    let sliding = SlidingWindowCounterCoreConfig::new(10, 6, 10);
+    let json = serde_json::to_string(&sliding).unwrap();
+    assert_eq!(serde_json::from_str::<SlidingWindowCounterCoreConfig>(&json).unwrap(), sliding);
+
+    let approximate = ApproximateSlidingWindowCoreConfig::new(10, 60);
+    let json = serde_json::to_string(&approximate).unwrap();
+    assert_eq!(serde_json::from_str::<ApproximateSlidingWindowCoreConfig>(&json).unwrap(), approximate);
+}
+
+#[test]
+fn test_fixed_window_options_default_when_omitted() {
+    let config: FixedWindowCounterCoreConfig = serde_json::from_str(r#"{"capacity":10,"window_size":60}"#).unwrap();
+    assert_eq!(config, FixedWindowCounterCoreConfig::new(10, 60));
+}
+
+#[test]
+fn test_accrual_cap_may_be_omitted() {
+    let config: TokenBucketCoreConfig =
+        serde_json::from_str(r#"{"capacity":10,"refill_interval":1,"refill_amount":1}"#).unwrap();
+    assert_eq!(config.accrual_cap, None);
+}
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+use std::thread;
+use rate_guard_core::combinators::ShardedTokenBucket;
+use rate_guard_core::cores::TokenBucketCore;
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+
+#[test]
+fn test_splits_capacity_and_refill_across_shards() {
+    let limiter = ShardedTokenBucket::new(3, 10, 2, 5);
+    assert_eq!(limiter.shard_count(), 3);
+
+    // Every shard gets the same capacity; the remainder is dropped
+    let capacities: Vec<Uint> = (0..3).map(|i| limiter.shard(i).capacity_remaining(0).unwrap()).collect();
+    assert_eq!(capacities, vec![3, 3, 3]);
+    assert_eq!(limiter.capacity_remaining(0), Ok(9));
+
+    for i in 0..3 {
+        let shard = limiter.shard(i);
+        let available = shard.capacity_remaining(0).unwrap();
+        assert_eq!(shard.try_acquire_at(0, available), Ok(()));
+    }
+    // Refills of 2, 2 and 1 tokens per interval
+    assert_eq!(limiter.capacity_remaining(2), Ok(5));
+    assert_eq!(limiter.steady_state_allowance(), (5, 2));
+}
+
+#[test]
+fn test_round_robin_routing() {
+    let limiter = ShardedTokenBucket::new(2, 4, 10, 2);
+    assert_eq!(limiter.try_acquire_at(0, 2), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1), Ok(()));
+    assert_eq!(limiter.shard(0).capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.shard(1).capacity_remaining(0), Ok(1));
+
+    // The empty shard denies even though the other still has a token
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 1), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(0));
+}
+
+#[test]
+fn test_hashed_routing_is_stable() {
+    let limiter = ShardedTokenBucket::new(4, 8, 10, 4);
+    assert_eq!(limiter.try_acquire_hashed_at(5, 0, 2), Ok(()));
+    assert_eq!(limiter.try_acquire_hashed_at(9, 0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.shard(1).capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.capacity_remaining(0), Ok(6));
+
+    assert_eq!(
+        limiter.try_acquire_hashed_verbose_at(13, 0, 1),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 10, available_next_refill: 1 })
+    );
+}
+
+#[test]
+fn test_request_larger_than_a_shard_is_beyond_capacity() {
+    let limiter = ShardedTokenBucket::new(4, 100, 1, 4);
+    assert_eq!(limiter.try_acquire_at(0, 26), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 26),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 26, capacity: 25 })
+    );
+}
+
+#[test]
+fn test_request_above_a_share_is_beyond_capacity_on_every_shard() {
+    let limiter = ShardedTokenBucket::new(3, 10, 1, 3);
+    for _ in 0..3 {
+        assert_eq!(limiter.try_acquire_at(0, 4), Err(SimpleRateLimitError::BeyondCapacity));
+    }
+    for index in 0..3 {
+        assert_eq!(limiter.try_acquire_hashed_at(index, 0, 3), Ok(()));
+    }
+}
+
+#[test]
+fn test_max_acquirable_is_the_fullest_shard() {
+    let limiter = ShardedTokenBucket::new(4, 100, 10, 4);
+    assert_eq!(limiter.max_acquirable_at(0), Ok(25));
+
+    assert_eq!(limiter.try_acquire_hashed_at(0, 0, 25), Ok(()));
+    assert_eq!(limiter.try_acquire_hashed_at(1, 0, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_hashed_at(2, 0, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_hashed_at(3, 0, 5), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(50));
+    assert_eq!(limiter.max_acquirable_at(0), Ok(20));
+    assert_eq!(RateLimitCore::max_acquirable_at(&limiter, 0), Ok(20));
+}
+
+#[test]
+#[should_panic(expected = "capacity must be at least shard_count")]
+fn test_capacity_below_shard_count_panics() {
+    ShardedTokenBucket::new(4, 3, 1, 4);
+}
+
+#[test]
+#[should_panic(expected = "refill_amount must be at least shard_count")]
+fn test_refill_below_shard_count_panics() {
+    ShardedTokenBucket::new(4, 100, 1, 3);
+}
+
+#[test]
+#[should_panic(expected = "shard_count must be greater than 0")]
+fn test_zero_shards_panics() {
+    ShardedTokenBucket::new(0, 100, 1, 4);
+}
+
+const THREADS: usize = 8;
+const ATTEMPTS: usize = 20_000;
+
+/// Hammers a limiter from several threads at one tick and returns the number
+/// of `(grants, contention failures)`.
+fn hammer<F>(acquire: F) -> (u64, u64)
+where
+    F: Fn(usize) -> Result<(), SimpleRateLimitError> + Send + Sync + 'static,
+{
+    let acquire = Arc::new(acquire);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread_index| {
+            let acquire = Arc::clone(&acquire);
+            thread::spawn(move || {
+                let (mut granted, mut contended) = (0u64, 0u64);
+                for _ in 0..ATTEMPTS {
+                    match acquire(thread_index) {
+                        Ok(()) => granted += 1,
+                        Err(SimpleRateLimitError::InsufficientCapacity) => {}
+                        Err(SimpleRateLimitError::ContentionFailure) => contended += 1,
+                        Err(e) => panic!("unexpected error: {:?}", e),
+                    }
+                }
+                (granted, contended)
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .fold((0, 0), |(g, c), (granted, contended)| (g + granted, c + contended))
+}
+
+#[test]
+fn test_sharding_reduces_contention_failures() {
+    // More tokens than attempts, so only contention can deny
+    const CAPACITY: Uint = 1_000_000;
+
+    let single = Arc::new(TokenBucketCore::new(CAPACITY, 1, 1));
+    let (single_granted, single_contended) = {
+        let single = Arc::clone(&single);
+        hammer(move |_| single.try_acquire_at(0, 1))
+    };
+
+    // One shard per thread: no two threads ever take the same lock
+    let sharded = Arc::new(ShardedTokenBucket::new(THREADS, CAPACITY, 1, THREADS as Uint));
+    let (sharded_granted, sharded_contended) = {
+        let sharded = Arc::clone(&sharded);
+        hammer(move |thread_index| sharded.try_acquire_hashed_at(thread_index as u64, 0, 1))
+    };
+
+    println!(
+        "single bucket: {} granted, {} contention failures; sharded: {} granted, {} contention failures",
+        single_granted, single_contended, sharded_granted, sharded_contended
+    );
+    assert_eq!(sharded_contended, 0);
+    assert_eq!(sharded_granted, (THREADS * ATTEMPTS) as u64);
+    assert_eq!(single_granted + single_contended, (THREADS * ATTEMPTS) as u64);
+    assert_eq!(sharded.capacity_remaining(0), Ok(CAPACITY - Uint::from(sharded_granted)));
+}
+
+#[test]
+fn test_round_robin_under_contention_never_over_grants() {
+    const CAPACITY: Uint = 4_000;
+
+    let sharded = Arc::new(ShardedTokenBucket::new(THREADS, CAPACITY, 1_000, THREADS as Uint));
+    let (granted, _) = {
+        let sharded = Arc::clone(&sharded);
+        hammer(move |_| sharded.try_acquire_at(0, 1))
+    };
+    assert!(Uint::from(granted) <= CAPACITY);
+    assert_eq!(sharded.capacity_remaining(0), Ok(CAPACITY - Uint::from(granted)));
+}
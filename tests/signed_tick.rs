@@ -0,0 +1,54 @@
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::wrappers::SignedTickAdapter;
+
+#[test]
+fn test_offset_translation() {
+    let limiter = SignedTickAdapter::new(FixedWindowCounterCore::new(10, 100), -1_000);
+    assert_eq!(limiter.to_unsigned(-1_001), None);
+    assert_eq!(limiter.to_unsigned(-1_000), Some(0));
+    assert_eq!(limiter.to_unsigned(-1), Some(999));
+    assert_eq!(limiter.to_unsigned(0), Some(1_000));
+    assert_eq!(limiter.to_unsigned(250), Some(1_250));
+}
+
+#[test]
+fn test_negative_zero_and_positive_ticks() {
+    // Window of 100 unsigned ticks: signed [-1000, -901], ..., [-100, -1], [0, 99]
+    let limiter = SignedTickAdapter::new(FixedWindowCounterCore::new(2, 100), -1_000);
+
+    assert_eq!(limiter.try_acquire_at_signed(-150, 2), Ok(()));
+    assert_eq!(limiter.try_acquire_at_signed(-101, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Signed ticks -100 and 0 fall in different windows after translation
+    assert_eq!(limiter.try_acquire_at_signed(-100, 2), Ok(()));
+    assert_eq!(limiter.try_acquire_at_signed(-1, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at_signed(0, 2), Ok(()));
+    assert_eq!(limiter.capacity_remaining_signed(99), Ok(0));
+    assert_eq!(limiter.capacity_remaining_signed(100), Ok(2));
+
+    // Going backwards in translated time is still expired
+    assert_eq!(limiter.try_acquire_at_signed(-500, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_rejection_below_base() {
+    let limiter = SignedTickAdapter::new(TokenBucketCore::new(10, 10, 1), 0);
+    assert_eq!(limiter.try_acquire_at_signed(-1, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(
+        limiter.try_acquire_verbose_at_signed(-1, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 0 })
+    );
+    assert_eq!(limiter.capacity_remaining_signed(i64::MIN), Err(SimpleRateLimitError::ExpiredTick));
+
+    // Nothing was consumed by the rejected calls
+    assert_eq!(limiter.capacity_remaining_signed(0), Ok(10));
+}
+
+#[test]
+fn test_extreme_bases_do_not_overflow() {
+    let limiter = SignedTickAdapter::new(FixedWindowCounterCore::new(10, 100), i64::MIN);
+    assert_eq!(limiter.to_unsigned(i64::MIN), Some(0));
+    assert_eq!(limiter.to_unsigned(i64::MAX), Some(Uint::from(u64::MAX)));
+    assert_eq!(limiter.try_acquire_at_signed(i64::MAX, 1), Ok(()));
+}
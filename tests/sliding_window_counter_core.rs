@@ -1,4 +1,4 @@
-use rate_guard_core::{ SimpleRateLimitError};
+use rate_guard_core::{ SimpleRateLimitError, VerboseRateLimitError};
 use rate_guard_core::cores::SlidingWindowCounterCore;
 
 #[test]
@@ -277,7 +277,7 @@ fn test_current_capacity_at() {
     // Check capacity at different ticks without state updates
     assert_eq!(counter.current_capacity_at(7).unwrap(), 50);  // Both buckets within window
     assert_eq!(counter.current_capacity_at(15).unwrap(), 50); // Both buckets still within window [0, 15]
-    assert_eq!(counter.current_capacity_at(25).unwrap(), 70); // Only bucket 1 within window [5, 25]
+    assert_eq!(counter.current_capacity_at(24).unwrap(), 70); // Only bucket 1 within window [5, 24]
 }
 
 #[test]
@@ -388,4 +388,92 @@ fn test_window_boundary_conditions() {
     
     // tick 40: sliding window [10, 40], bucket 0 [0-9] definitely expires
     assert_eq!(counter.capacity_remaining_or_0(40), 60);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_next_expiry_reports_oldest_bucket() {
+    let counter = SlidingWindowCounterCore::new(100, 10, 4); // window_ticks = 40
+
+    assert_eq!(counter.next_expiry(0), Ok(None));
+
+    // Populate buckets [10-19], [20-29] and [30-39]
+    assert_eq!(counter.try_acquire_at(12, 5), Ok(()));
+    assert_eq!(counter.try_acquire_at(25, 7), Ok(()));
+    assert_eq!(counter.try_acquire_at(38, 3), Ok(()));
+
+    // Bucket [10-19] is the oldest and is counted up to tick 49
+    assert_eq!(counter.next_expiry(38), Ok(Some((50, 5))));
+    assert_eq!(counter.current_capacity_at(49), Ok(85));
+    assert_eq!(counter.current_capacity_at(50), Ok(90));
+
+    // Once it has expired, the next oldest bucket is reported
+    assert_eq!(counter.next_expiry(50), Ok(Some((60, 7))));
+    assert_eq!(counter.next_expiry(60), Ok(Some((70, 3))));
+    assert_eq!(counter.next_expiry(70), Ok(None));
+}
+
+#[test]
+fn test_next_expiry_skips_empty_buckets() {
+    let counter = SlidingWindowCounterCore::new(100, 10, 4);
+    assert_eq!(counter.try_acquire_at(5, 1), Ok(()));
+    assert_eq!(counter.try_acquire_at(15, 0), Ok(()));
+    assert_eq!(counter.try_acquire_at(25, 2), Ok(()));
+
+    assert_eq!(counter.next_expiry(25), Ok(Some((40, 1))));
+    // Read-only: querying does not change the window
+    assert_eq!(counter.capacity_remaining(25), Ok(97));
+}
+
+#[test]
+fn test_next_expiry_matches_when_acquires_see_tokens_freed() {
+    let counter = SlidingWindowCounterCore::new(10, 10, 4);
+    assert_eq!(counter.try_acquire_at(12, 4), Ok(()));
+    assert_eq!(counter.try_acquire_at(25, 6), Ok(()));
+
+    let (expiry_tick, freed) = counter.next_expiry(25).unwrap().unwrap();
+    assert_eq!((expiry_tick, freed), (50, 4));
+    assert_eq!(counter.try_acquire_at(expiry_tick - 1, freed), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.try_acquire_at(expiry_tick, freed), Ok(()));
+}
+
+#[test]
+fn test_verbose_retry_releases_buckets_oldest_first_across_the_ring() {
+    let counter = SlidingWindowCounterCore::new(10, 10, 4); // window_ticks = 40
+    assert_eq!(counter.try_acquire_at(25, 3), Ok(()));
+    assert_eq!(counter.try_acquire_at(35, 3), Ok(()));
+    // Wraps around to the first slot, which now holds the newest bucket
+    assert_eq!(counter.try_acquire_at(45, 4), Ok(()));
+
+    // The buckets starting at 20 and 30 must both expire, at tick 70
+    assert_eq!(
+        counter.try_acquire_verbose_at(45, 5),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 5, available: 0, retry_after_ticks: 25, available_next_refill: 3 })
+    );
+}
+
+#[test]
+fn test_try_new_rejects_zero_parameters() {
+    use rate_guard_core::InvalidConfig;
+
+    assert_eq!(SlidingWindowCounterCore::try_new(0, 10, 4).err(), Some(InvalidConfig::ZeroCapacity));
+    assert_eq!(SlidingWindowCounterCore::try_new(10, 0, 4).err(), Some(InvalidConfig::ZeroBucketTicks));
+    assert_eq!(SlidingWindowCounterCore::try_new(10, 10, 0).err(), Some(InvalidConfig::ZeroBucketCount));
+    assert_eq!(InvalidConfig::ZeroBucketCount.to_string(), "bucket_count must be greater than 0");
+
+    let counter = SlidingWindowCounterCore::try_new(10, 5, 4).unwrap();
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+}
+
+#[test]
+fn test_try_new_rejects_unallocatable_bucket_count() {
+    use rate_guard_core::{InvalidConfig, Uint};
+
+    assert_eq!(
+        SlidingWindowCounterCore::try_new(10, 1, Uint::MAX).err(),
+        Some(InvalidConfig::BucketCountTooLarge { bucket_count: Uint::MAX })
+    );
+    assert_eq!(
+        SlidingWindowCounterCore::try_new(10, 1, Uint::MAX / 16).err(),
+        Some(InvalidConfig::BucketCountTooLarge { bucket_count: Uint::MAX / 16 })
+    );
+}
@@ -51,6 +51,7 @@ fn fill_window_then_fail() {
             acquiring,
             available,
             retry_after_ticks,
+            ..
         } => {
             assert_eq!(acquiring, 2);
             assert_eq!(available, 1);
@@ -86,3 +87,28 @@ fn partial_bucket_recovery_allows_progressive_acquire() {
     // tick 6: bucket at tick=0 is now expired
     assert_eq!(limiter.try_acquire_verbose_at(6, 1), Ok(()));
 }
+
+#[test]
+fn retry_after_releases_buckets_in_start_order_across_wraparound() {
+    // window_ticks = 20; slots wrap every 4 buckets
+    let limiter = new_sliding_window(10, 5, 4);
+    assert_eq!(limiter.try_acquire_verbose_at(12, 2), Ok(())); // slot 2, start 10
+    assert_eq!(limiter.try_acquire_verbose_at(17, 3), Ok(())); // slot 3, start 15
+    assert_eq!(limiter.try_acquire_verbose_at(21, 1), Ok(())); // slot 0, start 20
+    assert_eq!(limiter.try_acquire_verbose_at(27, 4), Ok(())); // slot 1, start 25
+
+    // The next refill is slot 2 leaving the window, which frees its 2 tokens
+    let insufficient = |acquiring, available, retry_after_ticks| {
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring, available, retry_after_ticks, available_next_refill: 2 })
+    };
+
+    // Slot 2 (start 10) expires first at 30, even though slot 0 has a lower index
+    assert_eq!(limiter.try_acquire_verbose_at(28, 1), insufficient(1, 0, 2));
+    assert_eq!(limiter.try_acquire_verbose_at(28, 2), insufficient(2, 0, 2));
+    assert_eq!(limiter.try_acquire_verbose_at(28, 3), insufficient(3, 0, 7));
+    assert_eq!(limiter.try_acquire_verbose_at(28, 6), insufficient(6, 0, 12));
+    assert_eq!(limiter.try_acquire_verbose_at(28, 10), insufficient(10, 0, 17));
+
+    // Retrying exactly then succeeds
+    assert_eq!(limiter.try_acquire_verbose_at(35, 5), Ok(()));
+}
@@ -0,0 +1,66 @@
+use rate_guard_core::cores::SlidingWindowCounterCore;
+use rate_guard_core::{SimpleRateLimitError, Uint, VerboseRateLimitError};
+
+/// Start of the last whole 10-tick bucket window of 4 buckets below `Uint::MAX`.
+const LAST_WINDOW_START: Uint = (Uint::MAX / 10) * 10 - 40;
+
+#[test]
+fn test_buckets_expire_normally_near_max() {
+    let counter = SlidingWindowCounterCore::new(10, 10, 4);
+    let t0 = LAST_WINDOW_START;
+
+    assert_eq!(counter.try_acquire_at(t0, 6), Ok(()));
+    assert_eq!(counter.try_acquire_at(t0 + 15, 4), Ok(()));
+    assert_eq!(counter.try_acquire_at(t0 + 39, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // The first bucket leaves the window exactly window_ticks after it started
+    assert_eq!(counter.capacity_remaining(t0 + 40), Ok(6));
+    assert_eq!(counter.try_acquire_at(Uint::MAX, 6), Ok(()));
+    assert_eq!(counter.capacity_remaining(Uint::MAX), Ok(0));
+}
+
+#[test]
+fn test_retry_after_near_max() {
+    let counter = SlidingWindowCounterCore::new(10, 10, 4);
+    let t0 = LAST_WINDOW_START;
+    counter.try_acquire_at(t0, 10).unwrap();
+
+    match counter.try_acquire_verbose_at(t0 + 5, 1) {
+        Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. }) => assert_eq!(retry_after_ticks, 35),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(counter.next_expiry(t0 + 5), Ok(Some((t0 + 40, 10))));
+}
+
+#[test]
+fn test_saturated_window_at_max_tick_does_not_panic() {
+    // bucket_ticks * bucket_count saturates to Uint::MAX
+    let counter = SlidingWindowCounterCore::new(100, Uint::MAX / 2, 4);
+
+    assert_eq!(counter.try_acquire_at(Uint::MAX - 1, 60), Ok(()));
+    assert_eq!(counter.try_acquire_at(Uint::MAX, 40), Ok(()));
+    assert_eq!(counter.try_acquire_at(Uint::MAX, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert!(counter.try_acquire_verbose_at(Uint::MAX, 1).is_err());
+    assert_eq!(counter.current_rate(Uint::MAX), Ok(100));
+    assert_eq!(counter.next_expiry(Uint::MAX), Ok(Some((Uint::MAX, 100))));
+}
+
+#[test]
+fn test_capacity_near_max_does_not_overflow() {
+    let counter = SlidingWindowCounterCore::new(Uint::MAX, 10, 4);
+    assert_eq!(counter.try_acquire_at(0, Uint::MAX - 1), Ok(()));
+    assert_eq!(counter.try_acquire_at(15, 1), Ok(()));
+    assert_eq!(counter.capacity_remaining(15), Ok(0));
+    assert!(counter.try_acquire_verbose_at(15, Uint::MAX).is_err());
+}
+
+#[cfg(feature = "tick-u128")]
+#[test]
+fn test_bucket_slots_stay_distinct_beyond_u64_ticks() {
+    // Adjacent buckets 2^64 - 1 and 2^64 must use different slots
+    let counter = SlidingWindowCounterCore::new(10, 1, 3);
+    let tick: Uint = u64::MAX as Uint;
+    assert_eq!(counter.try_acquire_at(tick, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(tick + 1, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.capacity_remaining(tick + 1), Ok(0));
+}
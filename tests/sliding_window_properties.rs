@@ -0,0 +1,99 @@
+use rate_guard_core::{SimpleRateLimitError, Uint};
+use rate_guard_core::cores::SlidingWindowCounterCore;
+
+/// Minimal linear congruential generator for deterministic pseudo-random inputs.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: Uint) -> Uint {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Uint::from(self.0 >> 33) % bound
+    }
+}
+
+/// Brute-force sliding log of every admitted acquire.
+struct SlidingLog {
+    bucket_ticks: Uint,
+    window_ticks: Uint,
+    entries: Vec<(Uint, Uint)>,
+}
+
+impl SlidingLog {
+    /// Tokens the core should count at `tick`: entries whose bucket starts inside
+    /// `[tick - window_ticks + 1, tick]`.
+    fn bucketed_usage(&self, tick: Uint) -> Uint {
+        self.entries
+            .iter()
+            .filter(|&&(t, _)| tick - (t / self.bucket_ticks) * self.bucket_ticks < self.window_ticks)
+            .map(|&(_, tokens)| tokens)
+            .sum()
+    }
+
+    /// Tokens acquired within the exact window `[tick - window_ticks + 1, tick]`.
+    fn exact_usage(&self, tick: Uint) -> Uint {
+        self.entries
+            .iter()
+            .filter(|&&(t, _)| tick - t < self.window_ticks)
+            .map(|&(_, tokens)| tokens)
+            .sum()
+    }
+}
+
+fn run_random_sequence(rng: &mut Lcg) {
+    let capacity = 1 + rng.next(50);
+    let bucket_ticks = 1 + rng.next(6);
+    let bucket_count = 1 + rng.next(5);
+    let window_ticks = bucket_ticks * bucket_count;
+    let counter = SlidingWindowCounterCore::new(capacity, bucket_ticks, bucket_count);
+    let mut log = SlidingLog { bucket_ticks, window_ticks, entries: Vec::new() };
+    let params = format!("capacity={} bucket_ticks={} bucket_count={}", capacity, bucket_ticks, bucket_count);
+
+    let mut tick: Uint = 0;
+    for _ in 0..80 {
+        // Mostly small steps, with occasional jumps across several windows
+        tick += if rng.next(10) == 0 { rng.next(window_ticks * 4) } else { rng.next(bucket_ticks + 1) };
+        let tokens = 1 + rng.next(capacity);
+
+        let used = capacity - counter.capacity_remaining(tick).unwrap();
+        assert!(used <= capacity, "{} tick={}", params, tick);
+        assert_eq!(used, log.bucketed_usage(tick), "{} tick={}", params, tick);
+
+        // The bucketed count never exceeds the exact count, and misses at most the
+        // tokens of the one bucket straddling the window start
+        let exact = log.exact_usage(tick);
+        let straddling_start = (tick + 1).saturating_sub(window_ticks) / bucket_ticks * bucket_ticks;
+        let straddling: Uint = log.entries
+            .iter()
+            .filter(|&&(t, _)| tick - t < window_ticks && t / bucket_ticks * bucket_ticks == straddling_start)
+            .map(|&(_, tokens)| tokens)
+            .sum();
+        assert!(used <= exact, "{} tick={}", params, tick);
+        assert!(exact - used <= straddling, "{} tick={}", params, tick);
+
+        let result = counter.try_acquire_at(tick, tokens);
+        if used + tokens <= capacity {
+            assert_eq!(result, Ok(()), "{} tick={} tokens={}", params, tick, tokens);
+            log.entries.push((tick, tokens));
+        } else {
+            assert_eq!(result, Err(SimpleRateLimitError::InsufficientCapacity), "{} tick={} tokens={}", params, tick, tokens);
+        }
+    }
+}
+
+#[test]
+fn test_in_window_usage_matches_bucketed_sliding_log() {
+    let mut rng = Lcg(42);
+    for _ in 0..500 {
+        run_random_sequence(&mut rng);
+    }
+}
+
+#[test]
+fn test_single_bucket_behaves_like_fixed_window() {
+    // bucket_count = 1 maps every tick to slot 0, so each new bucket resets it
+    let counter = SlidingWindowCounterCore::new(10, 5, 1);
+    assert_eq!(counter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(4, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(counter.try_acquire_at(5, 10), Ok(()));
+    assert_eq!(counter.try_acquire_at(103, 10), Ok(()));
+}
@@ -0,0 +1,118 @@
+use rate_guard_core::cores::{TokenBucketCore, TokenBucketCoreConfig, TokenBucketSnapshot};
+use rate_guard_core::{SimpleRateLimitError, Uint};
+
+#[test]
+fn test_snapshot_captures_config_and_state() {
+    let bucket = TokenBucketCore::from(TokenBucketCoreConfig {
+        capacity: 100,
+        refill_interval: 10,
+        refill_amount: 5,
+        accrual_cap: Some(20),
+    });
+    assert_eq!(bucket.try_acquire_at(0, 60), Ok(()));
+    assert_eq!(bucket.try_acquire_at(12, 10), Ok(()));
+
+    let snapshot = bucket.snapshot().unwrap();
+    assert_eq!(snapshot.config, TokenBucketCoreConfig {
+        capacity: 100,
+        refill_interval: 10,
+        refill_amount: 5,
+        accrual_cap: Some(20),
+    });
+    assert_eq!(snapshot.available, 35);
+    assert_eq!(snapshot.last_refill_tick, 10);
+    assert_eq!(snapshot.permille_credit, 0);
+}
+
+#[test]
+fn test_restore_continues_where_snapshot_left_off() {
+    let original = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(original.try_acquire_at(0, 8), Ok(()));
+    assert_eq!(original.try_acquire_at(7, 1), Ok(()));
+
+    let restored = TokenBucketCore::restore(original.snapshot().unwrap());
+    for tick in [7, 9, 15, 40] {
+        assert_eq!(restored.capacity_remaining(tick), original.capacity_remaining(tick));
+    }
+    assert_eq!(restored.try_acquire_at(40, 10), Ok(()));
+    assert_eq!(restored.try_acquire_at(40, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_restore_rejects_ticks_before_snapshot() {
+    let original = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(original.try_acquire_at(20, 1), Ok(()));
+
+    let restored = TokenBucketCore::restore(original.snapshot().unwrap());
+    assert_eq!(restored.try_acquire_at(19, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_restore_preserves_permille_credit() {
+    let original = TokenBucketCore::new(10, 100, 1);
+    assert_eq!(original.try_acquire_permille_at(0, 1500), Ok(()));
+
+    let snapshot = original.snapshot().unwrap();
+    assert_eq!(snapshot.permille_credit, 500);
+
+    let restored = TokenBucketCore::restore(snapshot);
+    assert_eq!(restored.try_acquire_permille_at(0, 500), Ok(()));
+    assert_eq!(restored.capacity_remaining(0), Ok(8));
+}
+
+#[test]
+fn test_restore_clamps_out_of_range_fields() {
+    let restored = TokenBucketCore::restore(TokenBucketSnapshot {
+        config: TokenBucketCoreConfig::new(10, 5, 2),
+        available: 50,
+        last_refill_tick: 0,
+        permille_credit: 5000,
+    });
+    assert_eq!(restored.capacity_remaining(0), Ok(10));
+    // A full bucket has no fractional part
+    assert_eq!(restored.snapshot().unwrap().permille_credit, 0);
+
+    let restored = TokenBucketCore::restore(TokenBucketSnapshot {
+        config: TokenBucketCoreConfig::new(10, 5, 2),
+        available: 4,
+        last_refill_tick: 0,
+        permille_credit: 5000,
+    });
+    assert_eq!(restored.snapshot().unwrap().permille_credit, 999);
+}
+
+#[test]
+#[should_panic(expected = "capacity must be greater than 0")]
+fn test_restore_zero_capacity_panics() {
+    TokenBucketCore::restore(TokenBucketSnapshot {
+        config: TokenBucketCoreConfig::new(0, 5, 2),
+        available: 0,
+        last_refill_tick: 0,
+        permille_credit: 0,
+    });
+}
+
+#[test]
+fn test_snapshot_large_tick() {
+    let bucket = TokenBucketCore::new(10, 1, 1);
+    let tick = Uint::MAX - 1;
+    assert_eq!(bucket.try_acquire_at(tick, 3), Ok(()));
+
+    let restored = TokenBucketCore::restore(bucket.snapshot().unwrap());
+    assert_eq!(restored.capacity_remaining(tick), Ok(7));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_snapshot_serde_round_trip() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(3, 4), Ok(()));
+
+    let snapshot = bucket.snapshot().unwrap();
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(
+        json,
+        r#"{"config":{"capacity":10,"refill_interval":5,"refill_amount":2,"accrual_cap":null},"available":6,"last_refill_tick":0,"permille_credit":0}"#
+    );
+    assert_eq!(serde_json::from_str::<TokenBucketSnapshot>(&json).unwrap(), snapshot);
+}
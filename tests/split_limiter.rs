@@ -0,0 +1,48 @@
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::combinators::SplitLimiter;
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+
+fn create_limiter() -> SplitLimiter<FixedWindowCounterCore, TokenBucketCore> {
+    // Successes: 100 per 60 ticks. Errors: burst of 3, one more every 10 ticks.
+    SplitLimiter::new(FixedWindowCounterCore::new(100, 60), TokenBucketCore::new(3, 10, 1))
+}
+
+#[test]
+fn test_error_budget_exhausts_independently() {
+    let limiter = create_limiter();
+
+    assert_eq!(limiter.try_acquire_at(0, 3, true), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1, true), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // Successes are unaffected by the exhausted error budget
+    assert_eq!(limiter.capacity_remaining(0, false), Ok(100));
+    assert_eq!(limiter.try_acquire_at(0, 50, false), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0, false), Ok(50));
+    assert_eq!(limiter.capacity_remaining(0, true), Ok(0));
+
+    // The error budget recovers on its own schedule
+    assert_eq!(limiter.try_acquire_at(10, 1, true), Ok(()));
+}
+
+#[test]
+fn test_success_budget_exhausts_independently() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_at(0, 100, false), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1, false), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(limiter.try_acquire_at(0, 1, true), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0, true), Ok(2));
+}
+
+#[test]
+fn test_verbose_routes_to_selected_budget() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_verbose_at(0, 3, true), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 1, true),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 1, available: 0, retry_after_ticks: 10, available_next_refill: 1 })
+    );
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 101, false),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 101, capacity: 100 })
+    );
+}
@@ -0,0 +1,134 @@
+use rate_guard_core::{StateDecodeError, Uint};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, ApproximateSlidingWindowCoreConfig, FixedWindowCounterCore,
+    FixedWindowCounterCoreConfig, SlidingWindowCounterCore, SlidingWindowCounterCoreConfig, TokenBucketCore,
+    TokenBucketCoreConfig,
+};
+
+const TICK_WIDTH: usize = std::mem::size_of::<Uint>();
+
+#[test]
+fn test_token_bucket_round_trip() {
+    let config = TokenBucketCoreConfig::new(10, 5, 2);
+    let bucket: TokenBucketCore = config.clone().into();
+    assert_eq!(bucket.try_acquire_at(3, 7), Ok(()));
+    assert_eq!(bucket.try_acquire_permille_at(3, 500), Ok(()));
+
+    let bytes = bucket.to_bytes().unwrap();
+    assert_eq!(bytes.len(), 3 + 3 * TICK_WIDTH);
+    assert_eq!(&bytes[..3], &[2, TICK_WIDTH as u8, 1]);
+
+    let restored = TokenBucketCore::from_bytes(config, &bytes).unwrap();
+    assert_eq!(restored.to_bytes().unwrap(), bytes);
+    for tick in [3, 5, 12, 40] as [Uint; 4] {
+        assert_eq!(restored.capacity_remaining(tick), bucket.capacity_remaining(tick));
+    }
+    assert_eq!(restored.try_acquire_at(40, 10), bucket.try_acquire_at(40, 10));
+}
+
+#[test]
+fn test_fixed_window_round_trip() {
+    let config = FixedWindowCounterCoreConfig { capacity: 10, window_size: 10, smoothing: true, grace: 0 };
+    let counter: FixedWindowCounterCore = config.clone().into();
+    assert_eq!(counter.try_acquire_at(2, 8), Ok(()));
+    assert_eq!(counter.try_acquire_at(13, 1), Ok(()));
+
+    let bytes = counter.to_bytes().unwrap();
+    let restored = FixedWindowCounterCore::from_bytes(config, &bytes).unwrap();
+    assert_eq!(restored.to_bytes().unwrap(), bytes);
+    for tick in [13, 15, 19, 25] as [Uint; 4] {
+        assert_eq!(restored.capacity_remaining(tick), counter.capacity_remaining(tick));
+    }
+}
+
+#[test]
+fn test_sliding_window_round_trip() {
+    let config = SlidingWindowCounterCoreConfig::new(10, 5, 4);
+    let counter: SlidingWindowCounterCore = config.clone().into();
+    for (tick, tokens) in [(0, 2), (6, 3), (12, 1), (17, 2)] as [(Uint, Uint); 4] {
+        assert_eq!(counter.try_acquire_at(tick, tokens), Ok(()));
+    }
+
+    let bytes = counter.to_bytes().unwrap();
+    assert_eq!(bytes.len(), 3 + (2 + 2 * 4) * TICK_WIDTH);
+    let restored = SlidingWindowCounterCore::from_bytes(config, &bytes).unwrap();
+    assert_eq!(restored.to_bytes().unwrap(), bytes);
+    for tick in [17, 20, 22, 26, 40] as [Uint; 5] {
+        assert_eq!(restored.capacity_remaining(tick), counter.capacity_remaining(tick));
+    }
+}
+
+#[test]
+fn test_approximate_sliding_window_round_trip() {
+    let config = ApproximateSlidingWindowCoreConfig::new(10, 10);
+    let counter: ApproximateSlidingWindowCore = config.clone().into();
+    assert_eq!(counter.try_acquire_at(4, 6), Ok(()));
+    assert_eq!(counter.try_acquire_at(13, 2), Ok(()));
+
+    let bytes = counter.to_bytes().unwrap();
+    let restored = ApproximateSlidingWindowCore::from_bytes(config, &bytes).unwrap();
+    assert_eq!(restored.to_bytes().unwrap(), bytes);
+    for tick in [13, 16, 19, 24] as [Uint; 4] {
+        assert_eq!(restored.capacity_remaining(tick), counter.capacity_remaining(tick));
+    }
+}
+
+#[test]
+fn test_version_mismatch_is_rejected() {
+    let config = TokenBucketCoreConfig::new(10, 5, 2);
+    let mut bytes = TokenBucketCore::from(config.clone()).to_bytes().unwrap();
+    bytes[0] = 1;
+    assert_eq!(
+        TokenBucketCore::from_bytes(config, &bytes).err(),
+        Some(StateDecodeError::UnsupportedVersion(1))
+    );
+}
+
+#[test]
+fn test_malformed_headers_are_rejected() {
+    let config = FixedWindowCounterCoreConfig::new(10, 10);
+    let bytes = FixedWindowCounterCore::from(config.clone()).to_bytes().unwrap();
+
+    let mut wrong_width = bytes.clone();
+    wrong_width[1] = 4;
+    assert_eq!(
+        FixedWindowCounterCore::from_bytes(config.clone(), &wrong_width).err(),
+        Some(StateDecodeError::TickWidthMismatch { expected: TICK_WIDTH as u8, found: 4 })
+    );
+
+    assert_eq!(
+        TokenBucketCore::from_bytes(TokenBucketCoreConfig::new(10, 5, 2), &bytes).err(),
+        Some(StateDecodeError::CoreMismatch { expected: 1, found: 2 })
+    );
+
+    assert_eq!(
+        FixedWindowCounterCore::from_bytes(config.clone(), &bytes[..bytes.len() - 1]).err(),
+        Some(StateDecodeError::InvalidLength)
+    );
+    let mut trailing = bytes;
+    trailing.push(0);
+    assert_eq!(FixedWindowCounterCore::from_bytes(config.clone(), &trailing).err(), Some(StateDecodeError::InvalidLength));
+    assert_eq!(FixedWindowCounterCore::from_bytes(config, &[]).err(), Some(StateDecodeError::InvalidLength));
+}
+
+#[test]
+fn test_state_not_matching_config_is_rejected() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    let bytes = bucket.to_bytes().unwrap();
+    assert_eq!(
+        TokenBucketCore::from_bytes(TokenBucketCoreConfig::new(5, 5, 2), &bytes).err(),
+        Some(StateDecodeError::InvalidState)
+    );
+
+    let counter = SlidingWindowCounterCore::new(10, 5, 4);
+    let bytes = counter.to_bytes().unwrap();
+    assert_eq!(
+        SlidingWindowCounterCore::from_bytes(SlidingWindowCounterCoreConfig::new(10, 5, 3), &bytes).err(),
+        Some(StateDecodeError::InvalidState)
+    );
+}
+
+#[test]
+fn test_decode_error_display() {
+    assert_eq!(StateDecodeError::UnsupportedVersion(9).to_string(), "unsupported state format version 9");
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+
+use rate_guard_core::Uint;
+use rate_guard_core::combinators::{AbTest, AllOf, Labeled, MoreGenerous, Scheduled, WithReserve};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, LifetimeQuotaCore, ScoreDecayCore,
+    SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::{DryRun, Margin, PerRequestCap, ScaledByReplicas};
+
+#[test]
+fn test_token_bucket_allowance_is_reduced_refill_rate() {
+    assert_eq!(TokenBucketCore::new(100, 10, 4).steady_state_allowance(), (2, 5));
+    assert_eq!(TokenBucketCore::new(100, 3, 7).steady_state_allowance(), (7, 3));
+    assert_eq!(TokenBucketCore::new(100, 6, 6).steady_state_allowance(), (1, 1));
+}
+
+#[test]
+fn test_window_allowances_are_reduced_capacity_per_window() {
+    assert_eq!(FixedWindowCounterCore::new(100, 60).steady_state_allowance(), (5, 3));
+    assert_eq!(ApproximateSlidingWindowCore::new(100, 60).steady_state_allowance(), (5, 3));
+    // Window of 4 buckets of 15 ticks
+    assert_eq!(SlidingWindowCounterCore::new(100, 15, 4).steady_state_allowance(), (5, 3));
+}
+
+#[test]
+fn test_equal_sustained_rates_compare_equal() {
+    let limiters: Vec<Box<dyn RateLimitCore>> = vec![
+        Box::new(TokenBucketCore::new(10, 3, 5)),
+        Box::new(FixedWindowCounterCore::new(100, 60)),
+        Box::new(SlidingWindowCounterCore::new(50, 10, 3)),
+        Box::new(ApproximateSlidingWindowCore::new(20, 12)),
+    ];
+    for limiter in &limiters {
+        assert_eq!(limiter.steady_state_allowance(), (5, 3));
+    }
+}
+
+#[test]
+fn test_lifetime_quota_has_no_renewing_allowance() {
+    assert_eq!(LifetimeQuotaCore::new(100).steady_state_allowance(), (0, 1));
+}
+
+#[test]
+fn test_pass_through_wrappers_report_inner_allowance() {
+    assert_eq!(DryRun::new(TokenBucketCore::new(10, 4, 2)).steady_state_allowance(), (1, 2));
+    assert_eq!(PerRequestCap::new(FixedWindowCounterCore::new(90, 30), 5).steady_state_allowance(), (3, 1));
+}
+
+#[test]
+fn test_ceiling_wrappers_scale_inner_allowance() {
+    // A ceiling of 80 out of 100 per 60-tick window
+    assert_eq!(Margin::new(FixedWindowCounterCore::new(100, 60), 100, 8000).steady_state_allowance(), (4, 3));
+
+    let replicas = Arc::new(AtomicUsize::new(2));
+    let scaled = ScaledByReplicas::new(FixedWindowCounterCore::new(100, 60), 100, replicas);
+    assert_eq!(scaled.steady_state_allowance(), (5, 6));
+}
+
+#[test]
+fn test_score_decay_allowance_is_decay_at_threshold() {
+    // Halving every tick removes half the threshold per tick
+    assert_eq!(ScoreDecayCore::new(100, 1).steady_state_allowance(), (50, 1));
+
+    // 1000 * (1 - 2^(-1/10)) is about 66.97 per tick
+    let (score, ticks) = ScoreDecayCore::new(1000, 10).steady_state_allowance();
+    assert_eq!(score / ticks, 66);
+}
+
+#[test]
+fn test_all_of_and_scheduled_report_the_lowest_allowance() {
+    let all_of = AllOf::new(vec![
+        Labeled::new("burst", TokenBucketCore::new(10, 2, 1)),
+        Labeled::new("quota", FixedWindowCounterCore::new(100, 60)),
+    ]);
+    assert_eq!(all_of.steady_state_allowance(), (1, 2));
+
+    let scheduled = Scheduled::new(TokenBucketCore::new(10, 2, 1), TokenBucketCore::new(10, 1, 2), |tick| tick % 2 == 0);
+    assert_eq!(scheduled.steady_state_allowance(), (1, 2));
+}
+
+#[test]
+fn test_combinators_charging_one_child_report_the_sum() {
+    let more_generous = MoreGenerous::new(vec![
+        Box::new(TokenBucketCore::new(10, 3, 5)),
+        Box::new(FixedWindowCounterCore::new(100, 60)),
+    ]);
+    assert_eq!(more_generous.steady_state_allowance(), (10, 3));
+
+    let with_reserve = WithReserve::new(TokenBucketCore::new(10, 1, 1), TokenBucketCore::new(5, 10, 1));
+    assert_eq!(with_reserve.steady_state_allowance(), (11, 10));
+}
+
+#[test]
+fn test_ab_test_reports_enforced_allowance() {
+    let ab = AbTest::new(TokenBucketCore::new(10, 4, 2), TokenBucketCore::new(10, 1, 1));
+    assert_eq!(ab.steady_state_allowance(), (1, 2));
+}
+
+#[test]
+fn test_extreme_allowances_compare_and_add_without_overflow() {
+    let all_of = AllOf::new(vec![
+        Labeled::new("a", TokenBucketCore::new(10, Uint::MAX, Uint::MAX - 1)),
+        Labeled::new("b", TokenBucketCore::new(10, Uint::MAX - 1, Uint::MAX - 2)),
+    ]);
+    assert_eq!(all_of.steady_state_allowance(), (Uint::MAX - 2, Uint::MAX - 1));
+
+    // The exact sum does not fit, so the larger allowance bounds it
+    let with_reserve = WithReserve::new(
+        TokenBucketCore::new(10, Uint::MAX, Uint::MAX - 1),
+        TokenBucketCore::new(10, Uint::MAX - 1, Uint::MAX - 2),
+    );
+    assert_eq!(with_reserve.steady_state_allowance(), (Uint::MAX - 1, Uint::MAX));
+}
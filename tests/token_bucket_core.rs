@@ -1,5 +1,5 @@
 use rate_guard_core::{Uint, SimpleRateLimitError};
-use rate_guard_core::cores::TokenBucketCore;
+use rate_guard_core::cores::{RefillPolicy, TokenBucketCore};
 
 #[test]
 fn test_new_token_bucket() {
@@ -260,7 +260,7 @@ fn test_capacity_remaining_or_0_refill_behavior() {
 
 #[test]
 fn test_current_vs_remaining_consistency() {
-    let bucket = TokenBucketCore::new(100, 10, 5);
+    let bucket = TokenBucketCore::new_with_refill_policy(100, 10, 5, RefillPolicy::OnReadAndWrite);
     
     // Use some tokens
     assert_eq!(bucket.try_acquire_at(0, 40), Ok(()));
@@ -272,4 +272,144 @@ fn test_current_vs_remaining_consistency() {
     // After capacity_remaining_or_0 triggers refill, current_capacity should reflect the update
     assert_eq!(bucket.capacity_remaining_or_0(10), 65);
     assert_eq!(bucket.current_capacity().unwrap(), 65);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_reads_do_not_commit_refill_by_default() {
+    let bucket = TokenBucketCore::new(100, 10, 5);
+    assert_eq!(bucket.try_acquire_at(0, 40), Ok(()));
+
+    // The read reports the refill but does not store it
+    assert_eq!(bucket.capacity_remaining(10), Ok(65));
+    assert_eq!(bucket.current_capacity().unwrap(), 60);
+
+    // Nor does it advance the last refill tick: an earlier tick is still accepted
+    assert_eq!(bucket.capacity_remaining(1000), Ok(100));
+    assert_eq!(bucket.try_acquire_at(5, 10), Ok(()));
+    assert_eq!(bucket.current_capacity().unwrap(), 50);
+    assert_eq!(bucket.capacity_remaining(10), Ok(55));
+}
+
+#[test]
+fn test_on_read_and_write_commits_refill() {
+    let bucket = TokenBucketCore::new_with_refill_policy(100, 10, 5, RefillPolicy::OnReadAndWrite);
+    assert_eq!(bucket.try_acquire_at(0, 40), Ok(()));
+    assert_eq!(bucket.capacity_remaining(20), Ok(70));
+    assert_eq!(bucket.try_acquire_at(5, 10), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_try_new_rejects_zero_parameters() {
+    use rate_guard_core::InvalidConfig;
+
+    assert_eq!(TokenBucketCore::try_new(0, 10, 5).err(), Some(InvalidConfig::ZeroCapacity));
+    assert_eq!(TokenBucketCore::try_new(100, 0, 5).err(), Some(InvalidConfig::ZeroRefillInterval));
+    assert_eq!(TokenBucketCore::try_new(100, 10, 0).err(), Some(InvalidConfig::ZeroRefillAmount));
+    assert_eq!(InvalidConfig::ZeroCapacity.to_string(), "capacity must be greater than 0");
+}
+
+#[test]
+fn test_try_from_config_validates_every_field() {
+    use rate_guard_core::cores::TokenBucketCoreConfig;
+    use rate_guard_core::InvalidConfig;
+
+    let config = TokenBucketCoreConfig::new(100, 10, 5);
+    assert_eq!(TokenBucketCore::try_from_config(TokenBucketCoreConfig::new(100, 0, 5)).err(), Some(InvalidConfig::ZeroRefillInterval));
+    assert_eq!(
+        TokenBucketCore::try_from_config(config.clone().with_accrual_cap(Some(0))).err(),
+        Some(InvalidConfig::ZeroAccrualCap)
+    );
+
+    let bucket = TokenBucketCore::try_from_config(config.with_accrual_cap(Some(20))).unwrap();
+    assert_eq!(bucket.try_acquire_at(0, 100), Ok(()));
+    assert_eq!(bucket.capacity_remaining(1000), Ok(20));
+}
+
+#[test]
+fn test_lenient_construction_accepts_refill_above_capacity() {
+    let bucket = TokenBucketCore::try_new(10, 5, 50).unwrap();
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    // The refill is capped at capacity
+    assert_eq!(bucket.capacity_remaining(5), Ok(10));
+
+    let _ = TokenBucketCore::new(10, 5, 50);
+}
+
+#[test]
+fn test_strict_construction_rejects_refill_above_capacity() {
+    use rate_guard_core::InvalidConfig;
+
+    assert_eq!(
+        TokenBucketCore::try_new_strict(10, 5, 50).err(),
+        Some(InvalidConfig::RefillExceedsCapacity { refill_amount: 50, capacity: 10 })
+    );
+    assert_eq!(
+        InvalidConfig::RefillExceedsCapacity { refill_amount: 50, capacity: 10 }.to_string(),
+        "refill_amount (50) must not exceed capacity (10)"
+    );
+    assert!(TokenBucketCore::try_new_strict(10, 5, 10).is_ok());
+    assert_eq!(TokenBucketCore::try_new_strict(0, 5, 10).err(), Some(InvalidConfig::ZeroCapacity));
+}
+
+#[test]
+fn test_refund_applies_due_refill_first() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    // Two refills are due by tick 10, then the refund is added on top
+    assert_eq!(bucket.refund_at(10, 3), Ok(()));
+    assert_eq!(bucket.capacity_remaining(10), Ok(7));
+    // Refunding past capacity clamps
+    assert_eq!(bucket.refund_at(10, 5), Ok(()));
+    assert_eq!(bucket.capacity_remaining(10), Ok(10));
+}
+
+#[test]
+fn test_zero_token_refund_is_noop() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(20, 4), Ok(()));
+    assert_eq!(bucket.refund_at(0, 0), Ok(()));
+    assert_eq!(bucket.last_rejected_expired_delta(), Ok(None));
+    assert_eq!(bucket.capacity_remaining(20), Ok(6));
+}
+
+#[test]
+fn test_current_capacity_at_projects_without_committing() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(10, 10), Ok(()));
+    assert_eq!(bucket.current_capacity_at(14), Ok(0));
+    assert_eq!(bucket.current_capacity_at(15), Ok(2));
+    assert_eq!(bucket.current_capacity_at(100), Ok(10));
+    // Earlier ticks report the stored tokens
+    assert_eq!(bucket.current_capacity_at(0), Ok(0));
+
+    // Nothing was committed: the refill interval still counts from tick 10
+    assert_eq!(bucket.current_capacity(), Ok(0));
+    assert_eq!(bucket.try_acquire_at(12, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(bucket.current_capacity_at(20), Ok(4));
+}
+
+#[test]
+fn test_current_capacity_at_ignores_refill_policy() {
+    let bucket = TokenBucketCore::new_with_refill_policy(10, 5, 2, RefillPolicy::OnReadAndWrite);
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.current_capacity_at(10), Ok(4));
+    assert_eq!(bucket.current_capacity(), Ok(0));
+}
+
+#[test]
+fn test_next_refill_tick() {
+    let bucket = TokenBucketCore::new(10, 5, 2);
+    // Full bucket: no refill pending
+    assert_eq!(bucket.next_refill_tick(), Ok(0));
+
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.next_refill_tick(), Ok(5));
+    assert_eq!(bucket.try_acquire_at(7, 1), Ok(()));
+    assert_eq!(bucket.next_refill_tick(), Ok(10));
+    assert_eq!(bucket.try_acquire_at(9, 2), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(bucket.try_acquire_at(10, 2), Ok(()));
+
+    // Refill is counted in whole intervals from the last refill
+    assert_eq!(bucket.try_acquire_at(102, 1), Ok(()));
+    assert_eq!(bucket.next_refill_tick(), Ok(105));
+}
@@ -51,6 +51,7 @@ fn fill_bucket_then_exceed_should_fail() {
             acquiring,
             available,
             retry_after_ticks,
+            ..
         }) => {
             assert_eq!(acquiring, 1);
             assert_eq!(available, 0);
@@ -61,6 +62,7 @@ fn fill_bucket_then_exceed_should_fail() {
 }
 
 #[test]
+#[allow(clippy::identity_op)]
 fn retry_after_tick_should_be_correct() {
     let limiter = new_token_bucket(5, 2, 2); // refill 2 tokens every 2 ticks
     assert_eq!(limiter.try_acquire_verbose_at(0, 5), Ok(())); // fill
@@ -0,0 +1,78 @@
+#![cfg(not(feature = "tick-u128"))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use rate_guard_core::cores::TokenBucketSeqlock;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+#[test]
+fn test_matches_token_bucket_semantics() {
+    let bucket = TokenBucketSeqlock::new(10, 5, 2);
+    assert_eq!(bucket.try_acquire_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(bucket.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(bucket.try_acquire_at(4, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(bucket.try_acquire_at(5, 2), Ok(()));
+    assert_eq!(bucket.try_acquire_at(3, 1), Err(SimpleRateLimitError::ExpiredTick));
+
+    assert_eq!(
+        bucket.try_acquire_verbose_at(6, 3),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 3, available: 0, retry_after_ticks: 9, available_next_refill: 2 })
+    );
+}
+
+#[test]
+fn test_reads_do_not_change_state() {
+    let bucket = TokenBucketSeqlock::new(10, 5, 2);
+    bucket.try_acquire_at(0, 10).unwrap();
+
+    assert_eq!(bucket.capacity_remaining(100), Ok(10));
+    assert_eq!(bucket.current_capacity(), 0);
+    // The stored refill tick is still 0, so earlier ticks remain acceptable
+    assert_eq!(bucket.capacity_remaining(5), Ok(2));
+}
+
+#[test]
+fn test_readers_never_see_torn_state_or_errors() {
+    const BASE: u64 = 1_000_000;
+    const QUERY_TICK: u64 = 100_000;
+    const WRITES: u64 = 20_000;
+
+    // Refills 1 token per tick; leave BASE tokens after the first acquire
+    let capacity = 1_000_000_000_000;
+    let bucket = Arc::new(TokenBucketSeqlock::new(capacity, 1, 1));
+    bucket.try_acquire_at(0, capacity - BASE).unwrap();
+    let done = Arc::new(AtomicBool::new(false));
+
+    // At tick k the writer leaves `BASE - k` tokens with the refill tick at k,
+    // so every consistent read at QUERY_TICK is `BASE + QUERY_TICK - 2k`: even
+    // and non-increasing. Mixing `available` and `last_refill_tick` from two
+    // different writes yields an odd value.
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let bucket = Arc::clone(&bucket);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut previous = u64::MAX;
+                let mut reads = 0u64;
+                while !done.load(Ordering::Acquire) || reads == 0 {
+                    let value = bucket.capacity_remaining(QUERY_TICK).expect("reads never fail");
+                    assert_eq!(value % 2, 0, "torn read: {}", value);
+                    assert!(value <= previous, "read went backwards: {} after {}", value, previous);
+                    previous = value;
+                    reads += 1;
+                }
+            })
+        })
+        .collect();
+
+    for k in 1..=WRITES {
+        assert_eq!(bucket.try_acquire_at(k, 2), Ok(()));
+    }
+    done.store(true, Ordering::Release);
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(bucket.current_capacity(), BASE - WRITES);
+}
@@ -0,0 +1,86 @@
+#![cfg(not(feature = "tick-u128"))]
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::thread;
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+use rate_guard_core::cores::TokenBucketShared;
+
+#[test]
+fn test_two_instances_share_state() {
+    let available = AtomicU64::new(0);
+    let last_refill_tick = AtomicU64::new(0);
+
+    let a = TokenBucketShared::new(10, 5, 2, &available, &last_refill_tick);
+    a.initialize();
+    let b = TokenBucketShared::new(10, 5, 2, &available, &last_refill_tick);
+
+    assert_eq!(a.try_acquire_at(0, 7), Ok(()));
+    assert_eq!(b.capacity_remaining(0), Ok(3));
+    assert_eq!(b.try_acquire_at(0, 4), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(b.try_acquire_at(0, 3), Ok(()));
+    assert_eq!(a.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // A refill observed by one instance is visible to the other
+    assert_eq!(b.capacity_remaining(10), Ok(4));
+    assert_eq!(a.try_acquire_at(10, 4), Ok(()));
+    assert_eq!(b.capacity_remaining(10), Ok(0));
+
+    // Time is shared too: the other instance cannot go backwards
+    assert_eq!(a.try_acquire_at(5, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_refill_is_credited_once() {
+    let available = AtomicU64::new(0);
+    let last_refill_tick = AtomicU64::new(0);
+    let a = TokenBucketShared::new(10, 5, 2, &available, &last_refill_tick);
+    a.initialize();
+    let b = TokenBucketShared::new(10, 5, 2, &available, &last_refill_tick);
+
+    assert_eq!(a.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(a.capacity_remaining(5), Ok(2));
+    assert_eq!(b.capacity_remaining(5), Ok(2));
+    assert_eq!(b.capacity_remaining(9), Ok(2));
+}
+
+#[test]
+fn test_concurrent_acquires_never_exceed_capacity() {
+    let shared = Arc::new((AtomicU64::new(0), AtomicU64::new(0)));
+    TokenBucketShared::new(1000, 1_000_000, 1, &shared.0, &shared.1).initialize();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                let bucket = TokenBucketShared::new(1000, 1_000_000, 1, &shared.0, &shared.1);
+                (0..500).filter(|_| bucket.try_acquire_at(0, 1).is_ok()).count()
+            })
+        })
+        .collect();
+    let admitted: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(admitted, 1000);
+}
+
+#[test]
+fn test_verbose_diagnostics() {
+    let available = AtomicU64::new(0);
+    let last_refill_tick = AtomicU64::new(0);
+    let bucket = TokenBucketShared::new(10, 5, 2, &available, &last_refill_tick);
+    bucket.initialize();
+
+    assert_eq!(bucket.try_acquire_verbose_at(1, 10), Ok(()));
+    assert_eq!(
+        bucket.try_acquire_verbose_at(2, 3),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 3, available: 0, retry_after_ticks: 8, available_next_refill: 2 })
+    );
+    assert_eq!(
+        bucket.try_acquire_verbose_at(2, 11),
+        Err(VerboseRateLimitError::BeyondCapacity { acquiring: 11, capacity: 10 })
+    );
+    assert_eq!(bucket.try_acquire_verbose_at(10, 4), Ok(()));
+    assert_eq!(
+        bucket.try_acquire_verbose_at(9, 1),
+        Err(VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 10 })
+    );
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::thread;
+use rate_guard_core::cores::{transfer_capacity, TokenBucketCore};
+use rate_guard_core::{SimpleRateLimitError, Uint};
+
+fn total(a: &TokenBucketCore, b: &TokenBucketCore, tick: Uint) -> Uint {
+    a.capacity_remaining(tick).unwrap() + b.capacity_remaining(tick).unwrap()
+}
+
+#[test]
+fn test_transfer_conserves_tokens() {
+    let a = TokenBucketCore::new(100, 10, 10);
+    let b = TokenBucketCore::new(100, 10, 10);
+    b.try_acquire_at(0, 60).unwrap();
+    let before = total(&a, &b, 0);
+
+    assert_eq!(transfer_capacity(&a, &b, 0, 25), Ok(0));
+    assert_eq!(a.capacity_remaining(0), Ok(75));
+    assert_eq!(b.capacity_remaining(0), Ok(65));
+    assert_eq!(total(&a, &b, 0), before);
+
+    assert_eq!(transfer_capacity(&b, &a, 0, 5), Ok(0));
+    assert_eq!(total(&a, &b, 0), before);
+}
+
+#[test]
+fn test_overflow_beyond_destination_capacity_is_returned() {
+    let a = TokenBucketCore::new(100, 10, 10);
+    let b = TokenBucketCore::new(50, 10, 10);
+    b.try_acquire_at(0, 20).unwrap();
+    let before = total(&a, &b, 0);
+
+    assert_eq!(transfer_capacity(&a, &b, 0, 30), Ok(10));
+    assert_eq!(a.capacity_remaining(0), Ok(70));
+    assert_eq!(b.capacity_remaining(0), Ok(50));
+    assert_eq!(total(&a, &b, 0) + 10, before);
+}
+
+#[test]
+fn test_insufficient_source_changes_nothing() {
+    let a = TokenBucketCore::new(100, 10, 10);
+    let b = TokenBucketCore::new(100, 10, 10);
+    a.try_acquire_at(0, 90).unwrap();
+    b.try_acquire_at(0, 90).unwrap();
+
+    assert_eq!(transfer_capacity(&a, &b, 0, 11), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(a.capacity_remaining(0), Ok(10));
+    assert_eq!(b.capacity_remaining(0), Ok(10));
+}
+
+#[test]
+fn test_both_buckets_are_refilled_to_tick_first() {
+    let a = TokenBucketCore::new(100, 10, 10);
+    let b = TokenBucketCore::new(100, 10, 10);
+    a.try_acquire_at(0, 100).unwrap();
+    b.try_acquire_at(0, 100).unwrap();
+
+    // a has refilled 20 tokens by tick 20
+    assert_eq!(transfer_capacity(&a, &b, 20, 20), Ok(0));
+    assert_eq!(a.capacity_remaining(20), Ok(0));
+    assert_eq!(b.capacity_remaining(20), Ok(40));
+    assert_eq!(transfer_capacity(&a, &b, 10, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_transfer_to_self_is_a_no_op() {
+    let a = TokenBucketCore::new(100, 10, 10);
+    a.try_acquire_at(0, 60).unwrap();
+    assert_eq!(transfer_capacity(&a, &a, 0, 40), Ok(0));
+    assert_eq!(a.capacity_remaining(0), Ok(40));
+    assert_eq!(transfer_capacity(&a, &a, 0, 41), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_opposite_transfers_do_not_deadlock() {
+    let a = Arc::new(TokenBucketCore::new(1_000_000, 10, 10));
+    let b = Arc::new(TokenBucketCore::new(1_000_000, 10, 10));
+    a.try_acquire_at(0, 500_000).unwrap();
+    b.try_acquire_at(0, 500_000).unwrap();
+
+    let handles: Vec<_> = (0..2)
+        .map(|i| {
+            let (a, b) = (Arc::clone(&a), Arc::clone(&b));
+            thread::spawn(move || {
+                for _ in 0..10_000 {
+                    let _ = if i == 0 { transfer_capacity(&a, &b, 0, 1) } else { transfer_capacity(&b, &a, 0, 1) };
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(total(&a, &b, 0), 1_000_000);
+}
@@ -0,0 +1,82 @@
+use rate_guard_core::{SimpleRateLimitError, TransitionInfo, Uint};
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::RateLimitCore;
+use rate_guard_core::wrappers::PerRequestCap;
+
+fn info(allowed: bool, refilled: Uint, window_rotated: bool) -> TransitionInfo {
+    TransitionInfo { allowed, refilled, leaked: 0, window_rotated }
+}
+
+#[test]
+fn test_token_bucket_reports_refill() {
+    let bucket = TokenBucketCore::new(10, 5, 3);
+    assert_eq!(bucket.try_acquire_detailed_at(0, 8), Ok(info(true, 0, false)));
+    // Within the same interval nothing is refilled
+    assert_eq!(bucket.try_acquire_detailed_at(4, 3), Ok(info(false, 0, false)));
+    // Crossing two intervals refills 6 tokens
+    assert_eq!(bucket.try_acquire_detailed_at(10, 8), Ok(info(true, 6, false)));
+    // Refill is reported after the capacity cap: six refills of 3 capped at 10
+    assert_eq!(bucket.try_acquire_detailed_at(40, 1), Ok(info(true, 10, false)));
+}
+
+#[test]
+fn test_fixed_window_reports_rotation() {
+    let counter = FixedWindowCounterCore::new(5, 10);
+    assert_eq!(counter.try_acquire_detailed_at(3, 5), Ok(info(true, 0, false)));
+    assert_eq!(counter.try_acquire_detailed_at(9, 1), Ok(info(false, 0, false)));
+    assert_eq!(counter.try_acquire_detailed_at(10, 1), Ok(info(true, 0, true)));
+    assert_eq!(counter.try_acquire_detailed_at(15, 1), Ok(info(true, 0, false)));
+    // Skipping several windows is still a single rotation
+    assert_eq!(counter.try_acquire_detailed_at(57, 1), Ok(info(true, 0, true)));
+}
+
+#[test]
+fn test_sliding_window_reports_bucket_rotation() {
+    let counter = SlidingWindowCounterCore::new(10, 5, 4);
+    assert_eq!(counter.try_acquire_detailed_at(2, 6), Ok(info(true, 0, false)));
+    assert_eq!(counter.try_acquire_detailed_at(4, 1), Ok(info(true, 0, false)));
+    assert_eq!(counter.try_acquire_detailed_at(6, 4), Ok(info(false, 0, true)));
+    assert_eq!(counter.try_acquire_detailed_at(8, 3), Ok(info(true, 0, false)));
+    // Tick 20 reuses the slot of the bucket starting at 0
+    assert_eq!(counter.try_acquire_detailed_at(20, 7), Ok(info(true, 0, true)));
+}
+
+#[test]
+fn test_approximate_reports_window_switch() {
+    let counter = ApproximateSlidingWindowCore::new(10, 10);
+    assert_eq!(counter.try_acquire_detailed_at(5, 10), Ok(info(true, 0, false)));
+    assert_eq!(counter.try_acquire_detailed_at(9, 1), Ok(info(false, 0, false)));
+    // At tick 10 the previous window still weighs 10 * 9 / 10, at tick 15 only 10 * 4 / 10
+    assert_eq!(counter.try_acquire_detailed_at(10, 2), Ok(info(false, 0, true)));
+    assert_eq!(counter.try_acquire_detailed_at(15, 6), Ok(info(true, 0, false)));
+}
+
+#[test]
+fn test_detailed_errors_match_fast_path() {
+    let bucket = TokenBucketCore::new(10, 5, 3);
+    assert_eq!(bucket.try_acquire_detailed_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(bucket.try_acquire_detailed_at(0, 0), Ok(info(true, 0, false)));
+    assert_eq!(bucket.try_acquire_detailed_at(10, 1), Ok(info(true, 0, false)));
+    assert_eq!(bucket.try_acquire_detailed_at(3, 1), Err(SimpleRateLimitError::ExpiredTick));
+
+    let counter = FixedWindowCounterCore::new(5, 10);
+    assert_eq!(counter.try_acquire_detailed_at(20, 1), Ok(info(true, 0, true)));
+    assert_eq!(counter.try_acquire_detailed_at(19, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_trait_dispatch_and_default() {
+    let counter = FixedWindowCounterCore::new(5, 10);
+    let core: &dyn RateLimitCore = &counter;
+    assert_eq!(core.try_acquire_detailed_at(0, 5), Ok(info(true, 0, false)));
+    assert_eq!(core.try_acquire_detailed_at(10, 1), Ok(info(true, 0, true)));
+
+    // Wrappers fall back to the default, which only reports the decision
+    let capped = PerRequestCap::new(FixedWindowCounterCore::new(5, 10), 3);
+    assert_eq!(capped.try_acquire_detailed_at(0, 3), Ok(info(true, 0, false)));
+    assert_eq!(capped.try_acquire_detailed_at(0, 3), Ok(info(false, 0, false)));
+    assert_eq!(capped.try_acquire_detailed_at(10, 3), Ok(info(true, 0, false)));
+    assert_eq!(capped.try_acquire_detailed_at(10, 4), Err(SimpleRateLimitError::BeyondCapacity));
+}
@@ -0,0 +1,345 @@
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use rate_guard_core::{
+    Dimension, DimensionalRateLimitError, SimpleRateLimitError, SimpleRateLimitResult, Uint,
+    VerboseRateLimitError, VerboseRateLimitResult,
+};
+use rate_guard_core::combinators::TwoDimensional;
+use rate_guard_core::cores::{
+    ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore,
+};
+use rate_guard_core::rate_limit::{RateLimitCore, RefundableCore};
+
+fn create_limiter() -> TwoDimensional<FixedWindowCounterCore, TokenBucketCore> {
+    // 10 requests per 100 ticks, 1000 bytes refilled at 100 bytes per 10 ticks
+    TwoDimensional::new(
+        FixedWindowCounterCore::new(10, 100),
+        TokenBucketCore::new(1000, 10, 100),
+    )
+}
+
+#[test]
+fn test_both_dimensions_admit() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_at(0, 1, 100), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok((9, 900)));
+}
+
+#[test]
+fn test_bytes_dimension_is_binding() {
+    let limiter = create_limiter();
+
+    // Plenty of requests left, but bytes run out first
+    assert_eq!(limiter.try_acquire_at(0, 1, 400), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 1, 400), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_at(0, 1, 400),
+        Err(SimpleRateLimitError::InsufficientCapacity)
+    );
+    assert_eq!(limiter.capacity_remaining(0), Ok((8, 200)));
+
+    // After one refill the bytes dimension admits again
+    assert_eq!(limiter.try_acquire_at(20, 1, 400), Ok(()));
+}
+
+#[test]
+fn test_requests_rolled_back_on_bytes_denial() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_at(0, 1, 1000), Ok(()));
+
+    for _ in 0..20 {
+        assert_eq!(
+            limiter.try_acquire_at(0, 1, 1),
+            Err(SimpleRateLimitError::InsufficientCapacity)
+        );
+    }
+
+    // None of the denied calls consumed a request
+    assert_eq!(limiter.capacity_remaining(0), Ok((9, 0)));
+    assert_eq!(limiter.try_acquire_at(10, 9, 100), Ok(()));
+}
+
+#[test]
+fn test_requests_dimension_denial_skips_bytes() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_at(0, 10, 10), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_at(0, 1, 10),
+        Err(SimpleRateLimitError::InsufficientCapacity)
+    );
+    assert_eq!(limiter.capacity_remaining(0), Ok((0, 990)));
+}
+
+#[test]
+fn test_beyond_capacity_in_bytes_rolls_back_requests() {
+    let limiter = create_limiter();
+    assert_eq!(
+        limiter.try_acquire_at(0, 1, 1001),
+        Err(SimpleRateLimitError::BeyondCapacity)
+    );
+    assert_eq!(limiter.capacity_remaining(0), Ok((10, 1000)));
+}
+
+#[test]
+fn test_refund_restores_capacity_on_every_core() {
+    fn check<L: RefundableCore>(limiter: L) {
+        assert_eq!(limiter.try_acquire_at(5, 4), Ok(()));
+        assert_eq!(limiter.capacity_remaining(5), Ok(6));
+        assert_eq!(limiter.refund_at(5, 4), Ok(()));
+        assert_eq!(limiter.capacity_remaining(5), Ok(10));
+        // Over-refunding is clamped
+        assert_eq!(limiter.refund_at(5, 100), Ok(()));
+        assert_eq!(limiter.capacity_remaining(5), Ok(10));
+        // Refunding in the past is rejected
+        assert_eq!(limiter.try_acquire_at(200, 1), Ok(()));
+        assert_eq!(limiter.refund_at(0, 1), Err(SimpleRateLimitError::ExpiredTick));
+    }
+
+    check(TokenBucketCore::new(10, 10, 1));
+    check(FixedWindowCounterCore::new(10, 100));
+    check(SlidingWindowCounterCore::new(10, 10, 10));
+    check(ApproximateSlidingWindowCore::new(10, 100));
+}
+
+fn insufficient(
+    dimension: Dimension,
+    acquiring: Uint,
+    available: Uint,
+    retry_after_ticks: Uint,
+    available_next_refill: Uint,
+) -> DimensionalRateLimitError {
+    DimensionalRateLimitError {
+        dimension,
+        error: VerboseRateLimitError::InsufficientCapacity {
+            acquiring,
+            available,
+            retry_after_ticks,
+            available_next_refill,
+        },
+    }
+}
+
+#[test]
+fn test_verbose_reports_bytes_dimension() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_verbose_at(0, 1, 950), Ok(()));
+
+    let err = limiter.try_acquire_verbose_at(5, 1, 100).unwrap_err();
+    assert_eq!(err, insufficient(Dimension::Bytes, 100, 50, 5, 150));
+    assert_eq!(err.retry_after_ticks(), Some(5));
+    assert_eq!(err.to_string(), format!("[bytes] {}", err.error));
+
+    // The requests taken by the denied call were refunded
+    assert_eq!(limiter.capacity_remaining(5), Ok((9, 50)));
+}
+
+#[test]
+fn test_verbose_reports_requests_dimension() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_verbose_at(0, 10, 100), Ok(()));
+
+    assert_eq!(
+        limiter.try_acquire_verbose_at(40, 1, 100),
+        Err(insufficient(Dimension::Requests, 1, 0, 60, 10))
+    );
+    // Bytes were only read, never taken
+    assert_eq!(limiter.capacity_remaining(40), Ok((0, 1000)));
+}
+
+#[test]
+fn test_verbose_both_denied_reports_later_retry() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_verbose_at(90, 10, 1000), Ok(()));
+
+    // Requests free up at tick 100, bytes need 5 refills (until tick 140)
+    assert_eq!(
+        limiter.try_acquire_verbose_at(90, 1, 500),
+        Err(insufficient(Dimension::Bytes, 500, 0, 50, 100))
+    );
+
+    // With a small byte request the requests window is the later one
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_verbose_at(50, 10, 1000), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(50, 1, 100),
+        Err(insufficient(Dimension::Requests, 1, 0, 50, 10))
+    );
+    assert_eq!(limiter.capacity_remaining(50), Ok((0, 0)));
+}
+
+#[test]
+fn test_verbose_prefers_permanent_bytes_error() {
+    let limiter = create_limiter();
+    assert_eq!(limiter.try_acquire_verbose_at(0, 10, 0), Ok(()));
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 1, 1001),
+        Err(DimensionalRateLimitError {
+            dimension: Dimension::Bytes,
+            error: VerboseRateLimitError::BeyondCapacity { acquiring: 1001, capacity: 1000 },
+        })
+    );
+}
+
+/// Request limiter whose lock a test can hold from another thread.
+struct Lockable {
+    inner: Arc<FixedWindowCounterCore>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl RateLimitCore for Lockable {
+    fn try_acquire_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let _guard = self.lock.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+        self.inner.try_acquire_at(tick, tokens)
+    }
+
+    fn try_acquire_verbose_at(&self, tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        let _guard = self.lock.try_lock().map_err(|_| VerboseRateLimitError::ContentionFailure)?;
+        self.inner.try_acquire_verbose_at(tick, tokens)
+    }
+
+    fn capacity_remaining(&self, tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        let _guard = self.lock.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+        self.inner.capacity_remaining(tick)
+    }
+}
+
+impl RefundableCore for Lockable {
+    fn refund_at(&self, tick: Uint, tokens: Uint) -> SimpleRateLimitResult {
+        let _guard = self.lock.try_lock().map_err(|_| SimpleRateLimitError::ContentionFailure)?;
+        self.inner.refund_at(tick, tokens)
+    }
+}
+
+/// Byte limiter that denies every request, running `on_deny` first.
+struct Denying<F: Fn() + Send + Sync> {
+    on_deny: F,
+}
+
+impl<F: Fn() + Send + Sync> RateLimitCore for Denying<F> {
+    fn try_acquire_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        (self.on_deny)();
+        Err(SimpleRateLimitError::InsufficientCapacity)
+    }
+
+    fn try_acquire_verbose_at(&self, _tick: Uint, tokens: Uint) -> VerboseRateLimitResult {
+        (self.on_deny)();
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: tokens, available: 0, retry_after_ticks: 1, available_next_refill: 0 })
+    }
+
+    fn capacity_remaining(&self, _tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        Ok(0)
+    }
+}
+
+impl<F: Fn() + Send + Sync> RefundableCore for Denying<F> {
+    fn refund_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        Ok(())
+    }
+}
+
+/// Has another thread take `lock` and hold it for a while.
+fn hold_from_another_thread(lock: &Arc<Mutex<()>>) {
+    let lock = Arc::clone(lock);
+    let (locked_tx, locked_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _guard = lock.lock().unwrap();
+        locked_tx.send(()).unwrap();
+        thread::sleep(Duration::from_millis(50));
+    });
+    locked_rx.recv().unwrap();
+}
+
+#[test]
+fn test_rollback_waits_out_contention() {
+    let requests = Arc::new(FixedWindowCounterCore::new(10, 100));
+    let lock = Arc::new(Mutex::new(()));
+    let held = Arc::clone(&lock);
+    // The request limiter's lock is held when the byte denial triggers the rollback
+    let limiter = TwoDimensional::new(
+        Lockable { inner: Arc::clone(&requests), lock },
+        Denying { on_deny: move || hold_from_another_thread(&held) },
+    );
+
+    assert_eq!(limiter.try_acquire_at(0, 3, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(requests.capacity_remaining(0), Ok(10));
+}
+
+#[test]
+fn test_failed_rollback_is_reported() {
+    let requests = Arc::new(FixedWindowCounterCore::new(10, 100));
+    let other_caller = Arc::clone(&requests);
+    // Another caller moves the request window on before the rollback
+    let limiter = TwoDimensional::new(
+        Lockable { inner: Arc::clone(&requests), lock: Arc::new(Mutex::new(())) },
+        Denying { on_deny: move || assert_eq!(other_caller.try_acquire_at(100, 1), Ok(())) },
+    );
+
+    assert_eq!(limiter.try_acquire_at(0, 3, 1), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+fn test_verbose_rollback_waits_out_contention() {
+    let requests = Arc::new(FixedWindowCounterCore::new(10, 100));
+    let lock = Arc::new(Mutex::new(()));
+    let held = Arc::clone(&lock);
+    let limiter = TwoDimensional::new(
+        Lockable { inner: Arc::clone(&requests), lock },
+        Denying { on_deny: move || hold_from_another_thread(&held) },
+    );
+
+    assert_eq!(limiter.try_acquire_verbose_at(0, 3, 1), Err(insufficient(Dimension::Bytes, 1, 0, 1, 0)));
+    assert_eq!(requests.capacity_remaining(0), Ok(10));
+}
+
+#[test]
+fn test_verbose_failed_rollback_is_reported() {
+    let requests = Arc::new(FixedWindowCounterCore::new(10, 100));
+    let other_caller = Arc::clone(&requests);
+    let limiter = TwoDimensional::new(
+        Lockable { inner: Arc::clone(&requests), lock: Arc::new(Mutex::new(())) },
+        Denying { on_deny: move || assert_eq!(other_caller.try_acquire_at(100, 1), Ok(())) },
+    );
+
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 3, 1),
+        Err(DimensionalRateLimitError {
+            dimension: Dimension::Requests,
+            error: VerboseRateLimitError::ExpiredTick { min_acceptable_tick: 1 },
+        })
+    );
+}
+
+/// Byte limiter with room to spare that must only ever be read.
+struct ReadOnly;
+
+impl RateLimitCore for ReadOnly {
+    fn try_acquire_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        panic!("bytes were taken");
+    }
+
+    fn try_acquire_verbose_at(&self, _tick: Uint, _tokens: Uint) -> VerboseRateLimitResult {
+        panic!("bytes were taken");
+    }
+
+    fn capacity_remaining(&self, _tick: Uint) -> Result<Uint, SimpleRateLimitError> {
+        Ok(1000)
+    }
+}
+
+impl RefundableCore for ReadOnly {
+    fn refund_at(&self, _tick: Uint, _tokens: Uint) -> SimpleRateLimitResult {
+        panic!("bytes were refunded");
+    }
+}
+
+#[test]
+fn test_verbose_requests_denial_only_reads_bytes() {
+    let limiter = TwoDimensional::new(FixedWindowCounterCore::new(1, 100), ReadOnly);
+    assert_eq!(limiter.requests().try_acquire_at(0, 1), Ok(()));
+
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 1, 10),
+        Err(insufficient(Dimension::Requests, 1, 0, 100, 1))
+    );
+}
@@ -0,0 +1,74 @@
+use rate_guard_core::cores::{ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore, TokenBucketCore};
+use rate_guard_core::rate_limit::{RateLimitCore, UnsyncRateLimit};
+use rate_guard_core::Uint;
+
+/// Deterministic (tick, tokens) sequence with non-decreasing ticks and occasional stale ticks.
+fn requests(count: usize) -> Vec<(Uint, Uint)> {
+    let mut seed: Uint = 0x2545_f491_4f6c_dd1d;
+    let mut tick: Uint = 0;
+    let mut out = Vec::with_capacity(count);
+    for _ in 0..count {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let r = seed >> 33;
+        let at = if r % 17 == 0 {
+            tick.saturating_sub(r % 7)
+        } else {
+            tick += r % 4;
+            tick
+        };
+        out.push((at, r % 13));
+    }
+    out
+}
+
+fn assert_same_decisions<L: RateLimitCore, U: UnsyncRateLimit>(locked: L, mut unsync: U) {
+    for (i, (tick, tokens)) in requests(2000).into_iter().enumerate() {
+        match i % 3 {
+            0 => assert_eq!(locked.try_acquire_at(tick, tokens), unsync.try_acquire_at(tick, tokens), "request {}", i),
+            1 => assert_eq!(locked.try_acquire_verbose_at(tick, tokens), unsync.try_acquire_verbose_at(tick, tokens), "request {}", i),
+            _ => assert_eq!(locked.capacity_remaining(tick), unsync.capacity_remaining(tick), "request {}", i),
+        }
+    }
+}
+
+#[test]
+fn token_bucket_unsync_matches_locked() {
+    assert_same_decisions(TokenBucketCore::new(10, 3, 2), TokenBucketCore::new(10, 3, 2).into_unsync());
+}
+
+#[test]
+fn fixed_window_unsync_matches_locked() {
+    assert_same_decisions(FixedWindowCounterCore::new(12, 8), FixedWindowCounterCore::new(12, 8).into_unsync());
+}
+
+#[test]
+fn sliding_window_unsync_matches_locked() {
+    assert_same_decisions(SlidingWindowCounterCore::new(12, 3, 4), SlidingWindowCounterCore::new(12, 3, 4).into_unsync());
+}
+
+#[test]
+fn approximate_sliding_window_unsync_matches_locked() {
+    assert_same_decisions(ApproximateSlidingWindowCore::new(12, 8), ApproximateSlidingWindowCore::new(12, 8).into_unsync());
+}
+
+#[test]
+fn into_unsync_keeps_state_both_ways() {
+    let core = TokenBucketCore::new(10, 100, 1);
+    core.try_acquire_at(0, 7).unwrap();
+
+    let mut unsync = core.into_unsync();
+    assert_eq!(unsync.capacity_remaining(0), Ok(3));
+    unsync.try_acquire_at(0, 3).unwrap();
+
+    let core = unsync.into_inner();
+    assert_eq!(core.capacity_remaining(0), Ok(0));
+}
+
+#[test]
+fn unsync_checks_capacity_before_state() {
+    let mut unsync = FixedWindowCounterCore::new(5, 10).into_unsync();
+    assert_eq!(unsync.try_acquire_at(0, 0), Ok(()));
+    assert_eq!(unsync.try_acquire_at(0, 6), Err(rate_guard_core::SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(unsync.capacity_remaining_or_0(0), 5);
+    assert_eq!(unsync.inner().capacity_remaining(0), Ok(5));
+}
@@ -0,0 +1,78 @@
+use rate_guard_core::cores::{ApproximateSlidingWindowCore, FixedWindowCounterCore, SlidingWindowCounterCore};
+use rate_guard_core::{SimpleRateLimitError, Uint};
+
+/// Acquires a varying number of tokens at increasing ticks and checks that
+/// `used + remaining == capacity` before and after each acquire.
+fn assert_complements(
+    capacity: Uint,
+    acquire: impl Fn(Uint, Uint) -> Result<(), SimpleRateLimitError>,
+    used: impl Fn(Uint) -> Result<Uint, SimpleRateLimitError>,
+    remaining: impl Fn(Uint) -> Result<Uint, SimpleRateLimitError>,
+) {
+    for tick in 0..120 {
+        assert_eq!(used(tick).unwrap() + remaining(tick).unwrap(), capacity, "tick {}", tick);
+        let _ = acquire(tick, tick % 4);
+        assert_eq!(used(tick).unwrap() + remaining(tick).unwrap(), capacity, "tick {}", tick);
+    }
+}
+
+#[test]
+fn test_fixed_window_used_complements_remaining() {
+    let counter = FixedWindowCounterCore::new(10, 7);
+    assert_complements(
+        10,
+        |tick, tokens| counter.try_acquire_at(tick, tokens),
+        |tick| counter.used_in_window(tick),
+        |tick| counter.capacity_remaining(tick),
+    );
+}
+
+#[test]
+fn test_sliding_window_used_complements_remaining() {
+    let counter = SlidingWindowCounterCore::new(10, 3, 4);
+    assert_complements(
+        10,
+        |tick, tokens| counter.try_acquire_at(tick, tokens),
+        |tick| counter.used_in_window(tick),
+        |tick| counter.capacity_remaining(tick),
+    );
+}
+
+#[test]
+fn test_approximate_used_complements_remaining() {
+    let counter = ApproximateSlidingWindowCore::new(10, 7);
+    assert_complements(
+        10,
+        |tick, tokens| counter.try_acquire_at(tick, tokens),
+        |tick| counter.used_in_window(tick),
+        |tick| counter.capacity_remaining(tick),
+    );
+}
+
+#[test]
+fn test_used_in_window_does_not_advance() {
+    let counter = FixedWindowCounterCore::new(10, 10);
+    assert_eq!(counter.try_acquire_at(5, 4), Ok(()));
+    assert_eq!(counter.used_in_window(5), Ok(4));
+    assert_eq!(counter.used_in_window(12), Ok(0));
+    // The window containing tick 5 is still current
+    assert_eq!(counter.used_in_window(9), Ok(4));
+    assert_eq!(counter.try_acquire_at(9, 6), Ok(()));
+}
+
+#[test]
+fn test_sliding_used_counts_only_buckets_in_window() {
+    let counter = SlidingWindowCounterCore::new(10, 5, 2);
+    assert_eq!(counter.try_acquire_at(0, 3), Ok(()));
+    assert_eq!(counter.try_acquire_at(5, 4), Ok(()));
+    assert_eq!(counter.used_in_window(9), Ok(7));
+    assert_eq!(counter.used_in_window(10), Ok(4));
+    assert_eq!(counter.used_in_window(15), Ok(0));
+}
+
+#[test]
+fn test_used_in_window_rejects_expired_tick() {
+    let counter = FixedWindowCounterCore::new(10, 10);
+    assert_eq!(counter.try_acquire_at(25, 1), Ok(()));
+    assert_eq!(counter.used_in_window(15), Err(SimpleRateLimitError::ExpiredTick));
+}
@@ -0,0 +1,121 @@
+use rate_guard_core::{SimpleRateLimitError, Uint};
+use rate_guard_core::cores::WeightedClassesCore;
+
+/// Offers every class more demand than the pool can serve, one token at a time
+/// in round-robin order, and returns the tokens admitted per class.
+fn saturate(core: &WeightedClassesCore, ticks: Uint) -> Vec<Uint> {
+    let mut admitted = vec![0; core.class_count()];
+    for tick in 0..ticks {
+        for _ in 0..100 {
+            for (class, count) in admitted.iter_mut().enumerate() {
+                if core.try_acquire_at(class, tick, 1).is_ok() {
+                    *count += 1;
+                }
+            }
+        }
+    }
+    admitted
+}
+
+#[test]
+fn test_saturated_classes_share_by_weight() {
+    let core = WeightedClassesCore::new(100, 10, vec![50, 30, 20]);
+    let admitted = saturate(&core, 1000);
+    let total: Uint = admitted.iter().sum();
+
+    // Heavier classes get more, in proportion to their weights (within 5%)
+    assert!(admitted[0] > admitted[1] && admitted[1] > admitted[2], "{:?}", admitted);
+    for (class, &weight) in [50, 30, 20].iter().enumerate() {
+        let expected = total * weight / 100;
+        let diff = admitted[class].abs_diff(expected);
+        assert!(diff * 20 <= expected, "class {} got {:?}", class, admitted);
+    }
+}
+
+#[test]
+fn test_lone_class_uses_whole_pool() {
+    let core = WeightedClassesCore::new(100, 10, vec![50, 30, 20]);
+    assert_eq!(core.capacity_remaining(2, 0), Ok(100));
+    assert_eq!(core.try_acquire_at(2, 0, 100), Ok(()));
+    assert_eq!(core.try_acquire_at(2, 0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(core.try_acquire_at(0, 0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+}
+
+#[test]
+fn test_guaranteed_share_survives_a_busy_class() {
+    let core = WeightedClassesCore::new(100, 10, vec![50, 30, 20]);
+    assert_eq!(core.try_acquire_at(0, 0, 60), Ok(()));
+
+    // Class 2 may use its guaranteed 20, and beyond it up to 20 / 70 of the
+    // recent consumption: 24 of 84
+    assert_eq!(core.capacity_remaining(2, 0), Ok(24));
+    assert_eq!(core.try_acquire_at(2, 0, 24), Ok(()));
+
+    // Class 0 is at exactly 50 / 70 and must wait, while class 1 takes the rest
+    assert_eq!(core.try_acquire_at(0, 0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+    assert_eq!(core.capacity_remaining(1, 0), Ok(16));
+    assert_eq!(core.try_acquire_at(1, 0, 16), Ok(()));
+}
+
+#[test]
+fn test_consumption_decays_over_next_window() {
+    let core = WeightedClassesCore::new(100, 10, vec![1, 1]);
+    assert_eq!(core.try_acquire_at(0, 0, 100), Ok(()));
+
+    // At tick 15 the previous window still weighs 100 * 4 / 10
+    assert_eq!(core.capacity_remaining(1, 15), Ok(50));
+    assert_eq!(core.capacity_remaining(0, 15), Ok(60));
+    assert_eq!(core.capacity_remaining(0, 19), Ok(100));
+}
+
+#[test]
+fn test_errors() {
+    let core = WeightedClassesCore::new(100, 10, vec![1, 2]);
+    assert_eq!(core.try_acquire_at(0, 0, 101), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(core.try_acquire_at(0, 25, 1), Ok(()));
+    assert_eq!(core.try_acquire_at(1, 12, 1), Err(SimpleRateLimitError::ExpiredTick));
+    assert_eq!(core.capacity_remaining(1, 3), Err(SimpleRateLimitError::ExpiredTick));
+}
+
+#[test]
+#[should_panic(expected = "class out of range")]
+fn test_unknown_class_panics() {
+    let core = WeightedClassesCore::new(100, 10, vec![1, 2]);
+    let _ = core.try_acquire_at(2, 0, 1);
+}
+
+#[test]
+#[should_panic(expected = "weights must be greater than 0")]
+fn test_zero_weight_panics() {
+    WeightedClassesCore::new(100, 10, vec![1, 0]);
+}
+
+#[test]
+fn test_fair_share_divides_by_active_classes() {
+    let core = WeightedClassesCore::new(120, 10, vec![1, 1, 1, 1]);
+    assert_eq!(core.fair_share(0), Ok(120));
+
+    assert_eq!(core.try_acquire_at(0, 0, 1), Ok(()));
+    assert_eq!(core.fair_share(0), Ok(120));
+    assert_eq!(core.try_acquire_at(1, 1, 1), Ok(()));
+    assert_eq!(core.fair_share(1), Ok(60));
+    assert_eq!(core.try_acquire_at(2, 2, 1), Ok(()));
+    assert_eq!(core.fair_share(2), Ok(40));
+    assert_eq!(core.try_acquire_at(3, 3, 1), Ok(()));
+    assert_eq!(core.fair_share(3), Ok(30));
+}
+
+#[test]
+fn test_fair_share_forgets_classes_outside_window() {
+    let core = WeightedClassesCore::new(100, 10, vec![3, 1, 1]);
+    assert_eq!(core.try_acquire_at(0, 0, 5), Ok(()));
+    assert_eq!(core.try_acquire_at(1, 8, 5), Ok(()));
+    assert_eq!(core.try_acquire_at(2, 8, 5), Ok(()));
+    // Weights do not matter, only how many classes are active
+    assert_eq!(core.fair_share(9), Ok(33));
+    // The previous window still overlaps the sliding window ending at 15
+    assert_eq!(core.fair_share(15), Ok(33));
+    // At tick 19 it no longer does
+    assert_eq!(core.fair_share(19), Ok(100));
+    assert_eq!(core.fair_share(5), Err(SimpleRateLimitError::ExpiredTick));
+}
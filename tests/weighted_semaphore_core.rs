@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::thread;
+use rate_guard_core::cores::WeightedSemaphoreCore;
+use rate_guard_core::SimpleRateLimitError;
+
+#[test]
+fn test_acquire_up_to_capacity() {
+    let semaphore = WeightedSemaphoreCore::new(10);
+    let a = semaphore.acquire(4).unwrap();
+    let b = semaphore.acquire(6).unwrap();
+    assert_eq!(a.weight() + b.weight(), 10);
+    assert_eq!(semaphore.in_flight(), Ok(10));
+    assert_eq!(semaphore.capacity_remaining(), Ok(0));
+}
+
+#[test]
+fn test_acquire_beyond_capacity_fails() {
+    let semaphore = WeightedSemaphoreCore::new(10);
+    let _held = semaphore.acquire(7).unwrap();
+    assert_eq!(semaphore.acquire(4).unwrap_err(), SimpleRateLimitError::InsufficientCapacity);
+    assert_eq!(semaphore.acquire(11).unwrap_err(), SimpleRateLimitError::BeyondCapacity);
+    assert_eq!(semaphore.in_flight(), Ok(7));
+}
+
+#[test]
+fn test_drop_releases_weight() {
+    let semaphore = WeightedSemaphoreCore::new(10);
+    let a = semaphore.acquire(7).unwrap();
+    {
+        let _b = semaphore.acquire(3).unwrap();
+        assert_eq!(semaphore.in_flight(), Ok(10));
+    }
+    assert_eq!(semaphore.in_flight(), Ok(7));
+
+    drop(a);
+    assert_eq!(semaphore.in_flight(), Ok(0));
+    assert!(semaphore.acquire(10).is_ok());
+}
+
+#[test]
+fn test_zero_weight_is_always_admitted() {
+    let semaphore = WeightedSemaphoreCore::new(1);
+    let _full = semaphore.acquire(1).unwrap();
+    assert_eq!(semaphore.acquire(0).map(|p| p.weight()), Ok(0));
+}
+
+#[test]
+fn test_concurrent_permits_never_exceed_capacity() {
+    let semaphore = Arc::new(WeightedSemaphoreCore::new(10));
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let semaphore = Arc::clone(&semaphore);
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    if let Ok(_permit) = semaphore.acquire(i + 2) {
+                        if let Ok(in_flight) = semaphore.in_flight() {
+                            assert!(in_flight <= 10);
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(semaphore.in_flight(), Ok(0));
+}
+
+#[test]
+#[should_panic(expected = "capacity must be greater than 0")]
+fn test_zero_capacity_panics() {
+    WeightedSemaphoreCore::new(0);
+}
@@ -0,0 +1,52 @@
+use rate_guard_core::cores::{FixedWindowCounterCore, WindowBounds};
+use rate_guard_core::{Uint, VerboseRateLimitError};
+
+fn bounds(start_tick: Uint, end_tick: Uint) -> WindowBounds {
+    WindowBounds { start_tick, end_tick }
+}
+
+#[test]
+fn test_allowed_request_reports_window_bounds() {
+    let counter = FixedWindowCounterCore::new(10, 60);
+
+    assert_eq!(counter.try_acquire_verbose_windowed_at(0, 3), (Ok(()), bounds(0, 59)));
+    assert_eq!(counter.try_acquire_verbose_windowed_at(59, 3), (Ok(()), bounds(0, 59)));
+    assert_eq!(counter.try_acquire_verbose_windowed_at(60, 3), (Ok(()), bounds(60, 119)));
+    assert_eq!(counter.try_acquire_verbose_windowed_at(200, 3), (Ok(()), bounds(180, 239)));
+}
+
+#[test]
+fn test_denied_request_reports_window_bounds() {
+    let counter = FixedWindowCounterCore::new(10, 60);
+    counter.try_acquire_at(130, 10).unwrap();
+
+    let (result, window) = counter.try_acquire_verbose_windowed_at(150, 1);
+    assert_eq!(window, bounds(120, 179));
+    assert_eq!(window.reset_tick(), 180);
+    match result {
+        Err(VerboseRateLimitError::InsufficientCapacity { retry_after_ticks, .. }) => {
+            assert_eq!(150 + retry_after_ticks, window.reset_tick());
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let (result, window) = counter.try_acquire_verbose_windowed_at(179, 11);
+    assert!(matches!(result, Err(VerboseRateLimitError::BeyondCapacity { .. })));
+    assert_eq!(window, bounds(120, 179));
+}
+
+#[test]
+fn test_window_bounds_do_not_depend_on_state() {
+    let counter = FixedWindowCounterCore::new(10, 7);
+    assert_eq!(counter.window_bounds_at(0), bounds(0, 6));
+    assert_eq!(counter.window_bounds_at(13), bounds(7, 13));
+    assert_eq!(counter.window_bounds_at(14), bounds(14, 20));
+}
+
+#[test]
+fn test_last_window_end_saturates() {
+    let counter = FixedWindowCounterCore::new(10, 7);
+    let window = counter.window_bounds_at(Uint::MAX);
+    assert_eq!(window, bounds(Uint::MAX - Uint::MAX % 7, Uint::MAX));
+    assert_eq!(window.reset_tick(), Uint::MAX);
+}
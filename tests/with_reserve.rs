@@ -0,0 +1,98 @@
+use rate_guard_core::combinators::{Pool, WithReserve};
+use rate_guard_core::cores::{FixedWindowCounterCore, TokenBucketCore};
+use rate_guard_core::{SimpleRateLimitError, VerboseRateLimitError};
+
+fn limiter() -> WithReserve<FixedWindowCounterCore, TokenBucketCore> {
+    WithReserve::new(FixedWindowCounterCore::new(10, 100), TokenBucketCore::new(4, 50, 1))
+}
+
+#[test]
+fn test_primary_is_charged_first() {
+    let limiter = limiter();
+    assert_eq!(limiter.try_acquire_pool_at(0, 6), Ok(Pool::Primary));
+    assert_eq!(limiter.primary().capacity_remaining(0), Ok(4));
+    assert_eq!(limiter.reserve().capacity_remaining(0), Ok(4));
+}
+
+#[test]
+fn test_borrows_from_reserve_after_primary_drains() {
+    let limiter = limiter();
+    assert_eq!(limiter.try_acquire_pool_at(0, 10), Ok(Pool::Primary));
+    assert_eq!(limiter.try_acquire_pool_at(0, 3), Ok(Pool::Reserve));
+    assert_eq!(limiter.primary().capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.reserve().capacity_remaining(0), Ok(1));
+}
+
+#[test]
+fn test_denied_only_when_both_pools_are_empty() {
+    let limiter = limiter();
+    assert_eq!(limiter.try_acquire_at(0, 10), Ok(()));
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(limiter.capacity_remaining(0), Ok(0));
+    assert_eq!(limiter.try_acquire_at(0, 1), Err(SimpleRateLimitError::InsufficientCapacity));
+
+    // The primary window resets before the reserve refills
+    assert_eq!(limiter.try_acquire_pool_at(100, 1), Ok(Pool::Primary));
+}
+
+#[test]
+fn test_capacity_remaining_is_sum() {
+    let limiter = limiter();
+    assert_eq!(limiter.capacity_remaining(0), Ok(14));
+    limiter.try_acquire_at(0, 10).unwrap();
+    limiter.try_acquire_at(0, 2).unwrap();
+    assert_eq!(limiter.capacity_remaining(0), Ok(2));
+}
+
+#[test]
+fn test_beyond_primary_capacity_is_not_borrowed() {
+    let limiter = limiter();
+    assert_eq!(limiter.try_acquire_at(0, 11), Err(SimpleRateLimitError::BeyondCapacity));
+    assert_eq!(limiter.capacity_remaining(0), Ok(14));
+}
+
+#[test]
+fn test_verbose_denial_reports_the_larger_pool() {
+    let limiter = limiter();
+    limiter.try_acquire_at(0, 8).unwrap();
+    limiter.try_acquire_at(0, 2).unwrap();
+    limiter.try_acquire_at(0, 3).unwrap();
+
+    match limiter.try_acquire_verbose_at(10, 2) {
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring, available, retry_after_ticks, available_next_refill }) => {
+            assert_eq!(acquiring, 2);
+            assert_eq!(available, 1);
+            // The reserve refills a token at tick 50, the primary window resets at tick 100
+            assert_eq!(retry_after_ticks, 40);
+            // A full primary window, more than the reserve's two tokens
+            assert_eq!(available_next_refill, 10);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_verbose_denial_never_claims_more_than_one_pool() {
+    let limiter = WithReserve::new(FixedWindowCounterCore::new(10, 100), TokenBucketCore::new(10, 50, 1));
+    assert_eq!(limiter.try_acquire_pool_at(0, 6), Ok(Pool::Primary));
+    assert_eq!(limiter.try_acquire_pool_at(0, 6), Ok(Pool::Reserve));
+    assert_eq!(limiter.max_acquirable_at(0), Ok(4));
+
+    // 4 tokens are left in each pool, and neither can take 6
+    assert_eq!(
+        limiter.try_acquire_verbose_at(0, 6),
+        Err(VerboseRateLimitError::InsufficientCapacity { acquiring: 6, available: 4, retry_after_ticks: 100, available_next_refill: 10 })
+    );
+}
+
+#[test]
+fn test_max_acquirable_is_the_larger_pool() {
+    let limiter = limiter();
+    assert_eq!(limiter.try_acquire_at(0, 7), Ok(()));
+    // 3 left in the primary and 4 in the reserve; a request is never split
+    assert_eq!(limiter.capacity_remaining(0), Ok(7));
+    assert_eq!(limiter.max_acquirable_at(0), Ok(4));
+    assert!(limiter.try_acquire_at(0, 5).is_err());
+    assert_eq!(limiter.try_acquire_at(0, 4), Ok(()));
+    assert_eq!(limiter.max_acquirable_at(0), Ok(3));
+}